@@ -0,0 +1,72 @@
+#!/usr/bin/env node
+import { parseArgs } from 'util'
+import { seededRandom } from '../lib/random.mjs'
+import { TARGETS } from './targets.mjs'
+
+const ALPHABET = '[]{}"\',:=#.\\\n\r\t 0123456789abcdefghijklmnopqrstuvwxyzÉé\u0000�'
+
+function mutate(input, random) {
+    const pick = (max) => Math.floor(random() * max)
+    const chars = [...input]
+    for(let rounds = 1 + pick(4); rounds > 0; rounds--) {
+        const at = pick(chars.length + 1)
+        switch(pick(4)) {
+            case 0:
+                chars.splice(at, 0, ALPHABET[pick(ALPHABET.length)])
+                break
+            case 1:
+                chars.splice(at, 1 + pick(4))
+                break
+            case 2:
+                chars.splice(at, 0, ...chars.slice(pick(chars.length), pick(chars.length)))
+                break
+            default:
+                chars[at] = ALPHABET[pick(ALPHABET.length)]
+        }
+    }
+    return chars.join('')
+}
+
+/**
+ * Mutates the seeds of `name` for `iterations` rounds.
+ *
+ * @returns {Promise<{input: string, error: Error} | null>} first failure
+ */
+export async function fuzz(name, { iterations = 10000, seed = 1 } = {}) {
+    const target = TARGETS[name]
+    const random = seededRandom(seed)
+    const corpus = [...target.seeds]
+    for(let i = 0; i < iterations; i++) {
+        const input = mutate(corpus[Math.floor(random() * corpus.length)], random)
+        try {
+            await target.check(input)
+        } catch(error) {
+            return { input, error }
+        }
+        if(corpus.length < 256 && random() < 0.05) {
+            corpus.push(input)
+        }
+    }
+    return null
+}
+
+if(import.meta.url == `file://${process.argv[1]}`) {
+    const { values } = parseArgs({
+        options: {
+            target: { type: 'string' },
+            iterations: { type: 'string', default: '100000' },
+            seed: { type: 'string', default: String(Date.now() % 2 ** 31) },
+        },
+    })
+    const names = values.target ? [values.target] : Object.keys(TARGETS)
+    for(const name of names) {
+        const failure = await fuzz(name, { iterations: Number(values.iterations), seed: Number(values.seed) })
+        if(failure) {
+            console.error(`[${name}] échec avec la graine ${values.seed} sur ${JSON.stringify(failure.input)}`)
+            console.error(failure.error)
+            process.exitCode = 1
+        } else {
+            console.log(`[${name}] ${values.iterations} entrées sans erreur (graine ${values.seed})`)
+        }
+    }
+}
@@ -0,0 +1,118 @@
+import { parseCsv } from '../lib/csv.mjs'
+import { InputError } from '../lib/errors.mjs'
+import { parseLdif } from '../lib/ldap.mjs'
+import { suspiciousNames } from '../lib/lint.mjs'
+import { generateGroups } from '../lib/matcher.mjs'
+import { minCostPerfectMatching } from '../lib/matching.mjs'
+import { parseNameList, parseRoster } from '../lib/roster.mjs'
+import { parseCompetencies } from '../lib/skills.mjs'
+import { parseToml } from '../lib/toml.mjs'
+
+/**
+ * Calls `parse`, tolerating only InputError.
+ */
+function expectCleanFailure(parse, input) {
+    try {
+        parse(input)
+    } catch(error) {
+        if(!(error instanceof InputError)) {
+            throw error
+        }
+    }
+}
+
+/**
+ * Least total cost of a perfect matching, trying every one of them.
+ *
+ * @param {number[]} vertices even count
+ * @param {(i: number, j: number) => number} cost
+ */
+function bruteForceMatching(vertices, cost) {
+    if(vertices.length == 0) {
+        return 0
+    }
+    const [first, ...rest] = vertices
+    return Math.min(...rest.map((other) => cost(first, other) + bruteForceMatching(rest.filter((vertex) => vertex != other), cost)))
+}
+
+/**
+ * Each target receives a fuzzed string and throws when it finds a bug.
+ *
+ * @type {Record<string, {seeds: string[], check: (input: string) => void | Promise<void>}>}
+ */
+export const TARGETS = {
+    roster: {
+        seeds: ['["Alice", "Bob", "Carol"]', '[]', '["Émile", {"name": "Zoé", "avatar": "https://x/z.png"}]'],
+        check: (input) => expectCleanFailure(parseRoster, input),
+    },
+    toml: {
+        seeds: [
+            'a = 1\n[ldap]\nfilter = "(x)" # comment\nlist = ["a", \'b\']\n[x.y]\nz = true\n',
+            'ldap.base = "dc=x"\n[skills]\nrequired = [\n  "HTML", # comment\n  ["a", 2],\n]\n',
+        ],
+        check: (input) => expectCleanFailure(parseToml, input),
+    },
+    ldif: {
+        seeds: ['dn: cn=a\ndisplayName: Alice\n\ndn: cn=b\ndisplayName:: w4ltaWxl\n'],
+        check: (input) => {
+            parseLdif(input)
+        },
+    },
+    csv: {
+        seeds: ['name;HTML;SQL\r\nAlice;3;"1,5"\r\n"Bob ""B""";;2\n', 'a,b\n"multi\nline",x'],
+        check: (input) => {
+            expectCleanFailure(parseCsv, input)
+            expectCleanFailure(parseCompetencies, input)
+        },
+    },
+    names: {
+        seeds: ['Alice, Bob,Carol\nDan', 'Alice BobMartin\nalice.martin@example.org\nZoé  Durand.'],
+        check: (input) => {
+            suspiciousNames(parseNameList(input))
+        },
+    },
+    matcher: {
+        seeds: ['Alice,Bob,Carol,Dan,Eve|Alice,Bob;Carol,Dan|never:Alice,Carol;together:Bob,Eve;together:Eve,Dan'],
+        check: async (input) => {
+            const [roster, last = '', rules = ''] = input.split('|')
+            const students = [...new Set(parseNameList(roster))]
+            const lastGroups = last.split(';').map(parseNameList).filter((group) => group.length > 0)
+            const constraints = rules.split(';')
+                .map((rule) => rule.split(':'))
+                .map(([type, names = '']) => ({ type, students: parseNameList(names) }))
+                .filter((constraint) => ['never', 'together'].includes(constraint.type) && constraint.students.length == 2)
+            const { groups } = await generateGroups(students, lastGroups, { iterations: 20, constraints })
+            const placed = groups.flat().sort()
+            if(placed.length != students.length || placed.some((name, index) => name != [...students].sort()[index])) {
+                throw new Error(`Groupes incohérents pour ${JSON.stringify(students)} : ${JSON.stringify(groups)}`)
+            }
+            for(const { type, students: [a, b] } of constraints) {
+                const groupOf = (name) => groups.findIndex((group) => group.includes(name))
+                if(type == 'together' && groupOf(a) != -1 && groupOf(b) != -1 && groupOf(a) != groupOf(b)) {
+                    throw new Error(`${a} et ${b} séparés malgré la contrainte : ${JSON.stringify(groups)}`)
+                }
+            }
+        },
+    },
+    matching: {
+        seeds: ['0 2 2 0 1 2 1 3 2 0 2 0 1 1 2 0 0 3 1 2 2 1 1 3 0 0 3 0 2 1 3 0 3 0 0 2 0 1 0 1 1 2 3 1 3 1 1 3 2 1 0 1 0 0 0 1 0 1 3 1 3 1 1 2 1 2 1 2 2 1 2 0 1 3 0 1 2 3 3 0 3 1 2 2 2 0 3 3 0 3 2 1 1 0 0 1 3 1 0 1', '0 1000000 1 2 1000000 0 0 1 1 0 0 3 2 1 3 0'],
+        check: (input) => {
+            // Numbers fill the cost matrix row by row, its upper half counting.
+            const costs = (input.match(/\d+/g) ?? []).map(Number).filter(Number.isSafeInteger)
+            let size = 0
+            while(size < 10 && (size + 2) ** 2 <= costs.length) {
+                size += 2
+            }
+            const cell = (i, j) => costs[Math.min(i, j) * size + Math.max(i, j)] ?? 0
+            const mate = minCostPerfectMatching(size, cell)
+            const total = mate.reduce((sum, other, i) => other > i ? sum + cell(i, other) : sum, 0)
+            if(mate.some((other, i) => mate[other] != i)) {
+                throw new Error(`Appariement incomplet : ${JSON.stringify(mate)}`)
+            }
+            const best = bruteForceMatching(Array.from({ length: size }, (_, i) => i), cell)
+            if(total != best) {
+                throw new Error(`Appariement de coût ${total} au lieu de ${best}`)
+            }
+        },
+    },
+}
@@ -0,0 +1,52 @@
+import fs from 'fs'
+import path from 'path'
+import { encrypt } from './encryption.mjs'
+import { t } from './i18n.mjs'
+
+/**
+ * @param {string} dbFile
+ */
+export function backupsDir(dbFile) {
+    return path.join(path.dirname(dbFile), 'backups')
+}
+
+/**
+ * @param {string} dbFile
+ */
+export function auditLogPath(dbFile) {
+    return path.join(path.dirname(dbFile), 'audit.log')
+}
+
+/**
+ * When `require_backup_before_destructive` is set in the `[safety]` table
+ * of groupgen.toml, copies the database aside before `action` changes
+ * saved data, and records the copy in the audit log. Any failure aborts
+ * the command before it touches anything.
+ *
+ * @param {import('./db.mjs').Db} db
+ * @param {Record<string, any>} config
+ * @param {string} action what is about to happen, for the audit log
+ * @param {{key?: string}} [options] `key` encrypts the copy of a database
+ *     still in clear, so that `db encrypt` leaves no cleartext copy behind
+ * @returns {string | null} path of the backup
+ */
+export function backupBeforeDestructive(db, config, action, { key } = {}) {
+    if(!config.safety?.require_backup_before_destructive || !fs.existsSync(db.file)) {
+        return null
+    }
+    const date = new Date().toISOString()
+    try {
+        const dir = backupsDir(db.file)
+        fs.mkdirSync(dir, { recursive: true })
+        const backup = path.join(dir, `db-${date.replace(/[:.]/g, '-')}.json`)
+        if(key != null) {
+            fs.writeFileSync(backup, JSON.stringify(encrypt(fs.readFileSync(db.file, 'utf8'), key), null, 4), { flag: 'wx', mode: 0o600 })
+        } else {
+            fs.copyFileSync(db.file, backup, fs.constants.COPYFILE_EXCL)
+        }
+        fs.appendFileSync(auditLogPath(db.file), `${date}\t${action}\tsauvegarde ${backup}\n`)
+        return backup
+    } catch(error) {
+        throw new Error(t`sauvegarde impossible avant « ${action} » (${error.message}), rien n'a été modifié`)
+    }
+}
@@ -0,0 +1,93 @@
+import { briefState } from './db.mjs'
+import { formatMonth, weekdayNames } from './format.mjs'
+import { t } from './i18n.mjs'
+import { stableUid } from './ics.mjs'
+import { sortNames } from './names.mjs'
+
+/**
+ * A brief on the calendar: done once its day has come, planned while it
+ * is a draft or dated later.
+ *
+ * @typedef {object} CalendarEntry
+ * @property {string} day YYYY-MM-DD, in local time
+ * @property {import('./model.mjs').BriefRecord} brief
+ * @property {boolean} planned
+ */
+
+/**
+ * @param {Date} date
+ * @returns {string} YYYY-MM-DD in local time
+ */
+function localDay(date) {
+    return `${date.getFullYear()}-${String(date.getMonth() + 1).padStart(2, '0')}-${String(date.getDate()).padStart(2, '0')}`
+}
+
+/**
+ * Dated briefs in chronological order, those without a date (imported
+ * from before dates were recorded) left out.
+ *
+ * @param {import('./model.mjs').BriefRecord[]} briefs
+ * @param {Date} [now]
+ * @returns {CalendarEntry[]}
+ */
+export function calendarEntries(briefs, now = new Date()) {
+    return briefs
+        .filter((brief) => brief.date != null)
+        .map((brief) => ({
+            day: localDay(new Date(brief.date)),
+            brief,
+            planned: briefState(brief) == 'draft' || new Date(brief.date) > now,
+        }))
+        .sort((a, b) => a.day.localeCompare(b.day) || a.brief.id - b.brief.id)
+}
+
+/**
+ * @param {import('./model.mjs').BriefRecord} brief
+ */
+export function briefTitle(brief) {
+    return brief.label ? t`Brief n°${brief.id} « ${brief.label} »` : t`Brief n°${brief.id}`
+}
+
+/**
+ * Month view, weeks starting on Monday, days with a brief marked `*`
+ * (done) or `+` (planned).
+ *
+ * @param {number} year
+ * @param {number} month 0 for January
+ * @param {CalendarEntry[]} entries of that month
+ * @returns {string[]} lines
+ */
+export function monthGrid(year, month, entries) {
+    const marks = new Map()
+    for(const { day, planned } of entries) {
+        const date = Number(day.slice(8))
+        // A brief done that day shows over one planned the same day.
+        marks.set(date, marks.get(date) == '*' || !planned ? '*' : '+')
+    }
+    const offset = (new Date(year, month, 1).getDay() + 6) % 7
+    const days = new Date(year, month + 1, 0).getDate()
+    const cells = [
+        ...new Array(offset).fill('    '),
+        ...Array.from({ length: days }, (_, index) => `${String(index + 1).padStart(3)}${marks.get(index + 1) ?? ' '}`),
+    ]
+    const lines = [formatMonth(year, month), weekdayNames().map((name) => name.padStart(3)).join(' ')]
+    for(let week = 0; week < cells.length; week += 7) {
+        lines.push(cells.slice(week, week + 7).join('').trimEnd())
+    }
+    return lines
+}
+
+/**
+ * One all-day event per brief, its groups in the description.
+ *
+ * @param {CalendarEntry[]} entries
+ * @returns {import('./ics.mjs').CalendarEvent[]}
+ */
+export function calendarEvents(entries) {
+    return entries.map(({ day, brief, planned }) => ({
+        uid: stableUid('calendar', brief.id),
+        summary: planned ? t`${briefTitle(brief)} (prévu)` : briefTitle(brief),
+        description: brief.groups.map((group, index) => t`Groupe ${index + 1} : ${sortNames(group).join(', ')}`).join('\n'),
+        start: day,
+    }))
+}
@@ -0,0 +1,65 @@
+import { t } from './i18n.mjs'
+
+/**
+ * Kinds of briefs (`generate --category`). A category can have a scoring
+ * profile in groupgen.toml listing the categories of past briefs it is
+ * matched against, so that a project avoids the pairs of the last
+ * project rather than those of yesterday's kata:
+ *
+ *     [categories.project]
+ *     count = ["project"]
+ *
+ * Without a profile, every past brief counts, as for uncategorised
+ * briefs.
+ */
+
+export const DEFAULT_CATEGORIES = ['solo-kata', 'project', 'workshop']
+
+/**
+ * @param {Record<string, any>} config parsed groupgen.toml
+ * @returns {string[]} the default categories, then those of `[categories]`
+ */
+export function knownCategories(config) {
+    return [...new Set([...DEFAULT_CATEGORIES, ...Object.keys(config.categories ?? {})])]
+}
+
+/**
+ * @param {string} value
+ * @param {Record<string, any>} config
+ */
+export function parseCategory(value, config) {
+    const known = knownCategories(config)
+    if(!known.includes(value)) {
+        throw new Error(t`Catégorie inconnue : ${value} (${known.join(', ')}, ou une table [categories.${value}] dans groupgen.toml)`)
+    }
+    return value
+}
+
+/**
+ * Categories of the past briefs a brief of `category` is matched against.
+ *
+ * @param {string | null} category
+ * @param {Record<string, any>} config
+ * @returns {string[] | null} null when every brief counts
+ */
+export function countedCategories(category, config) {
+    if(category == null) {
+        return null
+    }
+    const count = config.categories?.[category]?.count
+    if(count == null) {
+        return null
+    }
+    if(!Array.isArray(count) || count.some((name) => typeof name != 'string')) {
+        throw new Error(t`groupgen.toml : [categories.${category}] count doit être une liste de catégories`)
+    }
+    return count
+}
+
+/**
+ * @param {import('./model.mjs').BriefRecord[]} briefs
+ * @param {string[] | null} categories as returned by `countedCategories`
+ */
+export function briefsOfCategories(briefs, categories) {
+    return categories == null ? briefs : briefs.filter((brief) => categories.includes(brief.category))
+}
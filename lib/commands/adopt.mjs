@@ -0,0 +1,20 @@
+import { addBrief, openDb, saveDb } from '../db.mjs'
+import { t } from '../i18n.mjs'
+import { dropSession, findSession } from '../sessions.mjs'
+
+export const description = 'Enregistre dans l\'historique une session créée par quick'
+
+export const options = {}
+
+export async function run(options, [token]) {
+    if(token == null) {
+        throw new Error(t`Jeton de session manquant, ex. : groupgen adopt 1a2b3c4d`)
+    }
+    const db = openDb()
+    const session = findSession(db.file, token)
+    const brief = addBrief(db, session.groups, { date: new Date(session.date) })
+    // Should the history not be written, the session is left to adopt again.
+    saveDb(db)
+    dropSession(db.file, token)
+    console.log(t`Session ${token} enregistrée comme brief n°${brief.id}.`)
+}
@@ -0,0 +1,42 @@
+import { backupBeforeDestructive } from '../backup.mjs'
+import { loadConfig } from '../config.mjs'
+import { findBrief, lastBrief, openDb, parseBriefId, pastBriefs, saveDb } from '../db.mjs'
+import { t } from '../i18n.mjs'
+import { sortNames } from '../names.mjs'
+import { assignObservers, observationCounts } from '../observers.mjs'
+import { parseNameList } from '../roster.mjs'
+
+export const description = 'Répartit les groupes d\'un brief entre les formateurs qui les observent'
+
+export const options = {
+    trainers: { type: 'string' },
+    brief: { type: 'string' },
+}
+
+export async function run(options) {
+    const config = loadConfig()
+    const trainers = options.trainers ? parseNameList(options.trainers) : config.observers?.trainers ?? []
+    if(trainers.length == 0) {
+        throw new Error(t`Formateurs manquants, ex. : groupgen assign-observers --trainers "Anne,Marc" ou trainers dans [observers] de groupgen.toml`)
+    }
+    const db = openDb()
+    const brief = options.brief ? findBrief(db, parseBriefId(options.brief)) : lastBrief(db)
+    if(brief == null) {
+        throw new Error(t`Aucun brief enregistré`)
+    }
+    if(brief.observers != null) {
+        backupBeforeDestructive(db, config, `assign-observers, remplace les observateurs du brief n°${brief.id}`)
+    }
+    const history = observationCounts(pastBriefs(db.data.briefs).filter((other) => other.id != brief.id))
+    brief.observers = { trainers, assignment: assignObservers(brief.groups, trainers, history) }
+    saveDb(db)
+
+    console.log(t`Observateurs du brief n°${brief.id} :`)
+    for(const trainer of trainers) {
+        brief.groups.forEach((group, index) => {
+            if(brief.observers.assignment[index] == trainer) {
+                console.log(t`${trainer} : Groupe ${index + 1} (${sortNames(group).join(', ')})`)
+            }
+        })
+    }
+}
@@ -0,0 +1,41 @@
+import { backupBeforeDestructive } from '../backup.mjs'
+import { loadConfig } from '../config.mjs'
+import { findBrief, lastBrief, openDb, parseBriefId, pastBriefs, saveDb } from '../db.mjs'
+import { t } from '../i18n.mjs'
+import { sortNames } from '../names.mjs'
+import { assignSlots, averageSlotPositions } from '../slots.mjs'
+import { parseNameList } from '../roster.mjs'
+
+export const description = 'Répartit les groupes d\'un brief sur des créneaux de passage'
+
+export const options = {
+    slots: { type: 'string' },
+    brief: { type: 'string' },
+}
+
+export async function run(options) {
+    if(options.slots == null) {
+        throw new Error(t`Créneaux manquants, ex. : groupgen assign-slots --slots "9h,11h,14h"`)
+    }
+    const slots = parseNameList(options.slots)
+    const db = openDb()
+    const brief = options.brief ? findBrief(db, parseBriefId(options.brief)) : lastBrief(db)
+    if(brief == null) {
+        throw new Error(t`Aucun brief enregistré`)
+    }
+    if(brief.slots != null) {
+        backupBeforeDestructive(db, loadConfig(), `assign-slots, remplace les créneaux du brief n°${brief.id}`)
+    }
+    const history = averageSlotPositions(pastBriefs(db.data.briefs).filter((other) => other.id != brief.id))
+    brief.slots = { names: slots, assignment: assignSlots(brief.groups, slots, history) }
+    saveDb(db)
+
+    console.log(t`Passages du brief n°${brief.id} :`)
+    for(const slot of slots) {
+        brief.groups.forEach((group, index) => {
+            if(brief.slots.assignment[index] == slot) {
+                console.log(t`${slot} : Groupe ${index + 1} (${sortNames(group).join(', ')})`)
+            }
+        })
+    }
+}
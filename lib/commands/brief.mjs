@@ -0,0 +1,148 @@
+import { backupBeforeDestructive } from '../backup.mjs'
+import { parseCategory } from '../categories.mjs'
+import { loadConfig } from '../config.mjs'
+import { addBrief, briefState, findBrief, markConstraintsApplied, openDb, parseBriefId, saveDb, trashBrief } from '../db.mjs'
+import { t } from '../i18n.mjs'
+import { log } from '../log.mjs'
+import { channelsByName } from '../publishers/channels.mjs'
+import { announce } from '../publishers/queue.mjs'
+import { closeThread } from '../publishers/discord-threads.mjs'
+import { channelSecret } from '../publishers/index.mjs'
+
+export const description = 'Gère les briefs enregistrés (clone, activate, close, categorize, delete)'
+
+export const options = {
+    label: { type: 'string' },
+    date: { type: 'string' },
+    delete: { type: 'boolean' },
+}
+
+/**
+ * Records the exact composition of a past brief again, for projects where
+ * the same teams carry on over several sessions.
+ */
+function clone(options, [id]) {
+    if(id == null) {
+        throw new Error(t`Identifiant du brief manquant, ex. : groupgen brief clone 12`)
+    }
+    const date = options.date ? new Date(options.date) : new Date()
+    if(Number.isNaN(date.getTime())) {
+        throw new Error(t`Date invalide : ${options.date}`)
+    }
+    const db = openDb()
+    const source = findBrief(db, parseBriefId(id))
+    const groups = source.groups.map((group) => [...group])
+    const brief = addBrief(db, groups, { date, label: options.label ?? source.label, category: source.category })
+    brief.cloned_from = source.id
+    saveDb(db)
+    console.log(t`Brief n°${source.id} cloné en brief n°${brief.id}.`)
+}
+
+/**
+ * Moves a brief to the trash rather than erasing it.
+ */
+function remove(options, [id]) {
+    if(id == null) {
+        throw new Error(t`Identifiant du brief manquant, ex. : groupgen brief delete 12`)
+    }
+    const db = openDb()
+    findBrief(db, parseBriefId(id))
+    backupBeforeDestructive(db, loadConfig(), `brief delete ${id}`)
+    const brief = trashBrief(db, parseBriefId(id))
+    saveDb(db)
+    console.log(t`Brief n°${brief.id} mis à la corbeille, groupgen trash restore ${brief.id} pour l'annuler.`)
+}
+
+/**
+ * Turns a draft into the brief at work, from then on avoided by the
+ * next generations.
+ */
+function activate(options, [id]) {
+    if(id == null) {
+        throw new Error(t`Identifiant du brief manquant, ex. : groupgen brief activate 12`)
+    }
+    const db = openDb()
+    const brief = findBrief(db, parseBriefId(id))
+    if(briefState(brief) != 'draft') {
+        throw new Error(t`Le brief n°${brief.id} n'est pas un brouillon`)
+    }
+    delete brief.state
+    markConstraintsApplied((brief.context?.constraints ?? []).flatMap((constraintId) => db.data.constraints.filter((constraint) => constraint.id == constraintId)), brief)
+    saveDb(db)
+    console.log(t`Brief n°${brief.id} activé.`)
+}
+
+/**
+ * Files a brief under a category, typically one saved before categories
+ * were in use, so that scoring profiles take it into account.
+ */
+function categorize(options, [id, category]) {
+    if(id == null || category == null) {
+        throw new Error(t`Brief ou catégorie manquant, ex. : groupgen brief categorize 12 project`)
+    }
+    const db = openDb()
+    const brief = findBrief(db, parseBriefId(id))
+    brief.category = parseCategory(category, loadConfig())
+    saveDb(db)
+    console.log(t`Brief n°${brief.id} classé dans ${brief.category}.`)
+}
+
+/**
+ * Marks a brief as over and archives the chat threads opened for its
+ * groups, or deletes them with --delete. Threads that could not be
+ * closed are kept for another try. The brief is then sent to the
+ * channels listed in `on_close` of the `[lifecycle]` table, such as a
+ * file channel keeping an archive.
+ */
+async function close(options, [id]) {
+    if(id == null) {
+        throw new Error(t`Identifiant du brief manquant, ex. : groupgen brief close 12`)
+    }
+    const db = openDb()
+    const brief = findBrief(db, parseBriefId(id))
+    if(briefState(brief) == 'draft') {
+        throw new Error(t`Le brief n°${brief.id} est un brouillon, groupgen brief delete ${brief.id} pour l'abandonner`)
+    }
+    const config = loadConfig()
+    const hooks = channelsByName(config.lifecycle?.on_close ?? [], config)
+    const alreadyClosed = briefState(brief) == 'closed'
+    const failures = []
+    for(const thread of (brief.threads ?? []).filter((thread) => !thread.closed)) {
+        try {
+            const [channel] = channelsByName([thread.channel], config)
+            await closeThread(thread.id, channelSecret(channel, 'token'), { remove: options.delete })
+            thread.closed = true
+        } catch(error) {
+            failures.push(thread.group == null ? t`groupe retiré : ${error.message}` : t`groupe ${thread.group + 1} : ${error.message}`)
+        }
+    }
+    brief.state = 'closed'
+    brief.closed_at ??= new Date().toISOString()
+    saveDb(db)
+    const closed = (brief.threads ?? []).filter((thread) => thread.closed).length
+    if(closed == 0) {
+        console.log(t`Brief n°${brief.id} clos.`)
+    } else {
+        console.log(options.delete ? t`Brief n°${brief.id} clos, ${closed} fil(s) supprimé(s).` : t`Brief n°${brief.id} clos, ${closed} fil(s) archivé(s).`)
+    }
+    if(failures.length > 0) {
+        log.error(t`Fils non fermés, relancez groupgen brief close ${brief.id} :\n  ${failures.join('\n  ')}`)
+        process.exitCode = 1
+    }
+    if(hooks.length > 0 && !alreadyClosed) {
+        await announce(db, brief, hooks, config)
+    }
+}
+
+const ACTIONS = { clone, activate, close, categorize, delete: remove }
+
+/** Sub-commands, for shell completion. */
+export const actions = Object.keys(ACTIONS)
+
+export async function run(options, [action, ...args]) {
+    const handler = Object.hasOwn(ACTIONS, action) ? ACTIONS[action] : null
+    if(handler == null) {
+        throw new Error(t`Action inconnue : ${action ?? t`(aucune)`}, actions disponibles : ${actions.join(', ')}`)
+    }
+    await handler(options, args)
+}
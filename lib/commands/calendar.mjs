@@ -0,0 +1,59 @@
+import fs from 'fs'
+import path from 'path'
+import { briefTitle, calendarEntries, calendarEvents, monthGrid } from '../calendar.mjs'
+import { symbols } from '../console.mjs'
+import { openDb } from '../db.mjs'
+import { writeFileAtomic } from '../files.mjs'
+import { formatDate, formatMonth } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { calendar } from '../ics.mjs'
+
+export const description = 'Affiche le calendrier des briefs passés et prévus, mois par mois, ou l\'exporte en .ics (--ics)'
+
+export const options = {
+    month: { type: 'string' },
+    ics: { type: 'string' },
+}
+
+export async function run(options) {
+    if(options.month != null && !/^\d{4}-(0[1-9]|1[0-2])$/.test(options.month)) {
+        throw new Error(t`Mois invalide : ${options.month} (AAAA-MM)`)
+    }
+    const entries = calendarEntries(openDb().data.briefs)
+    if(options.ics != null) {
+        fs.mkdirSync(path.dirname(options.ics), { recursive: true })
+        writeFileAtomic(options.ics, calendar(calendarEvents(entries)))
+        console.log(t`${entries.length} brief(s) écrit(s) dans ${options.ics}.`)
+        return
+    }
+    if(entries.length == 0 && options.month == null) {
+        console.log(t`Aucun brief daté.`)
+        return
+    }
+
+    // Months since year 0, from the first brief to the last one.
+    const index = (day) => Number(day.slice(0, 4)) * 12 + Number(day.slice(5, 7)) - 1
+    const first = index(options.month ?? entries[0].day)
+    const last = options.month != null ? first : index(entries.at(-1).day)
+    for(let month = first; month <= last; month++) {
+        const year = Math.floor(month / 12)
+        const inMonth = entries.filter((entry) => index(entry.day) == month)
+        const lines = inMonth.map(({ day, brief, planned }) => planned
+            ? t`${briefTitle(brief)} (prévu) le ${formatDate(day)}, ${brief.groups.length} groupes`
+            : t`${briefTitle(brief)} le ${formatDate(day)}, ${brief.groups.length} groupes`)
+        if(month > first) {
+            console.log('')
+        }
+        if(symbols.linear) {
+            console.log(t`${formatMonth(year, month % 12)} : ${lines.length == 0 ? t`aucun brief` : lines.join(', ')}.`)
+            continue
+        }
+        console.log(monthGrid(year, month % 12, inMonth).join('\n'))
+        for(const line of lines) {
+            console.log(`  ${line}`)
+        }
+    }
+    if(!symbols.linear) {
+        console.log(`\n${t`* brief passé, + brief prévu (brouillon ou daté plus tard)`}`)
+    }
+}
@@ -0,0 +1,79 @@
+import { openDb, saveDb } from '../db.mjs'
+import { t } from '../i18n.mjs'
+import { emptyCohort } from '../model.mjs'
+import { dbPath } from '../paths.mjs'
+import { loadStudents } from '../roster.mjs'
+
+export const description = 'Gère les promos tenues dans la même base, à utiliser avec --cohort (list, create, students)'
+
+export const options = {
+    students: { type: 'string' },
+}
+
+/**
+ * @param {string | undefined} name
+ * @param {string} action for the example in the error
+ */
+function checkName(name, action) {
+    if(name == null) {
+        throw new Error(t`Nom de cohorte manquant, ex. : groupgen cohort ${action} promo-12`)
+    }
+    if(!/^[\p{L}\p{N}._-]+$/u.test(name)) {
+        throw new Error(t`Nom de cohorte invalide : ${name} (lettres, chiffres, . _ -)`)
+    }
+}
+
+function list() {
+    const cohorts = Object.entries(openDb(dbPath(), { cohort: null }).data.cohorts ?? {})
+    if(cohorts.length == 0) {
+        console.log(t`Aucune cohorte, à créer avec : groupgen cohort create promo-12 --students students.json`)
+        return
+    }
+    for(const [name, cohort] of cohorts) {
+        console.log(t`${name} : ${cohort.students.length} apprenant(s), ${cohort.briefs.length} brief(s)`)
+    }
+}
+
+/**
+ * Adds a cohort, with the roster of --students copied into the database.
+ */
+function create(options, [name]) {
+    checkName(name, 'create')
+    const db = openDb(dbPath(), { cohort: null })
+    db.data.cohorts ??= {}
+    if(Object.hasOwn(db.data.cohorts, name)) {
+        throw new Error(t`La cohorte ${name} existe déjà`)
+    }
+    const students = options.students != null ? loadStudents(options.students) : []
+    db.data.cohorts[name] = emptyCohort(students.map((student) => Object.keys(student).length == 1 ? student.name : student))
+    saveDb(db)
+    console.log(t`Cohorte ${name} créée avec ${students.length} apprenant(s), à utiliser avec : groupgen --cohort ${name}`)
+}
+
+/**
+ * Replaces the roster of a cohort, as students join or leave the promo.
+ */
+function students(options, [name]) {
+    checkName(name, 'students')
+    if(options.students == null) {
+        throw new Error(t`Liste des apprenants manquante, ex. : groupgen cohort students ${name} --students students.json`)
+    }
+    const db = openDb(dbPath(), { cohort: name })
+    const roster = loadStudents(options.students)
+    db.data.students = roster.map((student) => Object.keys(student).length == 1 ? student.name : student)
+    saveDb(db)
+    console.log(t`${roster.length} apprenant(s) enregistré(s) pour la cohorte ${name}.`)
+}
+
+const ACTIONS = { list, create, students }
+
+/** Sub-commands, for shell completion. */
+export const actions = Object.keys(ACTIONS)
+
+export async function run(options, [action, ...args]) {
+    const handler = Object.hasOwn(ACTIONS, action) ? ACTIONS[action] : null
+    if(handler == null) {
+        throw new Error(t`Action inconnue : ${action ?? t`(aucune)`}, actions disponibles : ${actions.join(', ')}`)
+    }
+    await handler(options, args)
+}
@@ -0,0 +1,66 @@
+import fs from 'fs'
+import path from 'path'
+import { symbols } from '../console.mjs'
+import { openDb, pastBriefs } from '../db.mjs'
+import { formatNumber, formatPercent } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { dbPath } from '../paths.mjs'
+import { cohortMetrics } from '../stats.mjs'
+
+export const description = 'Compare la rotation des binômes entre plusieurs promos (couverture, équité, répétitions)'
+
+export const options = {
+    briefs: { type: 'string', default: '10' },
+}
+
+/**
+ * A cohort is given as its db.json or the directory holding it, or by
+ * the name of a cohort kept in the current database (`--cohort`).
+ *
+ * @param {string} source
+ * @returns {{name: string, briefs: import('../model.mjs').BriefRecord[]}}
+ */
+function cohortHistory(source) {
+    if(!fs.existsSync(source)) {
+        const stored = openDb(dbPath(), { cohort: null }).data.cohorts?.[source]
+        if(stored == null) {
+            throw new Error(t`Historique introuvable : ${source} (ni fichier ni cohorte de ${dbPath()})`)
+        }
+        return { name: source, briefs: stored.briefs }
+    }
+    const file = fs.statSync(source).isDirectory() ? path.join(source, 'db.json') : source
+    if(!fs.existsSync(file)) {
+        throw new Error(t`Historique introuvable : ${file}`)
+    }
+    const name = path.basename(file) == 'db.json' ? path.basename(path.dirname(path.resolve(file))) : path.basename(file, '.json')
+    return { name, briefs: openDb(file, { cohort: null }).data.briefs }
+}
+
+export async function run(options, sources) {
+    if(sources.length < 2) {
+        throw new Error(t`Au moins deux promos à comparer, ex. : groupgen compare-cohorts promoA/ promoB/db.json`)
+    }
+    const horizon = Number(options.briefs)
+    if(!Number.isInteger(horizon) || horizon < 1) {
+        throw new Error(t`--briefs invalide : ${options.briefs}`)
+    }
+    const cohorts = sources.map((source) => {
+        const { name, briefs } = cohortHistory(source)
+        return { name, metrics: cohortMetrics(pastBriefs(briefs).map((brief) => brief.groups)) }
+    })
+
+    const width = Math.max(...cohorts.map(({ name }) => name.length))
+    for(const { name, metrics } of cohorts) {
+        const reached = metrics.coverage.findIndex((share) => share >= 0.5)
+        const coverage = metrics.coverage[Math.min(horizon, metrics.briefs) - 1] ?? 0
+        const fields = [
+            symbols.linear ? t`Promo ${name}` : name.padEnd(width),
+            t`${metrics.briefs} briefs, ${metrics.students} apprenants`,
+            t`couverture ${formatPercent(coverage)} après ${Math.min(horizon, metrics.briefs)} brief(s)`,
+            reached == -1 ? t`50 % non atteints` : t`50 % en ${reached + 1} brief(s)`,
+            t`${formatNumber(metrics.repeatsPerBrief, 1)} binôme(s) répété(s) par brief`,
+            t`${metrics.minPartners} à ${metrics.maxPartners} partenaires distincts`,
+        ]
+        console.log(symbols.linear ? `${fields.join(', ')}.` : fields.join(' | '))
+    }
+}
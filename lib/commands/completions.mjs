@@ -0,0 +1,11 @@
+import { completionScript } from '../completions.mjs'
+import { COMMANDS } from './index.mjs'
+
+export const description = 'Affiche le script de complétion des commandes et options pour bash, zsh, fish ou powershell'
+
+export const options = {}
+
+export async function run(options, [shell]) {
+    const modules = Object.fromEntries(await Promise.all(Object.entries(COMMANDS).map(async ([name, load]) => [name, await load()])))
+    process.stdout.write(completionScript(shell, modules))
+}
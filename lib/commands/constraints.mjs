@@ -0,0 +1,164 @@
+import { backupBeforeDestructive } from '../backup.mjs'
+import { readRegistry, writeRegistry } from '../confidential.mjs'
+import { loadConfig } from '../config.mjs'
+import { CONSTRAINT_TYPES, activeConstraints, describeConstraint, describeConstraintHistory } from '../constraints.mjs'
+import { addConstraint, importConfigConstraints, openDb, removeConstraint, saveDb } from '../db.mjs'
+import { formatDate } from '../format.mjs'
+import { fuzzySearch } from '../fuzzy.mjs'
+import { formatList, t } from '../i18n.mjs'
+import { studentsPath } from '../paths.mjs'
+import { Prompter } from '../prompt.mjs'
+import { loadRoster } from '../roster.mjs'
+
+export const description = 'Gère les contraintes de groupes (list, add, remove), et le registre confidentiel avec --admin'
+
+export const options = {
+    students: { type: 'string' },
+    all: { type: 'boolean' },
+    admin: { type: 'boolean' },
+}
+
+function openConstraints() {
+    const db = openDb()
+    importConfigConstraints(db, loadConfig())
+    return db
+}
+
+function list(options) {
+    const db = openConstraints()
+    if(options.admin) {
+        const records = readRegistry(db).filter((record) => options.all || record.removed_at == null)
+        if(records.length == 0) {
+            console.log(t`Registre confidentiel vide.`)
+        }
+        for(const record of records) {
+            console.log(`${record.id}. ${describeConstraint({ type: 'never', students: record.students })}${record.note ? ` (${record.note})` : ''}${record.removed_at ? t`, retirée le ${formatDate(record.removed_at)}` : ''}`)
+        }
+        return
+    }
+    const constraints = options.all
+        ? db.data.constraints
+        : db.data.constraints.filter((constraint) => activeConstraints([constraint], constraint.students).length > 0)
+    if(constraints.length == 0) {
+        console.log(options.all ? t`Aucune contrainte.` : t`Aucune contrainte en vigueur (--all pour l'historique).`)
+        return
+    }
+    for(const constraint of constraints) {
+        console.log(`${constraint.id}. ${describeConstraint(constraint)} (${describeConstraintHistory(constraint)})`)
+    }
+}
+
+/**
+ * @param {Prompter} prompter
+ * @param {string[]} roster
+ * @param {string} question
+ * @returns {Promise<string>}
+ */
+async function pickStudent(prompter, roster, question) {
+    for(;;) {
+        const query = await prompter.ask(question)
+        if(query == null) {
+            throw new Error(t`Saisie interrompue`)
+        }
+        const matches = fuzzySearch(query, roster).slice(0, 9)
+        if(matches.length == 0) {
+            console.log(t`Aucun apprenant ne correspond, réessayez.`)
+            continue
+        }
+        if(matches.length == 1) {
+            console.log(`→ ${matches[0]}`)
+            return matches[0]
+        }
+        matches.forEach((name, index) => console.log(`  ${index + 1}. ${name}`))
+        const choice = Number(await prompter.ask(t`Numéro [1-${matches.length}] : `))
+        if(matches[choice - 1]) {
+            return matches[choice - 1]
+        }
+        console.log(t`Choix invalide, réessayez.`)
+    }
+}
+
+async function add(options) {
+    const roster = loadRoster(options.students ?? studentsPath(), loadConfig())
+    const db = openConstraints()
+    const prompter = new Prompter()
+    try {
+        if(options.admin) {
+            await addConfidential(db, roster, prompter)
+            return
+        }
+        const types = Object.keys(CONSTRAINT_TYPES)
+        types.forEach((type, index) => console.log(`  ${index + 1}. ${t(CONSTRAINT_TYPES[type])}`))
+        const type = types[Number(await prompter.ask(t`Type de contrainte [1-${types.length}] : `)) - 1]
+        if(type == null) {
+            throw new Error(t`Type de contrainte invalide`)
+        }
+
+        const first = await pickStudent(prompter, roster, t`Premier apprenant (recherche) : `)
+        const second = await pickStudent(prompter, roster.filter((name) => name != first), t`Second apprenant (recherche) : `)
+
+        const expires = (await prompter.ask(t`Date de fin AAAA-MM-JJ (vide = sans fin) : `))?.trim() || null
+
+        const constraint = addConstraint(db, { type, students: [first, second], expires })
+        saveDb(db)
+        console.log(t`Contrainte n°${constraint.id} ajoutée : ${describeConstraint(constraint)}`)
+    } finally {
+        prompter.close()
+    }
+}
+
+/**
+ * Adds a "never" pair to the confidential registry, which only asks for
+ * the students and, for the admin's eyes, why.
+ *
+ * @param {import('../db.mjs').Db} db
+ * @param {string[]} roster
+ * @param {Prompter} prompter
+ */
+async function addConfidential(db, roster, prompter) {
+    const records = readRegistry(db)
+    const first = await pickStudent(prompter, roster, t`Premier apprenant (recherche) : `)
+    const second = await pickStudent(prompter, roster.filter((name) => name != first), t`Second apprenant (recherche) : `)
+    const note = (await prompter.ask(t`Motif, visible avec --admin seulement (vide = aucun) : `))?.trim() || null
+    const record = { id: (records.at(-1)?.id ?? 0) + 1, students: [first, second], note, created_at: new Date().toISOString(), removed_at: null }
+    writeRegistry(db, [...records, record])
+    saveDb(db)
+    console.log(t`Entrée confidentielle n°${record.id} ajoutée : ${formatList([first, second])} ne seront jamais ensemble.`)
+}
+
+function remove(options, [id]) {
+    if(id == null) {
+        throw new Error(t`Numéro de contrainte manquant, ex. : groupgen constraints remove 3`)
+    }
+    const db = openConstraints()
+    if(options.admin) {
+        const records = readRegistry(db)
+        const record = records.find((record) => record.id == Number(id) && record.removed_at == null)
+        if(record == null) {
+            throw new Error(t`Entrée confidentielle n°${id} introuvable`)
+        }
+        backupBeforeDestructive(db, loadConfig(), `constraints remove --admin ${id}`)
+        record.removed_at = new Date().toISOString()
+        writeRegistry(db, records)
+        saveDb(db)
+        console.log(t`Entrée confidentielle n°${record.id} retirée.`)
+        return
+    }
+    backupBeforeDestructive(db, loadConfig(), `constraints remove ${id}`)
+    const constraint = removeConstraint(db, Number(id))
+    saveDb(db)
+    console.log(t`Contrainte n°${constraint.id} retirée : ${describeConstraint(constraint)}`)
+}
+
+const ACTIONS = { list, add, remove }
+
+/** Sub-commands, for shell completion. */
+export const actions = Object.keys(ACTIONS)
+
+export async function run(options, [action, ...args]) {
+    const handler = Object.hasOwn(ACTIONS, action) ? ACTIONS[action] : null
+    if(handler == null) {
+        throw new Error(t`Action inconnue : ${action ?? t`(aucune)`}, actions disponibles : ${actions.join(', ')}`)
+    }
+    await handler(options, args)
+}
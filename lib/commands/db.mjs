@@ -0,0 +1,95 @@
+import fs from 'fs'
+import path from 'path'
+import { auditLogPath, backupBeforeDestructive, backupsDir } from '../backup.mjs'
+import { loadConfig } from '../config.mjs'
+import { openDb, saveDb } from '../db.mjs'
+import { KEY_ENV, isEncrypted } from '../encryption.mjs'
+import { formatList, t } from '../i18n.mjs'
+import { log } from '../log.mjs'
+import { registryCachePath, sessionsPath } from '../paths.mjs'
+
+export const description = 'Gère le fichier d\'historique (encrypt, decrypt)'
+
+export const options = {
+    purge: { type: 'boolean' },
+}
+
+/**
+ * Files kept next to the database that may hold names in clear: backups
+ * taken before it was encrypted, pending draws, the roster cache and the
+ * audit log.
+ *
+ * @param {string} dbFile
+ * @returns {string[]}
+ */
+function plaintextFiles(dbFile) {
+    const dir = backupsDir(dbFile)
+    const backups = fs.existsSync(dir) ? fs.readdirSync(dir).map((name) => path.join(dir, name)) : []
+    const encrypted = (file) => {
+        try {
+            return isEncrypted(JSON.parse(fs.readFileSync(file, 'utf8')))
+        } catch {
+            return false
+        }
+    }
+    return [...backups.filter((file) => !encrypted(file)), sessionsPath(dbFile), registryCachePath(dbFile), auditLogPath(dbFile)]
+        .filter((file) => fs.existsSync(file))
+}
+
+/**
+ * Encrypts the database with the passphrase of SGG_DB_KEY, along with its
+ * backup. Side files still in clear are listed, or deleted with --purge.
+ */
+function encrypt(options) {
+    const key = process.env[KEY_ENV]
+    if(!key) {
+        throw new Error(t`Phrase de passe manquante : définissez ${KEY_ENV}`)
+    }
+    const db = openDb()
+    if(db.key != null && fs.existsSync(db.file)) {
+        console.log(t`${db.file} est déjà chiffré.`)
+    } else {
+        backupBeforeDestructive(db, loadConfig(), 'db encrypt', { key })
+        db.key = key
+        saveDb(db)
+        console.log(t`${db.file} chiffré. Sans ${KEY_ENV}, l'historique est illisible : conservez la phrase de passe.`)
+    }
+    const plaintext = plaintextFiles(db.file)
+    if(plaintext.length == 0) {
+        return
+    }
+    if(options.purge) {
+        plaintext.forEach((file) => fs.rmSync(file))
+        console.log(t`Fichier(s) en clair supprimé(s) : ${formatList(plaintext)}.`)
+    } else {
+        log.warn(t`Toujours en clair à côté de l'historique : ${formatList(plaintext)}. groupgen db encrypt --purge pour les supprimer.`)
+    }
+}
+
+/**
+ * Stores the database in clear again.
+ */
+function decrypt() {
+    const db = openDb()
+    if(db.key == null) {
+        console.log(t`${db.file} n'est pas chiffré.`)
+        return
+    }
+    backupBeforeDestructive(db, loadConfig(), 'db decrypt')
+    db.key = null
+    saveDb(db)
+    console.log(t`${db.file} déchiffré.`)
+}
+
+const ACTIONS = { encrypt, decrypt }
+
+/** Sub-commands, for shell completion. */
+export const actions = Object.keys(ACTIONS)
+
+export async function run(options, [action]) {
+    const handler = Object.hasOwn(ACTIONS, action) ? ACTIONS[action] : null
+    if(handler == null) {
+        throw new Error(t`Action inconnue : ${action ?? t`(aucune)`}, actions disponibles : ${actions.join(', ')}`)
+    }
+    await handler(options)
+}
@@ -0,0 +1,70 @@
+import { backupBeforeDestructive } from '../backup.mjs'
+import { loadConfig } from '../config.mjs'
+import { amendBrief, briefState, findBrief, lastBrief, openDb, parseBriefId, removeGroup, saveDb } from '../db.mjs'
+import { t } from '../i18n.mjs'
+import { log } from '../log.mjs'
+import { channelsByName } from '../publishers/channels.mjs'
+import { announce } from '../publishers/queue.mjs'
+import { renderGroups } from '../render.mjs'
+
+export const description = 'Retire un apprenant d\'un brief enregistré (absence, abandon)'
+
+export const options = {
+    brief: { type: 'string' },
+    promote: { type: 'boolean' },
+    channel: { type: 'string', short: 'c', multiple: true },
+}
+
+export async function run(options, positionals) {
+    const name = positionals.join(' ').trim()
+    if(name == '') {
+        throw new Error(t`Nom de l'apprenant manquant, ex. : groupgen drop "Alice Martin" --brief 12`)
+    }
+    const config = loadConfig()
+    const channels = channelsByName(options.channel ?? [], config)
+    const db = openDb()
+    const brief = options.brief ? findBrief(db, parseBriefId(options.brief)) : lastBrief(db)
+    if(brief == null) {
+        throw new Error(t`Aucun brief enregistré`)
+    }
+    if(briefState(brief) == 'closed') {
+        throw new Error(t`Le brief n°${brief.id} est clos, ses groupes ne changent plus`)
+    }
+    const index = brief.groups.findIndex((group) => group.includes(name))
+    if(index == -1) {
+        throw new Error(t`${name} ne fait pas partie du brief n°${brief.id}`)
+    }
+
+    backupBeforeDestructive(db, config, `drop ${name} du brief n°${brief.id}`)
+    const group = brief.groups[index]
+    group.splice(group.indexOf(name), 1)
+    amendBrief(brief, { action: 'drop', student: name, group: index, emptied: group.length == 0 })
+    console.log(t`${name} est retiré(e) du groupe ${index + 1} du brief n°${brief.id}.`)
+
+    if(options.promote && group.length > 0) {
+        const solo = brief.groups.findIndex((other, position) => position != index && other.length == 1)
+        if(solo == -1) {
+            console.log(t`Aucun apprenant seul à déplacer dans la place libérée.`)
+        } else {
+            const [student] = brief.groups[solo]
+            group.push(student)
+            amendBrief(brief, { action: 'drop', student, group: solo, emptied: true })
+            amendBrief(brief, { action: 'add', student, group: index })
+            console.log(t`${student}, seul(e) dans le groupe ${solo + 1}, rejoint le groupe ${index + 1}.`)
+            removeGroup(brief, solo)
+        }
+    }
+    if(group.length == 0) {
+        removeGroup(brief, brief.groups.indexOf(group))
+    } else if(group.length == 1) {
+        log.warn(options.promote
+            ? t`${group[0]} se retrouve seul(e) dans son groupe.`
+            : t`${group[0]} se retrouve seul(e) dans son groupe, --promote le regroupe avec un autre apprenant seul.`)
+    }
+    saveDb(db)
+    console.log(renderGroups(brief.groups))
+
+    if(channels.length > 0) {
+        await announce(db, brief, channels, config)
+    }
+}
@@ -0,0 +1,101 @@
+import { briefTitle } from '../calendar.mjs'
+import { describeConstraint } from '../constraints.mjs'
+import { findBrief, openDb, parseBriefId } from '../db.mjs'
+import { formatDate } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { NEVER_PENALTY } from '../matcher.mjs'
+import { sortNames } from '../names.mjs'
+
+export const description = 'Explique comment la composition d\'un brief a été choisie'
+
+export const options = {
+    brief: { type: 'string' },
+}
+
+const TIE_BREAK_LABELS = {
+    'new-pairs': () => t`le plus de binômes inédits sur tout l'historique`,
+    churn: () => t`les numéros de groupe du brief précédent`,
+    alphabetical: () => t`l'ordre alphabétique`,
+}
+
+export async function run(options) {
+    if(options.brief == null) {
+        throw new Error(t`Brief manquant, ex. : groupgen explain --brief 12`)
+    }
+    const db = openDb()
+    const brief = findBrief(db, parseBriefId(options.brief))
+    console.log(brief.date ? t`${briefTitle(brief)}, généré le ${formatDate(brief.date)}` : t`${briefTitle(brief)}, date inconnue`)
+    if(brief.cloned_from != null) {
+        console.log(t`Copie des groupes du brief n°${brief.cloned_from} (brief clone), pas un nouveau tirage : les mêmes équipes continuent.`)
+    }
+    for(const { date, action, student, group } of brief.amendments ?? []) {
+        console.log(action == 'add'
+            ? t`Modifié le ${formatDate(date)} : ${student} ajouté(e) au groupe ${group + 1}.`
+            : t`Modifié le ${formatDate(date)} : ${student} retiré(e) du groupe ${group + 1}.`)
+    }
+    for(const { group, url } of brief.repositories ?? []) {
+        console.log(group == null ? t`Dépôt d'un groupe retiré : ${url}` : t`Dépôt du groupe ${group + 1} : ${url}`)
+    }
+
+    const { context } = brief
+    if(context == null) {
+        console.log(t`Aucun contexte de génération enregistré (brief importé, cloné, adopté ou antérieur à cette version).`)
+        return
+    }
+
+    console.log(context.previous_brief == null
+        ? t`${context.roster.length} apprenants, sans brief précédent.`
+        : t`${context.roster.length} apprenants, binômes du brief n°${context.previous_brief} évités.`)
+    if(brief.category != null) {
+        console.log(context.counted_categories != null
+            ? t`Catégorie : ${brief.category}, seuls les briefs ${context.counted_categories.join(', ')} pris en compte.`
+            : t`Catégorie : ${brief.category}.`)
+    }
+    if(context.group_size != null) {
+        console.log(t`Groupes de ${context.group_size} demandés.`)
+    }
+    if(context.tie_break != null) {
+        console.log(t`Égalités départagées par : ${TIE_BREAK_LABELS[context.tie_break]?.() ?? context.tie_break}.`)
+    }
+    if(context.excluded != null) {
+        console.log(t`Absent(s), laissé(s) de côté : ${sortNames(context.excluded).join(', ')}.`)
+    }
+    console.log(context.seed == null ? t`Graine : aucune, tirage non reproductible.` : t`Graine : ${context.seed} (même tirage avec --seed ${context.seed} sur le même historique).`)
+
+    const constraints = context.constraints.map((id) => db.data.constraints.find((constraint) => constraint.id == id))
+    if(constraints.length == 0) {
+        console.log(t`Contraintes en vigueur : aucune.`)
+    } else {
+        console.log(t`Contraintes en vigueur :`)
+        for(const constraint of constraints) {
+            console.log(`  - ${t`n°${constraint.id} ${describeConstraint(constraint)}`}`)
+        }
+    }
+
+    if(context.required_skills != null) {
+        console.log(t`Compétences requises dans chaque groupe : ${context.required_skills.join(', ')} (${context.missing_skills} manquante(s) au total).`)
+    }
+    const scores = context.improvements.map(formatScore).join(' → ')
+    console.log(context.required_skills
+        ? t`Recherche : ${context.iterations} tirages, meilleurs scores successifs ${scores} (binômes répétés + compétences manquantes).`
+        : t`Recherche : ${context.iterations} tirages, meilleurs scores successifs ${scores} (binômes répétés).`)
+    if(context.violations > 0) {
+        console.log(t`${context.violations} contrainte(s) "jamais ensemble" n'avaient pas pu être respectées.`)
+    }
+    if(context.swaps != null) {
+        console.log(t`Échanges faits à la main après le tirage : ${context.swaps.map(([a, b]) => t`${a} et ${b}`).join(', ')}.`)
+    }
+    console.log(t`Groupes retenus :`)
+    brief.groups.forEach((group, index) => {
+        const repeats = context.group_repeats[index]
+        console.log(`  ${index + 1}. ${sortNames(group).join(', ')} : ${repeats == 0 ? t`aucun binôme répété` : t`${repeats} binôme(s) répété(s)`}`)
+    })
+}
+
+/**
+ * @param {number} score as computed by CostMatrix.score
+ */
+function formatScore(score) {
+    const violations = Math.floor(score / NEVER_PENALTY)
+    return violations > 0 ? t`${score % NEVER_PENALTY} (+${violations} contrainte(s) violée(s))` : String(score)
+}
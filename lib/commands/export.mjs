@@ -0,0 +1,52 @@
+import fs from 'fs'
+import { loadConfig } from '../config.mjs'
+import { findBrief, lastBrief, openDb, parseBriefId } from '../db.mjs'
+import { avatarSheet } from '../export/avatars.mjs'
+import { groupsCsv } from '../export/csv.mjs'
+import { t } from '../i18n.mjs'
+import { studentsPath } from '../paths.mjs'
+import { loadStudents } from '../roster.mjs'
+
+export const description = 'Exporte un brief (--html : planche de groupes avec avatars, --csv : une ligne par groupe)'
+
+export const options = {
+    brief: { type: 'string' },
+    html: { type: 'boolean' },
+    csv: { type: 'boolean' },
+    all: { type: 'boolean' },
+    out: { type: 'string', short: 'o' },
+    students: { type: 'string' },
+}
+
+export async function run(options) {
+    if(!options.html && !options.csv) {
+        throw new Error(t`Format d'export manquant : --html ou --csv`)
+    }
+    const db = openDb()
+    if(options.csv && options.all) {
+        output(options.out, groupsCsv(db.data.briefs), t`${db.data.briefs.length} brief(s) exporté(s) dans ${options.out}.`)
+        return
+    }
+    const brief = options.brief ? findBrief(db, parseBriefId(options.brief)) : lastBrief(db)
+    if(brief == null) {
+        throw new Error(t`Aucun brief enregistré`)
+    }
+    if(options.csv) {
+        output(options.out, groupsCsv([brief]), t`Brief n°${brief.id} exporté dans ${options.out}.`)
+        return
+    }
+    const students = new Map(loadStudents(options.students ?? studentsPath(), loadConfig()).map((student) => [student.name, student]))
+    output(options.out, avatarSheet(brief, students), t`Planche du brief n°${brief.id} écrite dans ${options.out}, à imprimer depuis le navigateur (PDF possible).`)
+}
+
+/**
+ * Writes to `file` and reports it, or prints to stdout without `--out`.
+ */
+function output(file, content, message) {
+    if(file) {
+        fs.writeFileSync(file, content)
+        console.log(message)
+    } else {
+        process.stdout.write(content)
+    }
+}
@@ -0,0 +1,55 @@
+import fs from 'fs'
+import { addBrief, openDb, saveDb } from '../db.mjs'
+import { fakeHistory, fakeRoster } from '../fake.mjs'
+import { t } from '../i18n.mjs'
+import { log } from '../log.mjs'
+import { parseGroupSize } from '../matcher.mjs'
+import { parseSeed, randomSeed, seededRandom } from '../random.mjs'
+
+export const description = 'Génère une liste d\'apprenants fictifs (et un historique avec --history) pour les démos et les tests'
+
+export const options = {
+    size: { type: 'string', default: '28' },
+    locale: { type: 'string', default: 'fr' },
+    seed: { type: 'string' },
+    history: { type: 'string' },
+    'group-size': { type: 'string', default: '2' },
+    out: { type: 'string', short: 'o' },
+}
+
+export async function run(options) {
+    const size = Number(options.size)
+    if(!Number.isInteger(size) || size < 1) {
+        throw new Error(t`--size invalide : ${options.size}`)
+    }
+    const briefs = Number(options.history ?? 0)
+    if(!Number.isInteger(briefs) || briefs < 0) {
+        throw new Error(t`--history invalide : ${options.history}`)
+    }
+    const groupSize = parseGroupSize(options['group-size'])
+    const random = seededRandom(options.seed != null ? parseSeed(options.seed) : randomSeed())
+    const students = fakeRoster(size, { locale: options.locale, random })
+    const db = briefs > 0 ? openDb() : null
+    if(db != null && db.data.briefs.length > 0) {
+        throw new Error(t`${db.file} contient déjà des briefs, choisissez une autre base avec --db pour un historique fictif`)
+    }
+
+    const content = JSON.stringify(students, null, 4) + '\n'
+    if(options.out) {
+        fs.writeFileSync(options.out, content)
+        console.log(t`${size} apprenant(s) fictif(s) écrit(s) dans ${options.out}.`)
+    } else {
+        process.stdout.write(content)
+    }
+
+    if(db != null) {
+        // The roster may be on stdout, keep it free of anything else.
+        const report = options.out ? console.log : log.info
+        const names = students.map((student) => student.name)
+        for(const { date, groups } of await fakeHistory(names, briefs, { groupSize, random })) {
+            addBrief(db, groups, { date, label: 'Brief fictif' })
+        }
+        saveDb(db)
+        report(t`${briefs} brief(s) fictif(s) enregistré(s) dans ${db.file}.`)
+    }
+}
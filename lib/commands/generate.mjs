@@ -0,0 +1,342 @@
+import fs from 'fs'
+import path from 'path'
+import { log } from '../log.mjs'
+import { DEFAULT_ITERATIONS, parseGroupSize, parseTieBreak } from '../matcher.mjs'
+import { addBrief, importConfigConstraints, markConstraintsApplied, openDb, saveDb } from '../db.mjs'
+import { parseCategory } from '../categories.mjs'
+import { loadConfig } from '../config.mjs'
+import { describeConstraint } from '../constraints.mjs'
+import { absentees, drawGroups, drawPlan, generationContext } from '../draw.mjs'
+import { DRIFT_THRESHOLD, rosterDrift } from '../drift.mjs'
+import { readTemplate, scaffoldFiles } from '../export/scaffold.mjs'
+import { writeFileAtomic } from '../files.mjs'
+import { formatDateTime, formatPercent } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { reviewRoster } from '../lint.mjs'
+import { sortNames } from '../names.mjs'
+import { Prompter, confirm } from '../prompt.mjs'
+import { registryCachePath, studentsPath } from '../paths.mjs'
+import { channelsByName } from '../publishers/channels.mjs'
+import { announce, schedule } from '../publishers/queue.mjs'
+import { parseSeed, randomSeed } from '../random.mjs'
+import { HttpRegistryProvider, LocalProvider, resolveRoster } from '../registry.mjs'
+import { FORMATS, formatGroups, formatMatrix, formatOfFile, parseFormat } from '../render.mjs'
+import { isGroupable, isRosterFile, loadRoster } from '../roster.mjs'
+import { RunLog } from '../runlog.mjs'
+import { isFirstRun, runWizard } from '../setup.mjs'
+import { interruptSignal } from '../signals.mjs'
+import { missingSkills } from '../skills.mjs'
+import { lowAttendance, pairCounts } from '../stats.mjs'
+import { activePhase, checkTeams, describeTeams } from '../teams.mjs'
+
+export const description = 'Forme les groupes de la promo en évitant le brief précédent'
+
+export const options = {
+    'dry-run': { type: 'boolean' },
+    format: { type: 'string' },
+    output: { type: 'string', short: 'o' },
+    'no-matrix': { type: 'boolean' },
+    draft: { type: 'boolean' },
+    name: { type: 'string' },
+    category: { type: 'string' },
+    'group-size': { type: 'string', default: '2' },
+    seed: { type: 'string' },
+    iterations: { type: 'string', default: String(DEFAULT_ITERATIONS) },
+    'time-budget': { type: 'string' },
+    'tie-break': { type: 'string', default: 'none' },
+    'log-file': { type: 'string' },
+    scaffold: { type: 'string' },
+    publish: { type: 'string', multiple: true },
+    'publish-at': { type: 'string' },
+    'on-publish-failure': { type: 'string', default: 'pending' },
+    registry: { type: 'string' },
+    require: { type: 'string' },
+    students: { type: 'string' },
+    exclude: { type: 'string' },
+    'exclude-file': { type: 'string' },
+    confirm: { type: 'boolean' },
+    yes: { type: 'boolean', short: 'y' },
+}
+
+/**
+ * What happens to a generated brief when --publish fails: kept with its
+ * announcements queued for `publish --retry-pending`, or removed again as
+ * long as no channel announced it.
+ */
+const PUBLISH_FAILURE_MODES = ['pending', 'rollback']
+
+export async function run(options, positionals) {
+    if(positionals.length == 0 && options.students == null && isFirstRun() && process.stdin.isTTY && process.stdout.isTTY) {
+        console.log(t`Bienvenue ! Ni configuration, ni liste des apprenants, ni historique ici.`)
+        await runWizard('.')
+        console.log(t`Relancez groupgen pour former les premiers groupes.`)
+        return
+    }
+    const config = loadConfig()
+    if(!PUBLISH_FAILURE_MODES.includes(options['on-publish-failure'])) {
+        throw new Error(t`--on-publish-failure invalide : ${options['on-publish-failure']} (${PUBLISH_FAILURE_MODES.join(', ')})`)
+    }
+    const dryRun = options['dry-run'] ?? false
+    // With --output, --format is that of the file and the terminal keeps the boxes.
+    const format = parseFormat(options.output == null ? options.format : 'table')
+    const output = options.output != null ? outputFormat(options.output, options.format) : null
+    // Outside of the boxes, stdout is left to the groups for scripts to read.
+    const notice = format == 'table' ? console.log : log.info
+    const groupSize = parseGroupSize(options['group-size'])
+    const tieBreak = parseTieBreak(options['tie-break'])
+    let seed = options.seed != null ? parseSeed(options.seed) : randomSeed()
+    const maxIterations = Number(options.iterations)
+    if(!Number.isInteger(maxIterations) || maxIterations < 1) {
+        throw new Error(t`--iterations invalide : ${options.iterations}`)
+    }
+    const timeBudget = options['time-budget'] == null ? Infinity : Number(options['time-budget']) * 1000
+    if(!(timeBudget > 0)) {
+        throw new Error(t`--time-budget invalide : ${options['time-budget']} (secondes)`)
+    }
+    if(options.draft && options.publish) {
+        throw new Error(t`--publish n'a pas de sens avec --draft, publiez le brief une fois activé`)
+    }
+    if(dryRun && options.publish) {
+        throw new Error(t`--publish n'a pas de sens avec --dry-run, rien n'est enregistré`)
+    }
+    const label = options.name?.trim() || null
+    const category = options.category != null ? parseCategory(options.category, config) : null
+    if(options.name != null && label == null) {
+        throw new Error(t`--name vide, ex. : --name "Brief React #3"`)
+    }
+    const publishAt = options['publish-at'] != null ? new Date(options['publish-at']) : null
+    if(publishAt != null) {
+        if(!options.publish) {
+            throw new Error(t`--publish-at programme les canaux de --publish, ex. : --publish discord-promo --publish-at 2025-03-10T09:00`)
+        }
+        if(Number.isNaN(publishAt.getTime())) {
+            throw new Error(t`--publish-at invalide : ${options['publish-at']} (ex. : 2025-03-10T09:00)`)
+        }
+        if(publishAt <= new Date()) {
+            throw new Error(t`--publish-at est déjà passé : ${options['publish-at']}`)
+        }
+    }
+    if(dryRun && options.scaffold) {
+        throw new Error(t`--scaffold n'a pas de sens avec --dry-run, rien n'est enregistré`)
+    }
+    if(options.scaffold && fs.existsSync(options.scaffold) && fs.readdirSync(options.scaffold).length > 0) {
+        throw new Error(t`${options.scaffold} n'est pas vide, choisissez un autre dossier pour --scaffold`)
+    }
+    const template = options.scaffold && config.scaffold?.template ? readTemplate(config.scaffold.template) : null
+    const channels = channelsByName(options.publish ?? [], config)
+    const db = openDb()
+    importConfigConstraints(db, config, { save: !dryRun })
+    const provider = options.registry
+        ? new HttpRegistryProvider(options.registry, { cacheFile: registryCachePath(db.file), token: process.env.SGG_REGISTRY_TOKEN })
+        : new LocalProvider()
+    if(positionals.length > 1) {
+        throw new Error(t`Une seule liste d'apprenants attendue, reçu : ${positionals.join(' ')}`)
+    }
+    const source = options.students ?? positionals[0] ?? studentsPath()
+    if(isRosterFile(source)) {
+        await reviewRoster(source, loadRoster(source, config), { interactive: !options.yes })
+    }
+    const roster = await resolveRoster(loadRoster(source, config), provider)
+    log.verbose(t`Promo : ${roster.length} apprenants lus dans ${source}`)
+    const excluded = absentees(options, roster)
+    const peoples = roster.filter((name) => !excluded.includes(name))
+    if(excluded.length > 0) {
+        log.info(t`Absent(s), laissé(s) de côté pour ce brief : ${sortNames(excluded).join(', ')}`)
+    }
+    if(options.registry || /^ldaps?:\/\//.test(source)) {
+        db.data.roster_synced_at = new Date().toISOString()
+    }
+    const phase = activePhase(db.data.teams ?? [])
+    if(phase != null) {
+        reportFixedTeams(phase, peoples)
+        return
+    }
+    if(!isGroupable(peoples)) {
+        return
+    }
+    const plan = drawPlan(db, config, peoples, { category, require: options.require })
+    const { history, counted, previous, constraints, skills } = plan
+
+    const drift = rosterDrift(peoples, history)
+    if(history.length > 0 && drift.share >= DRIFT_THRESHOLD && !options.yes) {
+        log.warn(t`${formatPercent(drift.share)} des apprenants de la liste n'apparaissent pas dans l'historique (${db.file}),`)
+        log.warn(t`et ${drift.missing.length} noms des derniers briefs sont absents de la liste. Mauvais dossier ou mauvaise promo ?`)
+        if(!await confirm(t`Continuer quand même ? [o/N] `)) {
+            throw new Error(t`Génération annulée`)
+        }
+    }
+
+    const signal = interruptSignal()
+
+    const runLog = new RunLog()
+    runLog.section(t`Exécution du ${new Date().toISOString()}`, [t`Historique : ${db.file}`])
+    runLog.section(t`Promo (${peoples.length} apprenants)`, peoples)
+    runLog.section(t`Contraintes`, [
+        previous == null ? t`pas de brief précédent` : t`éviter les ${previous.groups.length} groupes du brief n°${previous.id}`,
+        ...(counted != null ? [t`briefs pris en compte : ${counted.join(', ')}`] : []),
+        ...constraints.map(describeConstraint),
+    ])
+    if(skills != null) {
+        runLog.section(t`Compétences requises dans chaque groupe`, skills.required)
+    }
+    if(groupSize != 2) {
+        runLog.section(t`Taille des groupes`, t`${groupSize} apprenants`)
+    }
+    if(tieBreak != 'none') {
+        runLog.section(t`Départage`, tieBreak)
+    }
+    // In a terminal the groups are shown before anything is saved, and
+    // another draw, with a fresh seed, can be asked for. Machine-readable
+    // formats are meant for scripts, which are not asked unless --confirm.
+    const review = !dryRun && !options.yes && (options.confirm ?? (format == 'table' && process.stdin.isTTY) ?? false)
+    const prompter = review ? new Prompter() : null
+    let result, duration_ms
+    let accepted = !review
+    for(let draw = 1; ; draw++) {
+        runLog.section(t`Graine`, options.seed == null || draw > 1 ? t`${seed} (tirée au hasard)` : String(seed))
+
+        const started = performance.now()
+        result = await drawGroups(plan, peoples, { seed, groupSize, tieBreak, iterations: maxIterations, timeBudget, signal })
+        const { groups, repeats, violations, iterations, interrupted, outOfTime } = result
+        duration_ms = Math.round(performance.now() - started)
+        log.verbose(t`Recherche : ${iterations} tirage(s) en ${duration_ms} ms avec la graine ${seed}, ${repeats} binôme(s) répété(s)`)
+        log.debug(t`Meilleurs scores successifs : ${result.improvements.join(', ')}`)
+
+        runLog.section(t`Recherche`, [
+            t`itérations : ${iterations}`,
+            t`binômes répétés : ${repeats}`,
+            t`contraintes non respectées : ${violations}`,
+            t`interrompue : ${interrupted ? t`oui` : outOfTime ? t`budget de temps atteint` : t`non`}`,
+        ])
+        runLog.section(t`Groupes`, groups.map((group, index) => `${index + 1}. ${sortNames(group).join(', ')}`))
+
+        if(outOfTime) {
+            notice(t`Budget de temps atteint après ${iterations} tirage(s) sur ${maxIterations}.`)
+        }
+        if(interrupted) {
+            notice(t`Recherche interrompue, meilleur résultat trouvé (${repeats} binôme(s) déjà vu(s)) :`)
+        }
+        console.log(formatGroups(groups, format))
+        if(format == 'table' && !options['no-matrix']) {
+            console.log(t`\nBriefs passés ensemble, ce tirage compris :`)
+            console.log(formatMatrix(peoples, pairCounts([...history.map((brief) => brief.groups), groups]), format, { lowAttendance: lowAttendance(history) }))
+        }
+        if(violations > 0) {
+            log.warn(t`${violations} contrainte(s) "jamais ensemble" n'ont pas pu être respectées`)
+        }
+        if(skills != null) {
+            groups.forEach((group, index) => {
+                const missing = missingSkills(group, skills.required, skills.mastered)
+                if(missing.length > 0) {
+                    log.warn(t`Groupe ${index + 1} : personne ne maîtrise ${missing.join(', ')}`)
+                }
+            })
+        }
+
+        if(!review || interrupted) {
+            break
+        }
+        const answer = (await prompter.ask(t`Enregistrer ces groupes ? [o/N/r] `))?.trim().toLowerCase() ?? ''
+        if(answer != 'r') {
+            accepted = /^(o|oui|y|yes)$/.test(answer)
+            break
+        }
+        seed = randomSeed()
+        notice(t`Nouveau tirage, graine ${seed} :`)
+    }
+    prompter?.close()
+    const { groups, interrupted } = result
+
+    if(output != null && (dryRun || accepted)) {
+        fs.mkdirSync(path.dirname(options.output), { recursive: true })
+        writeFileAtomic(options.output, `${formatGroups(groups, output)}\n`)
+        notice(t`Groupes écrits dans ${options.output}.`)
+    }
+    if(dryRun) {
+        notice(t`Simulation (--dry-run) : brief non enregistré.`)
+        runLog.section(t`Enregistrement`, t`simulation, non enregistré`)
+    } else if(interrupted ? await confirm(t`Enregistrer ce résultat ? [o/N] `) : accepted) {
+        const context = generationContext(plan, peoples, { seed, result, groupSize, tieBreak, excluded })
+        const saved = structuredClone(db.data)
+        const brief = addBrief(db, groups, { label, category, duration_ms, context, draft: options.draft })
+        if(!options.draft) {
+            markConstraintsApplied(constraints, brief)
+        }
+        saveDb(db)
+        notice(options.draft
+            ? t`Brouillon n°${brief.id}${label ? ` « ${label} »` : ''} enregistré, à modifier avec late-add et drop puis à valider avec : groupgen brief activate ${brief.id}`
+            : t`Brief n°${brief.id}${label ? ` « ${label} »` : ''} enregistré.`)
+        runLog.section(t`Enregistrement`, t`brief n°${brief.id} dans ${db.file}`)
+        if(options.scaffold) {
+            writeScaffold(options.scaffold, scaffoldFiles(brief, { template, deliverables: config.scaffold?.deliverables ?? [] }))
+            notice(t`Dossiers des groupes créés dans ${options.scaffold}, noms de dépôts ou de branches dans ${path.join(options.scaffold, 'names.txt')}.`)
+        }
+        if(publishAt != null) {
+            schedule(db, brief, channels, publishAt)
+            notice(t`Annonce programmée le ${formatDateTime(publishAt)} sur ${channels.length} canal(aux), envoyée par groupgen publish --retry-pending lancé après cette heure (tâche cron ou planifiée).`)
+        } else if(channels.length > 0) {
+            await announce(db, brief, channels, config, { rollback: options['on-publish-failure'] == 'rollback' ? saved : undefined })
+        }
+    } else {
+        notice(t`Résultat non enregistré.`)
+        runLog.section(t`Enregistrement`, t`non enregistré`)
+        if(interrupted) {
+            process.exitCode = 130
+        }
+    }
+
+    if(options['log-file']) {
+        runLog.write(options['log-file'])
+    }
+}
+
+/**
+ * @param {string} dir
+ * @param {Map<string, string | Buffer>} files
+ */
+function writeScaffold(dir, files) {
+    for(const [file, content] of files) {
+        const target = path.join(dir, file)
+        fs.mkdirSync(path.dirname(target), { recursive: true })
+        fs.writeFileSync(target, content)
+    }
+}
+
+/**
+ * Format of the --output file: --format when given, otherwise guessed
+ * from the extension.
+ *
+ * @param {string} file
+ * @param {string | undefined} format
+ */
+function outputFormat(file, format) {
+    if(format != null) {
+        return parseFormat(format)
+    }
+    const guessed = formatOfFile(file)
+    if(guessed == null) {
+        throw new Error(t`Format de ${file} inconnu : extension .md, .json, .csv ou .txt, ou --format ${FORMATS.filter((name) => name != 'table').join('|')}`)
+    }
+    return guessed
+}
+
+/**
+ * Shown instead of generating while fixed teams are in place, with the
+ * students they no longer match.
+ *
+ * @param {import('../teams.mjs').TeamPhase} phase
+ * @param {string[]} peoples
+ */
+function reportFixedTeams(phase, peoples) {
+    console.log(describeTeams(phase))
+    const { unknown, unassigned } = checkTeams(phase.teams, peoples)
+    if(unknown.length > 0) {
+        log.warn(t`Absents de la liste des apprenants : ${unknown.join(', ')}`)
+    }
+    if(unassigned.length > 0) {
+        log.warn(t`Sans équipe : ${unassigned.join(', ')}`)
+    }
+    console.log(phase.until
+        ? t`Équipes fixes en place jusqu'au ${phase.until}, pas de nouveau brief (groupgen teams end pour reprendre la rotation).`
+        : t`Équipes fixes en place, pas de nouveau brief (groupgen teams end pour reprendre la rotation).`)
+}
@@ -0,0 +1,41 @@
+import { openDb, pastBriefs } from '../db.mjs'
+import { formatNumber, formatPercent } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { sortNames } from '../names.mjs'
+import { graphMetrics } from '../stats.mjs'
+
+export const description = 'Analyse le graphe des collaborations de la promo (stats : composantes, diamètre, clustering)'
+
+export const options = {}
+
+function stats() {
+    const history = pastBriefs(openDb().data.briefs).map((brief) => brief.groups)
+    if(history.length == 0) {
+        console.log(t`Aucun brief enregistré.`)
+        return
+    }
+    const metrics = graphMetrics(history)
+    console.log(t`Graphe des collaborations après ${history.length} brief(s) :`)
+    console.log(`  ${t`${metrics.students} apprenants, ${metrics.edges} binômes formés au moins une fois (densité ${formatPercent(metrics.density)})`}`)
+    console.log(`  ${t`Composantes connexes : ${metrics.components.length}`}`)
+    if(metrics.components.length > 1) {
+        metrics.components.forEach((component, index) => console.log(`    ${index + 1}. ${sortNames(component).join(', ')}`))
+    }
+    console.log(`  ${metrics.components.length > 1
+        ? t`Diamètre : ${metrics.diameter} (plus longue chaîne de collaborations reliant deux apprenants d'une même composante)`
+        : t`Diamètre : ${metrics.diameter} (plus longue chaîne de collaborations reliant deux apprenants)`}`)
+    console.log(`  ${t`Coefficient de clustering moyen : ${formatNumber(metrics.clustering, 2)} (0 : les partenaires d'un apprenant ne se connaissent pas, 1 : tous ont travaillé ensemble)`}`)
+}
+
+const ACTIONS = { stats }
+
+/** Sub-commands, for shell completion. */
+export const actions = Object.keys(ACTIONS)
+
+export async function run(options, [action]) {
+    const handler = Object.hasOwn(ACTIONS, action) ? ACTIONS[action] : null
+    if(handler == null) {
+        throw new Error(t`Action inconnue : ${action ?? t`(aucune)`}, actions disponibles : ${actions.join(', ')}`)
+    }
+    await handler(options)
+}
@@ -0,0 +1,44 @@
+import { symbols } from '../console.mjs'
+import { briefState, briefsAsOf, openDb } from '../db.mjs'
+import { formatDate } from '../format.mjs'
+import { t } from '../i18n.mjs'
+
+export const description = 'Liste les briefs enregistrés, du plus récent au plus ancien'
+
+export const options = {
+    last: { type: 'string', short: 'n' },
+    'as-of': { type: 'string' },
+}
+
+export async function run(options) {
+    const limit = options.last == null ? Infinity : Number(options.last)
+    if(!(limit > 0) || (limit != Infinity && !Number.isInteger(limit))) {
+        throw new Error(t`--last invalide : ${options.last}`)
+    }
+    const saved = openDb().data.briefs
+    const briefs = options['as-of'] != null ? briefsAsOf(saved, options['as-of']) : saved
+    if(briefs.length == 0) {
+        console.log(t`Aucun brief enregistré.`)
+        return
+    }
+    const shown = briefs.slice(-Math.min(limit, briefs.length)).reverse()
+    const width = String(shown[0].id).length
+    for(const brief of shown) {
+        const students = brief.groups.flat().length
+        const details = [
+            brief.date ? formatDate(brief.date) : t`date inconnue`,
+            t`${brief.groups.length} groupes, ${students} apprenants`,
+            ...(brief.label ? [brief.label] : []),
+            ...(brief.category ? [brief.category] : []),
+            ...(brief.cloned_from != null ? [t`copie du n°${brief.cloned_from}`] : []),
+            ...(briefState(brief) == 'draft' ? [t`brouillon`] : briefState(brief) == 'closed' ? [t`clos`] : []),
+            ...(brief.amendments?.length ? [t`${brief.amendments.length} modification(s)`] : []),
+        ]
+        console.log(symbols.linear
+            ? t`Brief n°${brief.id} : ${details.join(', ')}.`
+            : `${t`n°${String(brief.id).padStart(width)}`}  ${details.join(' | ')}`)
+    }
+    if(shown.length < briefs.length) {
+        console.log(t`… ${briefs.length - shown.length} brief(s) plus ancien(s).`)
+    }
+}
@@ -0,0 +1,47 @@
+/**
+ * Command modules are only loaded when run, so that everyday commands
+ * don't pay for the integrations (HTTP publishers, SMTP, LDAP...).
+ *
+ * @type {Record<string, () => Promise<{description: string, options: import('util').ParseArgsConfig['options'], run: Function}>>}
+ */
+export const COMMANDS = {
+    generate: () => import('./generate.mjs'),
+    quick: () => import('./quick.mjs'),
+    init: () => import('./init.mjs'),
+    cohort: () => import('./cohort.mjs'),
+    adopt: () => import('./adopt.mjs'),
+    brief: () => import('./brief.mjs'),
+    history: () => import('./history.mjs'),
+    calendar: () => import('./calendar.mjs'),
+    trash: () => import('./trash.mjs'),
+    teams: () => import('./teams.mjs'),
+    'assign-slots': () => import('./assign-slots.mjs'),
+    'assign-observers': () => import('./assign-observers.mjs'),
+    usage: () => import('./usage.mjs'),
+    constraints: () => import('./constraints.mjs'),
+    explain: () => import('./explain.mjs'),
+    export: () => import('./export.mjs'),
+    publish: () => import('./publish.mjs'),
+    invites: () => import('./invites.mjs'),
+    'late-add': () => import('./late-add.mjs'),
+    drop: () => import('./drop.mjs'),
+    regenerate: () => import('./regenerate.mjs'),
+    'simulate-policies': () => import('./simulate-policies.mjs'),
+    matrix: () => import('./matrix.mjs'),
+    stats: () => import('./stats.mjs'),
+    graph: () => import('./graph.mjs'),
+    'self-update': () => import('./self-update.mjs'),
+    site: () => import('./site.mjs'),
+    status: () => import('./status.mjs'),
+    intake: () => import('./intake.mjs'),
+    survey: () => import('./survey.mjs'),
+    'compare-cohorts': () => import('./compare-cohorts.mjs'),
+    'suggest-config': () => import('./suggest-config.mjs'),
+    db: () => import('./db.mjs'),
+    secrets: () => import('./secrets.mjs'),
+    'fake-roster': () => import('./fake-roster.mjs'),
+    completions: () => import('./completions.mjs'),
+    tui: () => import('./tui.mjs'),
+    shell: () => import('./shell.mjs'),
+}
+export const DEFAULT_COMMAND = 'generate'
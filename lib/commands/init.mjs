@@ -0,0 +1,52 @@
+import fs from 'fs'
+import path from 'path'
+import { CONFIG_FILE } from '../config.mjs'
+import { openDb, saveDb } from '../db.mjs'
+import { t } from '../i18n.mjs'
+import { configTemplate, runWizard } from '../setup.mjs'
+
+export const description = 'Prépare un dossier de promo : students.json à remplir, historique vide et groupgen.toml par défaut, ou pas à pas avec --wizard'
+
+export const options = {
+    wizard: { type: 'boolean' },
+}
+
+export async function run(options, positionals) {
+    if(positionals.length > 1) {
+        throw new Error(t`Un seul dossier attendu, reçu : ${positionals.join(' ')}`)
+    }
+    const dir = positionals[0] ?? '.'
+    if(fs.existsSync(dir) && !fs.statSync(dir).isDirectory()) {
+        throw new Error(t`${dir} n'est pas un dossier`)
+    }
+    if(options.wizard) {
+        await runWizard(dir)
+        return
+    }
+    fs.mkdirSync(dir, { recursive: true })
+
+    const files = {
+        'students.json': (file) => fs.writeFileSync(file, '[\n]\n'),
+        'db.json': (file) => saveDb(openDb(file, { cohort: null })),
+        [CONFIG_FILE]: (file) => fs.writeFileSync(file, configTemplate()),
+    }
+    const roster = path.join(dir, 'students.json')
+    const fresh = !fs.existsSync(roster)
+    for(const [name, create] of Object.entries(files)) {
+        const file = path.join(dir, name)
+        if(fs.existsSync(file)) {
+            console.log(t`Conservé : ${file} (déjà présent)`)
+        } else {
+            create(file)
+            console.log(t`Créé : ${file}`)
+        }
+    }
+    if(!fresh) {
+        return
+    }
+    console.log('')
+    console.log(t`Ajoutez les apprenants dans ${roster}, un nom par ligne entre guillemets, séparés par des virgules,`)
+    console.log(dir == '.'
+        ? t`puis lancez groupgen pour former les premiers groupes.`
+        : t`puis lancez groupgen depuis ${dir} pour former les premiers groupes.`)
+}
@@ -0,0 +1,48 @@
+import fs from 'fs'
+import { loadConfig } from '../config.mjs'
+import { writeFileAtomic } from '../files.mjs'
+import { fuzzySearch } from '../fuzzy.mjs'
+import { t } from '../i18n.mjs'
+import { parseIntake } from '../intake.mjs'
+import { log } from '../log.mjs'
+import { studentsPath } from '../paths.mjs'
+import { loadRoster, rosterExists } from '../roster.mjs'
+import { formatCompetencies, loadCompetencies } from '../skills.mjs'
+
+export const description = 'Importe les auto-évaluations d\'un formulaire (export CSV) dans le fichier de compétences'
+
+export const options = {
+    form: { type: 'string' },
+}
+
+export async function run(options) {
+    if(options.form == null) {
+        throw new Error(t`Export du formulaire manquant, ex. : groupgen intake --form responses.csv`)
+    }
+    const config = loadConfig()
+    const mapping = config.intake
+    if(mapping?.name_column == null || mapping.skills == null) {
+        throw new Error(t`Correspondance des colonnes manquante : name_column et [intake.skills] dans groupgen.toml`)
+    }
+    const answers = parseIntake(fs.readFileSync(options.form, 'utf8'), mapping, options.form)
+
+    const file = config.skills?.file ?? 'competencies.csv'
+    const competencies = fs.existsSync(file) ? loadCompetencies(file) : new Map()
+    for(const [name, levels] of answers) {
+        competencies.set(name, new Map([...(competencies.get(name) ?? []), ...levels]))
+    }
+    writeFileAtomic(file, formatCompetencies(competencies))
+    console.log(t`${answers.size} auto-évaluation(s) importée(s) dans ${file}.`)
+
+    if(rosterExists(studentsPath())) {
+        const roster = loadRoster(studentsPath(), config)
+        for(const name of answers.keys()) {
+            if(!roster.includes(name)) {
+                const [suggestion] = fuzzySearch(name, roster)
+                log.warn(suggestion
+                    ? t`${name} n'est pas dans la liste des apprenants, ${suggestion} ?`
+                    : t`${name} n'est pas dans la liste des apprenants`)
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+import fs from 'fs'
+import path from 'path'
+import { briefTitle } from '../calendar.mjs'
+import { loadConfig } from '../config.mjs'
+import { findBrief, lastBrief, openDb, parseBriefId } from '../db.mjs'
+import { formatDate } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { calendar, stableUid } from '../ics.mjs'
+import { log } from '../log.mjs'
+import { slug, sortNames } from '../names.mjs'
+import { studentsPath } from '../paths.mjs'
+import { channelSecret } from '../publishers/index.mjs'
+import { loadStudents, parseNameList } from '../roster.mjs'
+import { sendMail } from '../smtp.mjs'
+
+export const description = 'Crée une invitation calendrier (.ics) par apprenant pour un brief, en fichiers ou par email'
+
+export const options = {
+    brief: { type: 'string' },
+    date: { type: 'string' },
+    start: { type: 'string', default: '09:00' },
+    end: { type: 'string', default: '17:00' },
+    rooms: { type: 'string' },
+    out: { type: 'string' },
+    email: { type: 'string' },
+    students: { type: 'string' },
+}
+
+export async function run(options) {
+    const db = openDb()
+    const brief = options.brief ? findBrief(db, parseBriefId(options.brief)) : lastBrief(db)
+    if(brief == null) {
+        throw new Error(t`Aucun brief enregistré`)
+    }
+    const date = options.date ?? brief.date?.slice(0, 10)
+    if(!/^\d{4}-\d{2}-\d{2}$/.test(date ?? '')) {
+        throw new Error(t`Date du brief manquante ou invalide, ex. : --date 2024-10-14`)
+    }
+    for(const time of [options.start, options.end]) {
+        if(!/^\d{2}:\d{2}$/.test(time)) {
+            throw new Error(t`Heure invalide : ${time} (HH:MM)`)
+        }
+    }
+    const config = loadConfig()
+    const students = new Map(loadStudents(options.students ?? studentsPath(), config).map((student) => [student.name, student]))
+    const rooms = options.rooms ? parseNameList(options.rooms) : []
+    const channel = options.email ? (config.channels ?? []).find((channel) => channel.name == options.email && channel.type == 'email') : null
+    if(options.email && channel == null) {
+        throw new Error(t`Canal email inconnu dans groupgen.toml : ${options.email}`)
+    }
+
+    const title = briefTitle(brief)
+    const outDir = options.out ?? `invites/brief-${brief.id}`
+    let written = 0
+    let sent = 0
+    for(const [index, group] of brief.groups.entries()) {
+        const room = rooms.length > 0 ? rooms[index % rooms.length] : undefined
+        const slot = brief.slots?.assignment[index]
+        for(const name of group) {
+            const partners = sortNames(group.filter((other) => other != name))
+            const event = {
+                uid: stableUid(brief.id, name),
+                summary: t`${title} avec ${partners.join(', ')}`,
+                description: [t`Groupe ${index + 1} : ${sortNames(group).join(', ')}`, slot ? t`Passage : ${slot}` : null].filter(Boolean).join('\n'),
+                location: room,
+                start: `${date}T${options.start}`,
+                end: `${date}T${options.end}`,
+                attendees: group.map((member) => ({ name: member, email: students.get(member)?.email })),
+            }
+            const email = students.get(name)?.email
+            if(channel && email) {
+                const ics = calendar([{ ...event, organizer: { name: channel.from, email: channel.from } }], { method: 'REQUEST' })
+                await sendMail({
+                    host: channel.smtp_host,
+                    port: channel.smtp_port,
+                    user: channel.smtp_user,
+                    password: channel.smtp_user ? channelSecret(channel, 'smtp_password') : undefined,
+                    plaintextAuth: channel.smtp_plaintext_auth == true,
+                }, {
+                    from: channel.from,
+                    to: [email],
+                    subject: t`Invitation : ${event.summary}`,
+                    text: `${event.summary}\n${event.description}\n${t`${formatDate(date)} de ${options.start} à ${options.end}`}${room ? `, ${room}` : ''}`,
+                    attachments: [{ filename: 'invitation.ics', contentType: 'text/calendar; method=REQUEST; charset=utf-8', content: ics }],
+                })
+                sent++
+            } else {
+                if(channel) {
+                    log.warn(t`${name} n'a pas d'email dans la liste, invitation écrite en fichier`)
+                }
+                fs.mkdirSync(outDir, { recursive: true })
+                fs.writeFileSync(path.join(outDir, `${slug(name)}.ics`), calendar([event]))
+                written++
+            }
+        }
+    }
+    if(sent > 0) {
+        console.log(t`${sent} invitation(s) envoyée(s) par email.`)
+    }
+    if(written > 0) {
+        console.log(t`${written} invitation(s) écrite(s) dans ${outDir}.`)
+    }
+}
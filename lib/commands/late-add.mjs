@@ -0,0 +1,62 @@
+import { backupBeforeDestructive } from '../backup.mjs'
+import { loadConfig } from '../config.mjs'
+import { confidentialConstraints } from '../confidential.mjs'
+import { activeConstraints } from '../constraints.mjs'
+import { amendBrief, briefState, findBrief, importConfigConstraints, lastBrief, openDb, parseBriefId, pastBriefs, saveDb } from '../db.mjs'
+import { t } from '../i18n.mjs'
+import { bestGroupFor } from '../matcher.mjs'
+import { channelsByName } from '../publishers/channels.mjs'
+import { announce } from '../publishers/queue.mjs'
+import { renderGroups } from '../render.mjs'
+
+export const description = 'Ajoute un apprenant arrivé en retard au groupe le plus adapté d\'un brief enregistré'
+
+export const options = {
+    brief: { type: 'string' },
+    'max-size': { type: 'string' },
+    channel: { type: 'string', short: 'c', multiple: true },
+}
+
+export async function run(options, positionals) {
+    const name = positionals.join(' ').trim()
+    if(name == '') {
+        throw new Error(t`Nom de l'apprenant manquant, ex. : groupgen late-add "Alice Martin" --brief 12`)
+    }
+    const config = loadConfig()
+    const channels = channelsByName(options.channel ?? [], config)
+    const db = openDb()
+    importConfigConstraints(db, config)
+    const brief = options.brief ? findBrief(db, parseBriefId(options.brief)) : lastBrief(db)
+    if(brief == null) {
+        throw new Error(t`Aucun brief enregistré`)
+    }
+    if(briefState(brief) == 'closed') {
+        throw new Error(t`Le brief n°${brief.id} est clos, ses groupes ne changent plus`)
+    }
+    // One more than the size the brief was generated with, a trio among pairs.
+    const maxSize = Number(options['max-size'] ?? (brief.context?.group_size ?? 2) + 1)
+    if(!Number.isInteger(maxSize) || maxSize < 2) {
+        throw new Error(t`Taille maximale invalide : ${options['max-size']}`)
+    }
+    if(brief.groups.some((group) => group.includes(name))) {
+        throw new Error(t`${name} fait déjà partie du brief n°${brief.id}`)
+    }
+
+    const previous = pastBriefs(db.data.briefs).filter((other) => other.id < brief.id).at(-1)
+    const members = [...brief.groups.flat(), name]
+    const constraints = [...activeConstraints(db.data.constraints, members), ...confidentialConstraints(db, members)]
+    const index = bestGroupFor(name, brief.groups, previous?.groups ?? null, { maxSize, constraints })
+    if(index == -1) {
+        throw new Error(t`Tous les groupes du brief n°${brief.id} ont déjà ${maxSize} membres ou plus (--max-size)`)
+    }
+    backupBeforeDestructive(db, config, `late-add ${name} au brief n°${brief.id}`)
+    brief.groups[index].push(name)
+    amendBrief(brief, { action: 'add', student: name, group: index })
+    saveDb(db)
+    console.log(renderGroups(brief.groups))
+    console.log(t`${name} rejoint le groupe ${index + 1} du brief n°${brief.id}.`)
+
+    if(channels.length > 0) {
+        await announce(db, brief, channels, config)
+    }
+}
@@ -0,0 +1,42 @@
+import { briefsAsOf, openDb, pastBriefs } from '../db.mjs'
+import { InputError } from '../errors.mjs'
+import { loadConfig } from '../config.mjs'
+import { formatDate } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { studentsPath } from '../paths.mjs'
+import { formatMatrix, parseFormat } from '../render.mjs'
+import { isGroupable, loadRoster, rosterExists } from '../roster.mjs'
+import { lowAttendance, pairCounts } from '../stats.mjs'
+
+export const description = 'Affiche la matrice des rencontres : combien de briefs chaque binôme a passé ensemble'
+
+export const options = {
+    format: { type: 'string', default: 'table' },
+    'as-of': { type: 'string' },
+    students: { type: 'string' },
+}
+
+export async function run(options) {
+    const format = parseFormat(options.format)
+    const db = openDb()
+    const saved = pastBriefs(db.data.briefs)
+    const briefs = options['as-of'] != null ? briefsAsOf(saved, options['as-of']) : saved
+    // Without a roster file, the matrix covers everyone found in the history.
+    const source = options.students ?? studentsPath()
+    const fromFile = options.students != null || rosterExists(source)
+    const roster = fromFile
+        ? loadRoster(source, loadConfig())
+        : [...new Set(briefs.flatMap((brief) => brief.groups.flat()))]
+    if(!fromFile && roster.length == 0) {
+        throw new InputError(t`Aucun apprenant : ni liste des apprenants ni brief enregistré`)
+    }
+    if(!isGroupable(roster)) {
+        return
+    }
+    if(format == 'table') {
+        console.log(options['as-of'] != null
+            ? t`Briefs passés ensemble, sur ${briefs.length} brief(s) au ${formatDate(options['as-of'])} :`
+            : t`Briefs passés ensemble, sur ${briefs.length} brief(s) :`)
+    }
+    console.log(formatMatrix(roster, pairCounts(briefs.map((brief) => brief.groups)), format, { lowAttendance: lowAttendance(briefs) }))
+}
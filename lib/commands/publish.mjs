@@ -0,0 +1,91 @@
+import { loadConfig } from '../config.mjs'
+import { findBrief, lastBrief, openDb, parseBriefId, saveDb } from '../db.mjs'
+import { formatDateTime } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { log } from '../log.mjs'
+import { channelsByName, reportResults } from '../publishers/channels.mjs'
+import { announce, retryPending } from '../publishers/queue.mjs'
+
+export const description = 'Publie un brief sur un ou plusieurs canaux (Discord, Slack, email, webhook, fichier, Trello, GitHub Projects, Notion, Matrix)'
+
+export const options = {
+    brief: { type: 'string' },
+    channel: { type: 'string', short: 'c', multiple: true },
+    'all-channels': { type: 'boolean' },
+    'retry-pending': { type: 'boolean' },
+    'trello-board': { type: 'string' },
+    'github-project': { type: 'string' },
+    'notion-database': { type: 'string' },
+    'matrix-room': { type: 'string' },
+    'matrix-homeserver': { type: 'string' },
+}
+
+/**
+ * Channels named with --channel (or all of them), plus the ad-hoc ones
+ * given directly as flags.
+ *
+ * @returns {Record<string, any>[]}
+ */
+function channelsFrom(options, config) {
+    const names = options['all-channels'] ? (config.channels ?? []).map((channel) => channel.name) : options.channel ?? []
+    const channels = channelsByName(names, config)
+    if(options['trello-board']) {
+        channels.push({ type: 'trello', board: options['trello-board'] })
+    }
+    if(options['github-project']) {
+        channels.push({ type: 'github-project', project: options['github-project'] })
+    }
+    if(options['notion-database']) {
+        channels.push({ type: 'notion', database: options['notion-database'] })
+    }
+    if(options['matrix-room']) {
+        const homeserver = options['matrix-homeserver'] ?? config.matrix?.homeserver
+        if(!homeserver) {
+            throw new Error(t`Serveur Matrix manquant : --matrix-homeserver ou homeserver dans [matrix] de groupgen.toml`)
+        }
+        channels.push({ type: 'matrix', room: options['matrix-room'], homeserver })
+    }
+    return channels
+}
+
+/**
+ * Sends the announcements queued after failed publications, and those
+ * scheduled with `generate --publish-at` once their time has come: meant
+ * to be run regularly, from cron or a scheduled task.
+ */
+async function retry(config) {
+    const db = openDb()
+    if((db.data.pending_publications ?? []).length == 0) {
+        console.log(t`Aucune annonce en attente.`)
+        return
+    }
+    const results = await retryPending(db, config)
+    saveDb(db)
+    reportResults(results)
+    const pending = db.data.pending_publications ?? []
+    const scheduled = pending.filter((entry) => entry.attempts == 0).map((entry) => new Date(entry.publish_at)).sort((a, b) => a - b)
+    if(scheduled.length > 0) {
+        console.log(t`${scheduled.length} annonce(s) programmée(s), la prochaine le ${formatDateTime(scheduled[0])}.`)
+    }
+    if(pending.length > scheduled.length) {
+        log.error(t`${pending.length - scheduled.length} annonce(s) toujours en attente.`)
+    }
+}
+
+export async function run(options) {
+    const config = loadConfig()
+    if(options['retry-pending']) {
+        await retry(config)
+        return
+    }
+    const channels = channelsFrom(options, config)
+    if(channels.length == 0) {
+        throw new Error(t`Aucune destination, ex. : groupgen publish --channel discord-promo ou --all-channels`)
+    }
+    const db = openDb()
+    const brief = options.brief ? findBrief(db, parseBriefId(options.brief)) : lastBrief(db)
+    if(brief == null) {
+        throw new Error(t`Aucun brief enregistré`)
+    }
+    await announce(db, brief, channels, config)
+}
@@ -0,0 +1,41 @@
+import { t } from '../i18n.mjs'
+import { generateGroups, parseGroupSize } from '../matcher.mjs'
+import { addBrief, openDb, saveDb } from '../db.mjs'
+import { dbPath } from '../paths.mjs'
+import { renderGroups } from '../render.mjs'
+import { isGroupable, parseNameList } from '../roster.mjs'
+import { stashSession } from '../sessions.mjs'
+import { interruptSignal } from '../signals.mjs'
+
+export const description = 'Forme des groupes à partir d\'une liste de noms, sans historique'
+
+export const options = {
+    save: { type: 'boolean' },
+    'group-size': { type: 'string', default: '2' },
+    name: { type: 'string' },
+}
+
+export async function run(options, [list]) {
+    if(list == null) {
+        throw new Error(t`Liste de noms manquante, ex. : groupgen quick "Alice,Bob,Carol"`)
+    }
+    if(options.name != null && !options.save) {
+        throw new Error(t`--name n'a de sens qu'avec --save`)
+    }
+    const groupSize = parseGroupSize(options['group-size'])
+    const peoples = parseNameList(list)
+    if(!isGroupable(peoples)) {
+        return
+    }
+    const { groups } = await generateGroups(peoples, null, { signal: interruptSignal(), groupSize })
+    console.log(renderGroups(groups))
+    if(options.save) {
+        const db = openDb()
+        const brief = addBrief(db, groups, { label: options.name?.trim() || null })
+        saveDb(db)
+        console.log(t`Brief n°${brief.id}${brief.label ? ` « ${brief.label} »` : ''} enregistré.`)
+    } else {
+        const token = stashSession(dbPath(), groups)
+        console.log(t`Non enregistré. Pour l'ajouter à l'historique plus tard : groupgen adopt ${token}`)
+    }
+}
@@ -0,0 +1,108 @@
+import { backupBeforeDestructive } from '../backup.mjs'
+import { loadConfig } from '../config.mjs'
+import { briefState, findBrief, importConfigConstraints, markConstraintsApplied, openDb, parseBriefId, pastBriefs, saveDb, trashVersion } from '../db.mjs'
+import { absentees, drawGroups, drawPlan, generationContext } from '../draw.mjs'
+import { t } from '../i18n.mjs'
+import { parseGroupSize, parseTieBreak } from '../matcher.mjs'
+import { studentsPath } from '../paths.mjs'
+import { parseSeed, randomSeed } from '../random.mjs'
+import { renderGroups } from '../render.mjs'
+import { isGroupable, loadRoster } from '../roster.mjs'
+
+export const description = 'Refait les groupes d\'un brief enregistré avec les présents, sous le même numéro (retards en masse)'
+
+export const options = {
+    brief: { type: 'string' },
+    force: { type: 'boolean' },
+    students: { type: 'string' },
+    exclude: { type: 'string' },
+    'exclude-file': { type: 'string' },
+    'group-size': { type: 'string' },
+    'tie-break': { type: 'string' },
+    require: { type: 'string' },
+    seed: { type: 'string' },
+}
+
+/**
+ * What was built on the groups of `brief` and would no longer match them:
+ * later briefs that avoided its pairs, and projects or threads opened for
+ * its groups.
+ *
+ * @param {import('../db.mjs').Db} db
+ * @param {import('../model.mjs').BriefRecord} brief
+ * @returns {string[]}
+ */
+function dependents(db, brief) {
+    const reasons = []
+    const later = pastBriefs(db.data.briefs).filter((other) => other.id > brief.id)
+    if(later.length > 0) {
+        reasons.push(t`${later.length} brief(s) tiré(s) après lui (n°${later.map((other) => other.id).join(', n°')})`)
+    }
+    if((brief.repositories ?? []).length > 0) {
+        reasons.push(t`${brief.repositories.length} dépôt(s) créé(s) pour ses groupes`)
+    }
+    if((brief.threads ?? []).length > 0) {
+        reasons.push(t`${brief.threads.length} fil(s) de discussion ouvert(s) pour ses groupes`)
+    }
+    return reasons
+}
+
+export async function run(options) {
+    if(options.brief == null) {
+        throw new Error(t`Brief à refaire manquant, ex. : groupgen regenerate --brief 12`)
+    }
+    const config = loadConfig()
+    const db = openDb()
+    importConfigConstraints(db, config)
+    const brief = findBrief(db, parseBriefId(options.brief))
+    if(briefState(brief) == 'closed') {
+        throw new Error(t`Le brief n°${brief.id} est clos, ses groupes ne changent plus`)
+    }
+    const reasons = dependents(db, brief)
+    if(reasons.length > 0 && !options.force) {
+        throw new Error(t`Le brief n°${brief.id} a servi depuis : ${reasons.join(', ')}. Relancez avec --force pour le refaire quand même`)
+    }
+
+    const roster = loadRoster(options.students ?? studentsPath(), config)
+    const excluded = absentees(options, roster)
+    const peoples = roster.filter((name) => !excluded.includes(name))
+    if(!isGroupable(peoples)) {
+        return
+    }
+    // Unless told otherwise, drawn again with the settings it was drawn with.
+    const groupSize = parseGroupSize(options['group-size'] ?? String(brief.context?.group_size ?? 2))
+    const tieBreak = parseTieBreak(options['tie-break'] ?? brief.context?.tie_break ?? 'none')
+    const seed = options.seed != null ? parseSeed(options.seed) : randomSeed()
+    const plan = drawPlan(db, config, peoples, {
+        category: brief.category ?? null,
+        before: brief.id,
+        require: options.require ?? brief.context?.required_skills?.join(','),
+    })
+    const started = performance.now()
+    const result = await drawGroups(plan, peoples, { seed, groupSize, tieBreak })
+
+    backupBeforeDestructive(db, config, `regenerate du brief n°${brief.id}`)
+    trashVersion(db, brief)
+    brief.groups = result.groups
+    brief.duration_ms = Math.round(performance.now() - started)
+    brief.context = { ...generationContext(plan, peoples, { seed, result, groupSize, tieBreak, excluded }), regenerated_at: new Date().toISOString() }
+    // All of these describe the old groups, index by index.
+    const stale = ['amendments', 'slots', 'observers', 'repositories', 'threads'].filter((key) => brief[key] != null)
+    for(const key of stale) {
+        delete brief[key]
+    }
+    if(briefState(brief) != 'draft') {
+        markConstraintsApplied(plan.constraints, brief)
+    }
+    saveDb(db)
+
+    console.log(renderGroups(brief.groups))
+    console.log(t`Brief n°${brief.id}${brief.label ? ` « ${brief.label} »` : ''} refait avec ${peoples.length} apprenants : ${result.repeats} binôme(s) répété(s)${result.violations > 0 ? t`, ${result.violations} contrainte(s) non respectée(s)` : ''}.`)
+    console.log(t`Composition précédente mise à la corbeille, groupgen trash restore ${brief.id} pour y revenir.`)
+    if(stale.includes('slots') || stale.includes('observers')) {
+        console.log(t`Créneaux et observateurs effacés, relancez assign-slots et assign-observers.`)
+    }
+    if(stale.includes('repositories') || stale.includes('threads')) {
+        console.log(t`Les dépôts et fils de discussion des anciens groupes existent toujours chez le fournisseur, à supprimer à la main.`)
+    }
+}
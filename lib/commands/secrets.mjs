@@ -0,0 +1,44 @@
+import { t } from '../i18n.mjs'
+import { deleteSecret, requireKeyring, setSecret } from '../keyring.mjs'
+import { askSecret } from '../prompt.mjs'
+
+export const description = 'Range les secrets des canaux dans le trousseau système (set, delete)'
+
+export const options = {}
+
+/**
+ * Stores a secret read from stdin, so it stays out of the shell history,
+ * without echo in a terminal.
+ */
+async function set(name) {
+    requireKeyring()
+    const value = await askSecret(t`Valeur de ${name} (masquée) : `)
+    if(!value) {
+        throw new Error(t`Valeur vide, rien n'a été enregistré`)
+    }
+    setSecret(name, value)
+    console.log(t`${name} enregistré dans le trousseau. Référencez-le avec <clé>_keyring = "${name}".`)
+}
+
+function remove(name) {
+    if(!deleteSecret(name)) {
+        throw new Error(t`Impossible de supprimer ${name} du trousseau`)
+    }
+    console.log(t`${name} supprimé du trousseau.`)
+}
+
+const ACTIONS = { set, delete: remove }
+
+/** Sub-commands, for shell completion. */
+export const actions = Object.keys(ACTIONS)
+
+export async function run(options, [action, name]) {
+    const handler = Object.hasOwn(ACTIONS, action) ? ACTIONS[action] : null
+    if(handler == null) {
+        throw new Error(t`Action inconnue : ${action ?? t`(aucune)`}, actions disponibles : ${actions.join(', ')}`)
+    }
+    if(!name) {
+        throw new Error(t`Nom du secret manquant`)
+    }
+    await handler(name)
+}
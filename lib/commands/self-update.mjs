@@ -0,0 +1,51 @@
+import { spawnSync } from 'child_process'
+import fs from 'fs'
+import os from 'os'
+import path from 'path'
+import { t } from '../i18n.mjs'
+import { CHECKSUMS_ASSET, compareVersions, download, latestRelease, verifyChecksum } from '../update.mjs'
+import { gitCommit, packageVersion } from '../version.mjs'
+
+export const description = 'Installe la dernière version publiée sur GitHub, après vérification de sa somme de contrôle'
+
+export const options = {
+    check: { type: 'boolean' },
+}
+
+export async function run(options) {
+    const current = packageVersion()
+    const release = await latestRelease()
+    if(compareVersions(release.version, current) <= 0) {
+        console.log(t`groupgen ${current} est à jour.`)
+        return
+    }
+    console.log(t`Nouvelle version disponible : ${release.version} (installée : ${current}), ${release.url}`)
+    if(options.check) {
+        return
+    }
+    if(gitCommit() != null) {
+        throw new Error(t`groupgen tourne depuis un dépôt git, mettez-le à jour avec git pull`)
+    }
+
+    const tarball = release.assets.find((asset) => asset.name.endsWith('.tgz'))
+    const sums = release.assets.find((asset) => asset.name == CHECKSUMS_ASSET)
+    if(tarball == null || sums == null) {
+        throw new Error(t`la release ${release.version} n'a pas d'archive .tgz ou de ${CHECKSUMS_ASSET}`)
+    }
+    const content = await download(tarball.browser_download_url)
+    verifyChecksum(content, (await download(sums.browser_download_url)).toString('utf8'), tarball.name)
+
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'groupgen-update-'))
+    try {
+        const file = path.join(dir, tarball.name)
+        fs.writeFileSync(file, content)
+        const npm = process.platform == 'win32' ? 'npm.cmd' : 'npm'
+        const result = spawnSync(npm, ['install', '--global', file], { stdio: 'inherit', shell: process.platform == 'win32' })
+        if(result.status != 0) {
+            throw new Error(t`npm install a échoué (code ${result.status ?? result.error?.message})`)
+        }
+    } finally {
+        fs.rmSync(dir, { recursive: true, force: true })
+    }
+    console.log(t`groupgen ${release.version} installé.`)
+}
@@ -0,0 +1,211 @@
+import { loadConfig } from '../config.mjs'
+import { confidentialConstraints } from '../confidential.mjs'
+import { activeConstraints } from '../constraints.mjs'
+import { addBrief, importConfigConstraints, markConstraintsApplied, openDb, pastBriefs, saveDb } from '../db.mjs'
+import { fold, fuzzySearch } from '../fuzzy.mjs'
+import { t } from '../i18n.mjs'
+import { generateGroups, parseGroupSize, scoreGroups } from '../matcher.mjs'
+import { studentsPath } from '../paths.mjs'
+import { Prompter } from '../prompt.mjs'
+import { randomSeed, seededRandom } from '../random.mjs'
+import { formatMatrix, renderGroups } from '../render.mjs'
+import { isGroupable, loadRoster } from '../roster.mjs'
+import { lowAttendance, pairCounts } from '../stats.mjs'
+
+export const description = 'Invite de commandes pour retoucher un tirage en cours (generate, swap, matrix, undo, save) sans relancer l\'outil'
+
+export const options = {
+    'group-size': { type: 'string', default: '2' },
+    students: { type: 'string' },
+}
+
+const help = () => [
+    `generate       ${t`nouveau tirage`}`,
+    `swap <A> <B>   ${t`échange deux apprenants de groupes différents ("Prénom Nom" entre guillemets, ou un bout du nom)`}`,
+    `undo           ${t`revient au tirage d'avant le dernier generate ou swap`}`,
+    `matrix         ${t`briefs passés ensemble, tirage en cours compris`}`,
+    `save           ${t`enregistre le tirage en cours comme brief`}`,
+    `quit           ${t`quitte (Ctrl-D aussi)`}`,
+]
+
+/**
+ * Words of a command line, "double quotes" keeping names with spaces
+ * together.
+ *
+ * @param {string} line
+ * @returns {string[]}
+ */
+function words(line) {
+    return (line.match(/"[^"]*"|\S+/g) ?? []).map((word) => word.replace(/^"(.*)"$/, '$1'))
+}
+
+/**
+ * The student of `roster` meant by `query`: the exact name, whatever its
+ * case and accents, or the only one matching it.
+ *
+ * @param {string} query
+ * @param {string[]} roster
+ */
+function findStudent(query, roster) {
+    const exact = roster.find((name) => fold(name) == fold(query))
+    if(exact != null) {
+        return exact
+    }
+    const matches = fuzzySearch(query, roster)
+    if(matches.length == 0) {
+        throw new Error(t`${query} : aucun apprenant de ce nom`)
+    }
+    if(matches.length > 1) {
+        throw new Error(t`${query} : ambigu (${matches.slice(0, 5).join(', ')}${matches.length > 5 ? '...' : ''})`)
+    }
+    return matches[0]
+}
+
+export async function run(options) {
+    const config = loadConfig()
+    const groupSize = parseGroupSize(options['group-size'])
+    const db = openDb()
+    importConfigConstraints(db, config)
+    const peoples = loadRoster(options.students ?? studentsPath(), config)
+    if(!isGroupable(peoples)) {
+        return
+    }
+    const constraints = activeConstraints(db.data.constraints, peoples)
+    const enforced = [...constraints, ...confidentialConstraints(db, peoples)]
+    const history = () => pastBriefs(db.data.briefs)
+
+    /** @type {{groups: string[][], seed: number, swaps: [string, string][], previous: any, iterations: number, improvements: number[]} | null} */
+    let draw = null
+    /** Draws before each generate or swap, for undo. */
+    const undo = []
+    let saved = null
+
+    const show = () => {
+        const { repeats, violations } = scoreGroups(peoples, draw.groups, draw.previous?.groups ?? null, enforced)
+        console.log(renderGroups(draw.groups))
+        const swaps = draw.swaps.length > 0 ? t`, ${draw.swaps.length} échange(s)` : ''
+        console.log(t`Graine ${draw.seed}${swaps} : ${repeats} binôme(s) répété(s)${violations > 0 ? t`, ${violations} contrainte(s) non respectée(s)` : ''}.`)
+    }
+    const current = () => {
+        if(draw == null) {
+            throw new Error(t`Aucun tirage en cours, tapez generate`)
+        }
+        if(saved != null) {
+            throw new Error(t`Tirage déjà enregistré comme brief n°${saved}, tapez generate pour un nouveau`)
+        }
+        return draw
+    }
+
+    const COMMANDS = {
+        async generate() {
+            const seed = randomSeed()
+            const previous = history().at(-1) ?? null
+            const result = await generateGroups(peoples, previous?.groups ?? null, { constraints: enforced, groupSize, random: seededRandom(seed) })
+            if(draw != null && saved == null) {
+                undo.push(draw)
+            }
+            draw = { groups: result.groups, seed, swaps: [], previous, iterations: result.iterations, improvements: result.improvements }
+            saved = null
+            show()
+        },
+        swap(args) {
+            const { groups } = current()
+            if(args.length != 2) {
+                throw new Error(t`Deux apprenants attendus, ex. : swap Alice "Bob Durand"`)
+            }
+            const [a, b] = args.map((query) => findStudent(query, peoples))
+            const groupA = groups.findIndex((group) => group.includes(a))
+            const groupB = groups.findIndex((group) => group.includes(b))
+            if(groupA == -1 || groupB == -1) {
+                throw new Error(t`${groupA == -1 ? a : b} n'est dans aucun groupe de ce tirage`)
+            }
+            if(groupA == groupB) {
+                throw new Error(t`${a} et ${b} sont déjà dans le même groupe`)
+            }
+            undo.push(structuredClone(draw))
+            groups[groupA][groups[groupA].indexOf(a)] = b
+            groups[groupB][groups[groupB].indexOf(b)] = a
+            draw.swaps.push([a, b])
+            console.log(t`${a} et ${b} échangés.`)
+            show()
+        },
+        undo() {
+            current()
+            if(undo.length == 0) {
+                throw new Error(t`Rien à annuler`)
+            }
+            draw = undo.pop()
+            show()
+        },
+        matrix() {
+            const groups = history().map((brief) => brief.groups)
+            if(draw != null && saved == null) {
+                groups.push(draw.groups)
+            }
+            console.log(formatMatrix(peoples, pairCounts(groups), 'table', { lowAttendance: lowAttendance(history()) }))
+        },
+        save() {
+            const { groups, seed, swaps, previous, iterations, improvements } = current()
+            // Scores are computed again, as swaps may have changed them.
+            const { repeats, violations, groupRepeats } = scoreGroups(peoples, groups, previous?.groups ?? null, enforced)
+            const context = {
+                roster: peoples,
+                previous_brief: previous?.id ?? null,
+                constraints: constraints.map((constraint) => constraint.id),
+                seed,
+                iterations,
+                repeats,
+                violations,
+                group_repeats: groupRepeats,
+                improvements,
+            }
+            if(groupSize != 2) {
+                context.group_size = groupSize
+            }
+            if(swaps.length > 0) {
+                context.swaps = swaps
+            }
+            const brief = addBrief(db, groups, { context })
+            markConstraintsApplied(constraints, brief)
+            saveDb(db)
+            saved = brief.id
+            undo.length = 0
+            console.log(t`Brief n°${brief.id} enregistré.`)
+        },
+        help() {
+            console.log(help().join('\n'))
+        },
+    }
+
+    console.log(t`${peoples.length} apprenants, groupes de ${groupSize}. Tapez help pour les commandes.`)
+    const prompter = new Prompter()
+    try {
+        for(;;) {
+            const line = await prompter.ask('groupgen> ')
+            if(line == null) {
+                break
+            }
+            const [command, ...args] = words(line)
+            if(command == null) {
+                continue
+            }
+            if(command == 'quit' || command == 'exit') {
+                break
+            }
+            if(!Object.hasOwn(COMMANDS, command)) {
+                console.log(t`Commande inconnue : ${command} (${Object.keys(COMMANDS).join(', ')}, quit)`)
+                continue
+            }
+            try {
+                await COMMANDS[command](args)
+            } catch(error) {
+                console.log(error.message)
+            }
+        }
+    } finally {
+        prompter.close()
+    }
+    if(draw != null && saved == null) {
+        console.log(t`Dernier tirage non enregistré.`)
+    }
+}
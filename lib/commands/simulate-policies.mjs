@@ -0,0 +1,43 @@
+import { formatPercent } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { DEFAULT_ITERATIONS } from '../matcher.mjs'
+import { POLICIES, simulatePolicy } from '../simulate.mjs'
+
+export const description = 'Compare la couverture et l\'équité de plusieurs réglages sur des semestres simulés'
+
+export const options = {
+    briefs: { type: 'string', default: '40' },
+    students: { type: 'string', default: '20' },
+    iterations: { type: 'string', default: String(DEFAULT_ITERATIONS) },
+}
+
+/**
+ * @param {string} value
+ * @param {string} flag
+ */
+function positiveInteger(value, flag) {
+    const number = Number(value)
+    if(!Number.isInteger(number) || number < 1) {
+        throw new Error(t`--${flag} invalide : ${value}`)
+    }
+    return number
+}
+
+export async function run(options) {
+    const briefs = positiveInteger(options.briefs, 'briefs')
+    const size = positiveInteger(options.students, 'students')
+    const iterations = positiveInteger(options.iterations, 'iterations')
+    if(size < 2) {
+        throw new Error(t`Au moins 2 apprenants sont nécessaires`)
+    }
+    const students = Array.from({ length: size }, (_, index) => `Apprenant ${index + 1}`)
+    const checkpoints = [...new Set([0.25, 0.5, 0.75, 1].map((share) => Math.max(1, Math.round(briefs * share))))]
+
+    console.log(t`${size} apprenants, ${briefs} briefs simulés par réglage.`)
+    console.log(t`Couverture (binômes possibles déjà formés) après ${checkpoints.join(' / ')} briefs :`)
+    for(const policy of POLICIES) {
+        const result = await simulatePolicy(students, briefs, policy, { iterations })
+        const curve = checkpoints.map((count) => formatPercent(result.coverage[count - 1]).padStart(5)).join(' ')
+        console.log(`  ${t(policy.name).padEnd(28)} ${curve}   ${t`${result.repeats} binôme(s) reformé(s), ${result.minPartners} à ${result.maxPartners} partenaires distincts par apprenant`}`)
+    }
+}
@@ -0,0 +1,22 @@
+import fs from 'fs'
+import path from 'path'
+import { openDb, pastBriefs } from '../db.mjs'
+import { buildSite } from '../export/site.mjs'
+import { t } from '../i18n.mjs'
+
+export const description = 'Génère un site statique de l\'historique (groupes actuels, briefs passés, matrice, pages apprenants)'
+
+export const options = {
+    out: { type: 'string', short: 'o', default: 'docs' },
+}
+
+export async function run(options) {
+    const db = openDb()
+    const files = buildSite(pastBriefs(db.data.briefs))
+    for(const [file, content] of files) {
+        const target = path.join(options.out, file)
+        fs.mkdirSync(path.dirname(target), { recursive: true })
+        fs.writeFileSync(target, content)
+    }
+    console.log(t`${files.size} pages écrites dans ${options.out}, à publier par exemple avec GitHub Pages.`)
+}
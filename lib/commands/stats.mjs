@@ -0,0 +1,83 @@
+import { briefsAsOf, openDb, pastBriefs } from '../db.mjs'
+import { formatDate, formatNumber } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { compareNames } from '../names.mjs'
+import { isGroupable } from '../roster.mjs'
+import { expectedRepeats, lowAttendance, repeatStats } from '../stats.mjs'
+
+export const description = 'Statistiques de répétition par apprenant, comparées au hasard avec --expected'
+
+export const options = {
+    expected: { type: 'boolean' },
+    runs: { type: 'string', default: '100' },
+    'as-of': { type: 'string' },
+}
+
+/**
+ * Risk of calling a student an anomaly by chance, split over the whole
+ * roster (Bonferroni) so that a large promo does not flag someone every
+ * time.
+ */
+const SIGNIFICANCE = 0.05
+
+/**
+ * Students of `brief` in the order the matcher saw them, with those
+ * added or dropped afterwards taken into account.
+ *
+ * @param {import('../model.mjs').BriefRecord} brief
+ */
+function generationRoster(brief) {
+    const present = brief.groups.flat()
+    const roster = (brief.context?.roster ?? []).filter((name) => present.includes(name))
+    return [...roster, ...present.filter((name) => !roster.includes(name))]
+}
+
+export async function run(options) {
+    const db = openDb()
+    const briefs = options['as-of'] != null ? briefsAsOf(pastBriefs(db.data.briefs), options['as-of']) : pastBriefs(db.data.briefs)
+    const history = briefs.map((brief) => brief.groups)
+    const rosters = briefs.map(generationRoster)
+    if(history.length == 0) {
+        console.log(t`Aucun brief enregistré.`)
+        return
+    }
+    const observed = [...repeatStats(history)].sort(([a, first], [b, second]) => second.repeats - first.repeats || compareNames(a, b))
+    if(!isGroupable(observed.map(([name]) => name))) {
+        return
+    }
+    const runs = Number(options.runs)
+    if(!Number.isInteger(runs) || runs < 1) {
+        throw new Error(t`--runs invalide : ${options.runs}`)
+    }
+    const samples = options.expected ? await expectedRepeats(rosters, runs) : null
+    const threshold = SIGNIFICANCE / observed.length
+    const absentees = lowAttendance(briefs)
+
+    console.log(options['as-of'] != null
+        ? t`${history.length} briefs, ${observed.length} apprenants au ${formatDate(options['as-of'])}.`
+        : t`${history.length} briefs, ${observed.length} apprenants.`)
+    const anomalies = []
+    for(const [name, student] of observed) {
+        const closest = student.closestCount > 1 ? t`, ${student.closestCount} fois avec ${student.closest}` : ''
+        const absences = absentees.has(name) ? t`, absent(e) à ${absentees.get(name).briefs - absentees.get(name).present} brief(s) *` : ''
+        let line = `  ${t`${name} : ${student.briefs} brief(s)${absences}, ${student.partners} partenaire(s) distinct(s), ${student.repeats} répétition(s)${closest}`}`
+        if(samples != null) {
+            const simulated = samples.get(name)
+            const mean = simulated.reduce((sum, value) => sum + value, 0) / simulated.length
+            const p = simulated.filter((value) => value >= student.repeats).length / simulated.length
+            line += ` ${t`(attendu ${formatNumber(mean, 1)}, p = ${formatNumber(p, 3)})`}`
+            if(student.repeats > mean && p < threshold) {
+                anomalies.push(name)
+            }
+        }
+        console.log(line)
+    }
+    if(absentees.size > 0) {
+        console.log(t`* souvent absent(e) : moins de partenaires et de répétitions sans que la rotation y soit pour quelque chose`)
+    }
+    if(samples != null) {
+        console.log(anomalies.length == 0
+            ? t`Aucune anomalie : les répétitions observées sont compatibles avec le hasard (${runs} simulations).`
+            : t`Anomalie(s) au seuil de ${SIGNIFICANCE} corrigé pour ${observed.length} apprenants : ${anomalies.join(', ')}`)
+    }
+}
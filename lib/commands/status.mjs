@@ -0,0 +1,95 @@
+import fs from 'fs'
+import { loadConfig } from '../config.mjs'
+import { openDb, pastBriefs } from '../db.mjs'
+import { formatDate } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { log } from '../log.mjs'
+import { studentsPath } from '../paths.mjs'
+
+export const description = 'Vérifie que l\'historique est à jour (dernier brief, synchronisation de la liste)'
+
+export const options = {
+    'brief-days': { type: 'string' },
+    'roster-days': { type: 'string' },
+    students: { type: 'string' },
+}
+
+const DAY_MS = 24 * 60 * 60 * 1000
+
+/**
+ * @param {Date} date
+ */
+function daysSince(date) {
+    return Math.floor((Date.now() - date.getTime()) / DAY_MS)
+}
+
+/**
+ * @param {string | number | undefined} value
+ * @param {number} fallback
+ * @param {string} name
+ */
+function days(value, fallback, name) {
+    const number = Number(value ?? fallback)
+    if(!Number.isInteger(number) || number < 1) {
+        throw new Error(t`${name} invalide : ${value}`)
+    }
+    return number
+}
+
+export async function run(options) {
+    const config = loadConfig()
+    const briefDays = days(options['brief-days'] ?? config.status?.brief_days, 14, '--brief-days')
+    const rosterDays = days(options['roster-days'] ?? config.status?.roster_days, 30, '--roster-days')
+    const db = openDb()
+    const warnings = []
+
+    console.log(t`Historique : ${db.file}, ${db.data.briefs.length} brief(s)`)
+    const past = pastBriefs(db.data.briefs)
+    const dated = past.filter((brief) => brief.date != null)
+    const last = dated.at(-1) ?? past.at(-1) ?? null
+    if(last == null) {
+        warnings.push(t`aucun brief enregistré`)
+    } else if(last.date == null) {
+        console.log(t`Dernier brief : n°${last.id}, date inconnue`)
+    } else {
+        const age = daysSince(new Date(last.date))
+        console.log(t`Dernier brief : n°${last.id} du ${formatDate(last.date)} (il y a ${age} jour(s))`)
+        if(age > briefDays) {
+            warnings.push(t`aucun brief depuis ${age} jours (seuil : ${briefDays}), un brief a-t-il été généré ailleurs ?`)
+        }
+    }
+
+    const drafts = db.data.briefs.filter((brief) => !past.includes(brief))
+    if(drafts.length > 0) {
+        console.log(t`Brouillon(s) en attente : ${drafts.map((brief) => t`n°${brief.id}`).join(', ')} (groupgen brief activate)`)
+    }
+
+    const invalid = db.data.invalid_briefs ?? []
+    if(invalid.length > 0) {
+        warnings.push(t`${invalid.length} brief(s) illisible(s) mis de côté dans invalid_briefs de ${db.file}, à corriger puis remettre dans briefs`)
+    }
+
+    const roster = options.students ?? studentsPath()
+    const synced = [
+        fs.existsSync(roster) ? fs.statSync(roster).mtime : null,
+        db.data.roster_synced_at ? new Date(db.data.roster_synced_at) : null,
+    ].filter(Boolean).sort((a, b) => b - a)[0]
+    if(synced == null) {
+        warnings.push(t`ni ${roster} ni synchronisation LDAP ou registre connue`)
+    } else {
+        const age = daysSince(synced)
+        console.log(t`Liste des apprenants mise à jour le ${formatDate(synced)} (il y a ${age} jour(s))`)
+        if(age > rosterDays) {
+            warnings.push(t`liste des apprenants non mise à jour depuis ${age} jours (seuil : ${rosterDays}), arrivées ou départs oubliés ?`)
+        }
+    }
+
+    for(const warning of warnings) {
+        log.warn(t`Attention : ${warning}`)
+    }
+    if(warnings.length > 0) {
+        process.exitCode = 1
+    } else {
+        console.log(t`Tout est à jour.`)
+    }
+}
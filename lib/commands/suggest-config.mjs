@@ -0,0 +1,32 @@
+import { loadConfig } from '../config.mjs'
+import { activeConstraints } from '../constraints.mjs'
+import { openDb, pastBriefs } from '../db.mjs'
+import { t } from '../i18n.mjs'
+import { formatSuggestions, suggestConstraints } from '../suggest.mjs'
+
+export const description = 'Propose des contraintes à coller dans groupgen.toml d\'après l\'historique (binômes trop répétés, apprenants isolés)'
+
+export const options = {
+    'min-repeats': { type: 'string', default: '4' },
+}
+
+export async function run(options) {
+    const minRepeats = Number(options['min-repeats'])
+    if(!Number.isInteger(minRepeats) || minRepeats < 2) {
+        throw new Error(t`--min-repeats invalide : ${options['min-repeats']}`)
+    }
+    const db = openDb()
+    const history = pastBriefs(db.data.briefs).map((brief) => brief.groups)
+    if(history.length == 0) {
+        throw new Error(t`Aucun brief enregistré`)
+    }
+    const roster = history.at(-1).flat()
+    const existing = [...activeConstraints(db.data.constraints, roster), ...loadConfig().constraints ?? []]
+    const suggestions = suggestConstraints(history, existing, { minRepeats })
+    if(suggestions.length == 0) {
+        console.log(t`Aucune suggestion d'après les ${history.length} briefs : pas de binôme formé ${minRepeats} fois ou plus ni d'apprenant isolé sans contrainte.`)
+        return
+    }
+    console.log(t`# ${suggestions.length} contrainte(s) suggérée(s) d'après ${history.length} briefs, à ajouter à groupgen.toml :\n`)
+    process.stdout.write(formatSuggestions(suggestions))
+}
@@ -0,0 +1,75 @@
+import fs from 'fs'
+import path from 'path'
+import { loadConfig } from '../config.mjs'
+import { activeConstraints } from '../constraints.mjs'
+import { addConstraint, importConfigConstraints, openDb, saveDb } from '../db.mjs'
+import { writeFileAtomic } from '../files.mjs'
+import { t } from '../i18n.mjs'
+import { log } from '../log.mjs'
+import { studentsPath } from '../paths.mjs'
+import { loadRoster } from '../roster.mjs'
+import { RESPONDENT_COLUMN, parseSurvey, surveyForm, surveyPairs } from '../survey.mjs'
+
+export const description = 'Prépare le sondage des préférences de binômes (--out form.csv) et importe les réponses en contraintes (import)'
+
+export const options = {
+    out: { type: 'string', short: 'o' },
+    students: { type: 'string' },
+    'name-column': { type: 'string', default: RESPONDENT_COLUMN },
+}
+
+function create(options) {
+    if(options.out == null) {
+        throw new Error(t`Fichier du sondage manquant, ex. : groupgen survey --out form.csv`)
+    }
+    const roster = loadRoster(options.students ?? studentsPath(), loadConfig())
+    fs.mkdirSync(path.dirname(options.out), { recursive: true })
+    writeFileAtomic(options.out, surveyForm(roster))
+    console.log(t`Sondage de ${roster.length} apprenants écrit dans ${options.out}, à importer une fois rempli avec : groupgen survey import ${options.out}`)
+}
+
+function importAnswers(options, [file]) {
+    if(file == null) {
+        throw new Error(t`Réponses manquantes, ex. : groupgen survey import form.csv`)
+    }
+    if(!fs.existsSync(file)) {
+        throw new Error(t`Réponses introuvables : ${file}`)
+    }
+    const config = loadConfig()
+    const roster = loadRoster(options.students ?? studentsPath(), config)
+    const { wishes, unknown } = parseSurvey(fs.readFileSync(file, 'utf8'), roster, { source: file, nameColumn: options['name-column'] })
+    for(const name of unknown) {
+        log.warn(t`${name} n'est pas dans la liste des apprenants, réponses ignorées`)
+    }
+    const pairs = surveyPairs(wishes)
+
+    const db = openDb()
+    importConfigConstraints(db, config)
+    const existing = activeConstraints(db.data.constraints, roster)
+    const known = (type, [a, b]) => existing.some((constraint) => constraint.type == type && constraint.students.includes(a) && constraint.students.includes(b))
+    let added = 0
+    for(const type of ['never', 'together']) {
+        for(const students of pairs[type].filter((pair) => !known(type, pair))) {
+            addConstraint(db, { type, students })
+            added++
+        }
+    }
+    saveDb(db)
+    console.log(t`${wishes.size} réponse(s) lue(s) : ${pairs.never.length} binôme(s) à éviter, ${pairs.together.length} binôme(s) à réunir, ${added} contrainte(s) ajoutée(s).`)
+    if(pairs.oneSided > 0) {
+        console.log(t`${pairs.oneSided} souhait(s) de travailler ensemble non réciproque(s) laissé(s) de côté.`)
+    }
+}
+
+const ACTIONS = { create, import: importAnswers }
+
+/** Sub-commands, for shell completion. */
+export const actions = Object.keys(ACTIONS)
+
+export async function run(options, [action = 'create', ...args]) {
+    const handler = Object.hasOwn(ACTIONS, action) ? ACTIONS[action] : null
+    if(handler == null) {
+        throw new Error(t`Action inconnue : ${action}, actions disponibles : ${actions.join(', ')}`)
+    }
+    await handler(options, args)
+}
@@ -0,0 +1,137 @@
+import { loadConfig } from '../config.mjs'
+import { confidentialConstraints } from '../confidential.mjs'
+import { activeConstraints } from '../constraints.mjs'
+import { findBrief, importConfigConstraints, lastBrief, openDb, parseBriefId, saveDb } from '../db.mjs'
+import { formatDate } from '../format.mjs'
+import { t } from '../i18n.mjs'
+import { log } from '../log.mjs'
+import { sortNames } from '../names.mjs'
+import { renderGroups } from '../render.mjs'
+import { studentsPath } from '../paths.mjs'
+import { loadRoster, parseNameList } from '../roster.mjs'
+import { activePhase, checkTeams, describeTeams, rotatePairs } from '../teams.mjs'
+
+export const description = 'Gère les équipes fixes d\'une phase projet (create, list, rotate-roles, pairs, end)'
+
+export const options = {
+    brief: { type: 'string' },
+    names: { type: 'string' },
+    roles: { type: 'string' },
+    until: { type: 'string' },
+    students: { type: 'string' },
+}
+
+/**
+ * Turns the groups of a brief (the last one by default) into teams kept
+ * until `--until` or `teams end`. Generation is skipped meanwhile.
+ */
+function create(options) {
+    if(options.until != null && !/^\d{4}-\d{2}-\d{2}$/.test(options.until)) {
+        throw new Error(t`Date de fin invalide : ${options.until} (AAAA-MM-JJ)`)
+    }
+    const config = loadConfig()
+    const db = openDb()
+    db.data.teams ??= []
+    const current = activePhase(db.data.teams)
+    if(current != null) {
+        throw new Error(t`Des équipes fixes sont déjà en place depuis le ${formatDate(current.created_at)}, terminez-les d'abord avec : groupgen teams end`)
+    }
+    const brief = options.brief ? findBrief(db, parseBriefId(options.brief)) : lastBrief(db)
+    if(brief == null) {
+        throw new Error(t`Aucun brief enregistré dont reprendre les groupes`)
+    }
+    const names = options.names ? parseNameList(options.names) : []
+    if(names.length > 0 && names.length != brief.groups.length) {
+        throw new Error(t`${names.length} noms d'équipe pour ${brief.groups.length} groupes dans le brief n°${brief.id}`)
+    }
+    const teams = brief.groups.map((group, index) => ({ name: names[index] ?? t`Équipe ${index + 1}`, members: sortNames(group) }))
+
+    const { unknown, unassigned } = checkTeams(teams, loadRoster(options.students ?? studentsPath(), config))
+    if(unknown.length > 0) {
+        throw new Error(t`${unknown.join(', ')} ne fait plus partie de la liste des apprenants, corrigez le brief n°${brief.id} (late-add, drop) avant de figer les équipes`)
+    }
+    if(unassigned.length > 0) {
+        log.warn(t`Sans équipe : ${unassigned.join(', ')}, à ajouter avec late-add --brief ${brief.id} avant de recréer les équipes.`)
+    }
+
+    const phase = {
+        id: (db.data.teams.at(-1)?.id ?? 0) + 1,
+        created_at: new Date().toISOString(),
+        until: options.until ?? null,
+        ended_at: null,
+        roles: options.roles ? parseNameList(options.roles) : config.teams?.roles ?? [],
+        rotation: 0,
+        teams,
+    }
+    db.data.teams.push(phase)
+    saveDb(db)
+    console.log(describeTeams(phase))
+    console.log(phase.until
+        ? t`Équipes fixées d'après le brief n°${brief.id} jusqu'au ${phase.until}, la génération est suspendue d'ici là.`
+        : t`Équipes fixées d'après le brief n°${brief.id}, la génération est suspendue d'ici là.`)
+}
+
+function currentPhase(db) {
+    const phase = activePhase(db.data.teams ?? [])
+    if(phase == null) {
+        throw new Error(t`Aucune équipe fixe en place, créez-en avec : groupgen teams create`)
+    }
+    return phase
+}
+
+function list() {
+    const phase = activePhase(openDb().data.teams ?? [])
+    if(phase == null) {
+        console.log(t`Aucune équipe fixe en place.`)
+        return
+    }
+    console.log(describeTeams(phase))
+}
+
+function rotateRoles() {
+    const db = openDb()
+    const phase = currentPhase(db)
+    if(phase.roles.length == 0) {
+        throw new Error(t`Aucun rôle défini pour ces équipes (--roles à la création ou roles dans [teams] de groupgen.toml)`)
+    }
+    phase.rotation++
+    saveDb(db)
+    console.log(describeTeams(phase))
+}
+
+/**
+ * Today's pair programming rotation inside each team.
+ */
+async function pairs() {
+    const db = openDb()
+    importConfigConstraints(db, loadConfig())
+    const phase = currentPhase(db)
+    const members = phase.teams.flatMap((team) => team.members)
+    const rotations = await rotatePairs(phase, [...activeConstraints(db.data.constraints, members), ...confidentialConstraints(db, members)])
+    saveDb(db)
+    for(const [name, groups] of rotations) {
+        console.log(`${name} :`)
+        console.log(renderGroups(groups))
+    }
+}
+
+function end() {
+    const db = openDb()
+    const phase = currentPhase(db)
+    phase.ended_at = new Date().toISOString()
+    saveDb(db)
+    console.log(t`Équipes fixes terminées, la génération reprend au prochain brief.`)
+}
+
+const ACTIONS = { create, list, 'rotate-roles': rotateRoles, pairs, end }
+
+/** Sub-commands, for shell completion. */
+export const actions = Object.keys(ACTIONS)
+
+export async function run(options, [action]) {
+    const handler = Object.hasOwn(ACTIONS, action) ? ACTIONS[action] : null
+    if(handler == null) {
+        throw new Error(t`Action inconnue : ${action ?? t`(aucune)`}, actions disponibles : ${actions.join(', ')}`)
+    }
+    await handler(options)
+}
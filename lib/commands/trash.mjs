@@ -0,0 +1,75 @@
+import { backupBeforeDestructive } from '../backup.mjs'
+import { loadConfig } from '../config.mjs'
+import { openDb, parseBriefId, restoreBrief, saveDb } from '../db.mjs'
+import { formatDate } from '../format.mjs'
+import { t } from '../i18n.mjs'
+
+export const description = 'Gère la corbeille des briefs supprimés (list, restore, empty)'
+
+export const options = {
+    all: { type: 'boolean' },
+}
+
+/** Days a deleted brief stays in the trash at least, unless `[safety] trash_days` says otherwise. */
+const DEFAULT_TRASH_DAYS = 30
+
+const DAY_MS = 24 * 60 * 60 * 1000
+
+function list() {
+    const trash = openDb().data.trash ?? []
+    if(trash.length == 0) {
+        console.log(t`La corbeille est vide.`)
+        return
+    }
+    for(const { deleted_at, brief, replaced } of trash) {
+        const label = brief.label ? ` « ${brief.label} »` : ''
+        const date = brief.date ? formatDate(brief.date) : t`date inconnue`
+        console.log(replaced
+            ? t`n°${brief.id}${label} du ${date}, version remplacée le ${formatDate(deleted_at)}`
+            : t`n°${brief.id}${label} du ${date}, supprimé le ${formatDate(deleted_at)}`)
+    }
+}
+
+function restore(options, [id]) {
+    if(id == null) {
+        throw new Error(t`Identifiant du brief manquant, ex. : groupgen trash restore 12`)
+    }
+    const db = openDb()
+    const brief = restoreBrief(db, parseBriefId(id))
+    saveDb(db)
+    console.log(t`Brief n°${brief.id} restauré.`)
+}
+
+/**
+ * Erases the briefs deleted more than `trash_days` ago, or all of them
+ * with --all.
+ */
+function empty(options) {
+    const config = loadConfig()
+    const days = config.safety?.trash_days ?? DEFAULT_TRASH_DAYS
+    const db = openDb()
+    const trash = db.data.trash ?? []
+    const limit = Date.now() - days * DAY_MS
+    const kept = options.all ? [] : trash.filter((entry) => new Date(entry.deleted_at).getTime() > limit)
+    if(kept.length == trash.length) {
+        console.log(trash.length == 0 ? t`La corbeille est vide.` : t`Rien à effacer : les ${trash.length} brief(s) de la corbeille ont moins de ${days} jours (--all pour tout effacer).`)
+        return
+    }
+    backupBeforeDestructive(db, config, `trash empty${options.all ? ' --all' : ''}`)
+    db.data.trash = kept
+    saveDb(db)
+    console.log(t`${trash.length - kept.length} brief(s) effacé(s) définitivement${kept.length > 0 ? t`, ${kept.length} conservé(s) moins de ${days} jours` : ''}.`)
+}
+
+const ACTIONS = { list, restore, empty }
+
+/** Sub-commands, for shell completion. */
+export const actions = Object.keys(ACTIONS)
+
+export async function run(options, [action, ...args]) {
+    const handler = Object.hasOwn(ACTIONS, action) ? ACTIONS[action] : null
+    if(handler == null) {
+        throw new Error(t`Action inconnue : ${action ?? t`(aucune)`}, actions disponibles : ${actions.join(', ')}`)
+    }
+    await handler(options, args)
+}
@@ -0,0 +1,137 @@
+import readline from 'readline'
+import { loadConfig } from '../config.mjs'
+import { confidentialConstraints } from '../confidential.mjs'
+import { activeConstraints } from '../constraints.mjs'
+import { addBrief, importConfigConstraints, markConstraintsApplied, openDb, pastBriefs, saveDb } from '../db.mjs'
+import { t } from '../i18n.mjs'
+import { generateGroups, parseGroupSize, scoreGroups } from '../matcher.mjs'
+import { studentsPath } from '../paths.mjs'
+import { randomSeed, seededRandom } from '../random.mjs'
+import { isGroupable, loadRoster } from '../roster.mjs'
+import { createState, handleKey, refreshHistory, renderScreen, showDraw } from '../tui.mjs'
+
+export const description = 'Interface plein écran : promo, matrice des binômes, tirage à retoucher avant de l\'enregistrer'
+
+export const options = {
+    'group-size': { type: 'string', default: '2' },
+    students: { type: 'string' },
+}
+
+/** Alternate screen, hidden cursor and no line wrapping while the TUI runs. */
+const ENTER = '\x1b[?1049h\x1b[?25l\x1b[?7l'
+const LEAVE = '\x1b[?7h\x1b[?25h\x1b[?1049l'
+
+export async function run(options) {
+    if(!process.stdin.isTTY || !process.stdout.isTTY) {
+        throw new Error(t`tui demande un terminal interactif, utilisez generate dans les scripts`)
+    }
+    const config = loadConfig()
+    const groupSize = parseGroupSize(options['group-size'])
+    const db = openDb()
+    importConfigConstraints(db, config)
+    const peoples = loadRoster(options.students ?? studentsPath(), config)
+    if(!isGroupable(peoples)) {
+        return
+    }
+    const constraints = activeConstraints(db.data.constraints, peoples)
+    const enforced = [...constraints, ...confidentialConstraints(db, peoples)]
+    const history = () => pastBriefs(db.data.briefs)
+    const state = createState(peoples, history().map((brief) => brief.groups))
+    let search = null
+
+    const draw = () => {
+        const lines = renderScreen(state, { columns: process.stdout.columns, rows: process.stdout.rows })
+        process.stdout.write('\x1b[H' + lines.map((line) => `${line}\x1b[K`).join('\n') + '\x1b[J')
+    }
+
+    const generate = async () => {
+        const seed = randomSeed()
+        const previous = history().at(-1) ?? null
+        const result = await generateGroups(peoples, previous?.groups ?? null, { constraints: enforced, groupSize, random: seededRandom(seed) })
+        search = { previous, iterations: result.iterations, improvements: result.improvements }
+        showDraw(state, result.groups, seed)
+        state.message += ` ${t`${result.repeats} binôme(s) répété(s).`}`
+    }
+
+    const save = () => {
+        // Scores are computed again, as hand-made swaps may have changed them.
+        const { repeats, violations, groupRepeats } = scoreGroups(peoples, state.groups, search.previous?.groups ?? null, enforced)
+        const context = {
+            roster: peoples,
+            previous_brief: search.previous?.id ?? null,
+            constraints: constraints.map((constraint) => constraint.id),
+            seed: state.seed,
+            iterations: search.iterations,
+            repeats,
+            violations,
+            group_repeats: groupRepeats,
+            improvements: search.improvements,
+        }
+        if(groupSize != 2) {
+            context.group_size = groupSize
+        }
+        if(state.swaps.length > 0) {
+            context.swaps = state.swaps
+        }
+        const brief = addBrief(db, state.groups, { context })
+        markConstraintsApplied(constraints, brief)
+        saveDb(db)
+        state.saved = brief.id
+        refreshHistory(state, history().map((other) => other.groups))
+        state.message = t`Brief n°${brief.id} enregistré.`
+    }
+
+    readline.emitKeypressEvents(process.stdin)
+    process.stdin.setRawMode(true)
+    process.stdout.write(ENTER)
+    process.stdout.on('resize', draw)
+    try {
+        draw()
+        await new Promise((resolve, reject) => {
+            let busy = false
+            const onKey = async (text, key) => {
+                if(busy) {
+                    return
+                }
+                if(key?.ctrl && key.name == 'c') {
+                    process.stdin.off('keypress', onKey)
+                    resolve()
+                    return
+                }
+                const action = handleKey(state, key?.name ?? text)
+                if(action == 'quit') {
+                    process.stdin.off('keypress', onKey)
+                    resolve()
+                    return
+                }
+                busy = true
+                try {
+                    if(action == 'generate') {
+                        state.message = t`Recherche…`
+                        draw()
+                        await generate()
+                    } else if(action == 'save') {
+                        save()
+                    }
+                    draw()
+                } catch(error) {
+                    process.stdin.off('keypress', onKey)
+                    reject(error)
+                } finally {
+                    busy = false
+                }
+            }
+            process.stdin.on('keypress', onKey)
+        })
+    } finally {
+        process.stdout.off('resize', draw)
+        process.stdout.write(LEAVE)
+        process.stdin.setRawMode(false)
+        process.stdin.pause()
+    }
+    if(state.saved != null) {
+        console.log(t`Brief n°${state.saved} enregistré.`)
+    } else if(state.groups != null) {
+        console.log(t`Dernier tirage non enregistré.`)
+    }
+}
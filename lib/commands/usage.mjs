@@ -0,0 +1,38 @@
+import { openDb } from '../db.mjs'
+import { formatNumber } from '../format.mjs'
+import { t } from '../i18n.mjs'
+
+export const description = 'Résume l\'utilisation locale de l\'outil (briefs par mois, taille de promo, temps de calcul)'
+
+export const options = {}
+
+export async function run() {
+    const db = openDb()
+    const briefs = db.data.briefs
+    if(briefs.length == 0) {
+        console.log(t`Aucun brief enregistré.`)
+        return
+    }
+
+    const perMonth = new Map()
+    for(const brief of briefs) {
+        const month = brief.date?.slice(0, 7) ?? t`date inconnue`
+        perMonth.set(month, (perMonth.get(month) ?? 0) + 1)
+    }
+    const rosterSizes = briefs.map((brief) => brief.groups.flat().length)
+    const durations = briefs.map((brief) => brief.duration_ms).filter((duration) => duration != null)
+
+    console.log(t`Briefs enregistrés : ${briefs.length}`)
+    console.log(t`Briefs par mois :`)
+    for(const [month, count] of [...perMonth].sort()) {
+        console.log(`  ${month} : ${count}`)
+    }
+    console.log(t`Taille moyenne de promo : ${formatNumber(average(rosterSizes), 1)} apprenants`)
+    console.log(durations.length > 0
+        ? t`Temps de calcul moyen : ${formatNumber(average(durations))} ms (sur ${durations.length} briefs)`
+        : t`Temps de calcul moyen : non mesuré`)
+}
+
+function average(values) {
+    return values.reduce((sum, value) => sum + value, 0) / values.length
+}
@@ -0,0 +1,153 @@
+import { COLOR_MODES } from './console.mjs'
+import { LANGUAGES, t } from './i18n.mjs'
+
+/**
+ * Shell completion scripts built from the commands' `options` and
+ * `actions`, so they can't drift from what the parser accepts.
+ *
+ * @typedef {{description: string, options?: import('util').ParseArgsConfig['options'], actions?: string[]}} CommandSpec
+ */
+
+const BIN = 'groupgen'
+
+/** Flags accepted before the command. */
+const GLOBAL_FLAGS = ['--db', '--cohort', '--lang', '--color', '--help', '--version']
+
+/**
+ * Words completed after `name`: its actions, then its flags.
+ *
+ * @param {CommandSpec} command
+ */
+function words(command) {
+    return [...command.actions ?? [], ...Object.keys(command.options ?? {}).map((option) => `--${option}`), '--help']
+}
+
+/**
+ * @param {string} text
+ */
+function singleQuoted(text) {
+    return `'${text.replaceAll('\'', '\'\\\'\'')}'`
+}
+
+/**
+ * @param {Record<string, CommandSpec>} commands
+ */
+function bash(commands) {
+    const cases = Object.entries(commands).map(([name, command]) => `        ${name}) words=${singleQuoted(words(command).join(' '))} ;;`)
+    return `# ${BIN} completion for bash, to load with: source <(${BIN} completions bash)
+_${BIN}() {
+    local cur="\${COMP_WORDS[COMP_CWORD]}" command="" words word skip=""
+    for word in "\${COMP_WORDS[@]:1:COMP_CWORD-1}"; do
+        if [ -n "$skip" ]; then skip=""; continue; fi
+        case "$word" in
+            --db|--cohort|--lang|--color) skip=1 ;;
+            -*) ;;
+            *) command="$word"; break ;;
+        esac
+    done
+    case "$command" in
+${cases.join('\n')}
+        *) words=${singleQuoted([...Object.keys(commands), ...GLOBAL_FLAGS].join(' '))} ;;
+    esac
+    COMPREPLY=($(compgen -W "$words" -- "$cur"))
+}
+complete -o default -F _${BIN} ${BIN}
+`
+}
+
+/**
+ * @param {Record<string, CommandSpec>} commands
+ */
+function zsh(commands) {
+    const described = Object.entries(commands).map(([name, { description }]) => `        ${singleQuoted(`${name}:${t(description).replaceAll(':', '\\:')}`)}`)
+    const cases = Object.entries(commands).map(([name, command]) => `        ${name}) compadd -- ${words(command).join(' ')} ;;`)
+    return `#compdef ${BIN}
+# ${BIN} completion for zsh, to load with: source <(${BIN} completions zsh)
+_${BIN}() {
+    local -a commands
+    commands=(
+${described.join('\n')}
+    )
+    local command=\${\${words[2,CURRENT-1]:#-*}[1]}
+    [[ \${words[2]} == (--db|--cohort|--lang|--color) ]] && command=\${\${words[4,CURRENT-1]:#-*}[1]}
+    case $command in
+${cases.join('\n')}
+        *) _describe 'commande' commands; compadd -- ${GLOBAL_FLAGS.join(' ')} ;;
+    esac
+    _files
+}
+compdef _${BIN} ${BIN}
+`
+}
+
+/**
+ * @param {Record<string, CommandSpec>} commands
+ */
+function fish(commands) {
+    const lines = [
+        `# ${BIN} completion for fish, to load with: ${BIN} completions fish | source`,
+        `complete -c ${BIN} -l db -r -d 'Base de l\\'historique'`,
+        `complete -c ${BIN} -l cohort -r -d 'Promo de la base'`,
+        `complete -c ${BIN} -l lang -r -a ${singleQuoted(LANGUAGES.join(' '))} -d 'Langue des messages'`,
+        `complete -c ${BIN} -l color -r -a ${singleQuoted(COLOR_MODES.join(' '))} -d 'Couleurs'`,
+        `complete -c ${BIN} -n __fish_use_subcommand -l help -s h`,
+        `complete -c ${BIN} -n __fish_use_subcommand -l version -s V`,
+    ]
+    for(const [name, command] of Object.entries(commands)) {
+        const seen = `-n '__fish_seen_subcommand_from ${name}'`
+        lines.push(`complete -c ${BIN} -n __fish_use_subcommand -a ${name} -d ${singleQuoted(t(command.description))}`)
+        if(command.actions?.length) {
+            lines.push(`complete -c ${BIN} ${seen} -a ${singleQuoted(command.actions.join(' '))}`)
+        }
+        for(const [option, spec] of Object.entries(command.options ?? {})) {
+            lines.push(`complete -c ${BIN} ${seen} -l ${option}${spec.short ? ` -s ${spec.short}` : ''}${spec.type == 'string' ? ' -r' : ''}`)
+        }
+    }
+    return lines.join('\n') + '\n'
+}
+
+/**
+ * @param {Record<string, CommandSpec>} commands
+ */
+function powershell(commands) {
+    const table = Object.entries(commands).map(([name, command]) => `        ${singleQuoted(name)} = @(${words(command).map(singleQuoted).join(', ')})`)
+    return `# ${BIN} completion for PowerShell, to load with: ${BIN} completions powershell | Out-String | Invoke-Expression
+Register-ArgumentCompleter -Native -CommandName ${BIN} -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $commands = @{
+${table.join('\n')}
+    }
+    $elements = @($commandAst.CommandElements | Select-Object -Skip 1 | ForEach-Object { $_.ToString() })
+    $command = $null
+    for ($i = 0; $i -lt $elements.Count; $i++) {
+        if ($elements[$i] -in '--db', '--cohort', '--lang', '--color') { $i++; continue }
+        if ($elements[$i] -notlike '-*' -and $elements[$i] -ne $wordToComplete) { $command = $elements[$i]; break }
+    }
+    if ($command -and $commands.ContainsKey($command)) {
+        $candidates = $commands[$command]
+    } else {
+        $candidates = @($commands.Keys | Sort-Object) + @(${GLOBAL_FLAGS.map(singleQuoted).join(', ')})
+    }
+    $candidates | Where-Object { $_ -like "$wordToComplete*" } | ForEach-Object {
+        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+    }
+}
+`
+}
+
+const GENERATORS = { bash, zsh, fish, powershell }
+
+export const SHELLS = Object.keys(GENERATORS)
+
+/**
+ * @param {string} shell one of SHELLS
+ * @param {Record<string, CommandSpec>} commands loaded command modules, by name
+ * @returns {string}
+ */
+export function completionScript(shell, commands) {
+    const generator = GENERATORS[shell]
+    if(generator == null) {
+        throw new Error(t`Shell non pris en charge : ${shell ?? '(aucun)'} (${SHELLS.join(', ')})`)
+    }
+    return generator(commands)
+}
@@ -0,0 +1,81 @@
+import { decrypt, encrypt } from './encryption.mjs'
+import { t } from './i18n.mjs'
+
+/**
+ * Environment variable holding the passphrase of the confidential
+ * registry, separate from that of the database.
+ */
+export const ADMIN_KEY_ENV = 'SGG_ADMIN_KEY'
+
+/**
+ * A "never" constraint kept out of sight: some separations involve
+ * private circumstances (conflict of interest, family ties, harassment)
+ * that trainers other than the admin must not read. The registry is
+ * stored encrypted in `confidential`, the matcher honours its entries
+ * but briefs don't record them, so neither `explain` nor exports can
+ * show them.
+ *
+ * @typedef {object} ConfidentialRecord
+ * @property {number} id
+ * @property {string[]} students exactly two names
+ * @property {string | null} note why, for the admin only
+ * @property {string} created_at ISO 8601 timestamp
+ * @property {string | null} removed_at ISO 8601 timestamp, null while in force
+ */
+
+/**
+ * @param {string | undefined} key
+ * @returns {string}
+ */
+function requireKey(key) {
+    if(!key) {
+        throw new Error(t`Registre confidentiel chiffré : définissez ${ADMIN_KEY_ENV} avec sa phrase de passe`)
+    }
+    return key
+}
+
+/**
+ * Entries of the registry, none when there is no registry yet.
+ *
+ * @param {import('./db.mjs').Db} db
+ * @param {string | undefined} [key]
+ * @returns {ConfidentialRecord[]}
+ */
+export function readRegistry(db, key = process.env[ADMIN_KEY_ENV]) {
+    if(db.data.confidential == null) {
+        return []
+    }
+    const passphrase = requireKey(key)
+    try {
+        return JSON.parse(decrypt(db.data.confidential, passphrase))
+    } catch {
+        throw new Error(t`Impossible de lire le registre confidentiel : clé ${ADMIN_KEY_ENV} incorrecte ou registre altéré`)
+    }
+}
+
+/**
+ * Encrypts `records` into the database, written with the next `saveDb`.
+ *
+ * @param {import('./db.mjs').Db} db
+ * @param {ConfidentialRecord[]} records
+ * @param {string | undefined} [key]
+ */
+export function writeRegistry(db, records, key = process.env[ADMIN_KEY_ENV]) {
+    db.data.confidential = encrypt(JSON.stringify(records), requireKey(key))
+}
+
+/**
+ * Registry entries in force between students of the roster, as "never"
+ * constraints for the matcher. Without an id, they stay out of the
+ * constraints recorded with a brief.
+ *
+ * @param {import('./db.mjs').Db} db
+ * @param {string[]} roster
+ * @returns {import('./constraints.mjs').Constraint[]}
+ */
+export function confidentialConstraints(db, roster) {
+    const present = new Set(roster)
+    return readRegistry(db)
+        .filter((record) => record.removed_at == null && record.students.every((name) => present.has(name)))
+        .map((record) => ({ type: 'never', students: record.students }))
+}
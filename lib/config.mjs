@@ -0,0 +1,79 @@
+import fs from 'fs'
+import path from 'path'
+import { t } from './i18n.mjs'
+import { configDir } from './paths.mjs'
+import { parseToml } from './toml.mjs'
+
+export const CONFIG_FILE = 'groupgen.toml'
+
+/**
+ * The configuration file in use: `groupgen.toml` in the working
+ * directory, or else the one in the per-user configuration directory.
+ */
+export function configPath() {
+    const local = path.resolve(CONFIG_FILE)
+    return fs.existsSync(local) ? local : path.join(configDir(), CONFIG_FILE)
+}
+
+/**
+ * Reads the configuration file, or an empty configuration when there is
+ * none.
+ *
+ * @returns {Record<string, any>}
+ */
+export function loadConfig(file = configPath()) {
+    if(!fs.existsSync(file)) {
+        return {}
+    }
+    return parseToml(fs.readFileSync(file, 'utf8'))
+}
+
+const ENV_PREFIX = 'SGG_'
+
+/** Spellings accepted for boolean flags set from the environment. */
+const BOOLEANS = { 1: true, true: true, yes: true, oui: true, 0: false, false: false, no: false, non: false }
+
+/**
+ * `[defaults]` keys set by `SGG_*` environment variables, for scripts and
+ * containers: `SGG_GROUP_SIZE=3` stands for `group_size = 3`. Variables
+ * matching no flag, such as `SGG_DB_KEY`, end up ignored like unknown
+ * keys of the table.
+ *
+ * @param {NodeJS.ProcessEnv} [env]
+ * @returns {Record<string, string>}
+ */
+export function envDefaults(env = process.env) {
+    return Object.fromEntries(Object.entries(env)
+        .filter(([name, value]) => name.startsWith(ENV_PREFIX) && value != '')
+        .map(([name, value]) => [name.slice(ENV_PREFIX.length).toLowerCase(), value]))
+}
+
+/**
+ * Option specs of a command with their defaults taken from the
+ * `[defaults]` table, where `group_size = 3` stands for `--group-size 3`.
+ * Keys matching no option of the command are left alone, the table
+ * being shared by all commands.
+ *
+ * @param {import('util').ParseArgsConfig['options']} options
+ * @param {Record<string, any>} defaults
+ * @param {Record<string, string>} [source] where each key was set when not in the file, for errors
+ * @returns {import('util').ParseArgsConfig['options']}
+ */
+export function withDefaults(options, defaults, source = {}) {
+    const merged = { ...options }
+    for(const [key, value] of Object.entries(defaults)) {
+        const name = key.replaceAll('_', '-')
+        const spec = merged[name]
+        if(spec == null) {
+            continue
+        }
+        const values = (spec.multiple && Array.isArray(value) ? value : [value])
+            .map((item) => spec.type == 'boolean' && typeof item == 'string' ? BOOLEANS[item.toLowerCase()] : item)
+        if(values.some((item) => spec.type == 'boolean' ? typeof item != 'boolean' : item == null || typeof item == 'object')) {
+            throw new Error(t`${source[key] ?? `${CONFIG_FILE} : [defaults] ${key}`} invalide : ${JSON.stringify(value)}`)
+        }
+        const converted = values.map((item) => spec.type == 'boolean' ? item : String(item))
+        merged[name] = { ...spec, default: spec.multiple ? converted : converted[0] }
+    }
+    return merged
+}
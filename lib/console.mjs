@@ -0,0 +1,80 @@
+import { t } from './i18n.mjs'
+
+/**
+ * Characters used to draw output. The ASCII set is used when the console
+ * can't be trusted with Unicode (legacy Windows cmd.exe, LANG=C, ...).
+ */
+export const UNICODE = {
+    topLeft: '┌', bottomLeft: '└', horizontal: '─', vertical: '│',
+    groups: '👥 ', warning: '⚠️  ', never: '·',
+}
+
+export const ASCII = {
+    topLeft: '+', bottomLeft: '+', horizontal: '-', vertical: '|',
+    groups: '', warning: '! ', never: '.',
+}
+
+/**
+ * Linear text for screen readers and speech synthesis: nothing drawn, no
+ * emoji, groups spelled out as sentences.
+ */
+export const SCREEN_READER = {
+    topLeft: '', bottomLeft: '', horizontal: '', vertical: '',
+    groups: '', warning: 'Attention : ', never: '', linear: true,
+}
+
+export function supportsUnicode() {
+    const { env } = process
+    if(env.SGG_ASCII) {
+        return false
+    }
+    if(process.platform != 'win32') {
+        return !/^(C|POSIX)$/.test(env.LC_ALL || env.LC_CTYPE || env.LANG || '') && env.TERM != 'linux'
+    }
+    // Windows Terminal, VS Code, ConEmu and mintty render UTF-8; plain
+    // cmd.exe / conhost do not.
+    return Boolean(env.WT_SESSION || env.TERM_PROGRAM == 'vscode' || env.ConEmuANSI == 'ON' || env.TERM?.startsWith('xterm'))
+}
+
+export let symbols = process.env.SGG_SCREEN_READER ? SCREEN_READER : supportsUnicode() ? UNICODE : ASCII
+
+/**
+ * Switches the output to `SCREEN_READER`, for `--screen-reader`.
+ */
+export function useScreenReader() {
+    symbols = SCREEN_READER
+}
+
+/** Values of `--color`. */
+export const COLOR_MODES = ['auto', 'always', 'never']
+
+const COLORS = { green: 32, yellow: 33, red: 31 }
+
+/**
+ * Colours are for terminals: not when stdout is piped or redirected, and
+ * not when NO_COLOR is set (https://no-color.org).
+ */
+export function supportsColor() {
+    return !process.env.NO_COLOR && Boolean(process.stdout.isTTY) && process.env.TERM != 'dumb'
+}
+
+export let colored = supportsColor()
+
+/**
+ * @param {string} mode one of COLOR_MODES, `always` overriding NO_COLOR
+ */
+export function useColor(mode) {
+    if(!COLOR_MODES.includes(mode)) {
+        throw new Error(t`--color invalide : ${mode} (${COLOR_MODES.join(', ')})`)
+    }
+    colored = mode == 'always' || mode == 'auto' && supportsColor()
+}
+
+/**
+ * @param {string} text
+ * @param {keyof COLORS} color
+ * @param {boolean} [enabled] whether colours are on, as set by `useColor` by default
+ */
+export function paint(text, color, enabled = colored) {
+    return enabled ? `\x1b[${COLORS[color]}m${text}\x1b[39m` : text
+}
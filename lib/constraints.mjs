@@ -0,0 +1,89 @@
+import { InputError } from './errors.mjs'
+import { formatDate } from './format.mjs'
+import { formatList, t } from './i18n.mjs'
+
+/**
+ * Kinds of constraint, with their label for trainers, to show through `t`.
+ */
+export const CONSTRAINT_TYPES = {
+    never: 'jamais ensemble',
+    together: 'toujours ensemble',
+}
+
+/**
+ * @typedef {object} Constraint
+ * @property {keyof CONSTRAINT_TYPES} type
+ * @property {string[]} students exactly two names
+ * @property {string | null} [expires] YYYY-MM-DD, last day the constraint applies
+ */
+
+/**
+ * A constraint as stored in the database. Constraints are never deleted,
+ * so that a past brief can be explained with the rules active back then.
+ *
+ * @typedef {Constraint & {
+ *     id: number,
+ *     created_at: string,
+ *     removed_at: string | null,
+ *     first_brief: number | null,
+ *     last_brief: number | null,
+ * }} ConstraintRecord
+ */
+
+/**
+ * @param {Constraint} constraint
+ * @param {string} where position of the constraint for error messages
+ */
+export function validateConstraint(constraint, where) {
+    if(!(constraint.type in CONSTRAINT_TYPES)) {
+        throw new InputError(t`${where} : type inconnu "${constraint.type}" (${Object.keys(CONSTRAINT_TYPES).join(', ')})`)
+    }
+    if(!Array.isArray(constraint.students) || constraint.students.length != 2) {
+        throw new InputError(t`${where} : deux apprenants attendus dans students`)
+    }
+    if(constraint.expires != null && !/^\d{4}-\d{2}-\d{2}$/.test(constraint.expires)) {
+        throw new InputError(t`${where} : date de fin invalide "${constraint.expires}" (AAAA-MM-JJ)`)
+    }
+}
+
+/**
+ * Constraints in force on `date` between students of the roster.
+ *
+ * @param {ConstraintRecord[]} constraints
+ * @param {string[]} roster
+ * @param {Date} [date]
+ * @returns {ConstraintRecord[]}
+ */
+export function activeConstraints(constraints, roster, date = new Date()) {
+    const today = date.toISOString().slice(0, 10)
+    const present = new Set(roster)
+    return constraints.filter((constraint) => constraint.removed_at == null
+        && (constraint.expires == null || constraint.expires >= today)
+        && constraint.students.every((name) => present.has(name)))
+}
+
+/**
+ * @param {Constraint} constraint
+ */
+export function describeConstraint(constraint) {
+    const label = t`${formatList(constraint.students)} : ${t(CONSTRAINT_TYPES[constraint.type])}`
+    return constraint.expires ? t`${label} jusqu'au ${formatDate(constraint.expires)}` : label
+}
+
+/**
+ * @param {ConstraintRecord} constraint
+ */
+export function describeConstraintHistory(constraint) {
+    const parts = [t`créée le ${formatDate(constraint.created_at)}`]
+    if(constraint.removed_at) {
+        parts.push(t`retirée le ${formatDate(constraint.removed_at)}`)
+    }
+    if(constraint.first_brief != null) {
+        parts.push(constraint.first_brief == constraint.last_brief
+            ? t`appliquée au brief n°${constraint.first_brief}`
+            : t`appliquée aux briefs n°${constraint.first_brief} à ${constraint.last_brief}`)
+    } else {
+        parts.push(t`jamais appliquée`)
+    }
+    return parts.join(', ')
+}
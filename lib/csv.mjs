@@ -0,0 +1,74 @@
+import { InputError } from './errors.mjs'
+import { t } from './i18n.mjs'
+
+/**
+ * Reads CSV as written by spreadsheets (RFC 4180): quoted fields may hold
+ * separators, newlines and doubled quotes. The separator is `;` when the
+ * first line has more of them than commas, as in French Excel exports.
+ *
+ * @param {string} text
+ * @param {string} [source] file name for error messages
+ * @returns {string[][]} rows of fields, blank lines skipped
+ */
+export function parseCsv(text, source = 'CSV') {
+    text = text.replace(/^\uFEFF/, '')
+    const header = text.split('\n', 1)[0]
+    const separator = header.split(';').length > header.split(',').length ? ';' : ','
+    const rows = []
+    let row = []
+    let field = ''
+    let quoted = false
+    let line = 1
+    for(let i = 0; i < text.length; i++) {
+        const char = text[i]
+        if(quoted) {
+            if(char == '"' && text[i + 1] == '"') {
+                field += '"'
+                i++
+            } else if(char == '"') {
+                quoted = false
+            } else {
+                field += char
+                if(char == '\n') {
+                    line++
+                }
+            }
+        } else if(char == '"' && field == '') {
+            quoted = true
+        } else if(char == separator) {
+            row.push(field)
+            field = ''
+        } else if(char == '\n' || char == '\r') {
+            if(char == '\r' && text[i + 1] == '\n') {
+                i++
+            }
+            row.push(field)
+            rows.push(row)
+            row = []
+            field = ''
+            line++
+        } else {
+            field += char
+        }
+    }
+    if(quoted) {
+        throw new InputError(t`${source} : guillemet non fermé (ligne ${line})`)
+    }
+    if(field != '' || row.length > 0) {
+        row.push(field)
+        rows.push(row)
+    }
+    return rows.filter((fields) => fields.some((value) => value.trim() != ''))
+}
+
+/**
+ * @param {unknown[][]} rows
+ * @returns {string} CSV with CRLF line ends, fields quoted when needed
+ */
+export function formatCsv(rows) {
+    const quote = (value) => {
+        const text = value == null ? '' : String(value)
+        return /[",;\r\n]/.test(text) ? `"${text.replaceAll('"', '""')}"` : text
+    }
+    return rows.map((row) => row.map(quote).join(',')).join('\r\n') + '\r\n'
+}
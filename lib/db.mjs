@@ -0,0 +1,412 @@
+import fs from 'fs'
+import path from 'path'
+import { validateConstraint } from './constraints.mjs'
+import { KEY_ENV, decrypt, encrypt, isEncrypted } from './encryption.mjs'
+import { t } from './i18n.mjs'
+import { log } from './log.mjs'
+import { briefProblem, emptyDatabase, parseBriefDocument, upgrade } from './model.mjs'
+import { writeFileAtomic } from './files.mjs'
+import { activeCohort, dbPath, legacyLastBriefPath } from './paths.mjs'
+
+/**
+ * @typedef {import('./model.mjs').Database} Database
+ * @typedef {import('./model.mjs').BriefRecord} BriefRecord
+ * @typedef {import('./constraints.mjs').Constraint} Constraint
+ * @typedef {import('./constraints.mjs').ConstraintRecord} ConstraintRecord
+ */
+
+/**
+ * @typedef {object} Db
+ * @property {string} file
+ * @property {Database} data the cohort's data when opened for one
+ * @property {string | null} key passphrase the file is encrypted with, null when stored in clear
+ * @property {string} [cohort] name of the cohort `data` belongs to
+ * @property {Database} [root] the whole file, when opened for a cohort
+ */
+
+/**
+ * Loads the history database, creating it in memory when missing. Nothing
+ * is written until `saveDb` is called. An encrypted database needs its
+ * passphrase in SGG_DB_KEY; a new database is encrypted when it is set.
+ * With a `cohort` (by default the one given with `--cohort`), `data` is
+ * that cohort's part of the file, so commands only see its briefs and
+ * constraints.
+ *
+ * @param {string} [file]
+ * @param {{cohort?: string | null}} [options]
+ * @returns {Db}
+ */
+export function openDb(file = dbPath(), { cohort = activeCohort() } = {}) {
+    const db = openFile(file)
+    if(cohort == null) {
+        return db
+    }
+    const data = db.data.cohorts?.[cohort]
+    if(data == null) {
+        const known = Object.keys(db.data.cohorts ?? {})
+        throw new Error(t`Cohorte inconnue : ${cohort}${known.length > 0 ? ` (${known.join(', ')})` : ''}, à créer avec : groupgen cohort create ${cohort}`)
+    }
+    data.constraints ??= []
+    return { ...db, data, cohort, root: db.data }
+}
+
+/**
+ * @param {string} file
+ * @returns {Db}
+ */
+function openFile(file) {
+    log.verbose(t`Historique : ${file}`)
+    if(fs.existsSync(file)) {
+        const raw = JSON.parse(fs.readFileSync(file))
+        if(!isEncrypted(raw)) {
+            const data = upgrade(raw)
+            data.constraints ??= []
+            setAsideInvalidBriefs(data, file)
+            return { file, data, key: null }
+        }
+        const key = process.env[KEY_ENV]
+        if(!key) {
+            throw new Error(t`${file} est chiffré : définissez ${KEY_ENV} avec sa phrase de passe`)
+        }
+        const data = upgrade(JSON.parse(decrypt(raw, key)))
+        data.constraints ??= []
+        setAsideInvalidBriefs(data, file)
+        return { file, data, key }
+    }
+    const data = emptyDatabase()
+    const legacy = legacyLastBriefPath(file)
+    if(fs.existsSync(legacy)) {
+        const { date, groups } = parseBriefDocument(JSON.parse(fs.readFileSync(legacy)))
+        data.briefs.push({ id: 1, date, label: null, groups })
+    }
+    return { file, data, key: process.env[KEY_ENV] || null }
+}
+
+/**
+ * Moves the briefs `briefProblem` finds fault with, in the file and in
+ * each cohort, to `invalid_briefs`: one bad entry then neither stops
+ * every command nor skews the pair counts. They are written there with
+ * the next save.
+ *
+ * @param {Database} data
+ * @param {string} file for the warnings
+ */
+function setAsideInvalidBriefs(data, file) {
+    const parts = [[file, data], ...Object.entries(data.cohorts ?? {}).map(([name, cohort]) => [`${file} (${name})`, cohort])]
+    for(const [where, part] of parts) {
+        if(!Array.isArray(part.briefs)) {
+            throw new Error(t`${where} : briefs doit être une liste`)
+        }
+        const ids = new Set()
+        part.briefs = part.briefs.filter((brief) => {
+            const reason = briefProblem(brief, ids)
+            if(reason == null) {
+                ids.add(brief.id)
+                return true
+            }
+            part.invalid_briefs ??= []
+            part.invalid_briefs.push({ found_at: new Date().toISOString(), reason, brief })
+            log.warn(t`${where} : brief ${brief?.id ?? '?'} mis de côté dans invalid_briefs (${reason}), les autres briefs restent utilisés`)
+            return false
+        })
+    }
+}
+
+/**
+ * @param {Db} db
+ */
+export function saveDb(db) {
+    if(db.cohort != null) {
+        // `data` may have been replaced, as by a rollback.
+        db.root.cohorts[db.cohort] = db.data
+    }
+    fs.mkdirSync(path.dirname(db.file), { recursive: true })
+    const content = JSON.stringify(db.root ?? db.data, null, 4)
+    writeFileAtomic(db.file, db.key ? JSON.stringify(encrypt(content, db.key), null, 4) : content)
+}
+
+/**
+ * @param {Db} db
+ * @returns {BriefRecord | null}
+ */
+export function lastBrief(db) {
+    return db.data.briefs.at(-1) ?? null
+}
+
+/**
+ * Where a brief stands: a draft is a preview that can still be edited,
+ * an active brief is the groups at work, a closed one is over. Briefs
+ * saved before states existed are active, or closed once `brief close`
+ * ran on them.
+ *
+ * @param {BriefRecord} brief
+ * @returns {'draft' | 'active' | 'closed'}
+ */
+export function briefState(brief) {
+    return brief.state ?? (brief.closed_at != null ? 'closed' : 'active')
+}
+
+/**
+ * Briefs that actually took place, leaving drafts out, for the history
+ * the matcher avoids and the reports.
+ *
+ * @param {BriefRecord[]} briefs
+ * @returns {BriefRecord[]}
+ */
+export function pastBriefs(briefs) {
+    return briefs.filter((brief) => briefState(brief) != 'draft')
+}
+
+/**
+ * @param {string} id as typed on the command line
+ * @returns {number}
+ */
+export function parseBriefId(id) {
+    const number = Number(id)
+    if(!Number.isInteger(number) || number < 1) {
+        throw new Error(t`Identifiant de brief invalide : ${id}`)
+    }
+    return number
+}
+
+/**
+ * @param {Db} db
+ * @param {number} id
+ * @returns {BriefRecord}
+ */
+export function findBrief(db, id) {
+    const brief = db.data.briefs.find((brief) => brief.id == id)
+    if(brief == null) {
+        throw new Error(t`Brief n°${id} introuvable`)
+    }
+    return brief
+}
+
+/**
+ * @param {Db} db
+ * @param {string[][]} groups
+ * @param {{date?: Date, label?: string | null, category?: string | null, duration_ms?: number, context?: import('./model.mjs').GenerationContext, draft?: boolean}} [options]
+ * @returns {BriefRecord}
+ */
+export function addBrief(db, groups, { date = new Date(), label = null, category = null, duration_ms, context, draft = false } = {}) {
+    // Trashed briefs keep their id, so that restoring one never clashes.
+    const id = Math.max(0, ...db.data.briefs.map((brief) => brief.id), ...(db.data.trash ?? []).map((entry) => entry.brief.id)) + 1
+    const brief = { id, date: date.toISOString(), label, groups }
+    if(category != null) {
+        brief.category = category
+    }
+    if(duration_ms != null) {
+        brief.duration_ms = duration_ms
+    }
+    if(context != null) {
+        brief.context = context
+    }
+    if(draft) {
+        brief.state = 'draft'
+    }
+    db.data.briefs.push(brief)
+    return brief
+}
+
+/**
+ * Moves a brief to the trash, from which `restoreBrief` can bring it back.
+ *
+ * @param {Db} db
+ * @param {number} id
+ * @returns {BriefRecord}
+ */
+export function trashBrief(db, id) {
+    const brief = findBrief(db, id)
+    db.data.briefs.splice(db.data.briefs.indexOf(brief), 1)
+    db.data.trash ??= []
+    db.data.trash.push({ deleted_at: new Date().toISOString(), brief })
+    return brief
+}
+
+/**
+ * Keeps a copy of a brief in the trash before its groups are drawn again,
+ * so that `restoreBrief` can bring the previous composition back.
+ *
+ * @param {Db} db
+ * @param {BriefRecord} brief
+ */
+export function trashVersion(db, brief) {
+    db.data.trash ??= []
+    db.data.trash.push({ deleted_at: new Date().toISOString(), brief: structuredClone(brief), replaced: true })
+}
+
+/**
+ * Puts a trashed brief back in the history, at its place by id. A version
+ * replaced by `regenerate` takes the place of the current one, which goes
+ * to the trash in turn.
+ *
+ * @param {Db} db
+ * @param {number} id
+ * @returns {BriefRecord}
+ */
+export function restoreBrief(db, id) {
+    const entry = (db.data.trash ?? []).findLast((entry) => entry.brief.id == id)
+    if(entry == null) {
+        throw new Error(t`Brief n°${id} absent de la corbeille`)
+    }
+    db.data.trash.splice(db.data.trash.indexOf(entry), 1)
+    const current = db.data.briefs.find((brief) => brief.id == id)
+    if(current != null) {
+        trashVersion(db, current)
+        db.data.briefs.splice(db.data.briefs.indexOf(current), 1)
+    }
+    const position = db.data.briefs.findIndex((brief) => brief.id > id)
+    db.data.briefs.splice(position == -1 ? db.data.briefs.length : position, 0, entry.brief)
+    return entry.brief
+}
+
+/**
+ * Records a change made to the groups of a saved brief.
+ *
+ * @param {BriefRecord} brief
+ * @param {Omit<import('./model.mjs').Amendment, 'date'>} amendment
+ */
+export function amendBrief(brief, amendment) {
+    brief.amendments ??= []
+    brief.amendments.push({ date: new Date().toISOString(), ...amendment })
+}
+
+/**
+ * Removes the group at `index` and keeps what is aligned with the groups
+ * in step: the timeslot, observer and repeat count of every later group
+ * move down with it. Threads and repositories of the removed group stay
+ * listed, with no group, so that `brief close` still archives them.
+ *
+ * @param {BriefRecord} brief
+ * @param {number} index
+ */
+export function removeGroup(brief, index) {
+    brief.groups.splice(index, 1)
+    brief.slots?.assignment.splice(index, 1)
+    brief.observers?.assignment.splice(index, 1)
+    brief.context?.group_repeats?.splice(index, 1)
+    for(const item of [...brief.threads ?? [], ...brief.repositories ?? []]) {
+        if(item.group == index) {
+            item.group = null
+        } else if(item.group > index) {
+            item.group--
+        }
+    }
+}
+
+/**
+ * The history as it stood at the end of `day`: later briefs are left out
+ * and later changes to the groups undone, in reverse order so that the
+ * group indexes they recorded line up again.
+ *
+ * @param {BriefRecord[]} briefs
+ * @param {string} day YYYY-MM-DD
+ * @returns {BriefRecord[]} copies, `briefs` is left untouched
+ */
+export function briefsAsOf(briefs, day) {
+    if(!/^\d{4}-\d{2}-\d{2}$/.test(day) || Number.isNaN(Date.parse(day))) {
+        throw new Error(t`Date invalide : ${day} (AAAA-MM-JJ)`)
+    }
+    return briefs.filter((brief) => brief.date == null || brief.date.slice(0, 10) <= day).map((original) => {
+        const brief = structuredClone(original)
+        const later = (brief.amendments ?? []).filter((amendment) => amendment.date.slice(0, 10) > day)
+        for(const { action, student, group, emptied } of later.reverse()) {
+            if(action == 'add') {
+                const index = brief.groups.findIndex((members) => members.includes(student))
+                brief.groups[index]?.splice(brief.groups[index].indexOf(student), 1)
+            } else if(emptied || brief.groups[group] == null) {
+                brief.groups.splice(group, 0, [student])
+            } else {
+                brief.groups[group].push(student)
+            }
+        }
+        if(brief.amendments != null) {
+            brief.amendments = brief.amendments.filter((amendment) => amendment.date.slice(0, 10) <= day)
+        }
+        return brief
+    })
+}
+
+/**
+ * @param {Db} db
+ * @param {Constraint} constraint
+ * @returns {ConstraintRecord}
+ */
+export function addConstraint(db, { type, students, expires = null }) {
+    const id = (db.data.constraints.at(-1)?.id ?? 0) + 1
+    const record = {
+        id,
+        type,
+        students,
+        expires,
+        created_at: new Date().toISOString(),
+        removed_at: null,
+        first_brief: null,
+        last_brief: null,
+    }
+    validateConstraint(record, t`Contrainte n°${id}`)
+    db.data.constraints.push(record)
+    return record
+}
+
+/**
+ * Marks a constraint as no longer in force, keeping it for history.
+ *
+ * @param {Db} db
+ * @param {number} id
+ * @returns {ConstraintRecord}
+ */
+export function removeConstraint(db, id) {
+    const constraint = db.data.constraints.find((constraint) => constraint.id == id && constraint.removed_at == null)
+    if(constraint == null) {
+        throw new Error(t`Contrainte n°${id} introuvable`)
+    }
+    constraint.removed_at = new Date().toISOString()
+    return constraint
+}
+
+/**
+ * Records that `constraints` were in force when `brief` was generated.
+ *
+ * @param {ConstraintRecord[]} constraints
+ * @param {BriefRecord} brief
+ */
+export function markConstraintsApplied(constraints, brief) {
+    for(const constraint of constraints) {
+        constraint.first_brief ??= brief.id
+        constraint.last_brief = brief.id
+    }
+}
+
+/**
+ * Moves `[[constraints]]` left in groupgen.toml by older releases into the
+ * database, skipping those already imported.
+ *
+ * @param {Db} db
+ * @param {Record<string, any>} config
+ * @param {{save?: boolean}} [options] false to import in memory only, for dry runs
+ * @returns {number} how many constraints were imported
+ */
+export function importConfigConstraints(db, config, { save = true } = {}) {
+    const imported = importNewConstraints(db, config)
+    if(imported > 0 && save) {
+        saveDb(db)
+        log.warn(t`${imported} contrainte(s) de groupgen.toml importée(s) dans ${db.file}, vous pouvez les retirer du fichier.`)
+    }
+    return imported
+}
+
+function importNewConstraints(db, config) {
+    const key = ({ type, students, expires }) => JSON.stringify([type, students, expires ?? null])
+    const known = new Set(db.data.constraints.map(key))
+    let imported = 0
+    for(const [index, constraint] of (config.constraints ?? []).entries()) {
+        validateConstraint(constraint, t`groupgen.toml, contrainte n°${index + 1}`)
+        if(!known.has(key(constraint))) {
+            addConstraint(db, constraint)
+            known.add(key(constraint))
+            imported++
+        }
+    }
+    return imported
+}
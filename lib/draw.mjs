@@ -0,0 +1,157 @@
+import fs from 'fs'
+import { briefsOfCategories, countedCategories } from './categories.mjs'
+import { confidentialConstraints } from './confidential.mjs'
+import { activeConstraints } from './constraints.mjs'
+import { pastBriefs } from './db.mjs'
+import { t } from './i18n.mjs'
+import { log } from './log.mjs'
+import { DEFAULT_ITERATIONS, generateGroups } from './matcher.mjs'
+import { seededRandom } from './random.mjs'
+import { parseNameList } from './roster.mjs'
+import { loadCompetencies, masteredSkills } from './skills.mjs'
+
+/**
+ * The steps of a draw shared by `generate` and `regenerate`, so that a
+ * brief drawn again weighs the same history, constraints and skills as
+ * when it was first drawn.
+ *
+ * @typedef {object} DrawPlan
+ * @property {import('./model.mjs').BriefRecord[]} history briefs that took place before the draw
+ * @property {string[] | null} counted categories of past briefs taken into account, null for all
+ * @property {import('./model.mjs').BriefRecord | null} previous brief whose groups are avoided
+ * @property {import('./constraints.mjs').ConstraintRecord[]} constraints recorded in the brief
+ * @property {import('./constraints.mjs').ConstraintRecord[]} enforced the same plus the confidential ones
+ * @property {import('./matcher.mjs').SkillRequirement | null} skills
+ */
+
+/**
+ * @param {import('./db.mjs').Db} db
+ * @param {Record<string, any>} config
+ * @param {string[]} peoples
+ * @param {{category?: string | null, before?: number, require?: string}} [options] `before` leaves out the briefs from this id on
+ * @returns {DrawPlan}
+ */
+export function drawPlan(db, config, peoples, { category = null, before = Infinity, require } = {}) {
+    // Drafts are previews, the groups to avoid are those that took place.
+    const history = pastBriefs(db.data.briefs).filter((brief) => brief.id < before)
+    const counted = countedCategories(category, config)
+    const constraints = activeConstraints(db.data.constraints, peoples)
+    return {
+        history,
+        counted,
+        previous: briefsOfCategories(history, counted).at(-1) ?? null,
+        constraints,
+        // Honoured like the others, but left out of the log and the brief.
+        enforced: [...constraints, ...confidentialConstraints(db, peoples)],
+        skills: skillRequirement(require, config, peoples),
+    }
+}
+
+/**
+ * @param {DrawPlan} plan
+ * @param {string[]} peoples
+ * @param {{seed: number, groupSize: number, tieBreak: string, iterations?: number, timeBudget?: number, signal?: AbortSignal}} options
+ */
+export function drawGroups(plan, peoples, { seed, groupSize, tieBreak, iterations = DEFAULT_ITERATIONS, timeBudget = Infinity, signal }) {
+    return generateGroups(peoples, plan.previous?.groups ?? null, {
+        signal,
+        constraints: plan.enforced,
+        skills: plan.skills,
+        groupSize,
+        random: seededRandom(seed),
+        iterations,
+        timeBudget,
+        tieBreak,
+        history: briefsOfCategories(plan.history, plan.counted).map((brief) => brief.groups),
+    })
+}
+
+/**
+ * What `explain` needs to tell how the groups of a brief were chosen.
+ *
+ * @param {DrawPlan} plan
+ * @param {string[]} peoples
+ * @param {{seed: number, result: Awaited<ReturnType<typeof generateGroups>>, groupSize: number, tieBreak: string, excluded: string[]}} draw
+ * @returns {import('./model.mjs').GenerationContext}
+ */
+export function generationContext(plan, peoples, { seed, result, groupSize, tieBreak, excluded }) {
+    const context = {
+        roster: peoples,
+        previous_brief: plan.previous?.id ?? null,
+        constraints: plan.constraints.map((constraint) => constraint.id),
+        seed,
+        iterations: result.iterations,
+        repeats: result.repeats,
+        violations: result.violations,
+        group_repeats: result.groupRepeats,
+        improvements: result.improvements,
+    }
+    if(groupSize != 2) {
+        context.group_size = groupSize
+    }
+    if(plan.counted != null) {
+        context.counted_categories = plan.counted
+    }
+    if(tieBreak != 'none') {
+        context.tie_break = tieBreak
+    }
+    if(excluded.length > 0) {
+        context.excluded = excluded
+    }
+    if(plan.skills != null) {
+        context.required_skills = plan.skills.required
+        context.missing_skills = result.missingSkills
+    }
+    return context
+}
+
+/**
+ * Students left out of this brief only, from --exclude and the lines of
+ * --exclude-file. Names missing from the roster are an error, as a typo
+ * would otherwise put the absentee in a group.
+ *
+ * @param {Record<string, any>} options
+ * @param {string[]} roster
+ * @returns {string[]}
+ */
+export function absentees(options, roster) {
+    const names = options.exclude ? parseNameList(options.exclude) : []
+    const file = options['exclude-file']
+    if(file != null) {
+        if(!fs.existsSync(file)) {
+            throw new Error(t`Fichier des absents introuvable : ${file}`)
+        }
+        names.push(...parseNameList(fs.readFileSync(file, 'utf8')))
+    }
+    const unknown = names.filter((name) => !roster.includes(name))
+    if(unknown.length > 0) {
+        throw new Error(t`Absent(s) inconnu(s) de la liste des apprenants : ${unknown.join(', ')}`)
+    }
+    return [...new Set(names)]
+}
+
+/**
+ * Skills every group should cover, from --require or the `[skills]` table
+ * of groupgen.toml, with what each student masters according to the
+ * competency file.
+ *
+ * @param {string | undefined} require value of --require
+ * @returns {import('./matcher.mjs').SkillRequirement | null}
+ */
+function skillRequirement(require, config, peoples) {
+    const settings = config.skills ?? {}
+    const required = require ? parseNameList(require) : settings.required ?? []
+    if(required.length == 0) {
+        return null
+    }
+    const competencies = loadCompetencies(settings.file ?? 'competencies.csv')
+    const unknown = peoples.filter((name) => !competencies.has(name))
+    if(unknown.length > 0) {
+        log.warn(t`Compétences inconnues pour ${unknown.join(', ')}`)
+    }
+    return {
+        required,
+        mastered: masteredSkills(competencies, required, settings.min_level ?? 1),
+        weight: settings.weight ?? 1,
+    }
+}
@@ -0,0 +1,28 @@
+/**
+ * Share of roster names never seen in the history above which the roster
+ * probably belongs to another promo.
+ */
+export const DRIFT_THRESHOLD = 0.5
+
+/**
+ * @typedef {object} RosterDrift
+ * @property {string[]} newcomers roster names absent from the history
+ * @property {string[]} missing names of the latest briefs absent from the roster
+ * @property {number} share `newcomers` relative to the roster size
+ */
+
+/**
+ * Compares the roster with the students of the latest briefs.
+ *
+ * @param {string[]} roster
+ * @param {import('./model.mjs').BriefRecord[]} briefs
+ * @param {number} [recent] how many of the latest briefs to compare with
+ * @returns {RosterDrift}
+ */
+export function rosterDrift(roster, briefs, recent = 5) {
+    const known = new Set(briefs.slice(-recent).flatMap((brief) => brief.groups.flat()))
+    const current = new Set(roster)
+    const newcomers = roster.filter((name) => !known.has(name))
+    const missing = [...known].filter((name) => !current.has(name))
+    return { newcomers, missing, share: roster.length == 0 ? 0 : newcomers.length / roster.length }
+}
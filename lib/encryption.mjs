@@ -0,0 +1,64 @@
+import crypto from 'crypto'
+import { t } from './i18n.mjs'
+
+/**
+ * Environment variable holding the passphrase of an encrypted database.
+ */
+export const KEY_ENV = 'SGG_DB_KEY'
+
+const ALGORITHM = 'aes-256-gcm'
+
+/**
+ * On-disk form of an encrypted document: AES-256-GCM with a key derived
+ * from a passphrase by scrypt. All binary fields are base64.
+ *
+ * @typedef {object} Envelope
+ * @property {'aes-256-gcm'} encrypted
+ * @property {string} salt
+ * @property {string} iv
+ * @property {string} tag
+ * @property {string} data
+ */
+
+/**
+ * @param {unknown} raw parsed JSON
+ * @returns {raw is Envelope}
+ */
+export function isEncrypted(raw) {
+    return raw != null && typeof raw == 'object' && raw.encrypted == ALGORITHM
+}
+
+/**
+ * @param {string} text
+ * @param {string} passphrase
+ * @returns {Envelope}
+ */
+export function encrypt(text, passphrase) {
+    const salt = crypto.randomBytes(16)
+    const iv = crypto.randomBytes(12)
+    const cipher = crypto.createCipheriv(ALGORITHM, crypto.scryptSync(passphrase, salt, 32), iv)
+    const data = Buffer.concat([cipher.update(text, 'utf8'), cipher.final()])
+    return {
+        encrypted: ALGORITHM,
+        salt: salt.toString('base64'),
+        iv: iv.toString('base64'),
+        tag: cipher.getAuthTag().toString('base64'),
+        data: data.toString('base64'),
+    }
+}
+
+/**
+ * @param {Envelope} envelope
+ * @param {string} passphrase
+ * @returns {string}
+ */
+export function decrypt(envelope, passphrase) {
+    const key = crypto.scryptSync(passphrase, Buffer.from(envelope.salt, 'base64'), 32)
+    const decipher = crypto.createDecipheriv(ALGORITHM, key, Buffer.from(envelope.iv, 'base64'))
+    decipher.setAuthTag(Buffer.from(envelope.tag, 'base64'))
+    try {
+        return Buffer.concat([decipher.update(Buffer.from(envelope.data, 'base64')), decipher.final()]).toString('utf8')
+    } catch {
+        throw new Error(t`Impossible de déchiffrer la base : clé ${KEY_ENV} incorrecte ou fichier altéré`)
+    }
+}
@@ -0,0 +1,7 @@
+/**
+ * Malformed user input (roster, configuration, command-line values).
+ * Parsers throw this and nothing else, whatever they are fed.
+ */
+export class InputError extends Error {
+    name = 'InputError'
+}
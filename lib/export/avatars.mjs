@@ -0,0 +1,55 @@
+import { escapeHtml, initials } from '../html.mjs'
+import { currentLang, t } from '../i18n.mjs'
+import { sortNames } from '../names.mjs'
+
+/**
+ * Printable sheet with one card per group showing members' avatars, meant
+ * to be pinned on project boards. Students without an avatar get their
+ * initials instead.
+ *
+ * @param {import('../model.mjs').BriefRecord} brief
+ * @param {Map<string, import('../roster.mjs').Student>} students by name
+ * @returns {string}
+ */
+export function avatarSheet(brief, students) {
+    const title = brief.label ?? t`Brief n°${brief.id}`
+    const member = (name) => {
+        const avatar = students.get(name)?.avatar
+        const picture = avatar
+            ? `<img src="${escapeHtml(avatar)}" alt="">`
+            : `<span class="initials">${escapeHtml(initials(name))}</span>`
+        return `<li>${picture}<span class="name">${escapeHtml(name)}</span></li>`
+    }
+    const cards = brief.groups.map((group, index) => `
+    <section class="group">
+        <h2>${t`Groupe ${index + 1}`}</h2>
+        <ul>${sortNames(group).map(member).join('')}</ul>
+    </section>`).join('')
+
+    return `<!DOCTYPE html>
+<html lang="${currentLang()}">
+<head>
+<meta charset="utf-8">
+<title>${escapeHtml(title)}</title>
+<style>
+    body { font-family: system-ui, sans-serif; margin: 1.5rem; }
+    h1 { margin: 0 0 1rem; }
+    .groups { display: grid; grid-template-columns: repeat(auto-fill, minmax(16rem, 1fr)); gap: 1rem; }
+    .group { border: 2px solid #333; border-radius: .5rem; padding: .75rem; break-inside: avoid; }
+    .group h2 { margin: 0 0 .5rem; font-size: 1.2rem; }
+    ul { list-style: none; margin: 0; padding: 0; }
+    li { display: flex; align-items: center; gap: .75rem; margin: .5rem 0; }
+    img, .initials { width: 4rem; height: 4rem; border-radius: 50%; object-fit: cover; flex: none; }
+    .initials { display: flex; align-items: center; justify-content: center; background: #ddd; font-weight: bold; font-size: 1.4rem; }
+    .name { font-size: 1.1rem; }
+    @page { size: A4; margin: 1cm; }
+</style>
+</head>
+<body>
+<h1>${escapeHtml(title)}</h1>
+<div class="groups">${cards}
+</div>
+</body>
+</html>
+`
+}
@@ -0,0 +1,22 @@
+import { formatCsv } from '../csv.mjs'
+import { sortNames } from '../names.mjs'
+
+/**
+ * One row per group, for attendance or administration tools: brief id,
+ * date, label, group number then one column per member.
+ *
+ * @param {import('../model.mjs').BriefRecord[]} briefs
+ * @returns {string}
+ */
+export function groupsCsv(briefs) {
+    const width = Math.max(0, ...briefs.flatMap((brief) => brief.groups.map((group) => group.length)))
+    const header = ['brief_id', 'date', 'label', 'group_number', ...Array.from({ length: width }, (_, index) => `member_${index + 1}`)]
+    const rows = briefs.flatMap((brief) => brief.groups.map((group, index) => [
+        brief.id,
+        brief.date?.slice(0, 10),
+        brief.label,
+        index + 1,
+        ...sortNames(group),
+    ]))
+    return formatCsv([header, ...rows])
+}
@@ -0,0 +1,91 @@
+import fs from 'fs'
+import path from 'path'
+import { t } from '../i18n.mjs'
+import { groupRepositoryName, sortNames } from '../names.mjs'
+
+/**
+ * Files of a template directory, by path relative to it.
+ *
+ * @param {string} dir
+ * @returns {Map<string, Buffer>}
+ */
+export function readTemplate(dir) {
+    if(!fs.existsSync(dir) || !fs.statSync(dir).isDirectory()) {
+        throw new Error(t`Modèle de dossier introuvable : ${dir}`)
+    }
+    const files = new Map()
+    for(const entry of fs.readdirSync(dir, { recursive: true, withFileTypes: true })) {
+        if(entry.isFile()) {
+            const file = path.join(entry.parentPath ?? entry.path, entry.name)
+            files.set(path.relative(dir, file), fs.readFileSync(file))
+        }
+    }
+    return files
+}
+
+/**
+ * @param {number} index
+ */
+function groupDir(index) {
+    return `groupe-${String(index + 1).padStart(2, '0')}`
+}
+
+/**
+ * The README written when there is no template: members and a checklist
+ * of the deliverables.
+ *
+ * @param {string} heading
+ * @param {string[]} members
+ * @param {string[]} deliverables
+ */
+function defaultReadme(heading, members, deliverables) {
+    return [
+        `# ${heading}`,
+        '',
+        '## Membres',
+        '',
+        ...members.map((name) => `- ${name}`),
+        ...(deliverables.length > 0 ? ['', '## Livrables', '', ...deliverables.map((item) => `- [ ] ${item}`)] : []),
+        '',
+    ].join('\n')
+}
+
+/**
+ * One directory per group of `brief`, from the files of `template` with
+ * `{{brief}}`, `{{group}}`, `{{members}}` and `{{deliverables}}` replaced
+ * in text files, and `names.txt` listing a repository or branch name per
+ * group. Without a template, each directory gets a README with the
+ * members and the deliverables to tick.
+ *
+ * @param {import('../model.mjs').BriefRecord} brief
+ * @param {{template?: Map<string, Buffer> | null, deliverables?: string[]}} [options]
+ * @returns {Map<string, string | Buffer>} contents by path
+ */
+export function scaffoldFiles(brief, { template = null, deliverables = [] } = {}) {
+    const files = new Map()
+    const names = []
+    brief.groups.forEach((group, index) => {
+        const dir = groupDir(index)
+        const members = sortNames(group)
+        const heading = `Groupe ${index + 1} du brief n°${brief.id}${brief.label ? ` « ${brief.label} »` : ''}`
+        names.push(groupRepositoryName(brief.id, index))
+        if(template == null) {
+            files.set(path.join(dir, 'README.md'), defaultReadme(heading, members, deliverables))
+            return
+        }
+        const values = {
+            brief: String(brief.id),
+            group: String(index + 1),
+            members: members.join(', '),
+            deliverables: deliverables.map((item) => `- [ ] ${item}`).join('\n'),
+        }
+        for(const [file, content] of template) {
+            // Text files get their placeholders filled, others are copied as is.
+            files.set(path.join(dir, file), content.includes(0)
+                ? content
+                : content.toString('utf8').replace(/\{\{(brief|group|members|deliverables)\}\}/g, (_, key) => values[key]))
+        }
+    })
+    files.set('names.txt', names.join('\n') + '\n')
+    return files
+}
@@ -0,0 +1,113 @@
+import { formatDate } from '../format.mjs'
+import { escapeHtml } from '../html.mjs'
+import { compareNames, pairKey, slug, sortNames } from '../names.mjs'
+import { pairCounts } from '../stats.mjs'
+
+const STYLE = `
+    body { font-family: system-ui, sans-serif; margin: 1.5rem auto; max-width: 60rem; padding: 0 1rem; }
+    nav { display: flex; gap: 1rem; margin-bottom: 1.5rem; }
+    .groups { display: grid; grid-template-columns: repeat(auto-fill, minmax(14rem, 1fr)); gap: 1rem; }
+    .group { border: 2px solid #333; border-radius: .5rem; padding: .5rem .75rem; }
+    .group h3 { margin: 0 0 .25rem; }
+    .group ul { margin: 0; padding-left: 1.2rem; }
+    table { border-collapse: collapse; }
+    th, td { border: 1px solid #ccc; padding: .25rem .5rem; }
+    .matrix td { text-align: center; min-width: 1.5rem; }
+    .matrix th.column { writing-mode: vertical-rl; transform: rotate(180deg); }
+`
+
+/**
+ * @param {string} title
+ * @param {string} body
+ * @param {string} root relative path to the site root, for links
+ */
+function page(title, body, root = '') {
+    return `<!DOCTYPE html>
+<html lang="fr">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>${escapeHtml(title)}</title>
+<style>${STYLE}</style>
+</head>
+<body>
+<nav><a href="${root}index.html">Groupes actuels</a><a href="${root}history.html">Historique</a><a href="${root}matrix.html">Matrice</a></nav>
+<h1>${escapeHtml(title)}</h1>
+${body}
+</body>
+</html>
+`
+}
+
+/**
+ * @param {import('../model.mjs').BriefRecord} brief
+ */
+function briefHeading(brief) {
+    const title = brief.label ? `Brief n°${brief.id} « ${brief.label} »` : `Brief n°${brief.id}`
+    return brief.date ? `${title}, ${formatDate(brief.date)}` : title
+}
+
+/**
+ * @param {import('../model.mjs').BriefRecord} brief
+ * @param {string} root
+ */
+function groupCards(brief, root = '') {
+    const link = (name) => `<a href="${root}students/${slug(name)}.html">${escapeHtml(name)}</a>`
+    return `<div class="groups">${brief.groups.map((group, index) => `
+    <section class="group">
+        <h3>Groupe ${index + 1}${brief.slots ? ` (${escapeHtml(brief.slots.assignment[index])})` : ''}</h3>
+        <ul>${sortNames(group).map((name) => `<li>${link(name)}</li>`).join('')}</ul>
+    </section>`).join('')}
+</div>`
+}
+
+/**
+ * Static site for students: current groups, history, a heatmap of how
+ * often each pair worked together and one page per student. Only names
+ * from the history are published, no emails or avatars.
+ *
+ * @param {import('../model.mjs').BriefRecord[]} briefs oldest first
+ * @returns {Map<string, string>} file contents by path relative to the site root
+ */
+export function buildSite(briefs) {
+    const files = new Map()
+    const current = briefs.at(-1)
+    files.set('index.html', page('Groupes actuels', current
+        ? `<h2>${escapeHtml(briefHeading(current))}</h2>\n${groupCards(current)}`
+        : '<p>Aucun brief pour le moment.</p>'))
+
+    files.set('history.html', page('Historique', [...briefs].reverse()
+        .map((brief) => `<h2>${escapeHtml(briefHeading(brief))}</h2>\n${groupCards(brief)}`).join('\n')))
+
+    const students = [...new Set(briefs.flatMap((brief) => brief.groups.flat()))].sort(compareNames)
+    const counts = pairCounts(briefs.map((brief) => brief.groups))
+    const max = Math.max(1, ...counts.values())
+    const cell = (a, b) => {
+        if(a == b) {
+            return '<td style="background: #eee"></td>'
+        }
+        const count = counts.get(pairKey(a, b)) ?? 0
+        const lightness = 100 - Math.round(55 * count / max)
+        return `<td style="background: hsl(210, 70%, ${lightness}%)" title="${escapeHtml(`${a} et ${b}`)}">${count || ''}</td>`
+    }
+    files.set('matrix.html', page('Matrice des binômes', `<p>Nombre de briefs passés ensemble, pour chaque paire d'apprenants.</p>
+<table class="matrix">
+<tr><th></th>${students.map((name) => `<th class="column">${escapeHtml(name)}</th>`).join('')}</tr>
+${students.map((a) => `<tr><th>${escapeHtml(a)}</th>${students.map((b) => cell(a, b)).join('')}</tr>`).join('\n')}
+</table>`))
+
+    for(const name of students) {
+        const rows = [...briefs].reverse()
+            .map((brief) => ({ brief, group: brief.groups.findIndex((group) => group.includes(name)) }))
+            .filter(({ group }) => group != -1)
+            .map(({ brief, group }) => {
+                const partners = sortNames(brief.groups[group].filter((other) => other != name))
+                return `<tr><td>${escapeHtml(briefHeading(brief))}</td><td>${group + 1}</td><td>${partners.map((other) => `<a href="${slug(other)}.html">${escapeHtml(other)}</a>`).join(', ')}</td></tr>`
+            })
+        files.set(`students/${slug(name)}.html`, page(name, `<table>
+<tr><th>Brief</th><th>Groupe</th><th>Avec</th></tr>
+${rows.join('\n')}
+</table>`, '../'))
+    }
+    return files
+}
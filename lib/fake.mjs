@@ -0,0 +1,103 @@
+import { t } from './i18n.mjs'
+import { generateGroups } from './matcher.mjs'
+
+/**
+ * Common first and last names, to build plausible rosters for demos,
+ * documentation and benchmarks.
+ */
+const NAMES = {
+    fr: {
+        first: [
+            'Adèle', 'Agathe', 'Alice', 'Amandine', 'Anaïs', 'Awa', 'Camille', 'Chloé', 'Clémence', 'Élodie',
+            'Emma', 'Inès', 'Jade', 'Juliette', 'Léa', 'Lina', 'Louise', 'Manon', 'Maëlys', 'Nadia',
+            'Océane', 'Sarah', 'Yasmine', 'Zoé', 'Adam', 'Amir', 'Antoine', 'Arthur', 'Baptiste', 'Bilal',
+            'Clément', 'Enzo', 'Étienne', 'Gabriel', 'Hugo', 'Jérôme', 'Karim', 'Léo', 'Lucas', 'Mamadou',
+            'Mathis', 'Maxime', 'Nathan', 'Noé', 'Raphaël', 'Sacha', 'Théo', 'Thomas', 'Yanis', 'Youssef',
+        ],
+        last: [
+            'Martin', 'Bernard', 'Dubois', 'Thomas', 'Robert', 'Richard', 'Petit', 'Durand', 'Leroy', 'Moreau',
+            'Simon', 'Laurent', 'Lefèvre', 'Michel', 'Garcia', 'David', 'Bertrand', 'Roux', 'Vincent', 'Fournier',
+            'Morel', 'Girard', 'André', 'Mercier', 'Dupont', 'Lambert', 'Bonnet', 'François', 'Martinez', 'Legrand',
+            'Garnier', 'Faure', 'Rousseau', 'Blanc', 'Guérin', 'Muller', 'Henry', 'Roussel', 'Nicolas', 'Perrin',
+            'Diallo', 'Benali', 'Nguyen', 'Traoré', 'Haddad', 'Lemaire', 'Chevalier', 'Gauthier', 'Masson', 'Da Silva',
+        ],
+        tracks: ['Développement web', 'Data', 'Cybersécurité'],
+        levels: ['débutant', 'intermédiaire', 'avancé'],
+    },
+}
+
+export const LOCALES = Object.keys(NAMES)
+
+/**
+ * @template T
+ * @param {T[]} items
+ * @param {() => number} random
+ * @returns {T}
+ */
+function pick(items, random) {
+    return items[Math.floor(random() * items.length)]
+}
+
+/**
+ * `prenom.nom`, without accents nor spaces, for made-up addresses.
+ *
+ * @param {string} name
+ */
+function emailLocalPart(name) {
+    return name.normalize('NFD').replace(/\p{Diacritic}/gu, '').toLowerCase().replace(/\s+/g, '.').replace(/[^a-z.-]/g, '')
+}
+
+/**
+ * A roster of `size` distinct made-up students, with the email and
+ * attributes of a structured roster. Addresses use the reserved
+ * example.org domain.
+ *
+ * @param {number} size
+ * @param {{locale?: string, random?: () => number}} [options]
+ * @returns {import('./roster.mjs').Student[]}
+ */
+export function fakeRoster(size, { locale = 'fr', random = Math.random } = {}) {
+    const names = NAMES[locale]
+    if(names == null) {
+        throw new Error(t`Langue non prise en charge : ${locale} (${LOCALES.join(', ')})`)
+    }
+    if(size > names.first.length * names.last.length) {
+        throw new Error(t`Pas assez de noms pour ${size} apprenants (au plus ${names.first.length * names.last.length})`)
+    }
+    const taken = new Set()
+    const students = []
+    while(students.length < size) {
+        const name = `${pick(names.first, random)} ${pick(names.last, random)}`
+        if(taken.has(name)) {
+            continue
+        }
+        taken.add(name)
+        students.push({
+            name,
+            email: `${emailLocalPart(name)}@example.org`,
+            attributes: { track: pick(names.tracks, random), level: pick(names.levels, random) },
+        })
+    }
+    return students
+}
+
+/**
+ * Groups of `count` weekly briefs for `names`, oldest first, each
+ * formed like the tool would, avoiding the pairs of the brief before.
+ *
+ * @param {string[]} names
+ * @param {number} count
+ * @param {{groupSize?: number, random?: () => number, until?: Date}} [options]
+ * @returns {Promise<{date: Date, groups: string[][]}[]>}
+ */
+export async function fakeHistory(names, count, { groupSize = 2, random = Math.random, until = new Date() } = {}) {
+    const history = []
+    let previous = null
+    for(let index = 0; index < count; index++) {
+        const { groups } = await generateGroups(names, previous, { groupSize, random, iterations: 1000 })
+        const date = new Date(until.getTime() - (count - index) * 7 * 24 * 60 * 60 * 1000)
+        history.push({ date, groups })
+        previous = groups
+    }
+    return history
+}
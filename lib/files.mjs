@@ -0,0 +1,20 @@
+import fs from 'fs'
+import path from 'path'
+
+/**
+ * Writes `content` next to `file` then renames it over the target, so an
+ * interruption never leaves a truncated file behind.
+ *
+ * @param {string} file
+ * @param {string} content
+ */
+export function writeFileAtomic(file, content) {
+    const tmp = path.join(path.dirname(file), `.${path.basename(file)}.${process.pid}.tmp`)
+    try {
+        fs.writeFileSync(tmp, content)
+        fs.renameSync(tmp, file)
+    } catch(error) {
+        fs.rmSync(tmp, { force: true })
+        throw error
+    }
+}
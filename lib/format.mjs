@@ -0,0 +1,93 @@
+import { t } from './i18n.mjs'
+
+/**
+ * Dates and numbers as shown to people, in the terminal, the HTML pages
+ * and the announcements, following one locale: French by default, or
+ * `locale` in `[defaults]` (`SGG_LOCALE`). Files meant for other tools
+ * (CSV, JSON, calendars, API payloads) keep ISO 8601 dates and plain
+ * numbers.
+ */
+
+export const DEFAULT_LOCALE = 'fr-FR'
+
+let locale = DEFAULT_LOCALE
+
+/**
+ * @param {string} tag BCP 47 language tag, such as `fr-BE` or `en-GB`
+ */
+export function useLocale(tag) {
+    let supported
+    try {
+        supported = Intl.DateTimeFormat.supportedLocalesOf(tag)
+    } catch {
+        supported = []
+    }
+    if(supported.length == 0) {
+        throw new Error(t`Locale inconnue : ${tag} (ex. : fr-FR, fr-BE, en-GB)`)
+    }
+    locale = tag
+}
+
+/**
+ * Day-only values (`2024-10-14`) are calendar days, shown as such
+ * whatever the time zone; timestamps are shown in local time.
+ *
+ * @param {string | Date} value
+ */
+function dateOf(value) {
+    return typeof value == 'string' && /^\d{4}-\d{2}-\d{2}$/.test(value)
+        ? { date: new Date(`${value}T00:00:00Z`), timeZone: 'UTC' }
+        : { date: new Date(value), timeZone: undefined }
+}
+
+/**
+ * @param {string | Date} value ISO 8601 timestamp or day, or a Date
+ */
+export function formatDate(value) {
+    const { date, timeZone } = dateOf(value)
+    return date.toLocaleDateString(locale, { dateStyle: 'short', timeZone })
+}
+
+/**
+ * @param {string | Date} value
+ */
+export function formatDateTime(value) {
+    const { date, timeZone } = dateOf(value)
+    return date.toLocaleString(locale, { dateStyle: 'short', timeStyle: 'short', timeZone })
+}
+
+/**
+ * "mars 2025".
+ *
+ * @param {number} year
+ * @param {number} month 0 for January
+ */
+export function formatMonth(year, month) {
+    return new Date(Date.UTC(year, month, 1)).toLocaleDateString(locale, { month: 'long', year: 'numeric', timeZone: 'UTC' })
+}
+
+/**
+ * Two-letter names of the days of the week, Monday first: "lu", "ma"...
+ */
+export function weekdayNames() {
+    // 2024-01-01 was a Monday.
+    return Array.from({ length: 7 }, (_, day) => new Date(Date.UTC(2024, 0, 1 + day))
+        .toLocaleDateString(locale, { weekday: 'short', timeZone: 'UTC' })
+        .slice(0, 2)
+        .toLowerCase())
+}
+
+/**
+ * @param {number} value
+ * @param {number} [digits] after the decimal separator, always shown
+ */
+export function formatNumber(value, digits = 0) {
+    return value.toLocaleString(locale, { minimumFractionDigits: digits, maximumFractionDigits: digits })
+}
+
+/**
+ * @param {number} share between 0 and 1
+ */
+export function formatPercent(share) {
+    return share.toLocaleString(locale, { style: 'percent', maximumFractionDigits: 0 })
+}
@@ -0,0 +1,45 @@
+import { compareNames } from './names.mjs'
+
+/**
+ * Lower case without diacritics, so "jerome" finds "Jérôme".
+ *
+ * @param {string} text
+ */
+export function fold(text) {
+    return text.normalize('NFD').replace(/\p{Diacritic}/gu, '').toLowerCase()
+}
+
+/**
+ * Ranks `candidates` matching `query` as a subsequence, best first:
+ * substring matches before scattered ones, then shorter names.
+ *
+ * @param {string} query
+ * @param {string[]} candidates
+ * @returns {string[]}
+ */
+export function fuzzySearch(query, candidates) {
+    const needle = fold(query.trim())
+    return candidates
+        .map((candidate) => ({ candidate, score: score(needle, fold(candidate)) }))
+        .filter(({ score }) => score != null)
+        .sort((a, b) => a.score - b.score || a.candidate.length - b.candidate.length || compareNames(a.candidate, b.candidate))
+        .map(({ candidate }) => candidate)
+}
+
+function score(needle, haystack) {
+    const index = haystack.indexOf(needle)
+    if(index != -1) {
+        return index
+    }
+    let position = 0
+    let gaps = 0
+    for(const char of needle) {
+        const found = haystack.indexOf(char, position)
+        if(found == -1) {
+            return null
+        }
+        gaps += found - position
+        position = found + 1
+    }
+    return 1000 + gaps
+}
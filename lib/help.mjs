@@ -0,0 +1,44 @@
+import { t } from './i18n.mjs'
+
+/**
+ * Help texts built from the `description` and `options` each command
+ * module exports, so they can't drift from what the parser accepts.
+ */
+
+/**
+ * @param {Record<string, {description: string}>} commands loaded modules, by name
+ * @param {string} defaultCommand run when no command is given
+ * @returns {string}
+ */
+export function commandList(commands, defaultCommand) {
+    const width = Math.max(...Object.keys(commands).map((name) => name.length))
+    return [
+        t`Usage : groupgen [--db <fichier>] [--cohort <nom>] [--lang fr|en] [-q | -v | -vv] [--log-json] [--screen-reader] [--color auto|always|never] [commande] [options]`,
+        '',
+        t`Commandes :`,
+        ...Object.entries(commands).map(([name, { description }]) =>
+            `  ${name.padEnd(width)}  ${t(description)}${name == defaultCommand ? t` (par défaut)` : ''}`),
+        '',
+        t`groupgen <commande> --help détaille les options d'une commande, groupgen --version affiche la version.`,
+    ].join('\n')
+}
+
+/**
+ * @param {string} name
+ * @param {{description: string, options: import('util').ParseArgsConfig['options']}} command
+ * @returns {string}
+ */
+export function commandHelp(name, { description, options }) {
+    const flags = Object.entries(options ?? {}).map(([option, spec]) => {
+        const value = spec.type == 'string' ? ` ${t`<valeur>`}${spec.multiple ? '...' : ''}` : ''
+        const short = spec.short ? `, -${spec.short}` : ''
+        const fallback = spec.default != null ? t` (défaut : ${spec.default})` : ''
+        return `  --${option}${short}${value}${fallback}`
+    })
+    return [
+        t`Usage : groupgen ${name} [options]`,
+        '',
+        t(description),
+        ...(flags.length > 0 ? ['', t`Options :`, ...flags] : []),
+    ].join('\n')
+}
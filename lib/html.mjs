@@ -0,0 +1,18 @@
+/**
+ * @param {unknown} value
+ */
+export function escapeHtml(value) {
+    return String(value)
+        .replaceAll('&', '&amp;')
+        .replaceAll('<', '&lt;')
+        .replaceAll('>', '&gt;')
+        .replaceAll('"', '&quot;')
+        .replaceAll('\'', '&#39;')
+}
+
+/**
+ * @param {string} name
+ */
+export function initials(name) {
+    return name.split(/[\s-]+/).filter(Boolean).slice(0, 2).map((part) => part[0].toUpperCase()).join('')
+}
@@ -0,0 +1,81 @@
+import EN from './messages/en.mjs'
+
+/**
+ * Language of the messages: French, in which the code is written, or one
+ * of the translation tables of lib/messages. Messages are written with the
+ * `t` tag, and the French text is looked up in the table, interpolated
+ * values replaced by `{0}`, `{1}`...:
+ *
+ *     t`Groupe ${index + 1}`    // 'Group 3' with `--lang en`
+ *
+ * A message missing from the table is shown in French.
+ */
+
+export const DEFAULT_LANG = 'fr'
+
+const MESSAGES = { fr: {}, en: EN }
+
+export const LANGUAGES = Object.keys(MESSAGES)
+
+let lang = DEFAULT_LANG
+
+/**
+ * @param {string} value
+ * @returns {string} one of LANGUAGES
+ */
+export function parseLang(value) {
+    if(!LANGUAGES.includes(value)) {
+        throw new Error(t`Langue inconnue : ${value} (${LANGUAGES.join(', ')})`)
+    }
+    return value
+}
+
+/**
+ * @param {string} value one of LANGUAGES
+ */
+export function useLang(value) {
+    lang = parseLang(value)
+}
+
+export function currentLang() {
+    return lang
+}
+
+/**
+ * Language of the POSIX locale variables (`LANG=en_GB.UTF-8`), when it
+ * has a translation table.
+ *
+ * @param {Record<string, string | undefined>} env
+ * @returns {string | null}
+ */
+export function detectLang(env = process.env) {
+    const locale = env.LC_ALL || env.LC_MESSAGES || env.LANG || ''
+    const language = locale.split(/[_.@-]/)[0].toLowerCase()
+    return LANGUAGES.includes(language) ? language : null
+}
+
+/**
+ * Message in the current language, as a template tag or called with a
+ * plain string (command descriptions).
+ *
+ * @param {TemplateStringsArray | string} strings
+ * @param {...any} values
+ * @returns {string}
+ */
+export function t(strings, ...values) {
+    if(typeof strings == 'string') {
+        return MESSAGES[lang][strings] ?? strings
+    }
+    const key = strings.reduce((text, part, index) => `${text}{${index - 1}}${part}`)
+    const message = MESSAGES[lang][key] ?? key
+    return message.replace(/\{(\d+)\}/g, (_, index) => values[index])
+}
+
+/**
+ * "A, B et C", "A, B and C".
+ *
+ * @param {string[]} items
+ */
+export function formatList(items) {
+    return new Intl.ListFormat(lang, { type: 'conjunction' }).format(items)
+}
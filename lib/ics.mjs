@@ -0,0 +1,89 @@
+import crypto from 'crypto'
+
+/**
+ * @typedef {object} CalendarEvent
+ * @property {string} uid stable identifier, so re-sending updates the event
+ * @property {string} summary
+ * @property {string} [description]
+ * @property {string} [location]
+ * @property {string} start local date-time YYYY-MM-DDTHH:MM, or a date YYYY-MM-DD for all-day events
+ * @property {string} [end] same format as `start`
+ * @property {{name: string, email: string}} [organizer]
+ * @property {{name: string, email?: string}[]} [attendees]
+ */
+
+/**
+ * Builds an iCalendar (RFC 5545) document. Times are floating local times,
+ * which calendar apps show in the reader's timezone as written.
+ *
+ * @param {CalendarEvent[]} events
+ * @param {{method?: 'PUBLISH' | 'REQUEST', now?: Date}} [options] `now` is the DTSTAMP
+ * @returns {string}
+ */
+export function calendar(events, { method = 'PUBLISH', now = new Date() } = {}) {
+    const stamp = now.toISOString().replace(/[-:]/g, '').replace(/\.\d+/, '')
+    const lines = ['BEGIN:VCALENDAR', 'VERSION:2.0', 'PRODID:-//simplon-group-generator//FR', `METHOD:${method}`]
+    for(const event of events) {
+        lines.push('BEGIN:VEVENT', `UID:${event.uid}`, `DTSTAMP:${stamp}`, ...dateLines(event))
+        lines.push(`SUMMARY:${escapeText(event.summary)}`)
+        if(event.description) {
+            lines.push(`DESCRIPTION:${escapeText(event.description)}`)
+        }
+        if(event.location) {
+            lines.push(`LOCATION:${escapeText(event.location)}`)
+        }
+        if(event.organizer) {
+            lines.push(`ORGANIZER;CN=${quoteParam(event.organizer.name)}:mailto:${event.organizer.email}`)
+        }
+        for(const attendee of event.attendees ?? []) {
+            if(attendee.email) {
+                lines.push(`ATTENDEE;CN=${quoteParam(attendee.name)};RSVP=FALSE:mailto:${attendee.email}`)
+            }
+        }
+        lines.push('END:VEVENT')
+    }
+    lines.push('END:VCALENDAR')
+    return lines.map(fold).join('\r\n') + '\r\n'
+}
+
+/**
+ * Identifier derived from `parts`, stable across runs.
+ *
+ * @param {...unknown} parts
+ */
+export function stableUid(...parts) {
+    return `${crypto.createHash('sha1').update(parts.join('\u0000')).digest('hex').slice(0, 20)}@simplon-group-generator`
+}
+
+function dateLines({ start, end }) {
+    const format = (value) => value.replace(/[-:]/g, '') + (value.includes('T') ? '00' : '')
+    if(!start.includes('T')) {
+        return [`DTSTART;VALUE=DATE:${format(start)}`, ...(end ? [`DTEND;VALUE=DATE:${format(end)}`] : [])]
+    }
+    return [`DTSTART:${format(start)}`, ...(end ? [`DTEND:${format(end)}`] : [])]
+}
+
+function escapeText(text) {
+    return text.replace(/[\\;,]/g, '\\$&').replace(/\r?\n/g, '\\n')
+}
+
+function quoteParam(value) {
+    return `"${value.replaceAll('"', '\'')}"`
+}
+
+/**
+ * Folds content lines longer than 75 octets, without splitting characters.
+ */
+function fold(line) {
+    const parts = []
+    let current = ''
+    for(const char of line) {
+        if(Buffer.byteLength(current + char) > (parts.length == 0 ? 75 : 74)) {
+            parts.push(current)
+            current = ''
+        }
+        current += char
+    }
+    parts.push(current)
+    return parts.join('\r\n ')
+}
@@ -0,0 +1,58 @@
+import { parseCsv } from './csv.mjs'
+import { InputError } from './errors.mjs'
+import { t } from './i18n.mjs'
+
+/**
+ * How to read a form export, from the `[intake]` table of groupgen.toml.
+ *
+ * @typedef {object} IntakeMapping
+ * @property {string} name_column header of the question asking the student's name
+ * @property {Record<string, string>} skills header of the question for each skill
+ * @property {Record<string, number>} [levels] level of textual answers ("Débutant" = 1...)
+ */
+
+/**
+ * Reads self-assessments from a form export (Google Forms, Microsoft
+ * Forms...). Answers come in chronological order, so a student's last
+ * answer wins.
+ *
+ * @param {string} text CSV export
+ * @param {IntakeMapping} mapping
+ * @param {string} [source]
+ * @returns {import('./skills.mjs').Competencies}
+ */
+export function parseIntake(text, mapping, source = 'formulaire') {
+    const [header, ...rows] = parseCsv(text, source)
+    const column = (title) => {
+        const index = header?.findIndex((cell) => cell.trim() == title.trim()) ?? -1
+        if(index == -1) {
+            throw new InputError(t`${source} : colonne "${title}" introuvable`)
+        }
+        return index
+    }
+    const nameColumn = column(mapping.name_column)
+    const skillColumns = Object.entries(mapping.skills).map(([skill, title]) => [skill, column(title)])
+    const levels = new Map(Object.entries(mapping.levels ?? {}).map(([answer, level]) => [answer.trim().toLowerCase(), level]))
+
+    const competencies = new Map()
+    for(const [index, row] of rows.entries()) {
+        const name = (row[nameColumn] ?? '').trim()
+        if(name == '') {
+            continue
+        }
+        const student = new Map()
+        for(const [skill, skillColumn] of skillColumns) {
+            const answer = (row[skillColumn] ?? '').trim()
+            if(answer == '') {
+                continue
+            }
+            const level = levels.get(answer.toLowerCase()) ?? Number(answer.replace(',', '.'))
+            if(!Number.isFinite(level)) {
+                throw new InputError(t`${source}, ligne ${index + 2} : réponse "${answer}" pour ${skill} sans niveau correspondant dans [intake.levels]`)
+            }
+            student.set(skill, level)
+        }
+        competencies.set(name, student)
+    }
+    return competencies
+}
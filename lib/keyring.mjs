@@ -0,0 +1,111 @@
+import { spawnSync } from 'child_process'
+import { t } from './i18n.mjs'
+
+/** Service name under which secrets are filed in the system keyring. */
+export const KEYRING_SERVICE = 'simplon-group-generator'
+
+/**
+ * Quotes an argument for the command line read by `security -i`.
+ *
+ * @param {string} value
+ */
+function securityQuote(value) {
+    if(/[\r\n]/.test(value)) {
+        throw new Error(t`Le trousseau macOS n'accepte pas de secret sur plusieurs lignes`)
+    }
+    return `"${value.replace(/["\\]/g, '\\$&')}"`
+}
+
+/**
+ * Command lines of the system keyring tools: libsecret's `secret-tool` on
+ * Linux, `security` (Keychain) on macOS. The value is always given on
+ * stdin, to `security` as a command of its interactive mode, so it never
+ * shows in the process list.
+ */
+const BACKENDS = {
+    linux: {
+        tool: 'secret-tool',
+        hint: 'installez libsecret-tools (secret-tool)',
+        set: (name, value) => ({ args: ['store', `--label=${KEYRING_SERVICE} ${name}`, 'service', KEYRING_SERVICE, 'account', name], input: value }),
+        get: (name) => ({ args: ['lookup', 'service', KEYRING_SERVICE, 'account', name] }),
+        delete: (name) => ({ args: ['clear', 'service', KEYRING_SERVICE, 'account', name] }),
+    },
+    darwin: {
+        tool: 'security',
+        hint: 'l\'outil security de macOS est requis',
+        // In interactive mode a failed command is only reported on stderr.
+        set: (name, value) => ({
+            args: ['-i'],
+            quietOnSuccess: true,
+            input: `${['add-generic-password', '-U', '-s', KEYRING_SERVICE, '-a', name, '-w', value].map(securityQuote).join(' ')}\n`,
+        }),
+        get: (name) => ({ args: ['find-generic-password', '-s', KEYRING_SERVICE, '-a', name, '-w'] }),
+        delete: (name) => ({ args: ['delete-generic-password', '-s', KEYRING_SERVICE, '-a', name] }),
+    },
+}
+
+function backend() {
+    const found = BACKENDS[process.platform]
+    if(found == null && process.platform == 'win32') {
+        throw new Error(t`Le Gestionnaire d'identification de Windows n'est pas pris en charge. Rangez le secret dans une variable d'environnement et référencez-la avec le suffixe _env, ex. : webhook_url_env = "DISCORD_WEBHOOK"`)
+    }
+    if(found == null) {
+        throw new Error(t`Trousseau système non pris en charge sur ${process.platform}, utilisez une variable d'environnement (suffixe _env)`)
+    }
+    return found
+}
+
+/**
+ * Fails as `setSecret` would when this system has no supported keyring,
+ * before the secret is asked for.
+ */
+export function requireKeyring() {
+    backend()
+}
+
+/**
+ * Runs one keyring operation.
+ *
+ * @returns {{ok: boolean, stdout: string, stderr: string}}
+ */
+function call(operation, name, value) {
+    const { tool, hint, ...commands } = backend()
+    const { args, input, quietOnSuccess } = commands[operation](name, value)
+    const result = spawnSync(tool, args, { encoding: 'utf8', input })
+    if(result.error?.code == 'ENOENT') {
+        throw new Error(t`${tool} introuvable, ${t(hint)}`)
+    }
+    if(result.error) {
+        throw new Error(t`${tool} a échoué : ${result.error.message}`)
+    }
+    const stderr = result.stderr.trim()
+    return { ok: result.status == 0 && !(quietOnSuccess && stderr != ''), stdout: result.stdout, stderr }
+}
+
+/**
+ * @param {string} name
+ * @param {string} value
+ */
+export function setSecret(name, value) {
+    const result = call('set', name, value)
+    if(!result.ok) {
+        throw new Error(t`Impossible d'enregistrer ${name} dans le trousseau : ${result.stderr}`)
+    }
+}
+
+/**
+ * @param {string} name
+ * @returns {string | null} null when the keyring has no such secret
+ */
+export function getSecret(name) {
+    const result = call('get', name)
+    return result.ok && result.stdout != '' ? result.stdout.replace(/\r?\n$/, '') : null
+}
+
+/**
+ * @param {string} name
+ * @returns {boolean} false when the keyring reported a failure
+ */
+export function deleteSecret(name) {
+    return call('delete', name).ok
+}
@@ -0,0 +1,106 @@
+import { spawnSync } from 'child_process'
+import fs from 'fs'
+import os from 'os'
+import path from 'path'
+import { InputError } from './errors.mjs'
+import { t } from './i18n.mjs'
+
+const DEFAULT_MAPPING = {
+    filter: '(memberOf=cn={group},ou=groups,{base})',
+    name_attribute: 'displayName',
+}
+
+/**
+ * Escapes a value for an LDAP search filter (RFC 4515), so that a group
+ * name can't widen or rewrite the filter it is put in.
+ *
+ * @param {string} value
+ * @returns {string}
+ */
+export function escapeFilterValue(value) {
+    return value.replace(/[\\*()\0]/g, (char) => `\\${char.charCodeAt(0).toString(16).padStart(2, '0')}`)
+}
+
+/**
+ * Lists the members of an LDAP group given as
+ * `ldap[s]://host[:port]/<base dn>?group=<name>`, using the OpenLDAP
+ * `ldapsearch` client. The search filter and attributes come from the
+ * `[ldap]` table of the configuration; `SGG_LDAP_PASSWORD` is used with
+ * `bind_dn` when set, handed to `ldapsearch` through a private temporary
+ * file rather than its command line, which other users can read.
+ *
+ * @param {string} source
+ * @param {Record<string, any>} [config] `[ldap]` table of groupgen.toml
+ * @returns {string[]}
+ */
+export function loadLdapRoster(source, config = {}) {
+    if(!URL.canParse(source)) {
+        throw new InputError(t`URL LDAP invalide : ${source}`)
+    }
+    const url = new URL(source)
+    const group = url.searchParams.get('group')
+    const base = decodeURIComponent(url.pathname.replace(/^\//, '')) || config.base
+    if(!group || !base) {
+        throw new InputError(t`URL LDAP incomplète : ${source} (attendu ldap://hôte/<base dn>?group=<groupe>)`)
+    }
+    const mapping = { ...DEFAULT_MAPPING, ...config }
+    const filter = mapping.filter.replaceAll('{group}', escapeFilterValue(group)).replaceAll('{base}', base)
+
+    const args = ['-LLL', '-x', '-H', `${url.protocol}//${url.host}`, '-b', base]
+    const password = mapping.bind_dn ? process.env.SGG_LDAP_PASSWORD : undefined
+    if(mapping.bind_dn) {
+        args.push('-D', mapping.bind_dn)
+    }
+    const dir = password ? fs.mkdtempSync(path.join(os.tmpdir(), 'groupgen-ldap-')) : null
+    let result
+    try {
+        if(dir != null) {
+            const file = path.join(dir, 'password')
+            // ldapsearch -y takes the whole file as the password, no newline.
+            fs.writeFileSync(file, password, { mode: 0o600 })
+            args.push('-y', file)
+        }
+        args.push(filter, mapping.name_attribute)
+        result = spawnSync('ldapsearch', args, { encoding: 'utf8' })
+    } finally {
+        if(dir != null) {
+            fs.rmSync(dir, { recursive: true, force: true })
+        }
+    }
+    if(result.error?.code == 'ENOENT') {
+        throw new Error(t`ldapsearch introuvable, installez les outils clients OpenLDAP (ldap-utils / openldap-clients)`)
+    }
+    if(result.status != 0) {
+        throw new Error(t`ldapsearch a échoué : ${result.stderr.trim()}`)
+    }
+    return parseLdif(result.stdout)
+        .map((entry) => entry[mapping.name_attribute.toLowerCase()])
+        .filter((name) => name != null)
+}
+
+/**
+ * @param {string} ldif
+ * @returns {Record<string, string>[]} first value of each attribute, keyed in lower case
+ */
+export function parseLdif(ldif) {
+    const entries = []
+    let entry = null
+    for(const line of ldif.replace(/\r?\n /g, '').split(/\r?\n/)) {
+        if(line.trim() == '') {
+            entry = null
+            continue
+        }
+        const match = line.match(/^([^:]+)(::?)\s?(.*)$/)
+        if(!match) {
+            continue
+        }
+        if(entry == null) {
+            entry = {}
+            entries.push(entry)
+        }
+        const [, attribute, separator, value] = match
+        const key = attribute.toLowerCase()
+        entry[key] ??= separator == '::' ? Buffer.from(value, 'base64').toString('utf8') : value
+    }
+    return entries
+}
@@ -0,0 +1,143 @@
+import fs from 'fs'
+import { writeFileAtomic } from './files.mjs'
+import { t } from './i18n.mjs'
+import { log } from './log.mjs'
+import { Prompter } from './prompt.mjs'
+
+/**
+ * A roster entry that looks like a copy-paste accident, with the ways to
+ * fix it. Each fix is the list of names replacing the entry, so that two
+ * students pasted together can be split.
+ *
+ * @typedef {object} Suspect
+ * @property {string} name
+ * @property {string} problem in French
+ * @property {string[][]} fixes
+ */
+
+const EMAIL = /\s*<?[\w.+-]+@[\w-]+(\.[\w-]+)+>?\s*/
+
+/** Lower case letter directly followed by an upper case one, "BobMartin". */
+const GLUED = /(\p{Ll})(\p{Lu})/u
+
+/** Names that legitimately have an inner capital: McCartney, MacLeod, DiCaprio... */
+const INNER_CAPITAL = /(^|\s)(Mc|Mac|Di|De|La|Le|Van|Von)\p{Lu}/u
+
+/**
+ * @param {string} word
+ */
+function capitalize(word) {
+    return word.charAt(0).toUpperCase() + word.slice(1).toLowerCase()
+}
+
+/**
+ * Looks for entries pasted from a spreadsheet or a mail client rather
+ * than typed: stray separators, emails instead of names, two names glued
+ * together.
+ *
+ * @param {string[]} names
+ * @returns {Suspect[]}
+ */
+export function suspiciousNames(names) {
+    const suspects = []
+    for(const name of names) {
+        const email = name.match(EMAIL)
+        if(email != null) {
+            const rest = name.replace(EMAIL, ' ').trim()
+            const local = email[0].trim().replace(/^</, '').split('@')[0]
+            suspects.push({
+                name,
+                problem: t`adresse email`,
+                fixes: [[rest != '' ? rest : local.split(/[._-]+/).filter(Boolean).map(capitalize).join(' ')]],
+            })
+            continue
+        }
+        if(/[,;]/.test(name.replace(/^[\s,;.:]+|[\s,;.:]+$/g, ''))) {
+            suspects.push({ name, problem: t`plusieurs noms séparés par une virgule`, fixes: [name.split(/[,;]/).map((part) => part.trim()).filter(Boolean)] })
+            continue
+        }
+        if(/^[,;.:]|[,;.:]$/.test(name)) {
+            suspects.push({ name, problem: t`ponctuation en trop`, fixes: [[name.replace(/^[\s,;.:]+|[\s,;.:]+$/g, '')]] })
+            continue
+        }
+        const glued = name.match(GLUED)
+        if(glued != null && !INNER_CAPITAL.test(name)) {
+            const cut = glued.index + 1
+            suspects.push({
+                name,
+                problem: t`noms collés`,
+                fixes: [[name.slice(0, cut).trim(), name.slice(cut).trim()], [`${name.slice(0, cut)} ${name.slice(cut)}`]],
+            })
+            continue
+        }
+        if(/\s{2,}/.test(name)) {
+            suspects.push({ name, problem: t`espaces en trop`, fixes: [[name.replace(/\s+/g, ' ')]] })
+        }
+    }
+    return suspects
+}
+
+/**
+ * Replaces suspect entries of a JSON roster file. Object entries keep
+ * their details on the first name replacing them.
+ *
+ * @param {string} file
+ * @param {Map<string, string[]>} fixes replacement names, by original name
+ */
+function fixRosterFile(file, fixes) {
+    const entries = JSON.parse(fs.readFileSync(file, 'utf8'))
+    const fixed = entries.flatMap((entry) => {
+        const name = (typeof entry == 'string' ? entry : entry.name).trim()
+        const names = fixes.get(name)
+        if(names == null) {
+            return [entry]
+        }
+        return names.map((replacement, index) => typeof entry == 'string' || index > 0 ? replacement : { ...entry, name: replacement })
+    })
+    writeFileAtomic(file, JSON.stringify(fixed, null, 4) + '\n')
+}
+
+/**
+ * Reports suspect entries of the roster file and, when `interactive`,
+ * offers to fix each of them in the file before the roster is used.
+ *
+ * @param {string} file JSON roster file
+ * @param {string[]} names as read from it
+ * @param {{interactive: boolean}} options
+ * @returns {Promise<boolean>} whether the file was changed
+ */
+export async function reviewRoster(file, names, { interactive }) {
+    const suspects = suspiciousNames(names)
+    if(suspects.length == 0) {
+        return false
+    }
+    const describe = (fix) => fix.map((name) => t`« ${name} »`).join(', ')
+    if(!interactive) {
+        for(const { name, problem, fixes } of suspects) {
+            log.warn(t`${file} : « ${name} » ressemble à une erreur de copier-coller (${problem}), peut-être ${describe(fixes[0])}`)
+        }
+        return false
+    }
+    /** @type {Map<string, string[]>} */
+    const chosen = new Map()
+    const prompter = new Prompter()
+    try {
+        for(const { name, problem, fixes } of suspects) {
+            console.log(t`« ${name} » ressemble à une erreur de copier-coller (${problem}) :`)
+            fixes.forEach((fix, index) => console.log(`  ${index + 1}. ${describe(fix)}`))
+            console.log(t`  0. garder tel quel`)
+            const choice = Number(await prompter.ask(t`Correction [0-${fixes.length}] : `) || 0)
+            if(fixes[choice - 1] != null) {
+                chosen.set(name, fixes[choice - 1])
+            }
+        }
+    } finally {
+        prompter.close()
+    }
+    if(chosen.size == 0) {
+        return false
+    }
+    fixRosterFile(file, chosen)
+    console.log(t`${chosen.size} correction(s) enregistrée(s) dans ${file}.`)
+    return true
+}
@@ -0,0 +1,74 @@
+import { format } from 'util'
+import { t } from './i18n.mjs'
+
+/**
+ * Diagnostics, written on stderr so that stdout keeps only the output of
+ * the command (groups, exports...). From the least to the most verbose:
+ * errors, warnings, notices of what was done, then details for `-v` and
+ * `-vv`.
+ */
+const LEVELS = ['error', 'warn', 'info', 'verbose', 'debug']
+
+let threshold = LEVELS.indexOf('info')
+let json = false
+
+/**
+ * @param {{level?: string, json?: boolean}} options json: one JSON object
+ *   per line, for automation
+ */
+export function configureLogging({ level = 'info', json: asJson = false } = {}) {
+    if(!LEVELS.includes(level)) {
+        throw new Error(t`Niveau de journalisation inconnu : ${level}`)
+    }
+    threshold = LEVELS.indexOf(level)
+    json = asJson
+}
+
+/**
+ * Takes the verbosity flags out of `argv`, wherever they are, as they
+ * apply to every command: `-q`/`--quiet`, `-v`/`--verbose` (twice, or
+ * `-vv`, for details) and `--log-json`.
+ *
+ * @param {string[]} argv modified in place
+ * @returns {{level: string, json: boolean}}
+ */
+export function takeLoggingFlags(argv) {
+    let verbosity = 0
+    let quiet = false
+    let asJson = false
+    for(let index = argv.length - 1; index >= 0; index--) {
+        const arg = argv[index]
+        if(arg == '-q' || arg == '--quiet') {
+            quiet = true
+        } else if(arg == '--verbose' || /^-v+$/.test(arg)) {
+            verbosity += arg == '--verbose' ? 1 : arg.length - 1
+        } else if(arg == '--log-json') {
+            asJson = true
+        } else {
+            continue
+        }
+        argv.splice(index, 1)
+    }
+    const level = quiet ? 'error' : LEVELS[Math.min(LEVELS.indexOf('info') + verbosity, LEVELS.length - 1)]
+    return { level, json: asJson }
+}
+
+/**
+ * @param {string} level
+ * @param {any[]} args as for console.log
+ */
+function write(level, args) {
+    if(LEVELS.indexOf(level) > threshold) {
+        return
+    }
+    const message = format(...args)
+    process.stderr.write(json ? JSON.stringify({ time: new Date().toISOString(), level, message }) + '\n' : message + '\n')
+}
+
+export const log = {
+    error: (...args) => write('error', args),
+    warn: (...args) => write('warn', args),
+    info: (...args) => write('info', args),
+    verbose: (...args) => write('verbose', args),
+    debug: (...args) => write('debug', args),
+}
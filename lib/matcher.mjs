@@ -0,0 +1,641 @@
+import { setImmediate as yieldToEventLoop } from 'timers/promises'
+import { InputError } from './errors.mjs'
+import { t } from './i18n.mjs'
+import { minCostPerfectMatching } from './matching.mjs'
+import { compareNames } from './names.mjs'
+
+export const DEFAULT_ITERATIONS = 10000
+
+/**
+ * Largest roster the matcher accepts. The cost matrix takes
+ * MAX_STUDENTS² bytes (250 kB) and one iteration stays well under a
+ * millisecond at this size.
+ */
+export const MAX_STUDENTS = 500
+
+/**
+ * Cost of putting together two students under a "never" constraint,
+ * larger than any number of repeats.
+ */
+export const NEVER_PENALTY = 1_000_000
+
+/**
+ * Square matrix of how many times each pair of students met, indexed by
+ * their position in the roster. Counts saturate at 255.
+ */
+export class CostMatrix {
+    /**
+     * @param {string[]} students
+     */
+    constructor(students) {
+        if(students.length > MAX_STUDENTS) {
+            throw new InputError(t`${students.length} apprenants : la limite supportée est de ${MAX_STUDENTS}`)
+        }
+        this.size = students.length
+        /** @type {Map<string, number>} */
+        this.index = new Map(students.map((name, index) => [name, index]))
+        this.costs = new Uint8Array(this.size * this.size)
+        this.forbidden = new Uint8Array(this.size * this.size)
+    }
+
+    /**
+     * Marks pairs that must never share a group.
+     *
+     * @param {[string, string][]} pairs
+     */
+    forbid(pairs) {
+        for(const [a, b] of pairs) {
+            const i = this.index.get(a)
+            const j = this.index.get(b)
+            if(i != null && j != null) {
+                this.forbidden[i * this.size + j] = 1
+                this.forbidden[j * this.size + i] = 1
+            }
+        }
+        return this
+    }
+
+    /**
+     * Counts every pair inside `groups`, ignoring students not in the roster.
+     *
+     * @param {string[][]} groups
+     */
+    addGroups(groups) {
+        for(const group of groups) {
+            const members = group.map((name) => this.index.get(name)).filter((index) => index != null)
+            for(let i = 0; i < members.length; i++) {
+                for(let j = i + 1; j < members.length; j++) {
+                    this.#increment(members[i], members[j])
+                    this.#increment(members[j], members[i])
+                }
+            }
+        }
+        return this
+    }
+
+    #increment(a, b) {
+        const cell = a * this.size + b
+        if(this.costs[cell] < 255) {
+            this.costs[cell]++
+        }
+    }
+
+    /**
+     * @param {number} a
+     * @param {number} b
+     */
+    get(a, b) {
+        return this.costs[a * this.size + b]
+    }
+
+    /**
+     * Makes `score` also count, for each group, the `required` skills none
+     * of its members masters, `weight` each.
+     *
+     * @param {Map<string, string[]>} mastered required skills of each student
+     * @param {string[]} required
+     * @param {number} [weight]
+     */
+    requireSkills(mastered, required, weight = 1) {
+        if(required.length > 32) {
+            throw new InputError(t`${required.length} compétences requises : la limite est de 32`)
+        }
+        this.skillMasks = new Uint32Array(this.size)
+        for(const [name, index] of this.index) {
+            for(const skill of mastered.get(name) ?? []) {
+                this.skillMasks[index] |= 1 << required.indexOf(skill)
+            }
+        }
+        this.fullMask = required.length == 32 ? 0xFFFFFFFF : (1 << required.length) - 1
+        this.skillWeight = weight
+        return this
+    }
+
+    /**
+     * @param {number[]} group student indices
+     * @returns {number} required skills not covered by the group
+     */
+    missingSkills(group) {
+        if(this.skillMasks == null) {
+            return 0
+        }
+        let covered = 0
+        for(const index of group) {
+            covered |= this.skillMasks[index]
+        }
+        let missing = (~covered & this.fullMask) >>> 0
+        let count = 0
+        while(missing != 0) {
+            missing &= missing - 1
+            count++
+        }
+        return count
+    }
+
+    /**
+     * Sum of the costs of every pair inside `group`, plus NEVER_PENALTY
+     * for each forbidden pair.
+     *
+     * @param {number[]} group student indices
+     */
+    pairCost(group) {
+        let total = 0
+        for(let i = 0; i < group.length; i++) {
+            for(let j = i + 1; j < group.length; j++) {
+                const cell = group[i] * this.size + group[j]
+                total += this.costs[cell] + this.forbidden[cell] * NEVER_PENALTY
+            }
+        }
+        return total
+    }
+
+    /**
+     * Cost of a grouping: `pairCost` of each group, plus the weighted
+     * missing skills when some are required.
+     *
+     * @param {number[][]} groups student indices
+     */
+    score(groups) {
+        let total = 0
+        for(const group of groups) {
+            total += this.pairCost(group)
+            if(this.skillMasks != null) {
+                total += this.skillWeight * this.missingSkills(group)
+            }
+        }
+        return total
+    }
+}
+
+/**
+ * Repeats and broken "never" constraints of groups changed by hand, as
+ * `generateGroups` reports them for its own.
+ *
+ * @param {string[]} students
+ * @param {string[][]} groups
+ * @param {string[][] | null} lastBrief groups to avoid
+ * @param {import('./constraints.mjs').Constraint[]} [constraints]
+ * @returns {{repeats: number, violations: number, groupRepeats: number[]}}
+ */
+export function scoreGroups(students, groups, lastBrief, constraints = []) {
+    const matrix = new CostMatrix(students)
+        .addGroups(lastBrief ?? [])
+        .forbid(constraints.filter((constraint) => constraint.type == 'never').map((constraint) => constraint.students))
+    const costs = groups.map((group) => matrix.pairCost(group.map((name) => matrix.index.get(name))))
+    const total = costs.reduce((sum, cost) => sum + cost, 0)
+    return {
+        repeats: total % NEVER_PENALTY,
+        violations: Math.floor(total / NEVER_PENALTY),
+        groupRepeats: costs.map((cost) => cost % NEVER_PENALTY),
+    }
+}
+
+/**
+ * Merges "together" pairs sharing a student into fixed groups of indices.
+ *
+ * @param {CostMatrix} matrix
+ * @param {[string, string][]} pairs
+ * @returns {number[][]}
+ */
+function fixedGroups(matrix, pairs) {
+    const parent = Array.from({ length: matrix.size }, (_, index) => index)
+    const find = (i) => parent[i] == i ? i : (parent[i] = find(parent[i]))
+    const linked = new Set()
+    for(const [a, b] of pairs) {
+        const i = matrix.index.get(a)
+        const j = matrix.index.get(b)
+        if(i != null && j != null) {
+            parent[find(i)] = find(j)
+            linked.add(i).add(j)
+        }
+    }
+    const groups = new Map()
+    for(const index of linked) {
+        const root = find(index)
+        groups.set(root, [...(groups.get(root) ?? []), index])
+    }
+    return [...groups.values()]
+}
+
+/**
+ * Indices of the students not already placed in a `fixed` group, computed
+ * once per search rather than on every draw.
+ *
+ * @param {number} size
+ * @param {number[][]} fixed
+ * @returns {number[]}
+ */
+function freeIndices(size, fixed) {
+    const placed = new Uint8Array(size)
+    for(const index of fixed.flat()) {
+        placed[index] = 1
+    }
+    return Array.from({ length: size }, (_, index) => index).filter((index) => placed[index] == 0)
+}
+
+/**
+ * Reads a `--group-size` value.
+ *
+ * @param {string} value
+ * @returns {number}
+ */
+export function parseGroupSize(value) {
+    const size = Number(value)
+    if(!Number.isInteger(size) || size < 2) {
+        throw new Error(t`Taille de groupe invalide : ${value} (2 ou plus)`)
+    }
+    return size
+}
+
+/**
+ * Sizes of the groups formed from `count` students: groups of `size`, the
+ * remainder spread one per group (a trio among pairs) when there are
+ * enough groups, otherwise `count` split as evenly as possible.
+ *
+ * @param {number} count
+ * @param {number} size
+ * @returns {number[]}
+ */
+export function groupSizes(count, size) {
+    const full = Math.floor(count / size)
+    const remainder = count % size
+    if(remainder <= full) {
+        return Array.from({ length: full }, (_, index) => index >= full - remainder ? size + 1 : size)
+    }
+    const groups = full + 1
+    return Array.from({ length: groups }, (_, index) => Math.floor(count / groups) + (index >= groups - count % groups ? 1 : 0))
+}
+
+/**
+ * @param {number[]} items left untouched
+ * @param {() => number} random
+ * @returns {number[]} a copy in random order
+ */
+function shuffled(items, random) {
+    const copy = items.slice()
+    for(let i = copy.length - 1; i > 0; i--) {
+        const j = Math.floor(random() * (i + 1))
+        const swap = copy[i]
+        copy[i] = copy[j]
+        copy[j] = swap
+    }
+    return copy
+}
+
+/**
+ * Groups the `free` indices at random after the `fixed` groups, which are
+ * first filled up to `size`. A student left alone joins the final group.
+ *
+ * @param {number[]} free as returned by `freeIndices`, left untouched
+ * @param {number[][]} [fixed]
+ * @param {number} [size]
+ * @param {() => number} [random]
+ * @returns {number[][]}
+ */
+function shuffleIntoGroups(free, fixed = [], size = 2, random = Math.random) {
+    const peoples = shuffled(free, random)
+    const groups = fixed.map((group) => [...group])
+    let next = 0
+    for(const group of groups) {
+        while(group.length < size && next < peoples.length) {
+            group.push(peoples[next++])
+        }
+    }
+    const left = peoples.length - next
+    if(left == 1 && groups.length > 0) {
+        groups[groups.length - 1].push(peoples[next])
+        return groups
+    }
+    for(const length of groupSizes(left, size)) {
+        groups.push(peoples.slice(next, next + length))
+        next += length
+    }
+    return groups
+}
+
+/**
+ * Pairs the `free` indices after the `fixed` groups with the least cost
+ * there is, by minimum-weight perfect matching: a pair's cost is its
+ * `score`, so repeats, "never" constraints and missing skills all count.
+ * Students are put in random order first, so that `random` picks among
+ * the best pairings. With an odd count, one student is matched with no
+ * one and then joins the group where they cost least, which may miss
+ * the best trio.
+ *
+ * @param {CostMatrix} matrix
+ * @param {number[]} free
+ * @param {number[][]} fixed
+ * @param {() => number} random
+ * @returns {number[][]}
+ */
+function optimalPairs(matrix, free, fixed, random) {
+    const order = shuffled(free, random)
+    // An extra vertex, free to pair with anyone, takes the odd one out.
+    const size = order.length + order.length % 2
+    const mate = minCostPerfectMatching(size, (i, j) => j >= order.length ? 0 : matrix.score([[order[i], order[j]]]))
+    const groups = fixed.map((group) => [...group])
+    let alone = null
+    order.forEach((student, i) => {
+        if(mate[i] >= order.length) {
+            alone = student
+        } else if(mate[i] > i) {
+            groups.push([student, order[mate[i]]])
+        }
+    })
+    if(alone != null) {
+        if(groups.length == 0) {
+            return [[alone]]
+        }
+        const extra = (group) => matrix.score([[...group, alone]]) - matrix.score([group])
+        groups.reduce((best, group) => extra(group) < extra(best) ? group : best).push(alone)
+    }
+    return groups
+}
+
+/**
+ * How `generateGroups` decides between groupings of the same score:
+ * `new-pairs` prefers the one forming the most pairs never seen in any
+ * past brief, `churn` numbers the groups so that students keep the group
+ * number they had in the last brief where possible, and `alphabetical`
+ * takes the first grouping in alphabetical order, so that the same
+ * inputs give the same groups whatever the draws. With `none`, the first
+ * grouping found wins.
+ */
+export const TIE_BREAKS = ['none', 'new-pairs', 'churn', 'alphabetical']
+
+/**
+ * @param {string} value
+ */
+export function parseTieBreak(value) {
+    if(!TIE_BREAKS.includes(value)) {
+        throw new Error(t`Départage invalide : ${value} (${TIE_BREAKS.join(', ')})`)
+    }
+    return value
+}
+
+/**
+ * @typedef {object} TieBreaker
+ * @property {(groups: number[][]) => any} key secondary score of a grouping
+ * @property {(a: any, b: any) => number} compare negative when `a` is better
+ * @property {(key: any) => boolean} settled no grouping can do better
+ */
+
+/**
+ * @param {string} tieBreak one of TIE_BREAKS
+ * @param {string[]} students
+ * @param {string[][][]} history groups of every past brief
+ * @returns {TieBreaker | null} null when the search needs no secondary score
+ */
+function tieBreaker(tieBreak, students, history) {
+    if(tieBreak == 'new-pairs') {
+        const met = new CostMatrix(students)
+        for(const groups of history) {
+            met.addGroups(groups)
+        }
+        return {
+            key: (groups) => {
+                let known = 0
+                for(const group of groups) {
+                    for(let i = 0; i < group.length; i++) {
+                        for(let j = i + 1; j < group.length; j++) {
+                            known += met.get(group[i], group[j]) > 0 ? 1 : 0
+                        }
+                    }
+                }
+                return known
+            },
+            compare: (a, b) => a - b,
+            settled: (known) => known == 0,
+        }
+    }
+    if(tieBreak == 'alphabetical') {
+        return {
+            key: (groups) => canonical(groups.map((group) => group.map((index) => students[index]))),
+            compare: compareGroupings,
+            settled: () => false,
+        }
+    }
+    return null
+}
+
+/**
+ * Members in alphabetical order, then groups by their first member.
+ *
+ * @param {string[][]} groups
+ */
+function canonical(groups) {
+    return groups
+        .map((group) => [...group].sort(compareNames))
+        .sort((a, b) => compareNames(a[0], b[0]))
+}
+
+/**
+ * Compares two canonical groupings member by member.
+ *
+ * @param {string[][]} a
+ * @param {string[][]} b
+ */
+function compareGroupings(a, b) {
+    const flatA = a.flat()
+    const flatB = b.flat()
+    for(let i = 0; i < Math.min(flatA.length, flatB.length); i++) {
+        const order = compareNames(flatA[i], flatB[i])
+        if(order != 0) {
+            return order
+        }
+    }
+    for(let i = 0; i < Math.min(a.length, b.length); i++) {
+        if(a[i].length != b[i].length) {
+            return b[i].length - a[i].length
+        }
+    }
+    return 0
+}
+
+/**
+ * Orders `groups` after `lastGroups`: each group of the last brief in
+ * turn hands its number to the remaining group sharing the most members
+ * with it, the groups left over coming last.
+ *
+ * @param {number[][]} groups
+ * @param {number[][]} lastGroups
+ */
+function keepGroupNumbers(groups, lastGroups) {
+    const left = groups.slice()
+    const ordered = []
+    for(const previous of lastGroups) {
+        if(left.length == 0) {
+            break
+        }
+        let best = 0
+        let bestShared = -1
+        left.forEach((group, index) => {
+            const shared = group.filter((member) => previous.includes(member)).length
+            if(shared > bestShared) {
+                best = index
+                bestShared = shared
+            }
+        })
+        ordered.push(left.splice(best, 1)[0])
+    }
+    return [...ordered, ...left]
+}
+
+/**
+ * @typedef {object} SearchResult
+ * @property {string[][]} groups best grouping found
+ * @property {number} repeats pairs already seen in the last brief
+ * @property {number} violations "never" constraints that could not be honoured
+ * @property {number[]} groupRepeats repeated pairs inside each group
+ * @property {number} missingSkills required skills missing, summed over the groups
+ * @property {number[]} improvements successive best scores found during the search, see `CostMatrix.score`
+ * @property {number} iterations candidates evaluated
+ * @property {boolean} interrupted the search was aborted before finishing
+ * @property {boolean} outOfTime the search stopped at the end of its time budget
+ */
+
+/**
+ * @typedef {object} SkillRequirement
+ * @property {string[]} required skills every group should cover
+ * @property {Map<string, string[]>} mastered required skills of each student
+ * @property {number} [weight] cost of a missing skill, in repeated pairs
+ */
+
+/**
+ * Draws random groupings into groups of `groupSize` (see `groupSizes`)
+ * and keeps the one repeating the fewest pairs of the last brief, every
+ * pair inside a group counting. Pairs start from the optimal pairing
+ * (see `optimalPairs`), which draws only improve on with an odd count or
+ * a tie-break. "together" constraints are always
+ * honoured, "never" ones unless impossible. With `skills`, each required
+ * skill missing from a group costs `weight` repeated pairs. Draws come
+ * from `random`, so a seeded generator makes the search reproducible.
+ * The loop yields to the event loop regularly so that `signal`
+ * (typically wired to SIGINT) can stop it; it also stops after
+ * `timeBudget` milliseconds, whichever of the limits comes first.
+ * Groupings of the same score are told apart by `tieBreak` (see
+ * TIE_BREAKS); `new-pairs` reads the earlier briefs from `history`, and
+ * with it or `alphabetical` the search goes on after a perfect score to
+ * look for a better tie.
+ *
+ * @param {string[]} students
+ * @param {string[][] | null} lastGroups
+ * @param {{iterations?: number, signal?: AbortSignal, constraints?: import('./constraints.mjs').Constraint[], skills?: SkillRequirement, groupSize?: number, random?: () => number, timeBudget?: number, tieBreak?: string, history?: string[][][]}} [options]
+ * @returns {Promise<SearchResult>}
+ */
+export async function generateGroups(students, lastGroups, { iterations = DEFAULT_ITERATIONS, signal, constraints = [], skills, groupSize = 2, random = Math.random, timeBudget = Infinity, tieBreak = 'none', history = [] } = {}) {
+    const pairsOf = (type) => constraints.filter((constraint) => constraint.type == type).map((constraint) => constraint.students)
+    const matrix = new CostMatrix(students).addGroups(lastGroups ?? []).forbid(pairsOf('never'))
+    if(skills != null && skills.required.length > 0) {
+        matrix.requireSkills(skills.mastered, skills.required, skills.weight)
+    }
+    const fixed = fixedGroups(matrix, pairsOf('together'))
+    const free = freeIndices(students.length, fixed)
+    const tie = tieBreaker(tieBreak, students, history)
+    let best = null
+    let bestScore = Infinity
+    let bestKey = null
+    const improvements = []
+    const deadline = performance.now() + timeBudget
+    let outOfTime = false
+    let done = 0
+    // With an even count, no draw can beat the matching, only tie with it.
+    const optimal = groupSize == 2 && free.length % 2 == 0
+    if(groupSize == 2 && free.length > 0) {
+        best = optimalPairs(matrix, free, fixed, random)
+        bestScore = matrix.score(best)
+        bestKey = tie?.key(best)
+        improvements.push(bestScore)
+        done++
+    }
+    while(done < iterations && !((bestScore == 0 || optimal) && (tie == null || tie.settled(bestKey)))) {
+        if(done % 256 == 0) {
+            await yieldToEventLoop()
+            if(signal?.aborted) {
+                break
+            }
+            if(done > 0 && performance.now() > deadline) {
+                outOfTime = true
+                break
+            }
+        }
+        const groups = shuffleIntoGroups(free, fixed, groupSize, random)
+        const score = matrix.score(groups)
+        if(score < bestScore) {
+            best = groups
+            bestScore = score
+            bestKey = tie?.key(groups)
+            improvements.push(score)
+        } else if(score == bestScore && tie != null) {
+            const key = tie.key(groups)
+            if(tie.compare(key, bestKey) < 0) {
+                best = groups
+                bestKey = key
+            }
+        }
+        done++
+    }
+    if(best == null) {
+        best = shuffleIntoGroups(free, fixed, groupSize, random)
+    }
+    if(tieBreak == 'alphabetical') {
+        const order = canonical(best.map((group) => group.map((index) => students[index])))
+        best = order.map((group) => group.map((name) => matrix.index.get(name)))
+    } else if(tieBreak == 'churn' && lastGroups != null) {
+        best = keepGroupNumbers(best, lastGroups.map((group) => group.map((name) => matrix.index.get(name)).filter((index) => index != null)))
+    }
+    const pairCosts = best.map((group) => matrix.pairCost(group))
+    const pairTotal = pairCosts.reduce((sum, cost) => sum + cost, 0)
+    return {
+        groups: best.map((group) => group.map((index) => students[index])),
+        repeats: pairTotal % NEVER_PENALTY,
+        violations: Math.floor(pairTotal / NEVER_PENALTY),
+        groupRepeats: pairCosts.map((cost) => cost % NEVER_PENALTY),
+        missingSkills: best.reduce((sum, group) => sum + matrix.missingSkills(group), 0),
+        improvements,
+        iterations: done,
+        interrupted: signal?.aborted ?? false,
+        outOfTime,
+    }
+}
+
+/**
+ * Chooses the group a student arriving late should join: the one with a
+ * "together" partner if any, otherwise the one adding the fewest pairs
+ * already seen in `lastGroups` ("never" constraints counting
+ * NEVER_PENALTY), the smallest group winning ties. Groups of `maxSize` or
+ * more are skipped.
+ *
+ * @param {string} student
+ * @param {string[][]} groups
+ * @param {string[][] | null} lastGroups
+ * @param {{maxSize?: number, constraints?: import('./constraints.mjs').Constraint[]}} [options]
+ * @returns {number} index in `groups`, -1 when every group is full
+ */
+export function bestGroupFor(student, groups, lastGroups, { maxSize = Infinity, constraints = [] } = {}) {
+    const partners = constraints
+        .filter((constraint) => constraint.type == 'together' && constraint.students.includes(student))
+        .flatMap((constraint) => constraint.students)
+    const together = groups.findIndex((group) => group.some((name) => partners.includes(name)))
+    if(together != -1) {
+        return together
+    }
+    const roster = [...new Set([...groups.flat(), student])]
+    const never = constraints.filter((constraint) => constraint.type == 'never').map((constraint) => constraint.students)
+    const matrix = new CostMatrix(roster).addGroups(lastGroups ?? []).forbid(never)
+    const newcomer = matrix.index.get(student)
+    let best = -1
+    let bestCost = Infinity
+    groups.forEach((group, index) => {
+        if(group.length >= maxSize) {
+            return
+        }
+        const members = group.map((name) => matrix.index.get(name))
+        const cost = matrix.score([[...members, newcomer]]) - matrix.score([members])
+        if(cost < bestCost || (cost == bestCost && group.length < groups[best].length)) {
+            best = index
+            bestCost = cost
+        }
+    })
+    return best
+}
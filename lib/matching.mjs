@@ -0,0 +1,467 @@
+/**
+ * Minimum-weight perfect matching on a complete graph, by Edmonds'
+ * blossom algorithm in the O(n³) primal-dual form of Joris van Rantwijk's
+ * mwmatching (public domain), which this follows step by step. Costs are
+ * turned into weights `top - cost`, so that the maximum-weight matching
+ * of largest cardinality is the perfect matching of least cost.
+ *
+ * @param {number} size number of vertices, even
+ * @param {(i: number, j: number) => number} cost integer cost of pairing i and j
+ * @returns {number[]} mate of each vertex
+ */
+export function minCostPerfectMatching(size, cost) {
+    /** @type {[number, number, number][]} */
+    const edges = []
+    let highest = 0
+    for(let i = 0; i < size; i++) {
+        for(let j = i + 1; j < size; j++) {
+            const value = cost(i, j)
+            highest = Math.max(highest, value)
+            edges.push([i, j, value])
+        }
+    }
+    for(const edge of edges) {
+        edge[2] = highest + 1 - edge[2]
+    }
+    return maxWeightMatching(size, edges)
+}
+
+/**
+ * Maximum-weight matching of maximum cardinality.
+ *
+ * @param {number} nvertex
+ * @param {[number, number, number][]} edges (i, j, weight), integer weights
+ * @returns {number[]} mate of each vertex, -1 when unmatched
+ */
+function maxWeightMatching(nvertex, edges) {
+    if(edges.length == 0) {
+        return new Array(nvertex).fill(-1)
+    }
+    const nedge = edges.length
+    const maxweight = edges.reduce((max, edge) => Math.max(max, edge[2]), 0)
+    // Endpoint p of edge p >> 1 is vertex edges[p >> 1][p & 1].
+    const endpoint = Array.from({ length: 2 * nedge }, (_, p) => edges[p >> 1][p & 1])
+    const neighbend = Array.from({ length: nvertex }, () => [])
+    edges.forEach(([i, j], k) => {
+        neighbend[i].push(2 * k + 1)
+        neighbend[j].push(2 * k)
+    })
+    const mate = new Array(nvertex).fill(-1)
+    // 0 free, 1 S (outer), 2 T (inner), 5 S being scanned by scanBlossom.
+    const label = new Array(2 * nvertex).fill(0)
+    const labelend = new Array(2 * nvertex).fill(-1)
+    const inblossom = Array.from({ length: nvertex }, (_, v) => v)
+    const blossomparent = new Array(2 * nvertex).fill(-1)
+    const blossomchilds = new Array(2 * nvertex).fill(null)
+    const blossombase = [...Array.from({ length: nvertex }, (_, v) => v), ...new Array(nvertex).fill(-1)]
+    const blossomendps = new Array(2 * nvertex).fill(null)
+    const bestedge = new Array(2 * nvertex).fill(-1)
+    const blossombestedges = new Array(2 * nvertex).fill(null)
+    const unusedblossoms = Array.from({ length: nvertex }, (_, b) => nvertex + b)
+    const dualvar = [...new Array(nvertex).fill(maxweight), ...new Array(nvertex).fill(0)]
+    let allowedge = new Array(nedge).fill(false)
+    let queue = []
+
+    const slack = (k) => {
+        const [i, j, wt] = edges[k]
+        return dualvar[i] + dualvar[j] - 2 * wt
+    }
+
+    const blossomLeaves = (b, leaves = []) => {
+        if(b < nvertex) {
+            leaves.push(b)
+        } else {
+            for(const t of blossomchilds[b]) {
+                blossomLeaves(t, leaves)
+            }
+        }
+        return leaves
+    }
+
+    const assignLabel = (w, t, p) => {
+        const b = inblossom[w]
+        label[w] = label[b] = t
+        labelend[w] = labelend[b] = p
+        bestedge[w] = bestedge[b] = -1
+        if(t == 1) {
+            queue.push(...blossomLeaves(b))
+        } else if(t == 2) {
+            const base = blossombase[b]
+            assignLabel(endpoint[mate[base]], 1, mate[base] ^ 1)
+        }
+    }
+
+    // Traces back from v and w to find a new blossom or an augmenting
+    // path; returns the base of the blossom, or -1.
+    const scanBlossom = (v, w) => {
+        const path = []
+        let base = -1
+        while(v != -1 || w != -1) {
+            let b = inblossom[v]
+            if(label[b] & 4) {
+                base = blossombase[b]
+                break
+            }
+            path.push(b)
+            label[b] = 5
+            if(labelend[b] == -1) {
+                v = -1
+            } else {
+                v = endpoint[labelend[b]]
+                b = inblossom[v]
+                v = endpoint[labelend[b]]
+            }
+            if(w != -1) {
+                [v, w] = [w, v]
+            }
+        }
+        for(const b of path) {
+            label[b] = 1
+        }
+        return base
+    }
+
+    const addBlossom = (base, k) => {
+        let [v, w] = edges[k]
+        const bb = inblossom[base]
+        let bv = inblossom[v]
+        let bw = inblossom[w]
+        const b = unusedblossoms.pop()
+        blossombase[b] = base
+        blossomparent[b] = -1
+        blossomparent[bb] = b
+        const path = []
+        const endps = []
+        while(bv != bb) {
+            blossomparent[bv] = b
+            path.push(bv)
+            endps.push(labelend[bv])
+            v = endpoint[labelend[bv]]
+            bv = inblossom[v]
+        }
+        path.push(bb)
+        path.reverse()
+        endps.reverse()
+        endps.push(2 * k)
+        while(bw != bb) {
+            blossomparent[bw] = b
+            path.push(bw)
+            endps.push(labelend[bw] ^ 1)
+            w = endpoint[labelend[bw]]
+            bw = inblossom[w]
+        }
+        blossomchilds[b] = path
+        blossomendps[b] = endps
+        label[b] = 1
+        labelend[b] = labelend[bb]
+        dualvar[b] = 0
+        for(const leaf of blossomLeaves(b)) {
+            if(label[inblossom[leaf]] == 2) {
+                queue.push(leaf)
+            }
+            inblossom[leaf] = b
+        }
+        const bestedgeto = new Array(2 * nvertex).fill(-1)
+        for(const child of path) {
+            const nblists = blossombestedges[child] == null
+                ? blossomLeaves(child).map((leaf) => neighbend[leaf].map((p) => p >> 1))
+                : [blossombestedges[child]]
+            for(const nblist of nblists) {
+                for(const edge of nblist) {
+                    let [i, j] = edges[edge]
+                    if(inblossom[j] == b) {
+                        [i, j] = [j, i]
+                    }
+                    const bj = inblossom[j]
+                    if(bj != b && label[bj] == 1 && (bestedgeto[bj] == -1 || slack(edge) < slack(bestedgeto[bj]))) {
+                        bestedgeto[bj] = edge
+                    }
+                }
+            }
+            blossombestedges[child] = null
+            bestedge[child] = -1
+        }
+        blossombestedges[b] = bestedgeto.filter((edge) => edge != -1)
+        bestedge[b] = -1
+        for(const edge of blossombestedges[b]) {
+            if(bestedge[b] == -1 || slack(edge) < slack(bestedge[b])) {
+                bestedge[b] = edge
+            }
+        }
+    }
+
+    const expandBlossom = (b, endstage) => {
+        for(const s of blossomchilds[b]) {
+            blossomparent[s] = -1
+            if(s < nvertex) {
+                inblossom[s] = s
+            } else if(endstage && dualvar[s] == 0) {
+                expandBlossom(s, endstage)
+            } else {
+                for(const leaf of blossomLeaves(s)) {
+                    inblossom[leaf] = s
+                }
+            }
+        }
+        if(!endstage && label[b] == 2) {
+            // Relabels the children on the path from the entry child to
+            // the base, then those left over as free or T.
+            const childs = blossomchilds[b]
+            const endps = blossomendps[b]
+            const entrychild = inblossom[endpoint[labelend[b] ^ 1]]
+            let j = childs.indexOf(entrychild)
+            let jstep, endptrick
+            if(j & 1) {
+                j -= childs.length
+                jstep = 1
+                endptrick = 0
+            } else {
+                jstep = -1
+                endptrick = 1
+            }
+            let p = labelend[b]
+            while(j != 0) {
+                label[endpoint[p ^ 1]] = 0
+                label[endpoint[endps.at(j - endptrick) ^ endptrick ^ 1]] = 0
+                assignLabel(endpoint[p ^ 1], 2, p)
+                allowedge[endps.at(j - endptrick) >> 1] = true
+                j += jstep
+                p = endps.at(j - endptrick) ^ endptrick
+                allowedge[p >> 1] = true
+                j += jstep
+            }
+            let bv = childs.at(j)
+            label[endpoint[p ^ 1]] = label[bv] = 2
+            labelend[endpoint[p ^ 1]] = labelend[bv] = p
+            bestedge[bv] = -1
+            j += jstep
+            while(childs.at(j) != entrychild) {
+                bv = childs.at(j)
+                if(label[bv] == 1) {
+                    j += jstep
+                    continue
+                }
+                const labelled = blossomLeaves(bv).find((leaf) => label[leaf] != 0)
+                if(labelled != null) {
+                    label[labelled] = 0
+                    label[endpoint[mate[blossombase[bv]]]] = 0
+                    assignLabel(labelled, 2, labelend[labelled])
+                }
+                j += jstep
+            }
+        }
+        label[b] = labelend[b] = -1
+        blossomchilds[b] = blossomendps[b] = null
+        blossombase[b] = -1
+        blossombestedges[b] = null
+        bestedge[b] = -1
+        unusedblossoms.push(b)
+    }
+
+    // Swaps matched and unmatched edges along the even path inside b from
+    // vertex v to the base, which v becomes.
+    const augmentBlossom = (b, v) => {
+        let t = v
+        while(blossomparent[t] != b) {
+            t = blossomparent[t]
+        }
+        if(t >= nvertex) {
+            augmentBlossom(t, v)
+        }
+        const i = blossomchilds[b].indexOf(t)
+        let j = i
+        let jstep, endptrick
+        if(i & 1) {
+            j -= blossomchilds[b].length
+            jstep = 1
+            endptrick = 0
+        } else {
+            jstep = -1
+            endptrick = 1
+        }
+        while(j != 0) {
+            j += jstep
+            t = blossomchilds[b].at(j)
+            const p = blossomendps[b].at(j - endptrick) ^ endptrick
+            if(t >= nvertex) {
+                augmentBlossom(t, endpoint[p])
+            }
+            j += jstep
+            t = blossomchilds[b].at(j)
+            if(t >= nvertex) {
+                augmentBlossom(t, endpoint[p ^ 1])
+            }
+            mate[endpoint[p]] = p ^ 1
+            mate[endpoint[p ^ 1]] = p
+        }
+        blossomchilds[b] = [...blossomchilds[b].slice(i), ...blossomchilds[b].slice(0, i)]
+        blossomendps[b] = [...blossomendps[b].slice(i), ...blossomendps[b].slice(0, i)]
+        blossombase[b] = blossombase[blossomchilds[b][0]]
+    }
+
+    const augmentMatching = (k) => {
+        const [v, w] = edges[k]
+        for(let [s, p] of [[v, 2 * k + 1], [w, 2 * k]]) {
+            for(;;) {
+                const bs = inblossom[s]
+                if(bs >= nvertex) {
+                    augmentBlossom(bs, s)
+                }
+                mate[s] = p
+                if(labelend[bs] == -1) {
+                    break
+                }
+                const t = endpoint[labelend[bs]]
+                const bt = inblossom[t]
+                s = endpoint[labelend[bt]]
+                const j = endpoint[labelend[bt] ^ 1]
+                if(bt >= nvertex) {
+                    augmentBlossom(bt, j)
+                }
+                mate[j] = labelend[bt]
+                p = labelend[bt] ^ 1
+            }
+        }
+    }
+
+    // Each stage augments the matching by one edge, or stops.
+    for(let stage = 0; stage < nvertex; stage++) {
+        label.fill(0)
+        bestedge.fill(-1)
+        blossombestedges.fill(null, nvertex)
+        allowedge = new Array(nedge).fill(false)
+        queue = []
+        for(let v = 0; v < nvertex; v++) {
+            if(mate[v] == -1 && label[inblossom[v]] == 0) {
+                assignLabel(v, 1, -1)
+            }
+        }
+        let augmented = false
+        for(;;) {
+            while(queue.length > 0 && !augmented) {
+                const v = queue.pop()
+                for(const p of neighbend[v]) {
+                    const k = p >> 1
+                    const w = endpoint[p]
+                    if(inblossom[v] == inblossom[w]) {
+                        continue
+                    }
+                    let kslack
+                    if(!allowedge[k]) {
+                        kslack = slack(k)
+                        if(kslack <= 0) {
+                            allowedge[k] = true
+                        }
+                    }
+                    if(allowedge[k]) {
+                        if(label[inblossom[w]] == 0) {
+                            assignLabel(w, 2, p ^ 1)
+                        } else if(label[inblossom[w]] == 1) {
+                            const base = scanBlossom(v, w)
+                            if(base >= 0) {
+                                addBlossom(base, k)
+                            } else {
+                                augmentMatching(k)
+                                augmented = true
+                                break
+                            }
+                        } else if(label[w] == 0) {
+                            label[w] = 2
+                            labelend[w] = p ^ 1
+                        }
+                    } else if(label[inblossom[w]] == 1) {
+                        const b = inblossom[v]
+                        if(bestedge[b] == -1 || kslack < slack(bestedge[b])) {
+                            bestedge[b] = k
+                        }
+                    } else if(label[w] == 0) {
+                        if(bestedge[w] == -1 || kslack < slack(bestedge[w])) {
+                            bestedge[w] = k
+                        }
+                    }
+                }
+            }
+            if(augmented) {
+                break
+            }
+
+            // No augmenting path with the edges allowed so far: update the
+            // dual variables by the largest step keeping them feasible.
+            let deltatype = -1
+            let delta = null
+            let deltaedge = null
+            let deltablossom = null
+            for(let v = 0; v < nvertex; v++) {
+                if(label[inblossom[v]] == 0 && bestedge[v] != -1) {
+                    const d = slack(bestedge[v])
+                    if(deltatype == -1 || d < delta) {
+                        delta = d
+                        deltatype = 2
+                        deltaedge = bestedge[v]
+                    }
+                }
+            }
+            for(let b = 0; b < 2 * nvertex; b++) {
+                if(blossomparent[b] == -1 && label[b] == 1 && bestedge[b] != -1) {
+                    const d = Math.floor(slack(bestedge[b]) / 2)
+                    if(deltatype == -1 || d < delta) {
+                        delta = d
+                        deltatype = 3
+                        deltaedge = bestedge[b]
+                    }
+                }
+            }
+            for(let b = nvertex; b < 2 * nvertex; b++) {
+                if(blossombase[b] >= 0 && blossomparent[b] == -1 && label[b] == 2 && (deltatype == -1 || dualvar[b] < delta)) {
+                    delta = dualvar[b]
+                    deltatype = 4
+                    deltablossom = b
+                }
+            }
+            if(deltatype == -1) {
+                deltatype = 1
+                delta = Math.max(0, Math.min(...dualvar.slice(0, nvertex)))
+            }
+            for(let v = 0; v < nvertex; v++) {
+                if(label[inblossom[v]] == 1) {
+                    dualvar[v] -= delta
+                } else if(label[inblossom[v]] == 2) {
+                    dualvar[v] += delta
+                }
+            }
+            for(let b = nvertex; b < 2 * nvertex; b++) {
+                if(blossombase[b] >= 0 && blossomparent[b] == -1) {
+                    if(label[b] == 1) {
+                        dualvar[b] += delta
+                    } else if(label[b] == 2) {
+                        dualvar[b] -= delta
+                    }
+                }
+            }
+            if(deltatype == 1) {
+                break
+            } else if(deltatype == 2) {
+                allowedge[deltaedge] = true
+                let [i, j] = edges[deltaedge]
+                if(label[inblossom[i]] == 0) {
+                    [i, j] = [j, i]
+                }
+                queue.push(i)
+            } else if(deltatype == 3) {
+                allowedge[deltaedge] = true
+                queue.push(edges[deltaedge][0])
+            } else {
+                expandBlossom(deltablossom, false)
+            }
+        }
+        if(!augmented) {
+            break
+        }
+        for(let b = nvertex; b < 2 * nvertex; b++) {
+            if(blossomparent[b] == -1 && blossombase[b] >= 0 && label[b] == 1 && dualvar[b] == 0) {
+                expandBlossom(b, true)
+            }
+        }
+    }
+    return mate.map((p) => p >= 0 ? endpoint[p] : -1)
+}
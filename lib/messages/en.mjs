@@ -0,0 +1,680 @@
+/**
+ * English messages, by French text. `{0}`, `{1}`... stand for the values
+ * interpolated in the `t` template, and can be moved around.
+ */
+export default {
+    // main.mjs, help and commands
+    'Erreur : {0}': 'Error: {0}',
+    'Erreur : chemin manquant après --db': 'Error: missing path after --db',
+    'Erreur : nom de cohorte manquant après --cohort': 'Error: missing cohort name after --cohort',
+    'Commande inconnue : {0}': 'Unknown command: {0}',
+    'Commandes disponibles : {0} (groupgen --help pour le détail)': 'Available commands: {0} (groupgen --help for details)',
+    'Langue inconnue : {0} ({1})': 'Unknown language: {0} ({1})',
+    '--color invalide : {0} ({1})': 'Invalid --color: {0} ({1})',
+    'Usage : groupgen [--db <fichier>] [--cohort <nom>] [--lang fr|en] [-q | -v | -vv] [--log-json] [--screen-reader] [--color auto|always|never] [commande] [options]':
+        'Usage: groupgen [--db <file>] [--cohort <name>] [--lang fr|en] [-q | -v | -vv] [--log-json] [--screen-reader] [--color auto|always|never] [command] [options]',
+    'Commandes :': 'Commands:',
+    ' (par défaut)': ' (default)',
+    'groupgen <commande> --help détaille les options d\'une commande, groupgen --version affiche la version.':
+        'groupgen <command> --help details the options of a command, groupgen --version shows the version.',
+    'Usage : groupgen {0} [options]': 'Usage: groupgen {0} [options]',
+    'Options :': 'Options:',
+    '<valeur>': '<value>',
+    ' (défaut : {0})': ' (default: {0})',
+
+    'Enregistre dans l\'historique une session créée par quick': 'Saves a session created by quick in the history',
+    'Répartit les groupes d\'un brief entre les formateurs qui les observent': 'Shares the groups of a brief among the trainers observing them',
+    'Répartit les groupes d\'un brief sur des créneaux de passage': 'Spreads the groups of a brief over presentation slots',
+    'Gère les briefs enregistrés (clone, activate, close, categorize, delete)': 'Manages saved briefs (clone, activate, close, categorize, delete)',
+    'Gère les promos tenues dans la même base, à utiliser avec --cohort (list, create, students)': 'Manages the cohorts kept in the same database, to use with --cohort (list, create, students)',
+    'Affiche le calendrier des briefs passés et prévus, mois par mois, ou l\'exporte en .ics (--ics)': 'Shows the calendar of past and planned briefs, month by month, or exports it as .ics (--ics)',
+    'Compare la rotation des binômes entre plusieurs promos (couverture, équité, répétitions)': 'Compares pair rotation across cohorts (coverage, fairness, repeats)',
+    'Affiche le script de complétion des commandes et options pour bash, zsh, fish ou powershell': 'Prints the completion script of commands and options for bash, zsh, fish or powershell',
+    'Gère les contraintes de groupes (list, add, remove), et le registre confidentiel avec --admin': 'Manages group constraints (list, add, remove), and the confidential registry with --admin',
+    'Gère le fichier d\'historique (encrypt, decrypt)': 'Manages the history file (encrypt, decrypt)',
+    'Retire un apprenant d\'un brief enregistré (absence, abandon)': 'Removes a student from a saved brief (absence, dropout)',
+    'Explique comment la composition d\'un brief a été choisie': 'Explains how the groups of a brief were chosen',
+    'Exporte un brief (--html : planche de groupes avec avatars, --csv : une ligne par groupe)': 'Exports a brief (--html: group sheet with avatars, --csv: one row per group)',
+    'Génère une liste d\'apprenants fictifs (et un historique avec --history) pour les démos et les tests': 'Generates a fake roster (and a history with --history) for demos and tests',
+    'Forme les groupes de la promo en évitant le brief précédent': 'Forms the groups of the cohort, avoiding the previous brief',
+    'Analyse le graphe des collaborations de la promo (stats : composantes, diamètre, clustering)': 'Analyses the collaboration graph of the cohort (stats: components, diameter, clustering)',
+    'Liste les briefs enregistrés, du plus récent au plus ancien': 'Lists saved briefs, most recent first',
+    'Prépare un dossier de promo : students.json à remplir, historique vide et groupgen.toml par défaut, ou pas à pas avec --wizard': 'Sets up a cohort folder: students.json to fill in, an empty history and a default groupgen.toml, or step by step with --wizard',
+    'Importe les auto-évaluations d\'un formulaire (export CSV) dans le fichier de compétences': 'Imports self-assessments from a form (CSV export) into the competency file',
+    'Crée une invitation calendrier (.ics) par apprenant pour un brief, en fichiers ou par email': 'Creates a calendar invitation (.ics) per student for a brief, as files or by email',
+    'Ajoute un apprenant arrivé en retard au groupe le plus adapté d\'un brief enregistré': 'Adds a late student to the best-suited group of a saved brief',
+    'Affiche la matrice des rencontres : combien de briefs chaque binôme a passé ensemble': 'Shows the pairing matrix: how many briefs each pair spent together',
+    'Publie un brief sur un ou plusieurs canaux (Discord, Slack, email, webhook, fichier, Trello, GitHub Projects, Notion, Matrix)': 'Publishes a brief on one or more channels (Discord, Slack, email, webhook, file, Trello, GitHub Projects, Notion, Matrix)',
+    'Forme des groupes à partir d\'une liste de noms, sans historique': 'Forms groups from a list of names, without history',
+    'Refait les groupes d\'un brief enregistré avec les présents, sous le même numéro (retards en masse)': 'Draws the groups of a saved brief again with the students present, under the same number (mass late arrivals)',
+    'Range les secrets des canaux dans le trousseau système (set, delete)': 'Stores channel secrets in the system keyring (set, delete)',
+    'Installe la dernière version publiée sur GitHub, après vérification de sa somme de contrôle': 'Installs the latest version published on GitHub, after checking its checksum',
+    'Compare la couverture et l\'équité de plusieurs réglages sur des semestres simulés': 'Compares the coverage and fairness of several settings over simulated semesters',
+    'Génère un site statique de l\'historique (groupes actuels, briefs passés, matrice, pages apprenants)': 'Generates a static site of the history (current groups, past briefs, matrix, student pages)',
+    'Prépare le sondage des préférences de binômes (--out form.csv) et importe les réponses en contraintes (import)': 'Prepares the pairing preferences survey (--out form.csv) and imports the answers as constraints (import)',
+    'Statistiques de répétition par apprenant, comparées au hasard avec --expected': 'Repeat statistics per student, compared to chance with --expected',
+    'Vérifie que l\'historique est à jour (dernier brief, synchronisation de la liste)': 'Checks that the history is up to date (last brief, roster sync)',
+    'Propose des contraintes à coller dans groupgen.toml d\'après l\'historique (binômes trop répétés, apprenants isolés)': 'Suggests constraints to paste into groupgen.toml from the history (over-repeated pairs, isolated students)',
+    'Gère les équipes fixes d\'une phase projet (create, list, rotate-roles, pairs, end)': 'Manages the fixed teams of a project phase (create, list, rotate-roles, pairs, end)',
+    'Gère la corbeille des briefs supprimés (list, restore, empty)': 'Manages the trash of deleted briefs (list, restore, empty)',
+    'Interface plein écran : promo, matrice des binômes, tirage à retoucher avant de l\'enregistrer': 'Full-screen interface: cohort, pairing matrix, draw to adjust before saving',
+    'Résume l\'utilisation locale de l\'outil (briefs par mois, taille de promo, temps de calcul)': 'Summarises local use of the tool (briefs per month, cohort size, computing time)',
+
+    // generate
+    'Bienvenue ! Ni configuration, ni liste des apprenants, ni historique ici.': 'Welcome! No configuration, roster or history here.',
+    'Relancez groupgen pour former les premiers groupes.': 'Run groupgen again to form the first groups.',
+    '--on-publish-failure invalide : {0} ({1})': 'Invalid --on-publish-failure: {0} ({1})',
+    '--iterations invalide : {0}': 'Invalid --iterations: {0}',
+    '--time-budget invalide : {0} (secondes)': 'Invalid --time-budget: {0} (seconds)',
+    '--publish n\'a pas de sens avec --draft, publiez le brief une fois activé': '--publish makes no sense with --draft, publish the brief once activated',
+    '--publish n\'a pas de sens avec --dry-run, rien n\'est enregistré': '--publish makes no sense with --dry-run, nothing is saved',
+    '--name vide, ex. : --name "Brief React #3"': 'Empty --name, e.g. --name "React brief #3"',
+    '--publish-at programme les canaux de --publish, ex. : --publish discord-promo --publish-at 2025-03-10T09:00': '--publish-at schedules the channels of --publish, e.g. --publish discord-promo --publish-at 2025-03-10T09:00',
+    '--publish-at invalide : {0} (ex. : 2025-03-10T09:00)': 'Invalid --publish-at: {0} (e.g. 2025-03-10T09:00)',
+    '--publish-at est déjà passé : {0}': '--publish-at is in the past: {0}',
+    '--scaffold n\'a pas de sens avec --dry-run, rien n\'est enregistré': '--scaffold makes no sense with --dry-run, nothing is saved',
+    '{0} n\'est pas vide, choisissez un autre dossier pour --scaffold': '{0} is not empty, choose another folder for --scaffold',
+    'Une seule liste d\'apprenants attendue, reçu : {0}': 'A single roster expected, got: {0}',
+    'Promo : {0} apprenants lus dans {1}': 'Cohort: {0} students read from {1}',
+    'Absent(s), laissé(s) de côté pour ce brief : {0}': 'Absent, left out of this brief: {0}',
+    '{0} des apprenants de la liste n\'apparaissent pas dans l\'historique ({1}),': '{0} of the students in the roster do not appear in the history ({1}),',
+    'et {0} noms des derniers briefs sont absents de la liste. Mauvais dossier ou mauvaise promo ?': 'and {0} names of the last briefs are missing from the roster. Wrong folder or wrong cohort?',
+    'Continuer quand même ? [o/N] ': 'Continue anyway? [y/N] ',
+    'Génération annulée': 'Generation cancelled',
+    'Recherche : {0} tirage(s) en {1} ms avec la graine {2}, {3} binôme(s) répété(s)': 'Search: {0} draw(s) in {1} ms with seed {2}, {3} repeated pair(s)',
+    'Meilleurs scores successifs : {0}': 'Successive best scores: {0}',
+    'Budget de temps atteint après {0} tirage(s) sur {1}.': 'Time budget reached after {0} draw(s) out of {1}.',
+    'Recherche interrompue, meilleur résultat trouvé ({0} binôme(s) déjà vu(s)) :': 'Search interrupted, best result found ({0} pair(s) already seen):',
+    '\nBriefs passés ensemble, ce tirage compris :': '\nBriefs spent together, this draw included:',
+    '{0} contrainte(s) "jamais ensemble" n\'ont pas pu être respectées': '{0} "never together" constraint(s) could not be met',
+    'Groupe {0} : personne ne maîtrise {1}': 'Group {0}: nobody masters {1}',
+    'Enregistrer ces groupes ? [o/N/r] ': 'Save these groups? [y/N/r] ',
+    'Nouveau tirage, graine {0} :': 'New draw, seed {0}:',
+    'Groupes écrits dans {0}.': 'Groups written to {0}.',
+    'Simulation (--dry-run) : brief non enregistré.': 'Dry run (--dry-run): brief not saved.',
+    'Enregistrer ce résultat ? [o/N] ': 'Save this result? [y/N] ',
+    'Brouillon n°{0}{1} enregistré, à modifier avec late-add et drop puis à valider avec : groupgen brief activate {2}': 'Draft #{0}{1} saved, to adjust with late-add and drop, then to confirm with: groupgen brief activate {2}',
+    'Brief n°{0}{1} enregistré.': 'Brief #{0}{1} saved.',
+    'Dossiers des groupes créés dans {0}, noms de dépôts ou de branches dans {1}.': 'Group folders created in {0}, repository or branch names in {1}.',
+    'Annonce programmée le {0} sur {1} canal(aux), envoyée par groupgen publish --retry-pending lancé après cette heure (tâche cron ou planifiée).': 'Announcement scheduled on {0} on {1} channel(s), sent by groupgen publish --retry-pending run after that time (cron or scheduled task).',
+    'Résultat non enregistré.': 'Result not saved.',
+    'Format de {0} inconnu : extension .md, .json, .csv ou .txt, ou --format {1}': 'Unknown format for {0}: extension .md, .json, .csv or .txt, or --format {1}',
+    'Fichier des absents introuvable : {0}': 'Absentee file not found: {0}',
+    'Absent(s) inconnu(s) de la liste des apprenants : {0}': 'Absentee(s) not in the roster: {0}',
+    'Absents de la liste des apprenants : {0}': 'Not in the roster: {0}',
+    'Sans équipe : {0}': 'Without a team: {0}',
+    'Équipes fixes en place jusqu\'au {0}, pas de nouveau brief (groupgen teams end pour reprendre la rotation).': 'Fixed teams in place until {0}, no new brief (groupgen teams end to resume the rotation).',
+    'Équipes fixes en place, pas de nouveau brief (groupgen teams end pour reprendre la rotation).': 'Fixed teams in place, no new brief (groupgen teams end to resume the rotation).',
+    'Compétences inconnues pour {0}': 'Unknown competencies for {0}',
+
+    // Run log of generate --log-file
+    'Exécution du {0}': 'Run of {0}',
+    'Historique : {0}': 'History: {0}',
+    'Promo ({0} apprenants)': 'Cohort ({0} students)',
+    'Contraintes': 'Constraints',
+    'pas de brief précédent': 'no previous brief',
+    'éviter les {0} groupes du brief n°{1}': 'avoid the {0} groups of brief #{1}',
+    'briefs pris en compte : {0}': 'briefs taken into account: {0}',
+    'Compétences requises dans chaque groupe': 'Skills required in each group',
+    'Taille des groupes': 'Group size',
+    '{0} apprenants': '{0} students',
+    'Départage': 'Tie-break',
+    'Graine': 'Seed',
+    '{0} (tirée au hasard)': '{0} (drawn at random)',
+    'Recherche': 'Search',
+    'itérations : {0}': 'iterations: {0}',
+    'binômes répétés : {0}': 'repeated pairs: {0}',
+    'contraintes non respectées : {0}': 'unmet constraints: {0}',
+    'interrompue : {0}': 'interrupted: {0}',
+    'oui': 'yes',
+    'non': 'no',
+    'budget de temps atteint': 'time budget reached',
+    'Groupes': 'Groups',
+    'Enregistrement': 'Saving',
+    'simulation, non enregistré': 'dry run, not saved',
+    'non enregistré': 'not saved',
+    'brief n°{0} dans {1}': 'brief #{0} in {1}',
+
+    // Groups and matrix
+    'Liste des groupes :': 'Groups:',
+    'Groupe {0}': 'Group {0}',
+    '1 groupe.': '1 group.',
+    '{0} groupes.': '{0} groups.',
+    'Le groupe {0} réunit {1}.': 'Group {0} brings together {1}.',
+    '| Groupe | Membres |': '| Group | Members |',
+    '--format invalide : {0} ({1})': 'Invalid --format: {0} ({1})',
+    '{0} (1 fois)': '{0} (once)',
+    '{0} ({1} fois)': '{0} ({1} times)',
+    '* souvent absent(e), ses cases vides tiennent à ses absences : {0}': '* often absent, their empty cells come from their absences: {0}',
+    '{0} ({1} brief(s) sur {2})': '{0} ({1} brief(s) out of {2})',
+    'Présent(e) à {0} brief(s) sur {1} seulement.': 'Present at only {0} brief(s) out of {1}.',
+    '{0} n\'a encore travaillé avec personne.': '{0} has not worked with anyone yet.',
+    '{0} a travaillé avec {1}.': '{0} worked with {1}.',
+    'Aucun apprenant : ni liste des apprenants ni brief enregistré': 'No students: neither a roster nor a saved brief',
+    'Briefs passés ensemble, sur {0} brief(s) au {1} :': 'Briefs spent together, over {0} brief(s) as of {1}:',
+    'Briefs passés ensemble, sur {0} brief(s) :': 'Briefs spent together, over {0} brief(s):',
+
+    // Roster
+    'Liste des apprenants introuvable : {0} (groupgen init pour démarrer)': 'Roster not found: {0} (groupgen init to get started)',
+    'liste des apprenants': 'roster',
+    'apprenants de la cohorte {0}': 'students of cohort {0}',
+    '{0} : JSON invalide ({1})': '{0}: invalid JSON ({1})',
+    '{0} : un tableau de noms est attendu': '{0}: an array of names is expected',
+    '{0} : entrée n°{1} invalide ({2}), un nom est attendu': '{0}: invalid entry #{1} ({2}), a name is expected',
+    '{0} : {1} de {2} doit être une chaîne': '{0}: {1} of {2} must be a string',
+    '{0} : {1} apparaît plusieurs fois': '{0}: {1} appears more than once',
+    'La liste des apprenants est vide, aucun groupe à former': 'The roster is empty, no groups to form',
+    'Un seul apprenant ({0}), aucun groupe à former. Rien n\'est enregistré.': 'A single student ({0}), no groups to form. Nothing is saved.',
+    '{0} apprenants : la limite supportée est de {1}': '{0} students: the supported limit is {1}',
+    '{0} compétences requises : la limite est de 32': '{0} required skills: the limit is 32',
+    'Taille de groupe invalide : {0} (2 ou plus)': 'Invalid group size: {0} (2 or more)',
+    'Départage invalide : {0} ({1})': 'Invalid tie-break: {0} ({1})',
+
+    // Roster review
+    'adresse email': 'email address',
+    'plusieurs noms séparés par une virgule': 'several names separated by a comma',
+    'ponctuation en trop': 'extra punctuation',
+    'noms collés': 'names stuck together',
+    'espaces en trop': 'extra spaces',
+    '« {0} »': '"{0}"',
+    '{0} : « {1} » ressemble à une erreur de copier-coller ({2}), peut-être {3}': '{0}: "{1}" looks like a copy-paste error ({2}), maybe {3}',
+    '« {0} » ressemble à une erreur de copier-coller ({1}) :': '"{0}" looks like a copy-paste error ({1}):',
+    '  0. garder tel quel': '  0. keep as is',
+    'Correction [0-{0}] : ': 'Fix [0-{0}]: ',
+    '{0} correction(s) enregistrée(s) dans {1}.': '{0} fix(es) saved in {1}.',
+
+    // History
+    'Cohorte inconnue : {0}{1}, à créer avec : groupgen cohort create {2}': 'Unknown cohort: {0}{1}, to create with: groupgen cohort create {2}',
+    '{0} est chiffré : définissez {1} avec sa phrase de passe': '{0} is encrypted: set {1} to its passphrase',
+    'Brief n°{0} introuvable': 'Brief #{0} not found',
+    'Brief n°{0} absent de la corbeille': 'Brief #{0} is not in the trash',
+    'Date invalide : {0} (AAAA-MM-JJ)': 'Invalid date: {0} (YYYY-MM-DD)',
+    'Contrainte n°{0} introuvable': 'Constraint #{0} not found',
+    '{0} contrainte(s) de groupgen.toml importée(s) dans {1}, vous pouvez les retirer du fichier.': '{0} constraint(s) of groupgen.toml imported into {1}, you can remove them from the file.',
+    '{0} : briefs doit être une liste': '{0}: briefs must be a list',
+    '{0} : brief {1} mis de côté dans invalid_briefs ({2}), les autres briefs restent utilisés': '{0}: brief {1} set aside in invalid_briefs ({2}), the other briefs are still used',
+    'pas un brief': 'not a brief',
+    'id invalide ({0})': 'invalid id ({0})',
+    'id {0} en double': 'id {0} used twice',
+    'groups doit être une liste de groupes': 'groups must be a list of groups',
+    'groupe {0} vide': 'group {0} is empty',
+    'membre vide ou invalide dans le groupe {0} ({1})': 'empty or invalid member in group {0} ({1})',
+    '{0} apparaît deux fois': '{0} appears twice',
+
+    // init and the first-run wizard
+    'Un seul dossier attendu, reçu : {0}': 'A single folder expected, got: {0}',
+    '{0} n\'est pas un dossier': '{0} is not a folder',
+    'Conservé : {0} (déjà présent)': 'Kept: {0} (already there)',
+    'Créé : {0}': 'Created: {0}',
+    'Ajoutez les apprenants dans {0}, un nom par ligne entre guillemets, séparés par des virgules,': 'Add the students to {0}, one name per line in double quotes, separated by commas,',
+    'puis lancez groupgen pour former les premiers groupes.': 'then run groupgen to form the first groups.',
+    'puis lancez groupgen depuis {0} pour former les premiers groupes.': 'then run groupgen from {0} to form the first groups.',
+    '{0} existe déjà, modifiez-le ou supprimez-le avant de relancer l\'assistant': '{0} already exists, edit or remove it before running the wizard again',
+    'Quelques questions pour démarrer, Entrée garde la valeur entre crochets.': 'A few questions to get started, Enter keeps the value in brackets.',
+    'Les chemins sont relatifs à {0}.': 'Paths are relative to {0}.',
+    'Liste des apprenants (fichier JSON ou URL ldap://)': 'Roster (JSON file or ldap:// URL)',
+    '{0} n\'existe pas encore : tapez les noms, un par ligne, puis une ligne vide.': '{0} does not exist yet: type the names, one per line, then an empty line.',
+    '{0} apprenant(s) enregistré(s) dans {1}.': '{0} student(s) saved in {1}.',
+    'Fichier de l\'historique': 'History file',
+    'Configuration écrite dans {0}.': 'Configuration written to {0}.',
+    'Configuration abandonnée': 'Configuration abandoned',
+
+    // Commands
+    'Invite de commandes pour retoucher un tirage en cours (generate, swap, matrix, undo, save) sans relancer l\'outil': 'Command prompt to touch up a draw in progress (generate, swap, matrix, undo, save) without restarting the tool',
+    'Action inconnue : {0}, actions disponibles : {1}': 'Unknown action: {0}, available actions: {1}',
+    '(aucune)': '(none)',
+    'Aucun brief enregistré': 'No brief saved',
+    'Aucun brief enregistré.': 'No brief saved.',
+    'Le brief n°{0} est clos, ses groupes ne changent plus': 'Brief #{0} is closed, its groups no longer change',
+    'date inconnue': 'unknown date',
+    'Brief n°{0} enregistré.': 'Brief #{0} saved.',
+    'Dernier tirage non enregistré.': 'Last draw not saved.',
+    'Groupe {0} : {1}': 'Group {0}: {1}',
+    '{0} : Groupe {1} ({2})': '{0}: Group {1} ({2})',
+    'Date invalide : {0}': 'Invalid date: {0}',
+    'aucun brief': 'no brief',
+
+    // adopt, assign-slots and assign-observers
+    'Jeton de session manquant, ex. : groupgen adopt 1a2b3c4d': 'Missing session token, e.g. groupgen adopt 1a2b3c4d',
+    'Session {0} enregistrée comme brief n°{1}.': 'Session {0} saved as brief #{1}.',
+    'Formateurs manquants, ex. : groupgen assign-observers --trainers "Anne,Marc" ou trainers dans [observers] de groupgen.toml': 'Missing trainers, e.g. groupgen assign-observers --trainers "Anne,Marc" or trainers in [observers] of groupgen.toml',
+    'Observateurs du brief n°{0} :': 'Observers of brief #{0}:',
+    'Créneaux manquants, ex. : groupgen assign-slots --slots "9h,11h,14h"': 'Missing slots, e.g. groupgen assign-slots --slots "9h,11h,14h"',
+    'Passages du brief n°{0} :': 'Slots of brief #{0}:',
+
+    // brief and trash
+    'Identifiant du brief manquant, ex. : groupgen brief clone 12': 'Missing brief id, e.g. groupgen brief clone 12',
+    'Identifiant de brief invalide : {0}': 'Invalid brief id: {0}',
+    'Brief n°{0} cloné en brief n°{1}.': 'Brief #{0} cloned as brief #{1}.',
+    'copie du n°{0}': 'copy of #{0}',
+    'Copie des groupes du brief n°{0} (brief clone), pas un nouveau tirage : les mêmes équipes continuent.': 'Copy of the groups of brief #{0} (brief clone), not a new draw: the same teams carry on.',
+    'Identifiant du brief manquant, ex. : groupgen brief delete 12': 'Missing brief id, e.g. groupgen brief delete 12',
+    'Brief n°{0} mis à la corbeille, groupgen trash restore {1} pour l\'annuler.': 'Brief #{0} moved to the trash, groupgen trash restore {1} to undo.',
+    'Identifiant du brief manquant, ex. : groupgen brief activate 12': 'Missing brief id, e.g. groupgen brief activate 12',
+    'Le brief n°{0} n\'est pas un brouillon': 'Brief #{0} is not a draft',
+    'Brief n°{0} activé.': 'Brief #{0} activated.',
+    'Brief ou catégorie manquant, ex. : groupgen brief categorize 12 project': 'Missing brief or category, e.g. groupgen brief categorize 12 project',
+    'Brief n°{0} classé dans {1}.': 'Brief #{0} filed under {1}.',
+    'Identifiant du brief manquant, ex. : groupgen brief close 12': 'Missing brief id, e.g. groupgen brief close 12',
+    'Le brief n°{0} est un brouillon, groupgen brief delete {1} pour l\'abandonner': 'Brief #{0} is a draft, groupgen brief delete {1} to abandon it',
+    'groupe {0} : {1}': 'group {0}: {1}',
+    'groupe retiré : {0}': 'removed group: {0}',
+    'Brief n°{0} clos.': 'Brief #{0} closed.',
+    'Brief n°{0} clos, {1} fil(s) supprimé(s).': 'Brief #{0} closed, {1} thread(s) deleted.',
+    'Brief n°{0} clos, {1} fil(s) archivé(s).': 'Brief #{0} closed, {1} thread(s) archived.',
+    'Fils non fermés, relancez groupgen brief close {0} :\n  {1}': 'Threads not closed, run groupgen brief close {0} again:\n  {1}',
+    'La corbeille est vide.': 'The trash is empty.',
+    'n°{0}{1} du {2}, supprimé le {3}': '#{0}{1} of {2}, deleted on {3}',
+    'n°{0}{1} du {2}, version remplacée le {3}': '#{0}{1} of {2}, version replaced on {3}',
+    'Identifiant du brief manquant, ex. : groupgen trash restore 12': 'Missing brief id, e.g. groupgen trash restore 12',
+    'Brief n°{0} restauré.': 'Brief #{0} restored.',
+    'Rien à effacer : les {0} brief(s) de la corbeille ont moins de {1} jours (--all pour tout effacer).': 'Nothing to erase: the {0} brief(s) in the trash are less than {1} days old (--all to erase everything).',
+    ', {0} conservé(s) moins de {1} jours': ', {0} kept, less than {1} days old',
+    '{0} brief(s) effacé(s) définitivement{1}.': '{0} brief(s) erased for good{1}.',
+
+    // calendar
+    'Brief n°{0} « {1} »': 'Brief #{0} "{1}"',
+    'Brief n°{0}': 'Brief #{0}',
+    '{0} (prévu)': '{0} (planned)',
+    'Mois invalide : {0} (AAAA-MM)': 'Invalid month: {0} (YYYY-MM)',
+    '{0} brief(s) écrit(s) dans {1}.': '{0} brief(s) written to {1}.',
+    'Aucun brief daté.': 'No dated brief.',
+    '{0} (prévu) le {1}, {2} groupes': '{0} (planned) on {1}, {2} groups',
+    '{0} le {1}, {2} groupes': '{0} on {1}, {2} groups',
+    '{0} : {1}.': '{0}: {1}.',
+    '* brief passé, + brief prévu (brouillon ou daté plus tard)': '* past brief, + planned brief (draft or dated later)',
+
+    // cohort and compare-cohorts
+    'Nom de cohorte manquant, ex. : groupgen cohort {0} promo-12': 'Missing cohort name, e.g. groupgen cohort {0} promo-12',
+    'Nom de cohorte invalide : {0} (lettres, chiffres, . _ -)': 'Invalid cohort name: {0} (letters, digits, . _ -)',
+    'Aucune cohorte, à créer avec : groupgen cohort create promo-12 --students students.json': 'No cohort, to create with: groupgen cohort create promo-12 --students students.json',
+    '{0} : {1} apprenant(s), {2} brief(s)': '{0}: {1} student(s), {2} brief(s)',
+    'La cohorte {0} existe déjà': 'Cohort {0} already exists',
+    'Cohorte {0} créée avec {1} apprenant(s), à utiliser avec : groupgen --cohort {2}': 'Cohort {0} created with {1} student(s), to use with: groupgen --cohort {2}',
+    'Liste des apprenants manquante, ex. : groupgen cohort students {0} --students students.json': 'Missing roster, e.g. groupgen cohort students {0} --students students.json',
+    '{0} apprenant(s) enregistré(s) pour la cohorte {1}.': '{0} student(s) saved for cohort {1}.',
+    'Historique introuvable : {0} (ni fichier ni cohorte de {1})': 'History not found: {0} (neither a file nor a cohort of {1})',
+    'Historique introuvable : {0}': 'History not found: {0}',
+    'Au moins deux promos à comparer, ex. : groupgen compare-cohorts promoA/ promoB/db.json': 'At least two cohorts to compare, e.g. groupgen compare-cohorts promoA/ promoB/db.json',
+    '--briefs invalide : {0}': 'Invalid --briefs: {0}',
+    'Promo {0}': 'Cohort {0}',
+    '{0} briefs, {1} apprenants': '{0} briefs, {1} students',
+    'couverture {0} après {1} brief(s)': 'coverage {0} after {1} brief(s)',
+    '50 % non atteints': '50% not reached',
+    '50 % en {0} brief(s)': '50% in {0} brief(s)',
+    '{0} binôme(s) répété(s) par brief': '{0} repeated pair(s) per brief',
+    '{0} à {1} partenaires distincts': '{0} to {1} distinct partners',
+
+    // constraints
+    'jamais ensemble': 'never together',
+    'toujours ensemble': 'always together',
+    '{0} : type inconnu "{1}" ({2})': '{0}: unknown type "{1}" ({2})',
+    '{0} : deux apprenants attendus dans students': '{0}: two students expected in students',
+    '{0} : date de fin invalide "{1}" (AAAA-MM-JJ)': '{0}: invalid end date "{1}" (YYYY-MM-DD)',
+    '{0} : {1}': '{0}: {1}',
+    '{0} jusqu\'au {1}': '{0} until {1}',
+    'créée le {0}': 'added on {0}',
+    'retirée le {0}': 'removed on {0}',
+    'appliquée au brief n°{0}': 'applied to brief #{0}',
+    'appliquée aux briefs n°{0} à {1}': 'applied to briefs #{0} to {1}',
+    'jamais appliquée': 'never applied',
+    'Registre confidentiel vide.': 'Confidential register is empty.',
+    ', retirée le {0}': ', removed on {0}',
+    'Aucune contrainte.': 'No constraint.',
+    'Aucune contrainte en vigueur (--all pour l\'historique).': 'No constraint in force (--all for the history).',
+    'Saisie interrompue': 'Input interrupted',
+    'Aucun apprenant ne correspond, réessayez.': 'No student matches, try again.',
+    'Numéro [1-{0}] : ': 'Number [1-{0}]: ',
+    'Choix invalide, réessayez.': 'Invalid choice, try again.',
+    'Type de contrainte [1-{0}] : ': 'Constraint type [1-{0}]: ',
+    'Type de contrainte invalide': 'Invalid constraint type',
+    'Premier apprenant (recherche) : ': 'First student (search): ',
+    'Second apprenant (recherche) : ': 'Second student (search): ',
+    'Date de fin AAAA-MM-JJ (vide = sans fin) : ': 'End date YYYY-MM-DD (empty = no end): ',
+    'Contrainte n°{0} ajoutée : {1}': 'Constraint #{0} added: {1}',
+    'Motif, visible avec --admin seulement (vide = aucun) : ': 'Reason, only shown with --admin (empty = none): ',
+    'Entrée confidentielle n°{0} ajoutée : {1} ne seront jamais ensemble.': 'Confidential entry #{0} added: {1} will never be together.',
+    'Numéro de contrainte manquant, ex. : groupgen constraints remove 3': 'Missing constraint number, e.g. groupgen constraints remove 3',
+    'Entrée confidentielle n°{0} introuvable': 'Confidential entry #{0} not found',
+    'Entrée confidentielle n°{0} retirée.': 'Confidential entry #{0} removed.',
+    'Contrainte n°{0} retirée : {1}': 'Constraint #{0} removed: {1}',
+
+    // db and secrets
+    'Phrase de passe manquante : définissez {0}': 'Missing passphrase: set {0}',
+    '{0} est déjà chiffré.': '{0} is already encrypted.',
+    '{0} chiffré. Sans {1}, l\'historique est illisible : conservez la phrase de passe.': '{0} encrypted. Without {1}, the history cannot be read: keep the passphrase.',
+    '{0} n\'est pas chiffré.': '{0} is not encrypted.',
+    '{0} déchiffré.': '{0} decrypted.',
+    'Fichier(s) en clair supprimé(s) : {0}.': 'Cleartext file(s) deleted: {0}.',
+    'Toujours en clair à côté de l\'historique : {0}. groupgen db encrypt --purge pour les supprimer.': 'Still in clear next to the history: {0}. groupgen db encrypt --purge to delete them.',
+    'Valeur de {0} (masquée) : ': 'Value of {0} (hidden): ',
+    'Valeur vide, rien n\'a été enregistré': 'Empty value, nothing was saved',
+    '{0} enregistré dans le trousseau. Référencez-le avec <clé>_keyring = "{1}".': '{0} saved in the keyring. Refer to it with <key>_keyring = "{1}".',
+    'Impossible de supprimer {0} du trousseau': 'Could not remove {0} from the keyring',
+    '{0} supprimé du trousseau.': '{0} removed from the keyring.',
+    'Nom du secret manquant': 'Missing secret name',
+
+    // drop and late-add
+    'Nom de l\'apprenant manquant, ex. : groupgen drop "Alice Martin" --brief 12': 'Missing student name, e.g. groupgen drop "Alice Martin" --brief 12',
+    '{0} ne fait pas partie du brief n°{1}': '{0} is not part of brief #{1}',
+    '{0} est retiré(e) du groupe {1} du brief n°{2}.': '{0} is removed from group {1} of brief #{2}.',
+    'Aucun apprenant seul à déplacer dans la place libérée.': 'No lone student to move into the freed place.',
+    '{0}, seul(e) dans le groupe {1}, rejoint le groupe {2}.': '{0}, alone in group {1}, joins group {2}.',
+    '{0} se retrouve seul(e) dans son groupe.': '{0} is now alone in their group.',
+    '{0} se retrouve seul(e) dans son groupe, --promote le regroupe avec un autre apprenant seul.': '{0} is now alone in their group, --promote puts them with another lone student.',
+    'Nom de l\'apprenant manquant, ex. : groupgen late-add "Alice Martin" --brief 12': 'Missing student name, e.g. groupgen late-add "Alice Martin" --brief 12',
+    'Taille maximale invalide : {0}': 'Invalid maximum size: {0}',
+    '{0} fait déjà partie du brief n°{1}': '{0} is already part of brief #{1}',
+    'Tous les groupes du brief n°{0} ont déjà {1} membres ou plus (--max-size)': 'All groups of brief #{0} already have {1} members or more (--max-size)',
+    '{0} rejoint le groupe {1} du brief n°{2}.': '{0} joins group {1} of brief #{2}.',
+
+    // explain
+    'le plus de binômes inédits sur tout l\'historique': 'the most pairs never formed in the whole history',
+    'les numéros de groupe du brief précédent': 'the group numbers of the previous brief',
+    'l\'ordre alphabétique': 'alphabetical order',
+    'Brief manquant, ex. : groupgen explain --brief 12': 'Missing brief, e.g. groupgen explain --brief 12',
+    '{0}, généré le {1}': '{0}, generated on {1}',
+    '{0}, date inconnue': '{0}, unknown date',
+    'Modifié le {0} : {1} ajouté(e) au groupe {2}.': 'Changed on {0}: {1} added to group {2}.',
+    'Modifié le {0} : {1} retiré(e) du groupe {2}.': 'Changed on {0}: {1} removed from group {2}.',
+    'Dépôt du groupe {0} : {1}': 'Repository of group {0}: {1}',
+    'Dépôt d\'un groupe retiré : {0}': 'Repository of a removed group: {0}',
+    'Aucun contexte de génération enregistré (brief importé, cloné, adopté ou antérieur à cette version).': 'No generation context saved (brief imported, cloned, adopted or older than this version).',
+    '{0} apprenants, sans brief précédent.': '{0} students, no previous brief.',
+    '{0} apprenants, binômes du brief n°{1} évités.': '{0} students, pairs of brief #{1} avoided.',
+    'Catégorie : {0}, seuls les briefs {1} pris en compte.': 'Category: {0}, only {1} briefs taken into account.',
+    'Catégorie : {0}.': 'Category: {0}.',
+    'Groupes de {0} demandés.': 'Groups of {0} requested.',
+    'Égalités départagées par : {0}.': 'Ties broken by: {0}.',
+    'Absent(s), laissé(s) de côté : {0}.': 'Absent, left out: {0}.',
+    'Graine : aucune, tirage non reproductible.': 'Seed: none, the draw cannot be reproduced.',
+    'Graine : {0} (même tirage avec --seed {1} sur le même historique).': 'Seed: {0} (same draw with --seed {1} on the same history).',
+    'Contraintes en vigueur : aucune.': 'Constraints in force: none.',
+    'Contraintes en vigueur :': 'Constraints in force:',
+    'n°{0} {1}': '#{0} {1}',
+    'Compétences requises dans chaque groupe : {0} ({1} manquante(s) au total).': 'Skills required in each group: {0} ({1} missing in total).',
+    'Recherche : {0} tirages, meilleurs scores successifs {1} (binômes répétés + compétences manquantes).': 'Search: {0} draws, successive best scores {1} (repeated pairs + missing skills).',
+    'Recherche : {0} tirages, meilleurs scores successifs {1} (binômes répétés).': 'Search: {0} draws, successive best scores {1} (repeated pairs).',
+    '{0} contrainte(s) "jamais ensemble" n\'avaient pas pu être respectées.': '{0} "never together" constraint(s) could not be met.',
+    'Échanges faits à la main après le tirage : {0}.': 'Swaps made by hand after the draw: {0}.',
+    'Groupes retenus :': 'Groups kept:',
+    'aucun binôme répété': 'no repeated pair',
+    '{0} binôme(s) répété(s)': '{0} repeated pair(s)',
+    '{0} et {1}': '{0} and {1}',
+    '{0} (+{1} contrainte(s) violée(s))': '{0} (+{1} constraint(s) broken)',
+
+    // export, site and fake-roster
+    'Format d\'export manquant : --html ou --csv': 'Missing export format: --html or --csv',
+    '{0} brief(s) exporté(s) dans {1}.': '{0} brief(s) exported to {1}.',
+    'Brief n°{0} exporté dans {1}.': 'Brief #{0} exported to {1}.',
+    'Planche du brief n°{0} écrite dans {1}, à imprimer depuis le navigateur (PDF possible).': 'Sheet of brief #{0} written to {1}, to print from the browser (PDF possible).',
+    '{0} pages écrites dans {1}, à publier par exemple avec GitHub Pages.': '{0} pages written to {1}, to publish for instance with GitHub Pages.',
+    '--size invalide : {0}': 'Invalid --size: {0}',
+    '--history invalide : {0}': 'Invalid --history: {0}',
+    '{0} contient déjà des briefs, choisissez une autre base avec --db pour un historique fictif': '{0} already holds briefs, pick another database with --db for a fake history',
+    '{0} apprenant(s) fictif(s) écrit(s) dans {1}.': '{0} fake student(s) written to {1}.',
+    '{0} brief(s) fictif(s) enregistré(s) dans {1}.': '{0} fake brief(s) saved in {1}.',
+
+    // graph, stats and usage
+    'Graphe des collaborations après {0} brief(s) :': 'Collaboration graph after {0} brief(s):',
+    '{0} apprenants, {1} binômes formés au moins une fois (densité {2})': '{0} students, {1} pairs formed at least once (density {2})',
+    'Composantes connexes : {0}': 'Connected components: {0}',
+    'Diamètre : {0} (plus longue chaîne de collaborations reliant deux apprenants d\'une même composante)': 'Diameter: {0} (longest chain of collaborations linking two students of the same component)',
+    'Diamètre : {0} (plus longue chaîne de collaborations reliant deux apprenants)': 'Diameter: {0} (longest chain of collaborations linking two students)',
+    'Coefficient de clustering moyen : {0} (0 : les partenaires d\'un apprenant ne se connaissent pas, 1 : tous ont travaillé ensemble)': 'Average clustering coefficient: {0} (0: the partners of a student do not know each other, 1: all have worked together)',
+    '--runs invalide : {0}': 'Invalid --runs: {0}',
+    '{0} briefs, {1} apprenants au {2}.': '{0} briefs, {1} students as of {2}.',
+    '{0} briefs, {1} apprenants.': '{0} briefs, {1} students.',
+    ', {0} fois avec {1}': ', {0} times with {1}',
+    ', absent(e) à {0} brief(s) *': ', absent from {0} brief(s) *',
+    '{0} : {1} brief(s){2}, {3} partenaire(s) distinct(s), {4} répétition(s){5}': '{0}: {1} brief(s){2}, {3} distinct partner(s), {4} repeat(s){5}',
+    '(attendu {0}, p = {1})': '(expected {0}, p = {1})',
+    '* souvent absent(e) : moins de partenaires et de répétitions sans que la rotation y soit pour quelque chose': '* often absent: fewer partners and repeats, through no fault of the rotation',
+    'Aucune anomalie : les répétitions observées sont compatibles avec le hasard ({0} simulations).': 'No anomaly: the observed repeats are consistent with chance ({0} simulations).',
+    'Anomalie(s) au seuil de {0} corrigé pour {1} apprenants : {2}': 'Anomaly(ies) at the {0} threshold corrected for {1} students: {2}',
+    'Briefs enregistrés : {0}': 'Saved briefs: {0}',
+    'Briefs par mois :': 'Briefs per month:',
+    'Taille moyenne de promo : {0} apprenants': 'Average cohort size: {0} students',
+    'Temps de calcul moyen : {0} ms (sur {1} briefs)': 'Average computing time: {0} ms (over {1} briefs)',
+    'Temps de calcul moyen : non mesuré': 'Average computing time: not measured',
+
+    // history and status
+    '--last invalide : {0}': 'Invalid --last: {0}',
+    '{0} groupes, {1} apprenants': '{0} groups, {1} students',
+    'brouillon': 'draft',
+    'clos': 'closed',
+    '{0} modification(s)': '{0} change(s)',
+    'Brief n°{0} : {1}.': 'Brief #{0}: {1}.',
+    '… {0} brief(s) plus ancien(s).': '… {0} older brief(s).',
+    '{0} invalide : {1}': 'Invalid {0}: {1}',
+    'n°{0}': '#{0}',
+    'Historique : {0}, {1} brief(s)': 'History: {0}, {1} brief(s)',
+    'aucun brief enregistré': 'no brief saved',
+    'Dernier brief : n°{0}, date inconnue': 'Last brief: #{0}, unknown date',
+    'Dernier brief : n°{0} du {1} (il y a {2} jour(s))': 'Last brief: #{0} of {1} ({2} day(s) ago)',
+    'aucun brief depuis {0} jours (seuil : {1}), un brief a-t-il été généré ailleurs ?': 'no brief for {0} days (threshold: {1}), was a brief generated elsewhere?',
+    'Brouillon(s) en attente : {0} (groupgen brief activate)': 'Pending draft(s): {0} (groupgen brief activate)',
+    '{0} brief(s) illisible(s) mis de côté dans invalid_briefs de {1}, à corriger puis remettre dans briefs': '{0} unreadable brief(s) set aside in invalid_briefs of {1}, to fix and put back in briefs',
+    'ni {0} ni synchronisation LDAP ou registre connue': 'neither {0} nor a known LDAP or registry sync',
+    'Liste des apprenants mise à jour le {0} (il y a {1} jour(s))': 'Roster updated on {0} ({1} day(s) ago)',
+    'liste des apprenants non mise à jour depuis {0} jours (seuil : {1}), arrivées ou départs oubliés ?': 'roster not updated for {0} days (threshold: {1}), forgotten arrivals or departures?',
+    'Attention : {0}': 'Warning: {0}',
+    'Tout est à jour.': 'Everything is up to date.',
+
+    // intake and survey
+    'Export du formulaire manquant, ex. : groupgen intake --form responses.csv': 'Missing form export, e.g. groupgen intake --form responses.csv',
+    'Correspondance des colonnes manquante : name_column et [intake.skills] dans groupgen.toml': 'Missing column mapping: name_column and [intake.skills] in groupgen.toml',
+    '{0} auto-évaluation(s) importée(s) dans {1}.': '{0} self-assessment(s) imported into {1}.',
+    '{0} n\'est pas dans la liste des apprenants, {1} ?': '{0} is not in the roster, {1}?',
+    '{0} n\'est pas dans la liste des apprenants': '{0} is not in the roster',
+    'Fichier du sondage manquant, ex. : groupgen survey --out form.csv': 'Missing survey file, e.g. groupgen survey --out form.csv',
+    'Sondage de {0} apprenants écrit dans {1}, à importer une fois rempli avec : groupgen survey import {2}': 'Survey of {0} students written to {1}, to import once filled in with: groupgen survey import {2}',
+    'Réponses manquantes, ex. : groupgen survey import form.csv': 'Missing answers, e.g. groupgen survey import form.csv',
+    'Réponses introuvables : {0}': 'Answers not found: {0}',
+    '{0} n\'est pas dans la liste des apprenants, réponses ignorées': '{0} is not in the roster, answers ignored',
+    '{0} réponse(s) lue(s) : {1} binôme(s) à éviter, {2} binôme(s) à réunir, {3} contrainte(s) ajoutée(s).': '{0} answer(s) read: {1} pair(s) to avoid, {2} pair(s) to bring together, {3} constraint(s) added.',
+    '{0} souhait(s) de travailler ensemble non réciproque(s) laissé(s) de côté.': '{0} one-sided wish(es) to work together left out.',
+
+    // invites and publish
+    'Date du brief manquante ou invalide, ex. : --date 2024-10-14': 'Missing or invalid brief date, e.g. --date 2024-10-14',
+    'Heure invalide : {0} (HH:MM)': 'Invalid time: {0} (HH:MM)',
+    'Canal email inconnu dans groupgen.toml : {0}': 'Unknown email channel in groupgen.toml: {0}',
+    '{0} avec {1}': '{0} with {1}',
+    'Passage : {0}': 'Slot: {0}',
+    'Invitation : {0}': 'Invitation: {0}',
+    '{0} de {1} à {2}': '{0} from {1} to {2}',
+    '{0} n\'a pas d\'email dans la liste, invitation écrite en fichier': '{0} has no email in the roster, invitation written to a file',
+    '{0} invitation(s) envoyée(s) par email.': '{0} invitation(s) sent by email.',
+    '{0} invitation(s) écrite(s) dans {1}.': '{0} invitation(s) written to {1}.',
+    'Serveur Matrix manquant : --matrix-homeserver ou homeserver dans [matrix] de groupgen.toml': 'Missing Matrix server: --matrix-homeserver or homeserver in [matrix] of groupgen.toml',
+    'Aucune annonce en attente.': 'No pending announcement.',
+    '{0} annonce(s) programmée(s), la prochaine le {1}.': '{0} scheduled announcement(s), the next one on {1}.',
+    '{0} annonce(s) toujours en attente.': '{0} announcement(s) still pending.',
+    'Aucune destination, ex. : groupgen publish --channel discord-promo ou --all-channels': 'No destination, e.g. groupgen publish --channel discord-promo or --all-channels',
+
+    // quick and regenerate
+    'Liste de noms manquante, ex. : groupgen quick "Alice,Bob,Carol"': 'Missing list of names, e.g. groupgen quick "Alice,Bob,Carol"',
+    '--name n\'a de sens qu\'avec --save': '--name only makes sense with --save',
+    'Non enregistré. Pour l\'ajouter à l\'historique plus tard : groupgen adopt {0}': 'Not saved. To add it to the history later: groupgen adopt {0}',
+    '{0} brief(s) tiré(s) après lui (n°{1})': '{0} brief(s) drawn after it (#{1})',
+    '{0} dépôt(s) créé(s) pour ses groupes': '{0} repository(ies) created for its groups',
+    '{0} fil(s) de discussion ouvert(s) pour ses groupes': '{0} discussion thread(s) opened for its groups',
+    'Brief à refaire manquant, ex. : groupgen regenerate --brief 12': 'Missing brief to redo, e.g. groupgen regenerate --brief 12',
+    'Le brief n°{0} a servi depuis : {1}. Relancez avec --force pour le refaire quand même': 'Brief #{0} has been used since: {1}. Run again with --force to redo it anyway',
+    'Brief n°{0}{1} refait avec {2} apprenants : {3} binôme(s) répété(s){4}.': 'Brief #{0}{1} redone with {2} students: {3} repeated pair(s){4}.',
+    'Créneaux et observateurs effacés, relancez assign-slots et assign-observers.': 'Slots and observers cleared, run assign-slots and assign-observers again.',
+    'Les dépôts et fils de discussion des anciens groupes existent toujours chez le fournisseur, à supprimer à la main.': 'The repositories and discussion threads of the old groups still exist at the provider, to delete by hand.',
+    'Composition précédente mise à la corbeille, groupgen trash restore {0} pour y revenir.': 'Previous composition moved to the trash, groupgen trash restore {0} to go back to it.',
+
+    // self-update
+    'groupgen {0} est à jour.': 'groupgen {0} is up to date.',
+    'Nouvelle version disponible : {0} (installée : {1}), {2}': 'New version available: {0} (installed: {1}), {2}',
+    'groupgen tourne depuis un dépôt git, mettez-le à jour avec git pull': 'groupgen runs from a git checkout, update it with git pull',
+    'la release {0} n\'a pas d\'archive .tgz ou de {1}': 'release {0} has no .tgz archive or {1}',
+    'npm install a échoué (code {0})': 'npm install failed (code {0})',
+    'groupgen {0} installé.': 'groupgen {0} installed.',
+
+    // shell and tui
+    'nouveau tirage': 'new draw',
+    'échange deux apprenants de groupes différents ("Prénom Nom" entre guillemets, ou un bout du nom)': 'swaps two students of different groups ("First Last" in double quotes, or part of the name)',
+    'revient au tirage d\'avant le dernier generate ou swap': 'goes back to the draw before the last generate or swap',
+    'briefs passés ensemble, tirage en cours compris': 'briefs spent together, current draw included',
+    'enregistre le tirage en cours comme brief': 'saves the current draw as a brief',
+    'quitte (Ctrl-D aussi)': 'quits (Ctrl-D too)',
+    '{0} : aucun apprenant de ce nom': '{0}: no student by that name',
+    '{0} : ambigu ({1}{2})': '{0}: ambiguous ({1}{2})',
+    ', {0} échange(s)': ', {0} swap(s)',
+    ', {0} contrainte(s) non respectée(s)': ', {0} unmet constraint(s)',
+    'Graine {0}{1} : {2} binôme(s) répété(s){3}.': 'Seed {0}{1}: {2} repeated pair(s){3}.',
+    'Aucun tirage en cours, tapez generate': 'No draw in progress, type generate',
+    'Tirage déjà enregistré comme brief n°{0}, tapez generate pour un nouveau': 'Draw already saved as brief #{0}, type generate for a new one',
+    'Deux apprenants attendus, ex. : swap Alice "Bob Durand"': 'Two students expected, e.g. swap Alice "Bob Durand"',
+    '{0} n\'est dans aucun groupe de ce tirage': '{0} is in no group of this draw',
+    '{0} et {1} sont déjà dans le même groupe': '{0} and {1} are already in the same group',
+    '{0} et {1} échangés.': '{0} and {1} swapped.',
+    'Rien à annuler': 'Nothing to undo',
+    '{0} apprenants, groupes de {1}. Tapez help pour les commandes.': '{0} students, groups of {1}. Type help for the commands.',
+    'Commande inconnue : {0} ({1}, quit)': 'Unknown command: {0} ({1}, quit)',
+    'tui demande un terminal interactif, utilisez generate dans les scripts': 'tui needs an interactive terminal, use generate in scripts',
+    '{0} binôme(s) répété(s).': '{0} repeated pair(s).',
+    'Recherche…': 'Searching…',
+    'Promo': 'Cohort',
+    'Matrice': 'Matrix',
+    '↑↓ parcourir': '↑↓ browse',
+    '↑↓←→ parcourir': '↑↓←→ browse',
+    'g tirer  ↑↓ parcourir  espace choisir puis échanger  s enregistrer': 'g draw  ↑↓ browse  space pick then swap  s save',
+    'tab/1-{0} écrans  q quitter': 'tab/1-{0} screens  q quit',
+    'Tirage avec la graine {0}.': 'Draw with seed {0}.',
+    'Aucun tirage, g pour former des groupes.': 'No draw, g to form groups.',
+    'Déjà enregistré comme brief n°{0}.': 'Already saved as brief #{0}.',
+    'Déjà enregistré comme brief n°{0}, g pour un nouveau tirage.': 'Already saved as brief #{0}, g for a new draw.',
+    'Ces deux apprenants sont déjà dans le même groupe.': 'These two students are already in the same group.',
+    '{0} brief(s), {1} partenaire(s) distinct(s), {2} répétition(s)': '{0} brief(s), {1} distinct partner(s), {2} repeat(s)',
+    '{0} et {1} : jamais ensemble': '{0} and {1}: never together',
+    '{0} et {1} : {2} brief(s) ensemble': '{0} and {1}: {2} brief(s) together',
+    'Aucun tirage : g pour former des groupes en évitant le brief précédent.': 'No draw: g to form groups avoiding the previous brief.',
+    'Groupe {0} ({1} paire(s) déjà formée(s))': 'Group {0} ({1} pair(s) already formed)',
+    'Enregistré comme brief n°{0}.': 'Saved as brief #{0}.',
+    'Graine {0}{1}, non enregistré.': 'Seed {0}{1}, not saved.',
+
+    // simulate-policies
+    'dernier brief': 'last brief',
+    '3 derniers briefs': 'last 3 briefs',
+    'tout l\'historique': 'whole history',
+    'dernier brief, 100 tirages': 'last brief, 100 draws',
+    '--{0} invalide : {1}': 'Invalid --{0}: {1}',
+    'Au moins 2 apprenants sont nécessaires': 'At least 2 students are needed',
+    '{0} apprenants, {1} briefs simulés par réglage.': '{0} students, {1} simulated briefs per setting.',
+    'Couverture (binômes possibles déjà formés) après {0} briefs :': 'Coverage (possible pairs already formed) after {0} briefs:',
+    '{0} binôme(s) reformé(s), {1} à {2} partenaires distincts par apprenant': '{0} pair(s) formed again, {1} to {2} distinct partners per student',
+
+    // suggest-config and teams
+    '--min-repeats invalide : {0}': 'Invalid --min-repeats: {0}',
+    'Aucune suggestion d\'après les {0} briefs : pas de binôme formé {1} fois ou plus ni d\'apprenant isolé sans contrainte.': 'No suggestion from the {0} briefs: no pair formed {1} times or more and no isolated student without a constraint.',
+    '# {0} contrainte(s) suggérée(s) d\'après {1} briefs, à ajouter à groupgen.toml :\n': '# {0} constraint(s) suggested from {1} briefs, to add to groupgen.toml:\n',
+    'Date de fin invalide : {0} (AAAA-MM-JJ)': 'Invalid end date: {0} (YYYY-MM-DD)',
+    'Des équipes fixes sont déjà en place depuis le {0}, terminez-les d\'abord avec : groupgen teams end': 'Fixed teams are already in place since {0}, end them first with: groupgen teams end',
+    'Aucun brief enregistré dont reprendre les groupes': 'No saved brief to take the groups from',
+    '{0} noms d\'équipe pour {1} groupes dans le brief n°{2}': '{0} team names for {1} groups in brief #{2}',
+    'Équipe {0}': 'Team {0}',
+    '{0} ne fait plus partie de la liste des apprenants, corrigez le brief n°{1} (late-add, drop) avant de figer les équipes': '{0} is no longer in the roster, fix brief #{1} (late-add, drop) before fixing the teams',
+    'Sans équipe : {0}, à ajouter avec late-add --brief {1} avant de recréer les équipes.': 'Without a team: {0}, to add with late-add --brief {1} before creating the teams again.',
+    'Équipes fixées d\'après le brief n°{0} jusqu\'au {1}, la génération est suspendue d\'ici là.': 'Teams fixed from brief #{0} until {1}, generation is suspended until then.',
+    'Équipes fixées d\'après le brief n°{0}, la génération est suspendue d\'ici là.': 'Teams fixed from brief #{0}, generation is suspended until then.',
+    'Aucune équipe fixe en place, créez-en avec : groupgen teams create': 'No fixed teams in place, create some with: groupgen teams create',
+    'Aucune équipe fixe en place.': 'No fixed teams in place.',
+    'Aucun rôle défini pour ces équipes (--roles à la création ou roles dans [teams] de groupgen.toml)': 'No role defined for these teams (--roles on creation or roles in [teams] of groupgen.toml)',
+    'Équipes fixes terminées, la génération reprend au prochain brief.': 'Fixed teams ended, generation resumes at the next brief.',
+
+    // configuration, input files and options
+    'Catégorie inconnue : {0} ({1}, ou une table [categories.{2}] dans groupgen.toml)': 'Unknown category: {0} ({1}, or a [categories.{2}] table in groupgen.toml)',
+    'groupgen.toml : [categories.{0}] count doit être une liste de catégories': 'groupgen.toml: [categories.{0}] count must be a list of categories',
+    'Shell non pris en charge : {0} ({1})': 'Unsupported shell: {0} ({1})',
+    '{0} : guillemet non fermé (ligne {1})': '{0}: unclosed quote (line {1})',
+    'Langue non prise en charge : {0} ({1})': 'Unsupported language: {0} ({1})',
+    'Pas assez de noms pour {0} apprenants (au plus {1})': 'Not enough names for {0} students (at most {1})',
+    'Locale inconnue : {0} (ex. : fr-FR, fr-BE, en-GB)': 'Unknown locale: {0} (e.g. fr-FR, fr-BE, en-GB)',
+    '{0} : colonne "{1}" introuvable': '{0}: column "{1}" not found',
+    '{0}, ligne {1} : réponse "{2}" pour {3} sans niveau correspondant dans [intake.levels]': '{0}, line {1}: answer "{2}" for {3} with no matching level in [intake.levels]',
+    'URL LDAP invalide : {0}': 'Invalid LDAP URL: {0}',
+    'URL LDAP incomplète : {0} (attendu ldap://hôte/<base dn>?group=<groupe>)': 'Incomplete LDAP URL: {0} (expected ldap://host/<base dn>?group=<group>)',
+    'ldapsearch introuvable, installez les outils clients OpenLDAP (ldap-utils / openldap-clients)': 'ldapsearch not found, install the OpenLDAP client tools (ldap-utils / openldap-clients)',
+    'ldapsearch a échoué : {0}': 'ldapsearch failed: {0}',
+    'Niveau de journalisation inconnu : {0}': 'Unknown log level: {0}',
+    'Graine invalide : {0} (entier positif, au plus {1})': 'Invalid seed: {0} (positive integer, at most {1})',
+    'Registre injoignable ({0}), utilisation du cache': 'Registry unreachable ({0}), using the cache',
+    '{0} inconnu du registre': '{0} unknown to the registry',
+    '{0} n\'est plus actif dans le registre, ignoré': '{0} is no longer active in the registry, skipped',
+    '{0} : la première colonne doit être "name"': '{0}: the first column must be "name"',
+    '{0}, ligne {1} : niveau "{2}" invalide pour {3}': '{0}, line {1}: invalid level "{2}" for {3}',
+    'ensemble dans {0} briefs sur {1}': 'together in {0} briefs out of {1}',
+    '{0} n\'a travaillé qu\'avec {1} personne(s) (médiane : {2}), jamais avec {3} qui en a côtoyé {4}': '{0} only worked with {1} people (median: {2}), never with {3} who worked with {4}',
+    '{0} : aucune colonne au nom d\'un apprenant de la liste': '{0}: no column named after a student of the roster',
+    '{0}, ligne {1} : réponse "{2}" pour {3} ({4}, {5} ou vide)': '{0}, line {1}: answer "{2}" for {3} ({4}, {5} or empty)',
+    'TOML invalide ligne {0} : {1} n\'est pas une table': 'Invalid TOML line {0}: {1} is not a table',
+    'TOML invalide ligne {0} : {1} n\'est pas un tableau de tables': 'Invalid TOML line {0}: {1} is not an array of tables',
+    'TOML invalide ligne {0} : tableau non fermé': 'Invalid TOML line {0}: unclosed array',
+    'TOML invalide ligne {0} : {1}': 'Invalid TOML line {0}: {1}',
+    'Clé TOML invalide ligne {0} : "{1}"': 'Invalid TOML key line {0}: "{1}"',
+    'Valeur TOML invalide ligne {0} : {1}': 'Invalid TOML value line {0}: {1}',
+    'Modèle de dossier introuvable : {0}': 'Folder template not found: {0}',
+
+    // data, encryption, backups and sessions
+    'sauvegarde impossible avant « {0} » ({1}), rien n\'a été modifié': 'cannot back up before "{0}" ({1}), nothing was changed',
+    'Registre confidentiel chiffré : définissez {0} avec sa phrase de passe': 'Encrypted confidential registry: set {0} to its passphrase',
+    'Impossible de lire le registre confidentiel : clé {0} incorrecte ou registre altéré': 'Cannot read the confidential registry: wrong {0} key or tampered registry',
+    'Contrainte n°{0}': 'Constraint #{0}',
+    'groupgen.toml, contrainte n°{0}': 'groupgen.toml, constraint #{0}',
+    'Impossible de déchiffrer la base : clé {0} incorrecte ou fichier altéré': 'Cannot decrypt the database: wrong {0} key or tampered file',
+    'Document JSON sans schema_version': 'JSON document without schema_version',
+    'schema_version {0} non supportée (max {1}), mettez à jour l\'outil': 'Unsupported schema_version {0} (max {1}), update the tool',
+    'Session {0} introuvable (expirée après {1} jours ?)': 'Session {0} not found (expired after {1} days?)',
+    'Le trousseau macOS n\'accepte pas de secret sur plusieurs lignes': 'The macOS keychain does not accept multi-line secrets',
+    'Trousseau système non pris en charge sur {0}, utilisez une variable d\'environnement (suffixe _env)': 'System keyring not supported on {0}, use an environment variable (_env suffix)',
+    'Le Gestionnaire d\'identification de Windows n\'est pas pris en charge. Rangez le secret dans une variable d\'environnement et référencez-la avec le suffixe _env, ex. : webhook_url_env = "DISCORD_WEBHOOK"': 'Windows Credential Manager is not supported. Keep the secret in an environment variable and reference it with the _env suffix, e.g. webhook_url_env = "DISCORD_WEBHOOK"',
+    '{0} introuvable, {1}': '{0} not found, {1}',
+    'installez libsecret-tools (secret-tool)': 'install libsecret-tools (secret-tool)',
+    'l\'outil security de macOS est requis': 'the macOS security tool is required',
+    '{0} a échoué : {1}': '{0} failed: {1}',
+    'Impossible d\'enregistrer {0} dans le trousseau : {1}': 'Cannot store {0} in the keyring: {1}',
+
+    // publishers
+    'aucun': 'none',
+    'Fichier': 'File',
+    'Discord (fils)': 'Discord (threads)',
+    'Canal inconnu : {0} (canaux de groupgen.toml : {1})': 'Unknown channel: {0} (groupgen.toml channels: {1})',
+    'type inconnu "{0}" ({1})': 'unknown type "{0}" ({1})',
+    '{0} : publié ({1})': '{0}: published ({1})',
+    '{0} : publié': '{0}: published',
+    '{0} : échec, {1}': '{0}: failed, {1}',
+    '{0}/{1} canaux publiés': '{0}/{1} channels published',
+    'channel (salon parent) manquant': 'missing channel (parent channel)',
+    '{0} fil(s) ouvert(s), sans identifiant Discord : {1}': '{0} thread(s) opened, without a Discord id: {1}',
+    '{0} fil(s) ouvert(s)': '{0} thread(s) opened',
+    'to, from et smtp_host sont requis pour un canal email': 'to, from and smtp_host are required for an email channel',
+    'envoyé à {0}': 'sent to {0}',
+    '{0} éléments ajoutés': '{0} items added',
+    'namespace (groupe GitLab) manquant': 'missing namespace (GitLab group)',
+    '{0} projets dans {1}, comptes introuvables : {2}': '{0} projects in {1}, accounts not found: {2}',
+    '{0} projets dans {1}': '{0} projects in {1}',
+    'Variable d\'environnement {0} manquante': 'Missing environment variable {0}',
+    'Secret {0} absent du trousseau, enregistrez-le avec : secrets set {1}': 'Secret {0} missing from the keyring, store it with: secrets set {1}',
+    '{0} (ou {1}_env, {2}_keyring) manquant pour le canal {3}': 'Missing {0} (or {1}_env, {2}_keyring) for channel {3}',
+    'connexion impossible à {0} ({1})': 'cannot connect to {0} ({1})',
+    'HTTP {0} sur {1} : {2}': 'HTTP {0} on {1}: {2}',
+    'Aucun canal n\'a publié le brief n°{0} : son enregistrement est annulé.': 'No channel published brief #{0}: its record is cancelled.',
+    '{0} annonce(s) mise(s) en attente, à renvoyer avec : groupgen publish --retry-pending': '{0} announcement(s) queued, to send again with: groupgen publish --retry-pending',
+    '{0}, brief n°{1}': '{0}, brief #{1}',
+    'brief supprimé, annonce abandonnée': 'brief deleted, announcement dropped',
+    'liste « {0} » créée': 'list "{0}" created',
+    'délai SMTP dépassé': 'SMTP timeout',
+    '{0} ne propose pas STARTTLS, identifiants SMTP non envoyés en clair (smtp_plaintext_auth = true pour l\'accepter)': '{0} does not offer STARTTLS, SMTP credentials not sent in clear (smtp_plaintext_auth = true to allow it)',
+    'connexion SMTP fermée': 'SMTP connection closed',
+    'réponse SMTP inattendue : {0}': 'unexpected SMTP response: {0}',
+
+    // updates and version
+    'HTTP {0} sur {1}': 'HTTP {0} on {1}',
+    '{0} absent de {1}': '{0} missing from {1}',
+    'somme de contrôle invalide pour {0}, téléchargement corrompu ou modifié': 'invalid checksum for {0}, corrupted or modified download',
+    'groupgen {0}{1}, schéma de données {2}, Node {3}': 'groupgen {0}{1}, data schema {2}, Node {3}',
+}
@@ -0,0 +1,277 @@
+import { t } from './i18n.mjs'
+
+/**
+ * Versioned JSON structures written to disk or printed for other tools.
+ *
+ * Every document carries a `schema_version`. Fields may be added within a
+ * version; renaming or removing one requires bumping SCHEMA_VERSION and
+ * teaching `upgrade` how to read the previous shape.
+ */
+
+export const SCHEMA_VERSION = 1
+
+/**
+ * @typedef {string[]} Group
+ */
+
+/**
+ * @typedef {object} BriefRecord
+ * @property {number} id
+ * @property {string | null} date ISO 8601 timestamp, null for imported legacy briefs
+ * @property {string | null} label
+ * @property {string} [category] kind of brief, see categories.mjs
+ * @property {Group[]} groups
+ * @property {Slots} [slots] presentation timeslots, once assigned
+ * @property {Observers} [observers] trainers observing the groups, once assigned
+ * @property {number} [duration_ms] time spent searching, for generated briefs
+ * @property {GenerationContext} [context] inputs and scores of the generation
+ * @property {Amendment[]} [amendments] changes made to the groups after the brief was saved
+ * @property {Repository[]} [repositories] projects created for the groups by a publisher
+ * @property {Thread[]} [threads] chat threads opened for the groups by a publisher
+ * @property {'draft' | 'active' | 'closed'} [state] see `briefState`, active when missing
+ * @property {string} [closed_at] ISO 8601 timestamp of `brief close`
+ * @property {number} [cloned_from] id of the brief whose groups `brief clone` copied
+ */
+
+/**
+ * @typedef {object} Thread
+ * @property {number | null} group index of the group in the brief, null once `drop` removed the group
+ * @property {string | null} channel `[[channels]]` entry it was opened with
+ * @property {string} id Discord channel id of the thread
+ * @property {boolean} [closed] archived or deleted by `brief close`
+ */
+
+/**
+ * @typedef {object} Repository
+ * @property {number | null} group index of the group in the brief, null once `drop` removed the group
+ * @property {'gitlab'} provider
+ * @property {number} id project id on the provider
+ * @property {string} url
+ */
+
+/**
+ * @typedef {object} Amendment
+ * @property {string} date ISO 8601 timestamp
+ * @property {'add' | 'drop'} action
+ * @property {string} student
+ * @property {number} group index of the group joined or left
+ * @property {boolean} [emptied] the group was left empty and removed
+ */
+
+/**
+ * Snapshot of what the matcher saw and found, stored with generated briefs
+ * so their composition can be explained later.
+ *
+ * @typedef {object} GenerationContext
+ * @property {string[]} roster students given to the matcher
+ * @property {number | null} previous_brief brief whose pairs were avoided
+ * @property {number[]} constraints ids of the constraints in force
+ * @property {number | null} seed of the draw, null for briefs generated before seeds were recorded
+ * @property {number} iterations candidates evaluated
+ * @property {number} repeats repeated pairs in the chosen grouping
+ * @property {number} violations "never" constraints broken
+ * @property {number[]} group_repeats repeated pairs inside each group
+ * @property {number[]} improvements successive best scores, the last one being chosen
+ * @property {number} [group_size] members per group asked for, when not pairs
+ * @property {string[]} [counted_categories] categories of the past briefs taken into account, when filtered
+ * @property {string} [tie_break] secondary objective of the search, see TIE_BREAKS in matcher.mjs
+ * @property {string[]} [excluded] absentees left out of this brief only
+ * @property {string} [regenerated_at] ISO 8601 timestamp of `regenerate`, which drew the groups again under the same id
+ * @property {[string, string][]} [swaps] students swapped by hand in `tui` after the draw
+ * @property {string[]} [required_skills] skills every group had to cover
+ * @property {number} [missing_skills] required skills missing, summed over the groups
+ */
+
+/**
+ * @typedef {object} Slots
+ * @property {string[]} names timeslots in chronological order
+ * @property {string[]} assignment slot of each group, aligned with `groups`
+ */
+
+/**
+ * @typedef {object} Observers
+ * @property {string[]} trainers
+ * @property {string[]} assignment trainer of each group, aligned with `groups`
+ */
+
+/**
+ * @typedef {object} Database
+ * @property {number} schema_version
+ * @property {BriefRecord[]} briefs ordered by id
+ * @property {import('./constraints.mjs').ConstraintRecord[]} constraints ordered by id
+ * @property {string} [roster_synced_at] last time the roster was read from LDAP or the registry
+ * @property {PendingPublication[]} [pending_publications] announcements that failed, to be sent again
+ * @property {import('./teams.mjs').TeamPhase[]} [teams] fixed team phases, the last one possibly in force
+ * @property {TrashedBrief[]} [trash] deleted briefs, until the trash is emptied
+ * @property {QuarantinedBrief[]} [invalid_briefs] unreadable briefs set aside when the file was loaded
+ * @property {import('./encryption.mjs').Envelope} [confidential] encrypted registry of private "never" pairs, see confidential.mjs
+ * @property {Record<string, Cohort>} [cohorts] other promos kept in the same file, by name
+ */
+
+/**
+ * A promo managed with `--cohort`: the fields of a Database but
+ * `schema_version` and `cohorts`, and its own roster.
+ *
+ * @typedef {Omit<Database, 'schema_version' | 'cohorts'> & {students: (string | import('./roster.mjs').Student)[]}} Cohort
+ */
+
+/**
+ * @typedef {object} TrashedBrief
+ * @property {string} deleted_at ISO 8601 timestamp
+ * @property {BriefRecord} brief
+ * @property {true} [replaced] earlier version of a brief drawn again by `regenerate`, still in the history under its id
+ */
+
+/**
+ * A brief of the file that can't be used, kept as found to be fixed by
+ * hand and moved back to `briefs`.
+ *
+ * @typedef {object} QuarantinedBrief
+ * @property {string} found_at ISO 8601 timestamp
+ * @property {string} reason
+ * @property {unknown} brief
+ */
+
+/**
+ * @typedef {object} PendingPublication
+ * @property {number} brief id of the brief to announce
+ * @property {string | Record<string, any>} channel name of a `[[channels]]` table, or the table itself for ad-hoc channels
+ * @property {string} queued_at ISO 8601 timestamp of the first failure, or of the scheduling
+ * @property {number} attempts 0 for an announcement scheduled and not tried yet
+ * @property {string | null} error message of the last failure
+ * @property {string} [publish_at] ISO 8601 timestamp before which it is not sent, from `generate --publish-at`
+ */
+
+/**
+ * @returns {Database}
+ */
+export function emptyDatabase() {
+    return {
+        schema_version: SCHEMA_VERSION,
+        briefs: [],
+        constraints: [],
+    }
+}
+
+/**
+ * @param {(string | import('./roster.mjs').Student)[]} [students]
+ * @returns {Cohort}
+ */
+export function emptyCohort(students = []) {
+    return {
+        students,
+        briefs: [],
+        constraints: [],
+    }
+}
+
+/**
+ * @typedef {BriefRecord & {schema_version: number}} BriefDocument
+ */
+
+/**
+ * A brief exported on its own.
+ *
+ * @param {BriefRecord} brief
+ * @returns {BriefDocument}
+ */
+export function briefDocument(brief) {
+    return { schema_version: SCHEMA_VERSION, ...brief }
+}
+
+/**
+ * Groups printed or written with `--format json`.
+ *
+ * @param {Group[]} groups
+ * @returns {{schema_version: number, groups: Group[]}}
+ */
+export function groupsDocument(groups) {
+    return { schema_version: SCHEMA_VERSION, groups }
+}
+
+/**
+ * @typedef {object} MatrixDocument meeting matrix printed with `--format json`
+ * @property {number} schema_version
+ * @property {string[]} students in alphabetical order
+ * @property {{students: [string, string], briefs: number}[]} pairs pairs that met at least once
+ * @property {({student: string} & import('./stats.mjs').Attendance)[]} low_attendance students often absent
+ */
+
+/**
+ * @param {string[]} students
+ * @param {MatrixDocument['pairs']} pairs
+ * @param {MatrixDocument['low_attendance']} lowAttendance
+ * @returns {MatrixDocument}
+ */
+export function matrixDocument(students, pairs, lowAttendance) {
+    return { schema_version: SCHEMA_VERSION, students, pairs, low_attendance: lowAttendance }
+}
+
+/**
+ * Reads a brief document, accepting the unversioned array written by
+ * older releases.
+ *
+ * @param {unknown} raw
+ * @returns {BriefDocument}
+ */
+export function parseBriefDocument(raw) {
+    if(Array.isArray(raw)) {
+        return { schema_version: SCHEMA_VERSION, id: 1, date: null, label: null, groups: raw }
+    }
+    return upgrade(raw)
+}
+
+/**
+ * What makes a brief of the history unusable: groups that are not lists
+ * of names, an empty group or name, a student listed twice, a missing or
+ * repeated id. Hand edits and merges of the file are the usual causes.
+ *
+ * @param {unknown} brief
+ * @param {Set<number>} ids of the briefs before it, for repeated ids
+ * @returns {string | null} null when the brief is fine
+ */
+export function briefProblem(brief, ids) {
+    if(brief == null || typeof brief != 'object' || Array.isArray(brief)) {
+        return t`pas un brief`
+    }
+    if(!Number.isInteger(brief.id) || brief.id < 1) {
+        return t`id invalide (${JSON.stringify(brief.id)})`
+    }
+    if(ids.has(brief.id)) {
+        return t`id ${brief.id} en double`
+    }
+    if(!Array.isArray(brief.groups) || brief.groups.some((group) => !Array.isArray(group))) {
+        return t`groups doit être une liste de groupes`
+    }
+    const seen = new Set()
+    for(const [index, group] of brief.groups.entries()) {
+        if(group.length == 0) {
+            return t`groupe ${index + 1} vide`
+        }
+        for(const member of group) {
+            if(typeof member != 'string' || member.trim() == '') {
+                return t`membre vide ou invalide dans le groupe ${index + 1} (${JSON.stringify(member)})`
+            }
+            if(seen.has(member)) {
+                return t`${member} apparaît deux fois`
+            }
+            seen.add(member)
+        }
+    }
+    return null
+}
+
+/**
+ * @template {{schema_version: number}} T
+ * @param {T} doc
+ * @returns {T}
+ */
+export function upgrade(doc) {
+    if(doc == null || typeof doc.schema_version != 'number') {
+        throw new Error(t`Document JSON sans schema_version`)
+    }
+    if(doc.schema_version > SCHEMA_VERSION) {
+        throw new Error(t`schema_version ${doc.schema_version} non supportée (max ${SCHEMA_VERSION}), mettez à jour l'outil`)
+    }
+    return doc
+}
@@ -0,0 +1,54 @@
+import { fold } from './fuzzy.mjs'
+
+/**
+ * Order in which names are shown: French collation, so that "Émile" comes
+ * right after "Emma" rather than after "Zoé", and "Apprenant 2" before
+ * "Apprenant 10". Display only: keys stored or compared internally use
+ * `pairKey`, which doesn't depend on the ICU version.
+ */
+const collator = new Intl.Collator('fr', { numeric: true })
+
+/**
+ * @param {string} a
+ * @param {string} b
+ */
+export function compareNames(a, b) {
+    return collator.compare(a, b)
+}
+
+/**
+ * @param {string[]} names
+ * @returns {string[]} sorted copy
+ */
+export function sortNames(names) {
+    return [...names].sort(compareNames)
+}
+
+/**
+ * Stable identifier of an unordered pair of students, by code point.
+ *
+ * @param {string} a
+ * @param {string} b
+ */
+export function pairKey(a, b) {
+    return a < b ? `${a}\n${b}` : `${b}\n${a}`
+}
+
+/**
+ * File name friendly form of a name: "Émile Zola" gives "emile-zola".
+ *
+ * @param {string} name
+ */
+export function slug(name) {
+    return fold(name).replace(/[^a-z0-9]+/g, '-').replace(/^-|-$/g, '')
+}
+
+/**
+ * Repository or branch name of a group: "brief-14-groupe-01".
+ *
+ * @param {number} briefId
+ * @param {number} index of the group in the brief
+ */
+export function groupRepositoryName(briefId, index) {
+    return `brief-${briefId}-groupe-${String(index + 1).padStart(2, '0')}`
+}
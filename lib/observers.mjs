@@ -0,0 +1,55 @@
+/**
+ * @typedef {import('./model.mjs').BriefRecord} BriefRecord
+ */
+
+/**
+ * How many times each trainer observed each student in `briefs`.
+ *
+ * @param {BriefRecord[]} briefs
+ * @returns {Map<string, Map<string, number>>} by trainer, then student
+ */
+export function observationCounts(briefs) {
+    const counts = new Map()
+    for(const brief of briefs) {
+        if(brief.observers == null) {
+            continue
+        }
+        brief.groups.forEach((group, index) => {
+            const trainer = brief.observers.assignment[index]
+            const seen = counts.get(trainer) ?? new Map()
+            for(const people of group) {
+                seen.set(people, (seen.get(people) ?? 0) + 1)
+            }
+            counts.set(trainer, seen)
+        })
+    }
+    return counts
+}
+
+/**
+ * Shares the groups evenly between `trainers`, each group going to the
+ * trainer who observed its members the least so far. Groups that the
+ * trainers know best are placed first, while everyone still has room.
+ *
+ * @param {string[][]} groups
+ * @param {string[]} trainers
+ * @param {Map<string, Map<string, number>>} history from `observationCounts`
+ * @returns {string[]} trainer of each group, aligned with `groups`
+ */
+export function assignObservers(groups, trainers, history) {
+    const familiarity = (trainer, group) => group.reduce((sum, people) => sum + (history.get(trainer)?.get(people) ?? 0), 0)
+    const capacity = Math.ceil(groups.length / trainers.length)
+    const load = new Map(trainers.map((trainer) => [trainer, 0]))
+    const order = groups
+        .map((group, index) => ({ index, group, known: Math.max(...trainers.map((trainer) => familiarity(trainer, group))) }))
+        .sort((a, b) => b.known - a.known)
+    const assignment = new Array(groups.length)
+    for(const { index, group } of order) {
+        const [trainer] = trainers
+            .filter((trainer) => load.get(trainer) < capacity)
+            .sort((a, b) => familiarity(a, group) - familiarity(b, group) || load.get(a) - load.get(b))
+        assignment[index] = trainer
+        load.set(trainer, load.get(trainer) + 1)
+    }
+    return assignment
+}
@@ -0,0 +1,130 @@
+import fs from 'fs'
+import os from 'os'
+import path from 'path'
+
+const APP_NAME = 'simplon-group-generator'
+
+/**
+ * Per-user directory for persistent data, following each platform's
+ * convention (%APPDATA% on Windows, Application Support on macOS,
+ * $XDG_DATA_HOME elsewhere).
+ */
+export function dataDir() {
+    switch(process.platform) {
+        case 'win32':
+            return path.join(process.env.APPDATA ?? path.join(os.homedir(), 'AppData', 'Roaming'), APP_NAME)
+        case 'darwin':
+            return path.join(os.homedir(), 'Library', 'Application Support', APP_NAME)
+        default:
+            return path.join(process.env.XDG_DATA_HOME || path.join(os.homedir(), '.local', 'share'), APP_NAME)
+    }
+}
+
+/**
+ * Per-user directory for configuration: $XDG_CONFIG_HOME on Linux and
+ * the same directory as the data elsewhere.
+ */
+export function configDir() {
+    if(process.platform == 'win32' || process.platform == 'darwin') {
+        return dataDir()
+    }
+    return path.join(process.env.XDG_CONFIG_HOME || path.join(os.homedir(), '.config'), APP_NAME)
+}
+
+/** @type {string | null} */
+let dbOverride = null
+
+/**
+ * Uses `file` as the history database for the rest of the run (`--db`).
+ * A directory, or a path ending with a separator, holds a `db.json`.
+ *
+ * @param {string} file
+ */
+export function useDbPath(file) {
+    const isDir = /[\\/]$/.test(file) || (fs.existsSync(file) && fs.statSync(file).isDirectory())
+    dbOverride = path.resolve(isDir ? path.join(file, 'db.json') : file)
+}
+
+/**
+ * Where the history database is stored: the `--db` path when given.
+ * Otherwise a database, or a `last_brief.json` from older releases,
+ * already present in the working directory wins so existing setups keep
+ * their history where it is.
+ */
+export function dbPath() {
+    if(dbOverride != null) {
+        return dbOverride
+    }
+    if(fs.existsSync(path.resolve('db.json')) || fs.existsSync(path.resolve('last_brief.json'))) {
+        return path.resolve('db.json')
+    }
+    return path.join(dataDir(), 'db.json')
+}
+
+/**
+ * `last_brief.json` written by older releases next to `dbFile`, imported
+ * as the first brief when the database is created.
+ *
+ * @param {string} dbFile
+ */
+export function legacyLastBriefPath(dbFile) {
+    return path.join(path.dirname(dbFile), 'last_brief.json')
+}
+
+/**
+ * @param {string} dbFile
+ */
+export function sessionsPath(dbFile) {
+    return path.join(path.dirname(dbFile), 'sessions.json')
+}
+
+/** @type {string | null} */
+let cohort = null
+
+/**
+ * Works on the cohort `name` of the database for the rest of the run
+ * (`--cohort`): its briefs, constraints and roster.
+ *
+ * @param {string} name
+ */
+export function useCohort(name) {
+    cohort = name
+}
+
+/**
+ * @returns {string | null} the cohort given with `--cohort`
+ */
+export function activeCohort() {
+    return cohort
+}
+
+/** @type {string | null} */
+let studentsOverride = null
+
+/**
+ * Uses `file` as the default roster for the rest of the run.
+ *
+ * @param {string} file
+ */
+export function useStudentsPath(file) {
+    studentsOverride = path.resolve(file)
+}
+
+/**
+ * The roster read when no `--students` is given: the one stored with the
+ * cohort (a `cohort:<name>` source, see `loadStudents`), the configured
+ * one, or `students.json` in the working directory.
+ */
+export function studentsPath() {
+    if(cohort != null) {
+        return `cohort:${cohort}`
+    }
+    return studentsOverride ?? path.resolve('students.json')
+}
+
+/**
+ * @param {string} dbFile
+ */
+export function registryCachePath(dbFile) {
+    return path.join(path.dirname(dbFile), 'registry-cache.json')
+}
@@ -0,0 +1,123 @@
+import readline from 'readline'
+
+/**
+ * Asks a yes/no question on the terminal. Ctrl-C or an empty answer count
+ * as "no".
+ *
+ * @param {string} question
+ * @returns {Promise<boolean>}
+ */
+export async function confirm(question) {
+    const prompter = new Prompter()
+    const answer = await prompter.ask(question)
+    prompter.close()
+    return /^(o|oui|y|yes)$/i.test(answer?.trim() ?? '')
+}
+
+/**
+ * Reads one line without echoing it, for secrets, so that it shows
+ * neither on screen nor in the terminal's scrollback. Piped input, which
+ * the terminal does not show anyway, is read as a plain line.
+ *
+ * @param {string} question
+ * @returns {Promise<string | null>} the answer, null on Ctrl-C or once input is closed
+ */
+export async function askSecret(question) {
+    if(!process.stdin.isTTY) {
+        const prompter = new Prompter()
+        const answer = await prompter.ask(question)
+        prompter.close()
+        return answer
+    }
+    // Echo goes off before the question shows, so no keystroke slips through.
+    process.stdin.setRawMode(true)
+    process.stdin.setEncoding('utf8')
+    process.stdin.resume()
+    process.stdout.write(question)
+    try {
+        return await new Promise((resolve) => {
+            let value = ''
+            const finish = (answer) => {
+                process.stdin.off('data', onData)
+                resolve(answer)
+            }
+            const onData = (chunk) => {
+                for(const char of chunk) {
+                    if(char == '\r' || char == '\n' || char == '\u0004') {
+                        return finish(value)
+                    }
+                    if(char == '\u0003') {
+                        return finish(null)
+                    }
+                    if(char == '\u007f' || char == '\b') {
+                        value = [...value].slice(0, -1).join('')
+                    } else if(char >= ' ') {
+                        value += char
+                    }
+                }
+            }
+            process.stdin.on('data', onData)
+        })
+    } finally {
+        process.stdin.setRawMode(false)
+        process.stdin.pause()
+        process.stdout.write('\n')
+    }
+}
+
+/**
+ * Line-based questions on stdin. Lines typed (or piped) ahead of a
+ * question are kept for it, which plain `rl.question` would lose.
+ */
+export class Prompter {
+    /** @type {string[]} */
+    #lines = []
+    /** @type {((line: string | null) => void) | null} */
+    #waiting = null
+    #closed = false
+
+    constructor() {
+        this.rl = readline.createInterface({ input: process.stdin, output: process.stdout })
+        this.rl.on('line', (line) => this.#push(line))
+        this.rl.on('close', () => {
+            this.#closed = true
+            this.#push(null)
+        })
+        this.rl.on('SIGINT', () => {
+            process.stdout.write('\n')
+            this.rl.close()
+        })
+    }
+
+    #push(line) {
+        if(this.#waiting) {
+            const resolve = this.#waiting
+            this.#waiting = null
+            resolve(line)
+        } else if(line != null) {
+            this.#lines.push(line)
+        }
+    }
+
+    /**
+     * @param {string} question
+     * @returns {Promise<string | null>} the answer, null once input is closed
+     */
+    ask(question) {
+        process.stdout.write(question)
+        if(this.#lines.length > 0) {
+            return Promise.resolve(this.#lines.shift())
+        }
+        if(this.#closed) {
+            process.stdout.write('\n')
+            return Promise.resolve(null)
+        }
+        return new Promise((resolve) => {
+            this.#waiting = resolve
+        })
+    }
+
+    close() {
+        this.rl.close()
+    }
+}
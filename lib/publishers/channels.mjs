@@ -0,0 +1,142 @@
+import { t } from '../i18n.mjs'
+import { log } from '../log.mjs'
+import { studentsPath } from '../paths.mjs'
+import { loadStudents, rosterExists } from '../roster.mjs'
+import { DiscordPublisher } from './discord.mjs'
+import { DiscordThreadsPublisher } from './discord-threads.mjs'
+import { EmailPublisher } from './email.mjs'
+import { FilePublisher } from './file.mjs'
+import { GithubProjectPublisher } from './github-project.mjs'
+import { GitlabPublisher } from './gitlab.mjs'
+import { channelSecret } from './index.mjs'
+import { MatrixPublisher } from './matrix.mjs'
+import { NotionPublisher } from './notion.mjs'
+import { SlackPublisher } from './slack.mjs'
+import { TrelloPublisher } from './trello.mjs'
+import { WebhookPublisher } from './webhook.mjs'
+
+/**
+ * Builds a publisher from a `[[channels]]` table of groupgen.toml, keyed
+ * by its `type`.
+ *
+ * @type {Record<string, (channel: Record<string, any>, config: Record<string, any>) => import('./index.mjs').Publisher>}
+ */
+const FACTORIES = {
+    discord: (channel) => new DiscordPublisher(channelSecret(channel, 'webhook_url')),
+    slack: (channel) => new SlackPublisher(channelSecret(channel, 'webhook_url')),
+    email: (channel) => new EmailPublisher(channel),
+    webhook: (channel) => new WebhookPublisher(channelSecret(channel, 'url'), channel.headers, channelSecret(channel, 'secret', { optional: true })),
+    file: (channel) => new FilePublisher(channel.path),
+    trello: (channel) => new TrelloPublisher(channel.board, channel.api_url),
+    'github-project': (channel) => new GithubProjectPublisher(channel.project, channel.api_url),
+    gitlab: (channel, config) => new GitlabPublisher(channel.namespace, channel.url ?? config.gitlab?.url, {
+        visibility: channel.visibility,
+        accounts: rosterAccounts((student) => student.attributes?.gitlab ?? student.email),
+    }),
+    'discord-threads': (channel) => new DiscordThreadsPublisher(channel.channel, channelSecret(channel, 'token'), {
+        channelName: channel.name,
+        accounts: rosterAccounts((student) => student.attributes?.discord),
+    }),
+    notion: (channel, config) => new NotionPublisher(channel.database, { ...config.notion, ...channel }),
+    matrix: (channel, config) => new MatrixPublisher(channel.room, channel.homeserver ?? config.matrix?.homeserver),
+}
+
+export const CHANNEL_TYPES = Object.keys(FACTORIES)
+
+/**
+ * Account of each student of the roster file on a platform, for the
+ * publishers inviting them.
+ *
+ * @param {(student: import('../roster.mjs').Student) => string | undefined} account
+ * @returns {Map<string, string>}
+ */
+function rosterAccounts(account) {
+    const file = studentsPath()
+    const students = rosterExists(file) ? loadStudents(file) : []
+    return new Map(students.flatMap((student) => account(student) ? [[student.name, account(student)]] : []))
+}
+
+/**
+ * Looks up `[[channels]]` tables of groupgen.toml by name.
+ *
+ * @param {string[]} names
+ * @param {Record<string, any>} config
+ * @returns {Record<string, any>[]}
+ */
+export function channelsByName(names, config) {
+    const configured = config.channels ?? []
+    return names.map((name) => {
+        const channel = configured.find((channel) => channel.name == name)
+        if(channel == null) {
+            throw new Error(t`Canal inconnu : ${name} (canaux de groupgen.toml : ${configured.map((channel) => channel.name).join(', ') || t`aucun`})`)
+        }
+        return channel
+    })
+}
+
+/**
+ * A misconfigured channel yields a publisher that fails when used, so it
+ * is reported with the others instead of preventing them from running.
+ *
+ * @param {Record<string, any>} channel
+ * @param {Record<string, any>} config
+ * @returns {import('./index.mjs').Publisher}
+ */
+export function createPublisher(channel, config) {
+    let publisher
+    try {
+        const factory = FACTORIES[channel.type]
+        if(factory == null) {
+            throw new Error(t`type inconnu "${channel.type}" (${CHANNEL_TYPES.join(', ')})`)
+        }
+        publisher = factory(channel, config)
+    } catch(error) {
+        publisher = { name: channel.type ?? '?', publish: () => Promise.reject(error) }
+    }
+    if(channel.name) {
+        publisher.name = `${channel.name} (${publisher.name})`
+    }
+    return publisher
+}
+
+/**
+ * Publishes `brief` on every publisher, one after the other, without
+ * stopping at the first failure.
+ *
+ * @param {import('./index.mjs').Publisher[]} publishers
+ * @param {import('../model.mjs').BriefRecord} brief
+ * @returns {Promise<{name: string, ok: boolean, detail: string | null}[]>}
+ */
+export async function publishAll(publishers, brief) {
+    const results = []
+    for(const publisher of publishers) {
+        try {
+            const detail = await publisher.publish(brief)
+            results.push({ name: publisher.name, ok: true, detail: detail ?? null })
+        } catch(error) {
+            results.push({ name: publisher.name, ok: false, detail: error.message })
+        }
+    }
+    return results
+}
+
+/**
+ * Prints the outcome of `publishAll`, setting the exit code to 1 when a
+ * channel failed.
+ *
+ * @param {{name: string, ok: boolean, detail: string | null}[]} results
+ */
+export function reportResults(results) {
+    for(const { name, ok, detail } of results) {
+        if(ok) {
+            console.log(detail ? t`${name} : publié (${detail})` : t`${name} : publié`)
+        } else {
+            log.error(t`${name} : échec, ${detail}`)
+        }
+    }
+    const failed = results.filter((result) => !result.ok).length
+    if(failed > 0) {
+        log.error(t`${results.length - failed}/${results.length} canaux publiés`)
+        process.exitCode = 1
+    }
+}
@@ -0,0 +1,92 @@
+import { t } from '../i18n.mjs'
+import { sortNames } from '../names.mjs'
+import { briefTitle, request } from './index.mjs'
+
+const API = 'https://discord.com/api/v10'
+
+/** Private thread, only visible to its members and moderators. */
+const PRIVATE_THREAD = 12
+
+/** Longest inactivity before Discord hides a thread, one week. */
+const AUTO_ARCHIVE_MINUTES = 10080
+
+/**
+ * Opens a private thread per group under a channel of the server, with a
+ * bot token, and adds the members. Threads are recorded in the brief so
+ * that `brief close` can archive or delete them, and so that publishing
+ * again does not open them twice.
+ *
+ * @implements {import('./index.mjs').Publisher}
+ */
+export class DiscordThreadsPublisher {
+    name = t`Discord (fils)`
+
+    /**
+     * @param {string} channel id of the parent text channel
+     * @param {string} token bot token
+     * @param {{channelName?: string, accounts?: Map<string, string>}} [options] channelName:
+     *   `[[channels]]` entry, to find the token again when closing; accounts:
+     *   Discord user id of each student, from the roster
+     */
+    constructor(channel, token, { channelName, accounts = new Map() } = {}) {
+        if(!channel) {
+            throw new Error(t`channel (salon parent) manquant`)
+        }
+        this.channel = channel
+        this.token = token
+        this.channelName = channelName ?? null
+        this.accounts = accounts
+    }
+
+    async publish(brief) {
+        const headers = { authorization: `Bot ${this.token}`, 'content-type': 'application/json' }
+        brief.threads ??= []
+        const unknown = []
+        let opened = 0
+        for(const [index, members] of brief.groups.entries()) {
+            if(brief.threads.some((thread) => thread.group == index && thread.channel == this.channelName)) {
+                continue
+            }
+            const thread = await request(`${API}/channels/${this.channel}/threads`, {
+                method: 'POST',
+                headers,
+                body: JSON.stringify({
+                    name: `Groupe ${index + 1}, ${briefTitle(brief)}`.slice(0, 100),
+                    type: PRIVATE_THREAD,
+                    invitable: false,
+                    auto_archive_duration: AUTO_ARCHIVE_MINUTES,
+                }),
+            })
+            brief.threads.push({ group: index, channel: this.channelName, id: thread.id })
+            opened++
+            for(const name of sortNames(members)) {
+                const user = this.accounts.get(name)
+                if(user == null) {
+                    unknown.push(name)
+                    continue
+                }
+                await request(`${API}/channels/${thread.id}/thread-members/${user}`, { method: 'PUT', headers })
+            }
+        }
+        return unknown.length > 0
+            ? t`${opened} fil(s) ouvert(s), sans identifiant Discord : ${unknown.join(', ')}`
+            : t`${opened} fil(s) ouvert(s)`
+    }
+}
+
+/**
+ * Archives and locks a thread opened by `DiscordThreadsPublisher`, or
+ * deletes it with `remove`.
+ *
+ * @param {string} thread id
+ * @param {string} token bot token
+ * @param {{remove?: boolean}} [options]
+ */
+export async function closeThread(thread, token, { remove = false } = {}) {
+    const headers = { authorization: `Bot ${token}`, 'content-type': 'application/json' }
+    if(remove) {
+        await request(`${API}/channels/${thread}`, { method: 'DELETE', headers })
+    } else {
+        await request(`${API}/channels/${thread}`, { method: 'PATCH', headers, body: JSON.stringify({ archived: true, locked: true }) })
+    }
+}
@@ -0,0 +1,25 @@
+import { announcement, request } from './index.mjs'
+
+/**
+ * Posts the announcement through a Discord channel webhook.
+ *
+ * @implements {import('./index.mjs').Publisher}
+ */
+export class DiscordPublisher {
+    name = 'Discord'
+
+    /**
+     * @param {string} webhookUrl
+     */
+    constructor(webhookUrl) {
+        this.webhookUrl = webhookUrl
+    }
+
+    async publish(brief) {
+        await request(this.webhookUrl, {
+            method: 'POST',
+            headers: { 'content-type': 'application/json' },
+            body: JSON.stringify({ content: announcement(brief).markdown, allowed_mentions: { parse: [] } }),
+        })
+    }
+}
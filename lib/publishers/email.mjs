@@ -0,0 +1,42 @@
+import { t } from '../i18n.mjs'
+import { sendMail } from '../smtp.mjs'
+import { announcement, briefTitle, channelSecret } from './index.mjs'
+
+/**
+ * Emails the announcement through an SMTP server.
+ *
+ * @implements {import('./index.mjs').Publisher}
+ */
+export class EmailPublisher {
+    name = 'Email'
+
+    /**
+     * @param {Record<string, any>} channel `to`, `from`, `smtp_host`,
+     *   `smtp_port`, `smtp_user` and `smtp_password` (or `smtp_password_env`),
+     *   `smtp_plaintext_auth` to allow credentials without TLS
+     */
+    constructor(channel) {
+        this.channel = channel
+    }
+
+    async publish(brief) {
+        const { channel } = this
+        const to = [channel.to].flat().filter(Boolean)
+        if(to.length == 0 || !channel.from || !channel.smtp_host) {
+            throw new Error(t`to, from et smtp_host sont requis pour un canal email`)
+        }
+        await sendMail({
+            host: channel.smtp_host,
+            port: channel.smtp_port,
+            user: channel.smtp_user,
+            password: channel.smtp_user ? channelSecret(channel, 'smtp_password') : undefined,
+            plaintextAuth: channel.smtp_plaintext_auth == true,
+        }, {
+            from: channel.from,
+            to,
+            subject: briefTitle(brief),
+            text: announcement(brief).text,
+        })
+        return t`envoyé à ${to.join(', ')}`
+    }
+}
@@ -0,0 +1,33 @@
+import fs from 'fs'
+import path from 'path'
+import { t } from '../i18n.mjs'
+import { briefDocument } from '../model.mjs'
+import { announcement } from './index.mjs'
+
+/**
+ * Writes the brief to a file, as the versioned JSON document when the path
+ * ends with `.json`, as Markdown otherwise. `{id}` in the path is replaced
+ * by the brief id.
+ *
+ * @implements {import('./index.mjs').Publisher}
+ */
+export class FilePublisher {
+    name = t`Fichier`
+
+    /**
+     * @param {string} pattern
+     */
+    constructor(pattern) {
+        this.pattern = pattern
+    }
+
+    async publish(brief) {
+        const file = this.pattern.replaceAll('{id}', String(brief.id))
+        const content = file.endsWith('.json')
+            ? JSON.stringify(briefDocument(brief), null, 4)
+            : announcement(brief).markdown + '\n'
+        fs.mkdirSync(path.dirname(path.resolve(file)), { recursive: true })
+        fs.writeFileSync(file, content)
+        return file
+    }
+}
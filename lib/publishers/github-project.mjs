@@ -0,0 +1,47 @@
+import { t } from '../i18n.mjs'
+import { briefTitle, request, requireEnv } from './index.mjs'
+
+const API = 'https://api.github.com/graphql'
+
+const ADD_DRAFT = `mutation($project: ID!, $title: String!, $body: String!) {
+    addProjectV2DraftIssue(input: {projectId: $project, title: $title, body: $body}) { projectItem { id } }
+}`
+
+/**
+ * Adds one draft item per group to a GitHub project (v2), using
+ * GITHUB_TOKEN.
+ *
+ * @implements {import('./index.mjs').Publisher}
+ */
+export class GithubProjectPublisher {
+    name = 'GitHub Projects'
+
+    /**
+     * @param {string} project project node id (PVT_...)
+     * @param {string} [api] GraphQL endpoint, that of GitHub Enterprise Server included
+     */
+    constructor(project, api = API) {
+        this.project = project
+        this.api = api
+    }
+
+    async publish(brief) {
+        const headers = { authorization: `Bearer ${requireEnv('GITHUB_TOKEN')}`, 'content-type': 'application/json' }
+        for(const [index, group] of brief.groups.entries()) {
+            const variables = {
+                project: this.project,
+                title: `${briefTitle(brief)}, groupe ${index + 1}`,
+                body: group.map((name) => `- ${name}`).join('\n'),
+            }
+            const result = await request(this.api, {
+                method: 'POST',
+                headers,
+                body: JSON.stringify({ query: ADD_DRAFT, variables }),
+            })
+            if(result.errors?.length) {
+                throw new Error(result.errors.map((error) => error.message).join(', '))
+            }
+        }
+        return t`${brief.groups.length} éléments ajoutés`
+    }
+}
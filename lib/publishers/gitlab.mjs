@@ -0,0 +1,108 @@
+import { t } from '../i18n.mjs'
+import { groupRepositoryName, sortNames } from '../names.mjs'
+import { briefTitle, request, requireEnv } from './index.mjs'
+
+/** Developer role, enough to push to the group's project. */
+const DEVELOPER_ACCESS = 30
+
+/**
+ * Creates one project per group under a GitLab group, with GITLAB_TOKEN,
+ * and adds the members as developers. Projects already created by an
+ * earlier attempt are reused, so publishing again only fills the gaps.
+ * The projects are recorded in the brief's `repositories`.
+ *
+ * @implements {import('./index.mjs').Publisher}
+ */
+export class GitlabPublisher {
+    name = 'GitLab'
+
+    /**
+     * @param {string} namespace full path of the GitLab group (simplon/promo-12)
+     * @param {string} [url] base URL of the instance
+     * @param {{visibility?: string, accounts?: Map<string, string>}} [options] accounts:
+     *   GitLab username or email of each student, from the roster
+     */
+    constructor(namespace, url = 'https://gitlab.com', { visibility = 'private', accounts = new Map() } = {}) {
+        if(!namespace) {
+            throw new Error(t`namespace (groupe GitLab) manquant`)
+        }
+        this.namespace = namespace
+        this.api = `${url.replace(/\/+$/, '')}/api/v4`
+        this.visibility = visibility
+        this.accounts = accounts
+    }
+
+    async publish(brief) {
+        const headers = { 'private-token': requireEnv('GITLAB_TOKEN'), 'content-type': 'application/json' }
+        const group = await request(`${this.api}/groups/${encodeURIComponent(this.namespace)}`, { headers })
+        const repositories = []
+        const unknown = []
+        for(const [index, members] of brief.groups.entries()) {
+            const path = groupRepositoryName(brief.id, index)
+            const project = await this.#findProject(path, headers) ?? await request(`${this.api}/projects`, {
+                method: 'POST',
+                headers,
+                body: JSON.stringify({
+                    name: path,
+                    path,
+                    namespace_id: group.id,
+                    visibility: this.visibility,
+                    description: `${briefTitle(brief)}, groupe ${index + 1} : ${sortNames(members).join(', ')}`,
+                }),
+            })
+            for(const name of members) {
+                const user = await this.#findUser(name, headers)
+                if(user == null) {
+                    unknown.push(name)
+                    continue
+                }
+                await this.#addMember(project.id, user.id, headers)
+            }
+            repositories.push({ group: index, provider: 'gitlab', id: project.id, url: project.web_url })
+        }
+        brief.repositories = repositories
+        return unknown.length > 0
+            ? t`${repositories.length} projets dans ${this.namespace}, comptes introuvables : ${unknown.join(', ')}`
+            : t`${repositories.length} projets dans ${this.namespace}`
+    }
+
+    async #findProject(path, headers) {
+        try {
+            return await request(`${this.api}/projects/${encodeURIComponent(`${this.namespace}/${path}`)}`, { headers })
+        } catch(error) {
+            if(/^HTTP 404 /.test(error.message)) {
+                return null
+            }
+            throw error
+        }
+    }
+
+    /**
+     * The account given in the roster (`attributes.gitlab`, or else the
+     * email), matched on username or public email.
+     */
+    async #findUser(name, headers) {
+        const account = this.accounts.get(name)
+        if(account == null) {
+            return null
+        }
+        const query = account.includes('@') ? `search=${encodeURIComponent(account)}` : `username=${encodeURIComponent(account)}`
+        const [user] = await request(`${this.api}/users?${query}`, { headers })
+        return user ?? null
+    }
+
+    async #addMember(project, user, headers) {
+        try {
+            await request(`${this.api}/projects/${project}/members`, {
+                method: 'POST',
+                headers,
+                body: JSON.stringify({ user_id: user, access_level: DEVELOPER_ACCESS }),
+            })
+        } catch(error) {
+            // Already a member, from an earlier attempt.
+            if(!/^HTTP 409 /.test(error.message)) {
+                throw error
+            }
+        }
+    }
+}
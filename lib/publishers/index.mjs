@@ -0,0 +1,103 @@
+import { escapeHtml } from '../html.mjs'
+import { t } from '../i18n.mjs'
+import { getSecret } from '../keyring.mjs'
+import { log } from '../log.mjs'
+import { sortNames } from '../names.mjs'
+
+/**
+ * Sends a saved brief somewhere outside the tool (boards, chats, wikis).
+ *
+ * @typedef {object} Publisher
+ * @property {string} name label used in reports
+ * @property {(brief: import('../model.mjs').BriefRecord) => Promise<string | void>} publish
+ *   resolves with an optional detail (URL...) once done, rejects on failure
+ */
+
+/**
+ * Title used by publishers for a brief.
+ *
+ * @param {import('../model.mjs').BriefRecord} brief
+ */
+export function briefTitle(brief) {
+    return brief.label ? `Brief n°${brief.id} : ${brief.label}` : `Brief n°${brief.id}`
+}
+
+/**
+ * Announcement for chat channels, in plain text, Markdown and HTML.
+ *
+ * @param {import('../model.mjs').BriefRecord} brief
+ * @returns {{text: string, markdown: string, html: string}}
+ */
+export function announcement(brief) {
+    const title = briefTitle(brief)
+    const groups = brief.groups.map((group, index) => ({ title: `Groupe ${index + 1}`, members: sortNames(group).join(', ') }))
+    return {
+        text: [`${title}`, ...groups.map((group) => `${group.title} : ${group.members}`)].join('\n'),
+        markdown: [`**${title}**`, ...groups.map((group) => `- **${group.title}** : ${group.members}`)].join('\n'),
+        html: `<strong>${escapeHtml(title)}</strong><ul>${groups.map((group) => `<li><strong>${group.title}</strong> : ${escapeHtml(group.members)}</li>`).join('')}</ul>`,
+    }
+}
+
+/**
+ * @param {string} name environment variable holding a secret
+ */
+export function requireEnv(name) {
+    const value = process.env[name]
+    if(!value) {
+        throw new Error(t`Variable d'environnement ${name} manquante`)
+    }
+    return value
+}
+
+/**
+ * Secret value of a channel setting, given either inline (`key`), as the
+ * name of an environment variable (`key_env`) or as the name of a secret
+ * stored with `secrets set` in the system keyring (`key_keyring`).
+ *
+ * @param {Record<string, any>} channel
+ * @param {string} key
+ * @param {{optional?: boolean}} [options] null rather than an error when not set at all
+ */
+export function channelSecret(channel, key, { optional = false } = {}) {
+    if(optional && !channel[key] && !channel[`${key}_env`] && !channel[`${key}_keyring`]) {
+        return null
+    }
+    if(channel[key]) {
+        return channel[key]
+    }
+    if(channel[`${key}_env`]) {
+        return requireEnv(channel[`${key}_env`])
+    }
+    if(channel[`${key}_keyring`]) {
+        const name = channel[`${key}_keyring`]
+        const value = getSecret(name)
+        if(value == null) {
+            throw new Error(t`Secret ${name} absent du trousseau, enregistrez-le avec : secrets set ${name}`)
+        }
+        return value
+    }
+    throw new Error(t`${key} (ou ${key}_env, ${key}_keyring) manquant pour le canal ${channel.name ?? channel.type}`)
+}
+
+/**
+ * `fetch` wrapper turning HTTP errors into exceptions.
+ *
+ * @param {string} url
+ * @param {RequestInit} init
+ */
+export async function request(url, init = {}) {
+    log.debug(`${init.method ?? 'GET'} ${url}`)
+    let response
+    try {
+        response = await fetch(url, { ...init, signal: AbortSignal.timeout(15000) })
+    } catch(error) {
+        throw new Error(t`connexion impossible à ${new URL(url).host} (${error.cause?.code ?? error.message})`)
+    }
+    log.debug(`HTTP ${response.status} ${url}`)
+    if(!response.ok) {
+        const body = await response.text()
+        throw new Error(t`HTTP ${response.status} sur ${new URL(url).host} : ${body.slice(0, 200)}`)
+    }
+    const type = response.headers.get('content-type') ?? ''
+    return type.includes('json') ? response.json() : response.text()
+}
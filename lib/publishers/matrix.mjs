@@ -0,0 +1,37 @@
+import crypto from 'crypto'
+import { announcement, request, requireEnv } from './index.mjs'
+
+/**
+ * Posts the announcement in a Matrix (Element) room, with the access
+ * token from MATRIX_ACCESS_TOKEN. The account must already be in the room.
+ *
+ * @implements {import('./index.mjs').Publisher}
+ */
+export class MatrixPublisher {
+    name = 'Matrix'
+
+    /**
+     * @param {string} room room id (!abc:example.org) or alias (#promo:example.org)
+     * @param {string} homeserver base URL, e.g. https://matrix.example.org
+     */
+    constructor(room, homeserver) {
+        this.room = room
+        this.homeserver = homeserver.replace(/\/+$/, '')
+    }
+
+    async publish(brief) {
+        const headers = { authorization: `Bearer ${requireEnv('MATRIX_ACCESS_TOKEN')}`, 'content-type': 'application/json' }
+        let room = this.room
+        if(room.startsWith('#')) {
+            room = (await request(`${this.homeserver}/_matrix/client/v3/directory/room/${encodeURIComponent(room)}`, { headers })).room_id
+        }
+        const { text, html } = announcement(brief)
+        const transaction = crypto.randomUUID()
+        const event = await request(`${this.homeserver}/_matrix/client/v3/rooms/${encodeURIComponent(room)}/send/m.room.message/${transaction}`, {
+            method: 'PUT',
+            headers,
+            body: JSON.stringify({ msgtype: 'm.text', body: text, format: 'org.matrix.custom.html', formatted_body: html }),
+        })
+        return event.event_id
+    }
+}
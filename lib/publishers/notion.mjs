@@ -0,0 +1,60 @@
+import { briefTitle, request, requireEnv } from './index.mjs'
+
+const API = 'https://api.notion.com/v1'
+
+const DEFAULT_PROPERTIES = {
+    title_property: 'Name',
+    date_property: 'Date',
+    label_property: null,
+}
+
+/**
+ * Appends the brief as a page of a Notion database: title and date as
+ * properties, groups as the page content. Uses NOTION_TOKEN; property
+ * names come from the `[notion]` table of groupgen.toml.
+ *
+ * @implements {import('./index.mjs').Publisher}
+ */
+export class NotionPublisher {
+    name = 'Notion'
+
+    /**
+     * @param {string} database database id
+     * @param {Record<string, any>} [config] `[notion]` table of groupgen.toml
+     */
+    constructor(database, config = {}) {
+        this.database = database
+        this.properties = { ...DEFAULT_PROPERTIES, ...config }
+        this.api = config.api_url ?? API
+    }
+
+    async publish(brief) {
+        const properties = {
+            [this.properties.title_property]: { title: [text(briefTitle(brief))] },
+        }
+        if(brief.date && this.properties.date_property) {
+            properties[this.properties.date_property] = { date: { start: brief.date.slice(0, 10) } }
+        }
+        if(brief.label && this.properties.label_property) {
+            properties[this.properties.label_property] = { rich_text: [text(brief.label)] }
+        }
+        const children = brief.groups.flatMap((group, index) => [
+            { object: 'block', type: 'heading_3', heading_3: { rich_text: [text(`Groupe ${index + 1}`)] } },
+            ...group.map((name) => ({ object: 'block', type: 'bulleted_list_item', bulleted_list_item: { rich_text: [text(name)] } })),
+        ])
+        const page = await request(`${this.api}/pages`, {
+            method: 'POST',
+            headers: {
+                authorization: `Bearer ${requireEnv('NOTION_TOKEN')}`,
+                'content-type': 'application/json',
+                'notion-version': '2022-06-28',
+            },
+            body: JSON.stringify({ parent: { database_id: this.database }, properties, children }),
+        })
+        return page.url
+    }
+}
+
+function text(content) {
+    return { type: 'text', text: { content } }
+}
@@ -0,0 +1,106 @@
+import { saveDb } from '../db.mjs'
+import { t } from '../i18n.mjs'
+import { log } from '../log.mjs'
+import { channelsByName, createPublisher, publishAll, reportResults } from './channels.mjs'
+
+/**
+ * Publishes `brief` on `channels`, reports it and saves the database.
+ * Failed announcements
+ * are kept in the database, so a flaky connection does not lose them:
+ * `publish --retry-pending` sends them again.
+ *
+ * @param {import('../db.mjs').Db} db
+ * @param {import('../model.mjs').BriefRecord} brief
+ * @param {Record<string, any>[]} channels
+ * @param {Record<string, any>} config
+ * @param {{rollback?: import('../model.mjs').Database}} [options] state to restore, instead
+ *   of queueing, when no channel could publish: the brief is then as if never saved
+ */
+export async function announce(db, brief, channels, config, { rollback } = {}) {
+    const results = await publishAll(channels.map((channel) => createPublisher(channel, config)), brief)
+    reportResults(results)
+    if(rollback != null && results.every((result) => !result.ok)) {
+        db.data = rollback
+        saveDb(db)
+        log.error(t`Aucun canal n'a publié le brief n°${brief.id} : son enregistrement est annulé.`)
+        return
+    }
+    const failed = results.flatMap((result, index) => result.ok ? [] : [{ channel: channels[index], error: result.detail }])
+    for(const { channel, error } of failed) {
+        enqueue(db, { brief: brief.id, channel: channel.name ?? channel, queued_at: new Date().toISOString(), attempts: 1, error })
+    }
+    // Also keeps what publishers recorded in the brief, such as repositories.
+    saveDb(db)
+    if(failed.length > 0) {
+        log.error(t`${failed.length} annonce(s) mise(s) en attente, à renvoyer avec : groupgen publish --retry-pending`)
+    }
+}
+
+/**
+ * Queues the announcement of `brief` on `channels` for `at`, to be sent
+ * by the first `publish --retry-pending` run after that time.
+ *
+ * @param {import('../db.mjs').Db} db
+ * @param {import('../model.mjs').BriefRecord} brief
+ * @param {Record<string, any>[]} channels
+ * @param {Date} at
+ */
+export function schedule(db, brief, channels, at) {
+    for(const channel of channels) {
+        enqueue(db, { brief: brief.id, channel: channel.name ?? channel, queued_at: new Date().toISOString(), attempts: 0, error: null, publish_at: at.toISOString() })
+    }
+    saveDb(db)
+}
+
+/**
+ * Adds `entry` to the queue, replacing an older attempt at the same brief
+ * and channel.
+ *
+ * @param {import('../db.mjs').Db} db
+ * @param {import('../model.mjs').PendingPublication} entry
+ */
+function enqueue(db, entry) {
+    const key = JSON.stringify([entry.brief, entry.channel])
+    const pending = (db.data.pending_publications ?? []).filter((other) => JSON.stringify([other.brief, other.channel]) != key)
+    db.data.pending_publications = [...pending, entry]
+}
+
+/**
+ * Sends the queued announcements again, and the scheduled ones whose time
+ * has come. Those failing once more stay queued, as do those scheduled
+ * later; those whose brief no longer exists are dropped.
+ *
+ * @param {import('../db.mjs').Db} db
+ * @param {Record<string, any>} config
+ * @returns {Promise<{name: string, ok: boolean, detail: string | null}[]>}
+ */
+export async function retryPending(db, config) {
+    const pending = db.data.pending_publications ?? []
+    delete db.data.pending_publications
+    const results = []
+    for(const entry of pending) {
+        if(entry.publish_at != null && new Date(entry.publish_at) > new Date()) {
+            enqueue(db, entry)
+            continue
+        }
+        const label = t`${typeof entry.channel == 'string' ? entry.channel : entry.channel.type}, brief n°${entry.brief}`
+        const brief = db.data.briefs.find((brief) => brief.id == entry.brief)
+        if(brief == null) {
+            results.push({ name: label, ok: false, detail: t`brief supprimé, annonce abandonnée` })
+            continue
+        }
+        let result
+        try {
+            const [channel] = typeof entry.channel == 'string' ? channelsByName([entry.channel], config) : [entry.channel]
+            result = (await publishAll([createPublisher(channel, config)], brief))[0]
+            result.name = t`${result.name}, brief n°${entry.brief}`
+        } catch(error) {
+            result = { name: label, ok: false, detail: error.message }
+        }
+        results.push(result)
+        if(!result.ok) {
+            enqueue(db, { ...entry, attempts: entry.attempts + 1, error: result.detail })
+        }
+    }
+    return results
+}
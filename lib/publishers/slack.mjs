@@ -0,0 +1,27 @@
+import { announcement, request } from './index.mjs'
+
+/**
+ * Posts the announcement through a Slack incoming webhook.
+ *
+ * @implements {import('./index.mjs').Publisher}
+ */
+export class SlackPublisher {
+    name = 'Slack'
+
+    /**
+     * @param {string} webhookUrl
+     */
+    constructor(webhookUrl) {
+        this.webhookUrl = webhookUrl
+    }
+
+    async publish(brief) {
+        // Slack mrkdwn uses single asterisks for bold
+        const text = announcement(brief).markdown.replaceAll('**', '*')
+        await request(this.webhookUrl, {
+            method: 'POST',
+            headers: { 'content-type': 'application/json' },
+            body: JSON.stringify({ text }),
+        })
+    }
+}
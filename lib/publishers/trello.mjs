@@ -0,0 +1,38 @@
+import { t } from '../i18n.mjs'
+import { briefTitle, request, requireEnv } from './index.mjs'
+
+const API = 'https://api.trello.com/1'
+
+/**
+ * Adds a list named after the brief to a Trello board, with one card per
+ * group. Credentials come from TRELLO_KEY and TRELLO_TOKEN.
+ *
+ * @implements {import('./index.mjs').Publisher}
+ */
+export class TrelloPublisher {
+    name = 'Trello'
+
+    /**
+     * @param {string} board board id (as in its URL)
+     * @param {string} [api] base URL of the REST API
+     */
+    constructor(board, api = API) {
+        this.board = board
+        this.api = api
+    }
+
+    async publish(brief) {
+        const auth = { key: requireEnv('TRELLO_KEY'), token: requireEnv('TRELLO_TOKEN') }
+        const list = await request(`${this.api}/lists?${new URLSearchParams({ ...auth, idBoard: this.board, name: briefTitle(brief), pos: 'bottom' })}`, { method: 'POST' })
+        for(const [index, group] of brief.groups.entries()) {
+            await request(`${this.api}/cards?${new URLSearchParams({
+                ...auth,
+                idList: list.id,
+                name: `Groupe ${index + 1}`,
+                desc: group.map((name) => `- ${name}`).join('\n'),
+                pos: 'bottom',
+            })}`, { method: 'POST' })
+        }
+        return t`liste « ${list.name} » créée`
+    }
+}
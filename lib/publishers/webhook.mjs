@@ -0,0 +1,76 @@
+import { createHmac, timingSafeEqual } from 'crypto'
+import { briefDocument } from '../model.mjs'
+import { request } from './index.mjs'
+
+export const SIGNATURE_HEADER = 'x-groupgen-signature'
+export const TIMESTAMP_HEADER = 'x-groupgen-timestamp'
+
+/** Age in seconds past which `verifyWebhook` takes a payload for a replay. */
+const DEFAULT_TOLERANCE = 300
+
+/**
+ * Signature of a webhook payload: HMAC-SHA256 of `<timestamp>.<body>`
+ * keyed with the channel's `secret`, hex encoded. The timestamp is part
+ * of it so that a captured request can't be replayed later.
+ *
+ * @param {string} secret
+ * @param {number | string} timestamp Unix time in seconds, as sent in TIMESTAMP_HEADER
+ * @param {string} body raw request body
+ */
+export function signPayload(secret, timestamp, body) {
+    return createHmac('sha256', secret).update(`${timestamp}.${body}`).digest('hex')
+}
+
+/**
+ * Checks a payload received from a `webhook` channel, for receivers
+ * written in Node: the signature must match and the timestamp be at most
+ * `tolerance` seconds away from `now`.
+ *
+ * @param {string} secret
+ * @param {string} body raw request body, before any JSON parsing
+ * @param {Record<string, string | string[] | undefined>} headers with lower-case names, as in `IncomingMessage.headers`
+ * @param {{tolerance?: number, now?: number}} [options] `now` in milliseconds
+ * @returns {boolean}
+ */
+export function verifyWebhook(secret, body, headers, { tolerance = DEFAULT_TOLERANCE, now = Date.now() } = {}) {
+    const timestamp = Number(headers[TIMESTAMP_HEADER])
+    const signature = String(headers[SIGNATURE_HEADER] ?? '')
+    if(!Number.isInteger(timestamp) || Math.abs(now / 1000 - timestamp) > tolerance || !signature.startsWith('sha256=')) {
+        return false
+    }
+    const expected = Buffer.from(signPayload(secret, timestamp, body))
+    const received = Buffer.from(signature.slice('sha256='.length))
+    return expected.length == received.length && timingSafeEqual(expected, received)
+}
+
+/**
+ * POSTs the versioned brief document (see model.mjs) as JSON to any URL,
+ * signed when the channel has a `secret` (see `signPayload`).
+ *
+ * @implements {import('./index.mjs').Publisher}
+ */
+export class WebhookPublisher {
+    name = 'Webhook'
+
+    /**
+     * @param {string} url
+     * @param {Record<string, string>} [headers] extra headers, e.g. authorization
+     * @param {string | null} [secret] key of the HMAC signature, none when null
+     */
+    constructor(url, headers = {}, secret = null) {
+        this.url = url
+        this.headers = headers
+        this.secret = secret
+    }
+
+    async publish(brief) {
+        const body = JSON.stringify(briefDocument(brief))
+        const headers = { ...this.headers, 'content-type': 'application/json' }
+        if(this.secret != null) {
+            const timestamp = Math.floor(Date.now() / 1000)
+            headers[TIMESTAMP_HEADER] = String(timestamp)
+            headers[SIGNATURE_HEADER] = `sha256=${signPayload(this.secret, timestamp, body)}`
+        }
+        await request(this.url, { method: 'POST', headers, body })
+    }
+}
@@ -0,0 +1,40 @@
+import { randomInt } from 'crypto'
+import { t } from './i18n.mjs'
+
+/**
+ * Deterministic PRNG (mulberry32), so that a draw can be replayed from
+ * its seed. Seeds above 2³² are folded into the 32-bit state.
+ *
+ * @param {number} seed non-negative integer, at most Number.MAX_SAFE_INTEGER
+ * @returns {() => number} uniform in [0, 1), like Math.random
+ */
+export function seededRandom(seed) {
+    let state = (seed % 2 ** 32) ^ Math.imul(Math.floor(seed / 2 ** 32), 0x9E3779B9)
+    return () => {
+        state = (state + 0x6D2B79F5) | 0
+        let t = Math.imul(state ^ (state >>> 15), 1 | state)
+        t = (t + Math.imul(t ^ (t >>> 7), 61 | t)) ^ t
+        return ((t ^ (t >>> 14)) >>> 0) / 4294967296
+    }
+}
+
+/**
+ * A fresh seed, for runs where none was asked for.
+ */
+export function randomSeed() {
+    return randomInt(2 ** 32)
+}
+
+/**
+ * Reads a `--seed` value.
+ *
+ * @param {string} value
+ * @returns {number}
+ */
+export function parseSeed(value) {
+    const seed = Number(value)
+    if(!/^\d+$/.test(value) || !Number.isSafeInteger(seed)) {
+        throw new Error(t`Graine invalide : ${value} (entier positif, au plus ${Number.MAX_SAFE_INTEGER})`)
+    }
+    return seed
+}
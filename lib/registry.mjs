@@ -0,0 +1,133 @@
+import fs from 'fs'
+import path from 'path'
+import { writeFileAtomic } from './files.mjs'
+import { t } from './i18n.mjs'
+import { log } from './log.mjs'
+
+const CACHE_TTL_MS = 24 * 3600 * 1000
+
+/**
+ * @typedef {object} StudentRecord
+ * @property {string} name official display name
+ * @property {string | null} email
+ * @property {boolean} active
+ */
+
+/**
+ * Resolves roster names against a source of truth.
+ *
+ * @typedef {object} StudentProvider
+ * @property {(names: string[]) => Promise<StudentRecord[]>} resolve
+ *   one record per input name, in the same order
+ */
+
+/**
+ * Takes the roster at face value. Used when no registry is configured.
+ *
+ * @implements {StudentProvider}
+ */
+export class LocalProvider {
+    async resolve(names) {
+        return names.map((name) => ({ name, email: null, active: true }))
+    }
+}
+
+/**
+ * Looks students up in the center's registry with
+ * `GET <baseUrl>/students/lookup?name=<name>`, which answers
+ * `{"name": "...", "email": "...", "active": true}` or 404.
+ *
+ * Answers are cached on disk for a day. When the registry can't be reached
+ * the cache is used whatever its age, and names never seen before are
+ * taken as-is.
+ *
+ * @implements {StudentProvider}
+ */
+export class HttpRegistryProvider {
+    /**
+     * @param {string} baseUrl
+     * @param {{cacheFile: string, token?: string, timeoutMs?: number}} options
+     */
+    constructor(baseUrl, { cacheFile, token, timeoutMs = 5000 }) {
+        this.baseUrl = baseUrl.replace(/\/+$/, '')
+        this.cacheFile = cacheFile
+        this.token = token
+        this.timeoutMs = timeoutMs
+        /** @type {string[]} */
+        this.warnings = []
+    }
+
+    async resolve(names) {
+        const cache = this.#loadCache()
+        let offline = false
+        const records = []
+        for(const name of names) {
+            const cached = cache[name]
+            if(cached && (offline || Date.now() - Date.parse(cached.fetched_at) < CACHE_TTL_MS)) {
+                records.push(cached.record)
+                continue
+            }
+            if(!offline) {
+                try {
+                    const record = await this.#lookup(name)
+                    cache[name] = { fetched_at: new Date().toISOString(), record }
+                    records.push(record)
+                    continue
+                } catch(error) {
+                    offline = true
+                    this.warnings.push(t`Registre injoignable (${error.message}), utilisation du cache`)
+                }
+            }
+            records.push(cached?.record ?? { name, email: null, active: true })
+        }
+        this.#saveCache(cache)
+        return records
+    }
+
+    async #lookup(name) {
+        const url = `${this.baseUrl}/students/lookup?name=${encodeURIComponent(name)}`
+        const headers = this.token ? { authorization: `Bearer ${this.token}` } : {}
+        const response = await fetch(url, { headers, signal: AbortSignal.timeout(this.timeoutMs) })
+        if(response.status == 404) {
+            this.warnings.push(t`${name} inconnu du registre`)
+            return { name, email: null, active: true }
+        }
+        if(!response.ok) {
+            throw new Error(`HTTP ${response.status}`)
+        }
+        const body = await response.json()
+        return { name: body.name ?? name, email: body.email ?? null, active: body.active ?? true }
+    }
+
+    #loadCache() {
+        return fs.existsSync(this.cacheFile) ? JSON.parse(fs.readFileSync(this.cacheFile)) : {}
+    }
+
+    #saveCache(cache) {
+        fs.mkdirSync(path.dirname(this.cacheFile), { recursive: true })
+        writeFileAtomic(this.cacheFile, JSON.stringify(cache, null, 4))
+    }
+}
+
+/**
+ * Replaces roster names by their official spelling and leaves out
+ * students who are no longer active.
+ *
+ * @param {string[]} names
+ * @param {StudentProvider} provider
+ * @returns {Promise<string[]>}
+ */
+export async function resolveRoster(names, provider) {
+    const records = await provider.resolve(names)
+    for(const warning of provider.warnings ?? []) {
+        log.warn(warning)
+    }
+    return records
+        .filter((record) => {
+            if(!record.active) {
+                log.warn(t`${record.name} n'est plus actif dans le registre, ignoré`)
+            }
+            return record.active
+        })
+        .map((record) => record.name)
+}
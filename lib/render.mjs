@@ -0,0 +1,179 @@
+import { colored, paint, symbols } from './console.mjs'
+import { formatCsv } from './csv.mjs'
+import { formatList, t } from './i18n.mjs'
+import { groupsDocument, matrixDocument } from './model.mjs'
+import { compareNames, pairKey, sortNames } from './names.mjs'
+
+/**
+ * Output formats of `--format`: boxes for people, the others for scripts
+ * and documents.
+ */
+export const FORMATS = ['table', 'json', 'csv', 'markdown']
+
+/**
+ * @param {string[][]} groups
+ * @param {typeof symbols} [chars] character set, the console's by default
+ * @returns {string}
+ */
+export function renderGroups(groups, chars = symbols) {
+    if(chars.linear) {
+        return [
+            groups.length == 1 ? t`1 groupe.` : t`${groups.length} groupes.`,
+            ...groups.map((group, index) => t`Le groupe ${index + 1} réunit ${formatList(sortNames(group))}.`),
+        ].join('\n')
+    }
+    const width = Math.max(20, ...groups.flat().map((name) => name.length + 2))
+    const lines = [`${chars.groups}${t`Liste des groupes :`}`]
+    groups.forEach((group, index) => {
+        const title = ` ${t`Groupe ${index + 1}`} `
+        lines.push(chars.topLeft + chars.horizontal + title + chars.horizontal.repeat(Math.max(0, width - title.length)))
+        for(const people of sortNames(group)) {
+            lines.push(`${chars.vertical} ${people}`)
+        }
+        lines.push(chars.bottomLeft + chars.horizontal.repeat(width + 1))
+    })
+    return lines.join('\n')
+}
+
+/**
+ * @param {string | undefined} format
+ * @returns {string} one of FORMATS
+ */
+export function parseFormat(format = 'table') {
+    if(!FORMATS.includes(format)) {
+        throw new Error(t`--format invalide : ${format} (${FORMATS.join(', ')})`)
+    }
+    return format
+}
+
+/** Formats of the files written with `--output`, by extension. */
+const EXTENSIONS = { '.txt': 'table', '.json': 'json', '.csv': 'csv', '.md': 'markdown', '.markdown': 'markdown' }
+
+/**
+ * @param {string} file
+ * @returns {string | null} one of FORMATS, null for an unknown extension
+ */
+export function formatOfFile(file) {
+    const extension = file.slice(file.lastIndexOf('.')).toLowerCase()
+    return file.includes('.') ? EXTENSIONS[extension] ?? null : null
+}
+
+/**
+ * `groups` in one of FORMATS, members in alphabetical order.
+ *
+ * @param {string[][]} groups
+ * @param {string} format
+ * @returns {string}
+ */
+export function formatGroups(groups, format) {
+    const sorted = groups.map(sortNames)
+    switch(format) {
+        case 'json':
+            return JSON.stringify(groupsDocument(sorted), null, 4)
+        case 'csv': {
+            const width = Math.max(0, ...sorted.map((group) => group.length))
+            const header = ['group_number', ...Array.from({ length: width }, (_, index) => `member_${index + 1}`)]
+            return formatCsv([header, ...sorted.map((group, index) => [index + 1, ...group])]).trimEnd()
+        }
+        case 'markdown':
+            return [
+                t`| Groupe | Membres |`,
+                '| --- | --- |',
+                ...sorted.map((group, index) => `| ${index + 1} | ${group.map((name) => name.replaceAll('|', '\\|')).join(', ')} |`),
+            ].join('\n')
+        default:
+            return renderGroups(groups)
+    }
+}
+
+/**
+ * How many briefs each pair of `roster` spent together, as a grid in the
+ * terminal and in Markdown, one row per student in CSV and the pairs that
+ * met in JSON. Students come in alphabetical order. In colour, the grid
+ * shows pairs that met once in green, twice in yellow and more in red.
+ * Students often absent are marked, as their empty cells don't mean the
+ * matcher kept them apart.
+ *
+ * @param {string[]} roster
+ * @param {Map<string, number>} counts by `pairKey`, see `pairCounts`
+ * @param {string} format one of FORMATS
+ * @param {{chars?: typeof symbols, color?: boolean, lowAttendance?: Map<string, import('./stats.mjs').Attendance>}} [options]
+ *     `chars` and `color` as the console and `--color` decide by default,
+ *     `lowAttendance` as returned by the function of that name
+ * @returns {string}
+ */
+export function formatMatrix(roster, counts, format, { chars = symbols, color = colored, lowAttendance = new Map() } = {}) {
+    const names = [...roster].sort(compareNames)
+    const count = (a, b) => counts.get(pairKey(a, b)) ?? 0
+    const absentees = names.filter((name) => lowAttendance.has(name))
+    const note = absentees.length == 0 ? null : t`* souvent absent(e), ses cases vides tiennent à ses absences : ${absentees.map((name) => {
+        const { present, briefs } = lowAttendance.get(name)
+        return t`${name} (${present} brief(s) sur ${briefs})`
+    }).join(', ')}`
+    switch(format) {
+        case 'json': {
+            const pairs = []
+            names.forEach((a, row) => {
+                for(const b of names.slice(row + 1)) {
+                    if(count(a, b) > 0) {
+                        pairs.push({ students: [a, b], briefs: count(a, b) })
+                    }
+                }
+            })
+            const attendance = absentees.map((name) => ({ student: name, ...lowAttendance.get(name) }))
+            return JSON.stringify(matrixDocument(names, pairs, attendance), null, 4)
+        }
+        case 'csv':
+            return formatCsv([
+                ['name', ...names],
+                ...names.map((a) => [a, ...names.map((b) => a == b ? '' : count(a, b))]),
+            ]).trimEnd()
+        case 'markdown': {
+            const escape = (name) => `${name.replaceAll('|', '\\|')}${lowAttendance.has(name) ? ' \\*' : ''}`
+            return [
+                `| | ${names.map(escape).join(' | ')} |`,
+                `| --- |${' --- |'.repeat(names.length)}`,
+                ...names.map((a) => `| ${escape(a)} | ${names.map((b) => a == b ? '-' : count(a, b)).join(' | ')} |`),
+                ...(note != null ? ['', `\\${note}`] : []),
+            ].join('\n')
+        }
+        default:
+            return renderMatrix(names, count, { chars, color, lowAttendance, note })
+    }
+}
+
+/**
+ * @param {string[]} names
+ * @param {(a: string, b: string) => number} count
+ * @param {{chars: typeof symbols, color: boolean, lowAttendance: Map<string, import('./stats.mjs').Attendance>, note: string | null}} options
+ */
+function renderMatrix(names, count, { chars, color, lowAttendance, note }) {
+    if(chars.linear) {
+        return names.map((a) => {
+            const partners = names.filter((b) => b != a && count(a, b) > 0).map((b) => count(a, b) == 1 ? t`${b} (1 fois)` : t`${b} (${count(a, b)} fois)`)
+            const sentence = partners.length == 0
+                ? t`${a} n'a encore travaillé avec personne.`
+                : t`${a} a travaillé avec ${formatList(partners)}.`
+            if(!lowAttendance.has(a)) {
+                return sentence
+            }
+            const { present, briefs } = lowAttendance.get(a)
+            return `${sentence} ${t`Présent(e) à ${present} brief(s) sur ${briefs} seulement.`}`
+        }).join('\n')
+    }
+    const label = Math.max(...names.map((name) => name.length + (lowAttendance.has(name) ? 2 : 0)))
+    const margin = String(names.length).length
+    const width = Math.max(String(names.length).length, ...names.flatMap((a) => names.map((b) => String(count(a, b)).length))) + 1
+    return [
+        `${' '.repeat(margin + label + 2)}${names.map((_, index) => String(index + 1).padStart(width)).join('')}`,
+        ...names.map((a, row) => {
+            const cells = names.map((b) => {
+                const met = a == b ? 0 : count(a, b)
+                const cell = (a == b ? '-' : met == 0 ? chars.never : String(met)).padStart(width)
+                return met == 0 ? cell : paint(cell, met == 1 ? 'green' : met == 2 ? 'yellow' : 'red', color)
+            })
+            return `${String(row + 1).padStart(margin)} ${`${a}${lowAttendance.has(a) ? ' *' : ''}`.padEnd(label)} ${cells.join('')}`
+        }),
+        ...(note != null ? [note] : []),
+    ].join('\n')
+}
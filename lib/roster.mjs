@@ -0,0 +1,141 @@
+import fs from 'fs'
+import { openDb } from './db.mjs'
+import { InputError } from './errors.mjs'
+import { t } from './i18n.mjs'
+import { loadLdapRoster } from './ldap.mjs'
+
+/**
+ * A roster entry. Plain names in students.json become records with only
+ * a `name`.
+ *
+ * @typedef {object} Student
+ * @property {string} name
+ * @property {string} [email]
+ * @property {string} [avatar] image URL
+ * @property {Record<string, string>} [attributes] free-form details (track, level...)
+ */
+
+/**
+ * @param {string} source JSON roster file, an `ldap://` URL designating
+ *   a directory group, or `cohort:<name>` for the roster stored with a
+ *   cohort of the database
+ * @param {Record<string, any>} [config] parsed groupgen.toml
+ * @returns {string[]}
+ */
+export function loadRoster(source, config = {}) {
+    return loadStudents(source, config).map((student) => student.name)
+}
+
+/**
+ * Like `loadRoster`, keeping the details of structured entries.
+ *
+ * @param {string} source
+ * @param {Record<string, any>} [config]
+ * @returns {Student[]}
+ */
+export function loadStudents(source, config = {}) {
+    if(/^ldaps?:\/\//.test(source)) {
+        return loadLdapRoster(source, config.ldap).map((name) => ({ name }))
+    }
+    if(source.startsWith('cohort:')) {
+        const cohort = source.slice('cohort:'.length)
+        return parseStudents(JSON.stringify(openDb(undefined, { cohort }).data.students ?? []), t`apprenants de la cohorte ${cohort}`)
+    }
+    if(!fs.existsSync(source)) {
+        throw new InputError(t`Liste des apprenants introuvable : ${source} (groupgen init pour démarrer)`)
+    }
+    return parseStudents(fs.readFileSync(source, 'utf8'), source)
+}
+
+/**
+ * Whether `source` designates a roster, files being looked for on disk.
+ *
+ * @param {string} source
+ */
+export function rosterExists(source) {
+    return /^(ldaps?:\/\/|cohort:)/.test(source) || fs.existsSync(source)
+}
+
+/**
+ * Whether `source` is a file that fixes can be written back to.
+ *
+ * @param {string} source
+ */
+export function isRosterFile(source) {
+    return !/^(ldaps?:\/\/|cohort:)/.test(source)
+}
+
+/**
+ * @param {string} text JSON roster
+ * @param {string} [source] file name for error messages
+ * @returns {string[]}
+ */
+export function parseRoster(text, source) {
+    return parseStudents(text, source).map((student) => student.name)
+}
+
+/**
+ * Reads a JSON array whose entries are either names or objects with at
+ * least a `name`.
+ *
+ * @param {string} text
+ * @param {string} [source] file name for error messages
+ * @returns {Student[]}
+ */
+export function parseStudents(text, source = t`liste des apprenants`) {
+    let roster
+    try {
+        roster = JSON.parse(text)
+    } catch(error) {
+        throw new InputError(t`${source} : JSON invalide (${error.message})`)
+    }
+    if(!Array.isArray(roster)) {
+        throw new InputError(t`${source} : un tableau de noms est attendu`)
+    }
+    const students = roster.map((entry, index) => {
+        const student = typeof entry == 'string' ? { name: entry } : entry
+        if(student == null || typeof student != 'object' || typeof student.name != 'string' || student.name.trim() == '') {
+            throw new InputError(t`${source} : entrée n°${index + 1} invalide (${JSON.stringify(entry)}), un nom est attendu`)
+        }
+        for(const field of ['email', 'avatar']) {
+            if(student[field] != null && typeof student[field] != 'string') {
+                throw new InputError(t`${source} : ${field} de ${student.name} doit être une chaîne`)
+            }
+        }
+        return { ...student, name: student.name.trim() }
+    })
+    const names = students.map((student) => student.name)
+    const duplicate = names.find((name, index) => names.indexOf(name) != index)
+    if(duplicate != null) {
+        throw new InputError(t`${source} : ${duplicate} apparaît plusieurs fois`)
+    }
+    return students
+}
+
+/**
+ * Splits a comma or newline separated list typed on the command line.
+ *
+ * @param {string} list
+ * @returns {string[]}
+ */
+export function parseNameList(list) {
+    return list.split(/[,\n]/).map((name) => name.trim()).filter((name) => name.length > 0)
+}
+
+/**
+ * Empty rosters are an error; a single student is reported but leaves
+ * nothing to group or save.
+ *
+ * @param {string[]} names
+ * @returns {boolean} whether groups can be formed
+ */
+export function isGroupable(names) {
+    if(names.length == 0) {
+        throw new InputError(t`La liste des apprenants est vide, aucun groupe à former`)
+    }
+    if(names.length == 1) {
+        console.log(t`Un seul apprenant (${names[0]}), aucun groupe à former. Rien n'est enregistré.`)
+        return false
+    }
+    return true
+}
@@ -0,0 +1,29 @@
+import fs from 'fs'
+
+/**
+ * Plain-text archive of one run, written with `--log-file`. It records
+ * everything needed to understand a brief without the stored history.
+ */
+export class RunLog {
+    /** @type {string[]} */
+    #lines = []
+
+    /**
+     * @param {string} title
+     * @param {string | string[]} [content]
+     */
+    section(title, content = []) {
+        this.#lines.push(`== ${title} ==`)
+        for(const line of [content].flat()) {
+            this.#lines.push(line)
+        }
+        this.#lines.push('')
+    }
+
+    /**
+     * @param {string} file
+     */
+    write(file) {
+        fs.appendFileSync(file, this.#lines.join('\n') + '\n')
+    }
+}
@@ -0,0 +1,74 @@
+import crypto from 'crypto'
+import fs from 'fs'
+import path from 'path'
+import { writeFileAtomic } from './files.mjs'
+import { t } from './i18n.mjs'
+import { sessionsPath } from './paths.mjs'
+
+const RETENTION_DAYS = 30
+
+/**
+ * @typedef {object} Session
+ * @property {string} token
+ * @property {string} date
+ * @property {string[][]} groups
+ */
+
+/**
+ * @param {string} dbFile
+ * @returns {Session[]}
+ */
+function loadSessions(dbFile) {
+    const file = sessionsPath(dbFile)
+    return fs.existsSync(file) ? JSON.parse(fs.readFileSync(file)) : []
+}
+
+/**
+ * @param {string} dbFile
+ * @param {Session[]} sessions
+ */
+function saveSessions(dbFile, sessions) {
+    const file = sessionsPath(dbFile)
+    fs.mkdirSync(path.dirname(file), { recursive: true })
+    writeFileAtomic(file, JSON.stringify(sessions, null, 4))
+}
+
+/**
+ * Keeps an unsaved ad-hoc run aside so it can be adopted into the history
+ * later. Sessions older than RETENTION_DAYS are forgotten.
+ *
+ * @param {string} dbFile
+ * @param {string[][]} groups
+ * @returns {string} token identifying the session
+ */
+export function stashSession(dbFile, groups) {
+    const limit = Date.now() - RETENTION_DAYS * 24 * 3600 * 1000
+    const sessions = loadSessions(dbFile).filter((session) => Date.parse(session.date) >= limit)
+    const token = crypto.randomBytes(4).toString('hex')
+    sessions.push({ token, date: new Date().toISOString(), groups })
+    saveSessions(dbFile, sessions)
+    return token
+}
+
+/**
+ * @param {string} dbFile
+ * @param {string} token
+ * @returns {Session}
+ */
+export function findSession(dbFile, token) {
+    const session = loadSessions(dbFile).find((session) => session.token == token)
+    if(session == null) {
+        throw new Error(t`Session ${token} introuvable (expirée après ${RETENTION_DAYS} jours ?)`)
+    }
+    return session
+}
+
+/**
+ * Forgets the session matching `token`, once it is safely in the history.
+ *
+ * @param {string} dbFile
+ * @param {string} token
+ */
+export function dropSession(dbFile, token) {
+    saveSessions(dbFile, loadSessions(dbFile).filter((session) => session.token != token))
+}
@@ -0,0 +1,107 @@
+import fs from 'fs'
+import path from 'path'
+import { CONFIG_FILE, configPath } from './config.mjs'
+import { t } from './i18n.mjs'
+import { parseGroupSize } from './matcher.mjs'
+import { dbPath, studentsPath } from './paths.mjs'
+import { Prompter } from './prompt.mjs'
+
+/**
+ * @param {string} value
+ */
+function tomlString(value) {
+    return JSON.stringify(value)
+}
+
+/**
+ * groupgen.toml written by `init` and the first-run wizard, with the most
+ * common settings commented out.
+ *
+ * @param {{db?: string, students?: string, groupSize?: number}} [settings] paths relative to the file
+ */
+export function configTemplate({ db = 'db.json', students = 'students.json', groupSize = 2 } = {}) {
+    return `# Configuration de groupgen, toutes les options sont décrites dans le README.
+
+[defaults]
+db = ${tomlString(db)}
+students = ${tomlString(students)}
+${groupSize == 2 ? '# group_size = 3' : `group_size = ${groupSize}`}
+# iterations = 10000
+# tie_break = "new-pairs"      # none, new-pairs, churn ou alphabetical
+# locale = "fr-FR"
+# lang = "en"                  # messages en anglais
+
+# Contraintes, aussi gérables avec : groupgen constraints add
+# [[constraints]]
+# type = "never"               # ou "together"
+# students = ["Alice Martin", "Bob Durand"]
+`
+}
+
+/**
+ * Nothing set up yet: no configuration, roster or history where the tool
+ * looks for them.
+ */
+export function isFirstRun() {
+    return !fs.existsSync(configPath()) && !fs.existsSync(studentsPath()) && !fs.existsSync(dbPath())
+}
+
+/**
+ * Asks for the roster, the group size and where to keep the history,
+ * then writes groupgen.toml in `dir`. A roster file that does not exist
+ * yet is filled with names typed one per line.
+ *
+ * @param {string} dir
+ */
+export async function runWizard(dir) {
+    const file = path.join(dir, CONFIG_FILE)
+    if(fs.existsSync(file)) {
+        throw new Error(t`${file} existe déjà, modifiez-le ou supprimez-le avant de relancer l'assistant`)
+    }
+    const prompter = new Prompter()
+    try {
+        console.log(t`Quelques questions pour démarrer, Entrée garde la valeur entre crochets.`)
+        console.log(t`Les chemins sont relatifs à ${path.resolve(dir)}.`)
+        const ask = async (question, fallback) => {
+            const answer = await prompter.ask(`${question} [${fallback}] : `)
+            if(answer == null) {
+                throw new Error(t`Configuration abandonnée`)
+            }
+            return answer.trim() || fallback
+        }
+
+        const students = await ask(t`Liste des apprenants (fichier JSON ou URL ldap://)`, 'students.json')
+        const rosterFile = path.join(dir, students)
+        if(!/^ldaps?:\/\//.test(students) && !fs.existsSync(rosterFile)) {
+            console.log(t`${students} n'existe pas encore : tapez les noms, un par ligne, puis une ligne vide.`)
+            const names = []
+            for(;;) {
+                const line = await prompter.ask('  ')
+                if(line == null || line.trim() == '') {
+                    break
+                }
+                names.push(line.trim())
+            }
+            fs.mkdirSync(path.dirname(rosterFile), { recursive: true })
+            fs.writeFileSync(rosterFile, `${JSON.stringify(names, null, 4)}\n`)
+            console.log(t`${names.length} apprenant(s) enregistré(s) dans ${rosterFile}.`)
+        }
+
+        let groupSize = null
+        while(groupSize == null) {
+            try {
+                groupSize = parseGroupSize(await ask(t`Taille des groupes`, '2'))
+            } catch(error) {
+                console.log(error.message)
+            }
+        }
+
+        const db = await ask(t`Fichier de l'historique`, 'db.json')
+
+        fs.mkdirSync(dir, { recursive: true })
+        fs.writeFileSync(file, configTemplate({ db, students, groupSize }))
+        console.log(t`Configuration écrite dans ${file}.`)
+    } finally {
+        prompter.close()
+    }
+}
@@ -0,0 +1,16 @@
+/**
+ * First Ctrl-C aborts the returned signal so long operations can stop
+ * cleanly, a second one quits right away.
+ *
+ * @returns {AbortSignal}
+ */
+export function interruptSignal() {
+    const controller = new AbortController()
+    process.on('SIGINT', () => {
+        if(controller.signal.aborted) {
+            process.exit(130)
+        }
+        controller.abort()
+    })
+    return controller.signal
+}
@@ -0,0 +1,50 @@
+import { generateGroups } from './matcher.mjs'
+import { cohortMetrics } from './stats.mjs'
+
+/**
+ * A way of running the matcher over a semester: how many past briefs
+ * count as "already seen" pairs, and how many candidates are drawn.
+ *
+ * @typedef {object} Policy
+ * @property {string} name
+ * @property {number} window past briefs whose pairs are avoided
+ * @property {number} [iterations]
+ */
+
+/** @type {Policy[]} */
+export const POLICIES = [
+    { name: 'dernier brief', window: 1 },
+    { name: '3 derniers briefs', window: 3 },
+    { name: 'tout l\'historique', window: Infinity },
+    { name: 'dernier brief, 100 tirages', window: 1, iterations: 100 },
+]
+
+/**
+ * @typedef {object} SimulationResult
+ * @property {Policy} policy
+ * @property {number[]} coverage share of all possible pairs met at least once, after each brief
+ * @property {number} repeats pairs formed again after having already worked together
+ * @property {number} minPartners fewest distinct partners met by a student
+ * @property {number} maxPartners most distinct partners met by a student
+ */
+
+/**
+ * Runs `briefs` successive generations of `students` under `policy`.
+ *
+ * @param {string[]} students
+ * @param {number} briefs
+ * @param {Policy} policy
+ * @param {{iterations?: number}} [options] default candidates per brief
+ * @returns {Promise<SimulationResult>}
+ */
+export async function simulatePolicy(students, briefs, policy, { iterations } = {}) {
+    /** @type {string[][][]} */
+    const history = []
+    for(let brief = 0; brief < briefs; brief++) {
+        const window = history.slice(Math.max(0, history.length - policy.window)).flat()
+        const { groups } = await generateGroups(students, window, { iterations: policy.iterations ?? iterations })
+        history.push(groups)
+    }
+    const { coverage, repeats, minPartners, maxPartners } = cohortMetrics(history)
+    return { policy, coverage, repeats, minPartners, maxPartners }
+}
@@ -0,0 +1,92 @@
+import fs from 'fs'
+import { formatCsv, parseCsv } from './csv.mjs'
+import { InputError } from './errors.mjs'
+import { t } from './i18n.mjs'
+
+/**
+ * Level of each student in each skill, as kept by trainers in a CSV file
+ * with a `name` column followed by one column per skill:
+ *
+ *     name,HTML,SQL,Git
+ *     Alice,3,1,2
+ *
+ * @typedef {Map<string, Map<string, number>>} Competencies
+ */
+
+/**
+ * @param {string} text
+ * @param {string} [source]
+ * @returns {Competencies}
+ */
+export function parseCompetencies(text, source = 'compétences') {
+    const [header, ...rows] = parseCsv(text, source)
+    if(header == null || header[0].trim().toLowerCase() != 'name') {
+        throw new InputError(t`${source} : la première colonne doit être "name"`)
+    }
+    const skills = header.slice(1).map((skill) => skill.trim())
+    const competencies = new Map()
+    for(const [index, [name = '', ...levels]] of rows.entries()) {
+        const student = new Map()
+        skills.forEach((skill, column) => {
+            const value = (levels[column] ?? '').trim()
+            if(value == '') {
+                return
+            }
+            const level = Number(value.replace(',', '.'))
+            if(!Number.isFinite(level)) {
+                throw new InputError(t`${source}, ligne ${index + 2} : niveau "${value}" invalide pour ${skill}`)
+            }
+            student.set(skill, level)
+        })
+        competencies.set(name.trim(), student)
+    }
+    return competencies
+}
+
+/**
+ * @param {string} file
+ * @returns {Competencies}
+ */
+export function loadCompetencies(file) {
+    return parseCompetencies(fs.readFileSync(file, 'utf8'), file)
+}
+
+/**
+ * @param {Competencies} competencies
+ * @returns {string} CSV readable by `parseCompetencies`
+ */
+export function formatCompetencies(competencies) {
+    const skills = [...new Set([...competencies.values()].flatMap((levels) => [...levels.keys()]))]
+    return formatCsv([
+        ['name', ...skills],
+        ...[...competencies].map(([name, levels]) => [name, ...skills.map((skill) => levels.get(skill))]),
+    ])
+}
+
+/**
+ * Required skills each student masters.
+ *
+ * @param {Competencies} competencies
+ * @param {string[]} required
+ * @param {number} minLevel
+ * @returns {Map<string, string[]>}
+ */
+export function masteredSkills(competencies, required, minLevel) {
+    const fold = (skill) => skill.toLowerCase()
+    return new Map([...competencies].map(([name, levels]) => {
+        const mastered = [...levels].filter(([, level]) => level >= minLevel).map(([skill]) => fold(skill))
+        return [name, required.filter((skill) => mastered.includes(fold(skill)))]
+    }))
+}
+
+/**
+ * Required skills no member of `group` masters.
+ *
+ * @param {string[]} group
+ * @param {string[]} required
+ * @param {Map<string, string[]>} mastered from `masteredSkills`
+ */
+export function missingSkills(group, required, mastered) {
+    const covered = new Set(group.flatMap((name) => mastered.get(name) ?? []))
+    return required.filter((skill) => !covered.has(skill))
+}
@@ -0,0 +1,51 @@
+/**
+ * @typedef {import('./model.mjs').BriefRecord} BriefRecord
+ */
+
+/**
+ * Relative position of each student's past presentations, 0 for the first
+ * slot of the day and 1 for the last, averaged over the briefs that had
+ * slots assigned.
+ *
+ * @param {BriefRecord[]} briefs
+ * @returns {Map<string, number>}
+ */
+export function averageSlotPositions(briefs) {
+    const totals = new Map()
+    for(const brief of briefs) {
+        if(brief.slots == null || brief.slots.names.length < 2) {
+            continue
+        }
+        const last = brief.slots.names.length - 1
+        brief.groups.forEach((group, index) => {
+            const position = brief.slots.names.indexOf(brief.slots.assignment[index]) / last
+            for(const people of group) {
+                const [sum, count] = totals.get(people) ?? [0, 0]
+                totals.set(people, [sum + position, count + 1])
+            }
+        })
+    }
+    return new Map([...totals].map(([people, [sum, count]]) => [people, sum / count]))
+}
+
+/**
+ * Spreads groups evenly over `slots`, slot sizes differing by one at
+ * most, and sending the groups whose members
+ * presented latest in the past to the earliest slots.
+ *
+ * @param {string[][]} groups
+ * @param {string[]} slots in chronological order
+ * @param {Map<string, number>} history from `averageSlotPositions`
+ * @returns {string[]} slot of each group, aligned with `groups`
+ */
+export function assignSlots(groups, slots, history) {
+    const lateness = (group) => group.reduce((sum, people) => sum + (history.get(people) ?? 0.5), 0) / group.length
+    const order = groups
+        .map((group, index) => ({ index, lateness: lateness(group) }))
+        .sort((a, b) => b.lateness - a.lateness)
+    const assignment = new Array(groups.length)
+    order.forEach(({ index }, rank) => {
+        assignment[index] = slots[Math.floor(rank * slots.length / groups.length)]
+    })
+    return assignment
+}
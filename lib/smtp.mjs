@@ -0,0 +1,157 @@
+import crypto from 'crypto'
+import net from 'net'
+import os from 'os'
+import tls from 'tls'
+import { t } from './i18n.mjs'
+
+/**
+ * @typedef {object} Mail
+ * @property {string} from
+ * @property {string[]} to
+ * @property {string} subject
+ * @property {string} text
+ * @property {{filename: string, contentType: string, content: string}[]} [attachments]
+ */
+
+/**
+ * @typedef {object} SmtpOptions
+ * @property {string} host
+ * @property {number} [port] 465 uses implicit TLS, other ports STARTTLS,
+ *   required before authenticating
+ * @property {string} [user]
+ * @property {string} [password]
+ * @property {boolean} [plaintextAuth] allows sending `user` and `password`
+ *   over a connection left unencrypted, for a relay on a trusted network
+ */
+
+/**
+ * Minimal SMTP client: (START)TLS, AUTH PLAIN and a single UTF-8 message,
+ * optionally with attachments. Credentials only go over TLS: a server that
+ * doesn't offer STARTTLS, or a reply stripped of it on the way, fails the
+ * sending rather than the password going out in clear.
+ *
+ * @param {SmtpOptions} options
+ * @param {Mail} mail
+ */
+export async function sendMail({ host, port = 587, user, password, plaintextAuth = false }, mail) {
+    let socket = port == 465
+        ? tls.connect({ host, port, servername: host })
+        : net.connect({ host, port })
+    socket.setTimeout(15000, () => socket.destroy(new Error(t`délai SMTP dépassé`)))
+    let reader = replies(socket)
+    try {
+        await expect(reader, 220)
+        let features = await command(socket, reader, `EHLO ${os.hostname()}`, 250)
+        if(!(socket instanceof tls.TLSSocket) && /STARTTLS/i.test(features)) {
+            await command(socket, reader, 'STARTTLS', 220)
+            socket = tls.connect({ socket, servername: host })
+            reader = replies(socket)
+            features = await command(socket, reader, `EHLO ${os.hostname()}`, 250)
+        }
+        if(user) {
+            if(!(socket instanceof tls.TLSSocket) && !plaintextAuth) {
+                throw new Error(t`${host} ne propose pas STARTTLS, identifiants SMTP non envoyés en clair (smtp_plaintext_auth = true pour l'accepter)`)
+            }
+            const token = Buffer.from(`\u0000${user}\u0000${password ?? ''}`).toString('base64')
+            await command(socket, reader, `AUTH PLAIN ${token}`, 235)
+        }
+        await command(socket, reader, `MAIL FROM:<${mail.from}>`, 250)
+        for(const recipient of mail.to) {
+            await command(socket, reader, `RCPT TO:<${recipient}>`, 250)
+        }
+        await command(socket, reader, 'DATA', 354)
+        await command(socket, reader, `${formatMessage(mail).replace(/^\./gm, '..')}\r\n.`, 250)
+        await command(socket, reader, 'QUIT', 221)
+    } finally {
+        socket.end()
+    }
+}
+
+/**
+ * @param {Mail} mail
+ */
+export function formatMessage({ from, to, subject, text, attachments = [] }) {
+    const headers = [
+        `From: ${from}`,
+        `To: ${to.join(', ')}`,
+        `Subject: =?UTF-8?B?${Buffer.from(subject).toString('base64')}?=`,
+        `Date: ${new Date().toUTCString()}`,
+        'MIME-Version: 1.0',
+    ]
+    const body = (content) => Buffer.from(content).toString('base64').replace(/.{76}/g, '$&\r\n')
+    const textPart = ['Content-Type: text/plain; charset=utf-8', 'Content-Transfer-Encoding: base64', '', body(text)]
+    if(attachments.length == 0) {
+        return [...headers, ...textPart].join('\r\n')
+    }
+    const boundary = `groupgen-${crypto.randomUUID()}`
+    const parts = [
+        textPart,
+        ...attachments.map((attachment) => [
+            `Content-Type: ${attachment.contentType}; name="${attachment.filename}"`,
+            `Content-Disposition: attachment; filename="${attachment.filename}"`,
+            'Content-Transfer-Encoding: base64',
+            '',
+            body(attachment.content),
+        ]),
+    ]
+    return [
+        ...headers,
+        `Content-Type: multipart/mixed; boundary="${boundary}"`,
+        '',
+        ...parts.flatMap((part) => [`--${boundary}`, ...part]),
+        `--${boundary}--`,
+    ].join('\r\n')
+}
+
+/**
+ * Yields complete (possibly multi-line) SMTP replies.
+ *
+ * @param {net.Socket} socket
+ */
+function replies(socket) {
+    let buffer = ''
+    const queue = []
+    let waiting = null
+    let failure = null
+    const settle = () => {
+        let match
+        while((match = buffer.match(/^(?:\d{3}-.*\r\n)*\d{3}(?: .*)?\r\n/))) {
+            buffer = buffer.slice(match[0].length)
+            queue.push(match[0])
+        }
+        if(waiting && (queue.length > 0 || failure)) {
+            const { resolve, reject } = waiting
+            waiting = null
+            failure && queue.length == 0 ? reject(failure) : resolve(queue.shift())
+        }
+    }
+    socket.on('data', (chunk) => {
+        buffer += chunk.toString('utf8')
+        settle()
+    })
+    socket.on('error', (error) => {
+        failure = error
+        settle()
+    })
+    socket.on('close', () => {
+        failure ??= new Error(t`connexion SMTP fermée`)
+        settle()
+    })
+    return () => new Promise((resolve, reject) => {
+        waiting = { resolve, reject }
+        settle()
+    })
+}
+
+async function expect(reader, code) {
+    const reply = await reader()
+    if(!reply.startsWith(String(code))) {
+        throw new Error(t`réponse SMTP inattendue : ${reply.trim()}`)
+    }
+    return reply
+}
+
+function command(socket, reader, line, code) {
+    socket.write(`${line}\r\n`)
+    return expect(reader, code)
+}
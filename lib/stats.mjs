@@ -0,0 +1,295 @@
+import { generateGroups } from './matcher.mjs'
+import { pairKey } from './names.mjs'
+
+/**
+ * @typedef {object} StudentStats
+ * @property {number} briefs briefs the student took part in
+ * @property {number} partners distinct partners met
+ * @property {number} repeats times they were grouped with someone already met
+ * @property {string | null} closest partner met most often
+ * @property {number} closestCount
+ */
+
+/**
+ * Counts, for each student, how often they were grouped again with
+ * someone they had already worked with.
+ *
+ * @param {string[][][]} history groups of each brief, oldest first
+ * @returns {Map<string, StudentStats>}
+ */
+export function repeatStats(history) {
+    /** @type {Map<string, Map<string, number>>} */
+    const met = new Map()
+    const stats = new Map()
+    const entry = (name) => {
+        if(!stats.has(name)) {
+            stats.set(name, { briefs: 0, partners: 0, repeats: 0, closest: null, closestCount: 0 })
+            met.set(name, new Map())
+        }
+        return stats.get(name)
+    }
+    for(const groups of history) {
+        for(const group of groups) {
+            for(const name of group) {
+                const student = entry(name)
+                const seen = met.get(name)
+                student.briefs++
+                for(const other of group) {
+                    if(other == name) {
+                        continue
+                    }
+                    const count = (seen.get(other) ?? 0) + 1
+                    seen.set(other, count)
+                    if(count > 1) {
+                        student.repeats++
+                    } else {
+                        student.partners++
+                    }
+                    if(count > student.closestCount) {
+                        student.closest = other
+                        student.closestCount = count
+                    }
+                }
+            }
+        }
+    }
+    return stats
+}
+
+/**
+ * How many briefs each pair of students spent in the same group.
+ *
+ * @param {string[][][]} history groups of each brief
+ * @returns {Map<string, number>} counts by `pairKey`, pairs never grouped left out
+ */
+export function pairCounts(history) {
+    const counts = new Map()
+    for(const group of history.flat()) {
+        for(let i = 0; i < group.length; i++) {
+            for(let j = i + 1; j < group.length; j++) {
+                const key = pairKey(group[i], group[j])
+                counts.set(key, (counts.get(key) ?? 0) + 1)
+            }
+        }
+    }
+    return counts
+}
+
+/**
+ * Below this share of briefs attended, a student's empty cells in the
+ * matrix say more about their absences than about the matcher.
+ */
+export const LOW_ATTENDANCE = 0.75
+
+/**
+ * @typedef {object} Attendance
+ * @property {number} present briefs the student was in a group for
+ * @property {number} briefs those plus the briefs they missed
+ */
+
+/**
+ * Attendance of each student from the absences recorded in the history:
+ * left out of a brief with `generate --exclude`, or removed from it with
+ * `drop`. Briefs from before a student joined don't count against them.
+ *
+ * @param {import('./model.mjs').BriefRecord[]} briefs
+ * @returns {Map<string, Attendance>}
+ */
+export function attendance(briefs) {
+    const students = new Map()
+    const entry = (name) => {
+        if(!students.has(name)) {
+            students.set(name, { present: 0, briefs: 0 })
+        }
+        return students.get(name)
+    }
+    for(const brief of briefs) {
+        const present = new Set(brief.groups.flat())
+        const dropped = (brief.amendments ?? []).filter((amendment) => amendment.action == 'drop').map((amendment) => amendment.student)
+        const absent = new Set([...brief.context?.excluded ?? [], ...dropped].filter((name) => !present.has(name)))
+        for(const name of present) {
+            entry(name).present++
+            entry(name).briefs++
+        }
+        for(const name of absent) {
+            entry(name).briefs++
+        }
+    }
+    return students
+}
+
+/**
+ * Students present at fewer than LOW_ATTENDANCE of their briefs.
+ *
+ * @param {import('./model.mjs').BriefRecord[]} briefs
+ * @returns {Map<string, Attendance>}
+ */
+export function lowAttendance(briefs) {
+    return new Map([...attendance(briefs)].filter(([, { present, briefs }]) => present < LOW_ATTENDANCE * briefs))
+}
+
+/**
+ * Replays `rosters` `runs` times through the matcher, avoiding the
+ * previous brief as `generate` does, and returns the repeats each student
+ * got in every run. Roster order matters (it decides who may end up in a
+ * trio), so pass the rosters as `generate` saw them.
+ *
+ * @param {string[][]} rosters students of each brief, oldest first
+ * @param {number} runs
+ * @param {{iterations?: number}} [options]
+ * @returns {Promise<Map<string, number[]>>}
+ */
+export async function expectedRepeats(rosters, runs, { iterations } = {}) {
+    /** @type {Map<string, number[]>} */
+    const samples = new Map()
+    for(let run = 0; run < runs; run++) {
+        const simulated = []
+        for(const roster of rosters) {
+            const result = await generateGroups(roster, simulated.at(-1) ?? null, { iterations })
+            simulated.push(result.groups)
+        }
+        for(const [name, { repeats }] of repeatStats(simulated)) {
+            samples.set(name, [...(samples.get(name) ?? []), repeats])
+        }
+    }
+    return samples
+}
+
+/**
+ * @typedef {object} CohortMetrics
+ * @property {number} briefs
+ * @property {number} students distinct students in the history
+ * @property {number[]} coverage share of the possible pairs formed at least once, after each brief
+ * @property {number} repeats pairs formed again after having already worked together
+ * @property {number} repeatsPerBrief `repeats` on average per brief
+ * @property {number} minPartners fewest distinct partners met by a student
+ * @property {number} maxPartners most distinct partners met by a student
+ */
+
+/**
+ * Summarizes how a cohort's history rotated students, for comparisons
+ * between classes.
+ *
+ * @param {string[][][]} history groups of each brief, oldest first
+ * @returns {CohortMetrics}
+ */
+export function cohortMetrics(history) {
+    const students = new Set(history.flat(2))
+    const possible = Math.max(1, students.size * (students.size - 1) / 2)
+    const met = new Set()
+    const coverage = []
+    let repeats = 0
+    for(const groups of history) {
+        for(const group of groups) {
+            for(let i = 0; i < group.length; i++) {
+                for(let j = i + 1; j < group.length; j++) {
+                    const key = pairKey(group[i], group[j])
+                    if(met.has(key)) {
+                        repeats++
+                    }
+                    met.add(key)
+                }
+            }
+        }
+        coverage.push(met.size / possible)
+    }
+    const partners = [...repeatStats(history).values()].map((student) => student.partners)
+    return {
+        briefs: history.length,
+        students: students.size,
+        coverage,
+        repeats,
+        repeatsPerBrief: history.length > 0 ? repeats / history.length : 0,
+        minPartners: partners.length > 0 ? Math.min(...partners) : 0,
+        maxPartners: partners.length > 0 ? Math.max(...partners) : 0,
+    }
+}
+
+/**
+ * @typedef {object} GraphMetrics
+ * @property {number} students
+ * @property {number} edges pairs who worked together at least once
+ * @property {number} density `edges` over all possible pairs
+ * @property {string[][]} components groups of students linked by a chain of collaborations, largest first
+ * @property {number} diameter longest shortest path within a component
+ * @property {number} clustering average local clustering coefficient
+ */
+
+/**
+ * Metrics of the collaboration graph, where students are linked when they
+ * shared a group at least once: how fragmented the promo is, how far
+ * apart two students can be, and how much partners of a student also
+ * worked with each other.
+ *
+ * @param {string[][][]} history groups of each brief, oldest first
+ * @returns {GraphMetrics}
+ */
+export function graphMetrics(history) {
+    /** @type {Map<string, Set<string>>} */
+    const neighbours = new Map()
+    for(const group of history.flat()) {
+        for(const name of group) {
+            const linked = neighbours.get(name) ?? new Set()
+            neighbours.set(name, linked)
+            for(const other of group) {
+                if(other != name) {
+                    linked.add(other)
+                }
+            }
+        }
+    }
+    const distances = (start) => {
+        const seen = new Map([[start, 0]])
+        const queue = [start]
+        for(let head = 0; head < queue.length; head++) {
+            for(const next of neighbours.get(queue[head])) {
+                if(!seen.has(next)) {
+                    seen.set(next, seen.get(queue[head]) + 1)
+                    queue.push(next)
+                }
+            }
+        }
+        return seen
+    }
+
+    const components = []
+    const placed = new Set()
+    let diameter = 0
+    for(const name of neighbours.keys()) {
+        const reached = distances(name)
+        diameter = Math.max(diameter, ...reached.values())
+        if(!placed.has(name)) {
+            components.push([...reached.keys()])
+            reached.forEach((_, member) => placed.add(member))
+        }
+    }
+    components.sort((a, b) => b.length - a.length)
+
+    let clustering = 0
+    for(const linked of neighbours.values()) {
+        const partners = [...linked]
+        if(partners.length < 2) {
+            continue
+        }
+        let triangles = 0
+        for(let i = 0; i < partners.length; i++) {
+            for(let j = i + 1; j < partners.length; j++) {
+                if(neighbours.get(partners[i]).has(partners[j])) {
+                    triangles++
+                }
+            }
+        }
+        clustering += triangles / (partners.length * (partners.length - 1) / 2)
+    }
+
+    const students = neighbours.size
+    const edges = [...neighbours.values()].reduce((sum, linked) => sum + linked.size, 0) / 2
+    return {
+        students,
+        edges,
+        density: students > 1 ? edges / (students * (students - 1) / 2) : 0,
+        components,
+        diameter,
+        clustering: students > 0 ? clustering / students : 0,
+    }
+}
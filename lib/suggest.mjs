@@ -0,0 +1,84 @@
+import { t } from './i18n.mjs'
+import { compareNames, pairKey, sortNames } from './names.mjs'
+import { repeatStats } from './stats.mjs'
+
+/**
+ * @typedef {object} Suggestion
+ * @property {import('./constraints.mjs').Constraint} constraint
+ * @property {string} reason why the constraint would help, in French
+ */
+
+/**
+ * Looks for painful patterns in the history of the students still in the
+ * cohort (those of the last brief) and proposes constraints against them:
+ * "never" for pairs grouped together at least `minRepeats` times,
+ * "together" to pair students who met less than half as many partners as
+ * the median student with someone they never worked with.
+ *
+ * @param {string[][][]} history groups of each brief, oldest first
+ * @param {import('./constraints.mjs').Constraint[]} existing constraints already in force, not suggested again
+ * @param {{minRepeats?: number}} [options]
+ * @returns {Suggestion[]}
+ */
+export function suggestConstraints(history, existing, { minRepeats = 4 } = {}) {
+    const roster = sortNames(history.at(-1)?.flat() ?? [])
+    const present = new Set(roster)
+    const covered = new Set(existing.map((constraint) => pairKey(...constraint.students)))
+    const suggestions = []
+
+    /** @type {Map<string, number>} */
+    const together = new Map()
+    for(const group of history.flat()) {
+        for(let i = 0; i < group.length; i++) {
+            for(let j = i + 1; j < group.length; j++) {
+                const key = pairKey(group[i], group[j])
+                together.set(key, (together.get(key) ?? 0) + 1)
+            }
+        }
+    }
+    for(const [key, count] of together) {
+        const students = sortNames(key.split('\n'))
+        if(count >= minRepeats && students.every((name) => present.has(name)) && !covered.has(key)) {
+            suggestions.push({ constraint: { type: 'never', students }, reason: t`ensemble dans ${count} briefs sur ${history.length}` })
+            covered.add(key)
+        }
+    }
+    suggestions.sort((a, b) => compareNames(a.constraint.students.join(), b.constraint.students.join()))
+
+    const stats = repeatStats(history)
+    const partners = roster.map((name) => stats.get(name).partners).sort((a, b) => a - b)
+    const median = partners[Math.floor(partners.length / 2)] ?? 0
+    const isolated = roster.filter((name) => stats.get(name).briefs > 1 && stats.get(name).partners * 2 < median)
+    const paired = new Set(existing.filter((constraint) => constraint.type == 'together').flatMap((constraint) => constraint.students))
+    for(const name of isolated.filter((name) => !paired.has(name))) {
+        const candidates = roster.filter((other) => other != name && !paired.has(other)
+            && !together.has(pairKey(name, other)) && !covered.has(pairKey(name, other)))
+        candidates.sort((a, b) => stats.get(b).partners - stats.get(a).partners || compareNames(a, b))
+        const partner = candidates[0]
+        if(partner == null) {
+            continue
+        }
+        paired.add(name).add(partner)
+        covered.add(pairKey(name, partner))
+        suggestions.push({
+            constraint: { type: 'together', students: [name, partner] },
+            reason: t`${name} n'a travaillé qu'avec ${stats.get(name).partners} personne(s) (médiane : ${median}), jamais avec ${partner} qui en a côtoyé ${stats.get(partner).partners}`,
+        })
+    }
+    return suggestions
+}
+
+/**
+ * Suggestions as `[[constraints]]` tables ready to paste in groupgen.toml.
+ *
+ * @param {Suggestion[]} suggestions
+ * @returns {string}
+ */
+export function formatSuggestions(suggestions) {
+    return suggestions.map(({ constraint, reason }) => [
+        `# ${reason}`,
+        '[[constraints]]',
+        `type = "${constraint.type}"`,
+        `students = [${constraint.students.map((name) => JSON.stringify(name)).join(', ')}]`,
+    ].join('\n')).join('\n\n') + '\n'
+}
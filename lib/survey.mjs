@@ -0,0 +1,131 @@
+import { formatCsv, parseCsv } from './csv.mjs'
+import { InputError } from './errors.mjs'
+import { t } from './i18n.mjs'
+
+/**
+ * Header of the column holding the respondent's name in the survey.
+ */
+export const RESPONDENT_COLUMN = 'Apprenant'
+
+/**
+ * Answers a student can give about a classmate, by constraint type. The
+ * French labels of CONSTRAINT_TYPES are understood too.
+ */
+const ANSWERS = {
+    together: ['ensemble', 'toujours ensemble', 'together'],
+    never: ['jamais', 'jamais ensemble', 'never'],
+}
+
+/**
+ * Survey to hand out: one row per student, one column per classmate, each
+ * cell to fill with "ensemble" or "jamais", or left empty. A last row,
+ * without a name, explains the answers.
+ *
+ * @param {string[]} roster
+ * @returns {string} CSV
+ */
+export function surveyForm(roster) {
+    return formatCsv([
+        [RESPONDENT_COLUMN, ...roster],
+        ...roster.map((name) => [name, ...roster.map((classmate) => classmate == name ? '-' : '')]),
+        ['', `Sur ta ligne, ${ANSWERS.together[0]} : tu aimerais travailler avec, ${ANSWERS.never[0]} : tu préfères éviter, vide : peu importe`],
+    ])
+}
+
+/**
+ * @typedef {object} SurveyAnswers
+ * @property {Map<string, Map<string, 'together' | 'never'>>} wishes answers of each respondent, by classmate
+ * @property {string[]} unknown respondents missing from the roster, left out
+ */
+
+/**
+ * Reads the filled survey, or the CSV export of a form asking the same
+ * as a grid, whose columns are titled "Question [Classmate]". Answers
+ * come in chronological order, so a student's last answer wins.
+ *
+ * @param {string} text CSV
+ * @param {string[]} roster
+ * @param {{source?: string, nameColumn?: string}} [options]
+ * @returns {SurveyAnswers}
+ */
+export function parseSurvey(text, roster, { source = 'sondage', nameColumn = RESPONDENT_COLUMN } = {}) {
+    const [header, ...rows] = parseCsv(text, source)
+    const respondentColumn = header?.findIndex((cell) => cell.trim() == nameColumn) ?? -1
+    if(respondentColumn == -1) {
+        throw new InputError(t`${source} : colonne "${nameColumn}" introuvable`)
+    }
+    const classmateColumns = header.flatMap((cell, index) => {
+        const name = cell.match(/\[([^\]]+)\]\s*$/)?.[1].trim() ?? cell.trim()
+        return index != respondentColumn && roster.includes(name) ? [[name, index]] : []
+    })
+    if(classmateColumns.length == 0) {
+        throw new InputError(t`${source} : aucune colonne au nom d'un apprenant de la liste`)
+    }
+    const answers = new Map(Object.entries(ANSWERS).flatMap(([type, words]) => words.map((word) => [word, type])))
+
+    const wishes = new Map()
+    const unknown = new Set()
+    for(const [index, row] of rows.entries()) {
+        const respondent = (row[respondentColumn] ?? '').trim()
+        if(respondent == '') {
+            continue
+        }
+        if(!roster.includes(respondent)) {
+            unknown.add(respondent)
+            continue
+        }
+        const student = new Map()
+        for(const [classmate, column] of classmateColumns) {
+            const answer = (row[column] ?? '').trim().toLowerCase()
+            if(answer == '' || answer == '-' || classmate == respondent) {
+                continue
+            }
+            if(!answers.has(answer)) {
+                throw new InputError(t`${source}, ligne ${index + 2} : réponse "${row[column].trim()}" pour ${classmate} (${ANSWERS.together[0]}, ${ANSWERS.never[0]} ou vide)`)
+            }
+            student.set(classmate, answers.get(answer))
+        }
+        wishes.set(respondent, student)
+    }
+    return { wishes, unknown: [...unknown] }
+}
+
+/**
+ * @typedef {object} SurveyPairs
+ * @property {[string, string][]} never pairs one of whom would rather not work with the other
+ * @property {[string, string][]} together pairs who both asked to work together
+ * @property {number} oneSided "ensemble" answers not given back, left out
+ */
+
+/**
+ * Constraints the answers call for. Anyone can keep away from a
+ * classmate, but "together" constraints are always honoured by the
+ * matcher, so it takes both students asking for it; "jamais" wins when
+ * they disagree.
+ *
+ * @param {SurveyAnswers['wishes']} wishes
+ * @returns {SurveyPairs}
+ */
+export function surveyPairs(wishes) {
+    const wish = (a, b) => wishes.get(a)?.get(b)
+    const never = new Map()
+    const together = new Map()
+    let oneSided = 0
+    for(const [respondent, answers] of wishes) {
+        for(const [classmate, type] of answers) {
+            const pair = [respondent, classmate].sort()
+            const key = pair.join('\n')
+            if(type == 'never') {
+                never.set(key, pair)
+            } else if(wish(classmate, respondent) == 'together') {
+                together.set(key, pair)
+            } else if(wish(classmate, respondent) == null) {
+                oneSided++
+            }
+        }
+    }
+    for(const key of never.keys()) {
+        together.delete(key)
+    }
+    return { never: [...never.values()], together: [...together.values()], oneSided }
+}
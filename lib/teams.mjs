@@ -0,0 +1,109 @@
+import { generateGroups } from './matcher.mjs'
+
+/**
+ * A project phase during which teams stay the same from one brief to the
+ * next, with roles rotating among their members.
+ *
+ * @typedef {object} TeamPhase
+ * @property {number} id
+ * @property {string} created_at ISO 8601 timestamp
+ * @property {string | null} until YYYY-MM-DD, last day of the phase
+ * @property {string | null} ended_at ISO 8601 timestamp, when ended early with `teams end`
+ * @property {string[]} roles handed out in turn within each team
+ * @property {number} rotation times roles were rotated
+ * @property {Team[]} teams
+ */
+
+/**
+ * @typedef {object} Team
+ * @property {string} name
+ * @property {string[]} members
+ * @property {PairRotation[]} [pairs] pair programming rotations inside the team, oldest first
+ */
+
+/**
+ * @typedef {object} PairRotation
+ * @property {string} date ISO 8601 timestamp
+ * @property {string[][]} groups
+ */
+
+/**
+ * The phase in force on `date`, if any.
+ *
+ * @param {TeamPhase[]} phases
+ * @param {Date} [date]
+ * @returns {TeamPhase | null}
+ */
+export function activePhase(phases, date = new Date()) {
+    const today = date.toISOString().slice(0, 10)
+    return phases.findLast((phase) => phase.ended_at == null && (phase.until == null || phase.until >= today)) ?? null
+}
+
+/**
+ * Differences between the teams and the roster: members who left, and
+ * students who have no team.
+ *
+ * @param {Team[]} teams
+ * @param {string[]} roster
+ * @returns {{unknown: string[], unassigned: string[]}}
+ */
+export function checkTeams(teams, roster) {
+    const members = new Set(teams.flatMap((team) => team.members))
+    const present = new Set(roster)
+    return {
+        unknown: [...members].filter((name) => !present.has(name)),
+        unassigned: roster.filter((name) => !members.has(name)),
+    }
+}
+
+/**
+ * Role of each member of `team`: after each rotation, every member takes
+ * the role of the next one. With fewer roles than members, the remaining
+ * members have none.
+ *
+ * @param {Team} team
+ * @param {TeamPhase} phase
+ * @returns {{member: string, role: string | null}[]}
+ */
+export function teamRoles(team, phase) {
+    const size = team.members.length
+    return team.members.map((member, index) => ({ member, role: phase.roles[(index + phase.rotation) % size] ?? null }))
+}
+
+/**
+ * @param {TeamPhase} phase
+ * @returns {string}
+ */
+export function describeTeams(phase) {
+    return phase.teams.map((team) => [
+        `${team.name} :`,
+        ...teamRoles(team, phase).map(({ member, role }) => `  ${member}${role ? ` (${role})` : ''}`),
+    ].join('\n')).join('\n')
+}
+
+/**
+ * Pairs the members of each team among themselves and records it in the
+ * team. A team avoids the pairs of its last `members - 2` rotations,
+ * which for an even team is a full round where everyone met once.
+ *
+ * @param {TeamPhase} phase
+ * @param {import('./constraints.mjs').ConstraintRecord[]} constraints
+ * @param {Date} [date]
+ * @returns {Promise<Map<string, string[][]>>} the new pairs, by team name
+ */
+export async function rotatePairs(phase, constraints, date = new Date()) {
+    const rotations = new Map()
+    for(const team of phase.teams) {
+        if(team.members.length < 2) {
+            continue
+        }
+        team.pairs ??= []
+        const window = Math.max(1, team.members.length - 2)
+        const previous = team.pairs.slice(-window).flatMap((rotation) => rotation.groups)
+        const inTeam = constraints.filter((constraint) => constraint.students.every((name) => team.members.includes(name)))
+        const { groups } = await generateGroups(team.members, previous, { constraints: inTeam })
+        team.pairs.push({ date: date.toISOString(), groups })
+        rotations.set(team.name, groups)
+    }
+    return rotations
+}
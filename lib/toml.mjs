@@ -0,0 +1,193 @@
+import { InputError } from './errors.mjs'
+import { t } from './i18n.mjs'
+
+/**
+ * Parser for the subset of TOML used by the configuration file: tables,
+ * arrays of tables, dotted table names and keys, bare or quoted keys,
+ * strings, numbers, booleans and arrays, possibly spread over several
+ * lines. Dates, inline tables and multi-line strings are not supported.
+ *
+ * @param {string} text
+ * @returns {Record<string, any>}
+ */
+export function parseToml(text) {
+    const root = {}
+    let table = root
+    const lines = text.split(/\r?\n/)
+    for(let index = 0; index < lines.length; index++) {
+        const raw = lines[index]
+        const lineNumber = index + 1
+        let line = stripComment(raw).trim()
+        if(line == '') {
+            continue
+        }
+        const header = line.match(/^\[(\[)?([^\[\]]+)\](\])?$/)
+        if(header && Boolean(header[1]) == Boolean(header[3])) {
+            const keys = splitKey(header[2], lineNumber)
+            const last = header[1] ? keys.pop() : null
+            table = keys.reduce((parent, key) => subTable(parent, key, lineNumber), root)
+            if(last != null) {
+                const array = table[last] ??= []
+                if(!Array.isArray(array) || array.some((item) => typeof item != 'object')) {
+                    throw new InputError(t`TOML invalide ligne ${lineNumber} : ${last} n'est pas un tableau de tables`)
+                }
+                table = {}
+                array.push(table)
+            }
+            continue
+        }
+        // An array left open goes on until its closing bracket.
+        while(openBrackets(line) > 0) {
+            if(++index == lines.length) {
+                throw new InputError(t`TOML invalide ligne ${lineNumber} : tableau non fermé`)
+            }
+            line += ' ' + stripComment(lines[index]).trim()
+        }
+        const assignment = line.match(/^((?:"[^"]*"|'[^']*'|[A-Za-z0-9_\-]+)(?:\s*\.\s*(?:"[^"]*"|'[^']*'|[A-Za-z0-9_\-]+))*)\s*=\s*(.+)$/)
+        if(!assignment) {
+            throw new InputError(t`TOML invalide ligne ${lineNumber} : ${raw}`)
+        }
+        const keys = splitKey(assignment[1], lineNumber)
+        const last = keys.pop()
+        keys.reduce((parent, key) => subTable(parent, key, lineNumber), table)[last] = parseValue(assignment[2].trim(), lineNumber)
+    }
+    return root
+}
+
+/**
+ * Parts of a dotted key, `a."b.c".d` giving `a`, `b.c` and `d`.
+ */
+function splitKey(text, lineNumber) {
+    // The empty alternative keeps the empty parts of `a..b`, to reject them.
+    const parts = text.match(/"[^"]*"|'[^']*'|[^.]+|(?<=^|\.)(?=\.|$)/g)
+    return parts.map((part) => checkKey(unquote(part.trim()), lineNumber))
+}
+
+/**
+ * Table `key` of `table`, created when missing. A name given to an array
+ * of tables stands for its last table.
+ */
+function subTable(table, key, lineNumber) {
+    let child = Object.hasOwn(table, key) ? table[key] : (table[key] = {})
+    if(Array.isArray(child)) {
+        child = child.at(-1)
+    }
+    if(typeof child != 'object') {
+        throw new InputError(t`TOML invalide ligne ${lineNumber} : ${key} n'est pas une table`)
+    }
+    return child
+}
+
+/**
+ * Brackets opened and not yet closed in `line`, outside strings.
+ */
+function openBrackets(line) {
+    let depth = 0
+    let quote = null
+    for(let i = 0; i < line.length; i++) {
+        const char = line[i]
+        if(quote) {
+            if(char == '\\' && quote == '"') {
+                i++
+            } else if(char == quote) {
+                quote = null
+            }
+        } else if(char == '"' || char == '\'') {
+            quote = char
+        } else if(char == '[') {
+            depth++
+        } else if(char == ']') {
+            depth--
+        }
+    }
+    return depth
+}
+
+function stripComment(line) {
+    let quote = null
+    for(let i = 0; i < line.length; i++) {
+        const char = line[i]
+        if(quote) {
+            if(char == '\\' && quote == '"') {
+                i++
+            } else if(char == quote) {
+                quote = null
+            }
+        } else if(char == '"' || char == '\'') {
+            quote = char
+        } else if(char == '#') {
+            return line.slice(0, i)
+        }
+    }
+    return line
+}
+
+function unquote(key) {
+    return key.replace(/^["']|["']$/g, '')
+}
+
+function checkKey(key, lineNumber) {
+    if(key == '' || key == '__proto__') {
+        throw new InputError(t`Clé TOML invalide ligne ${lineNumber} : "${key}"`)
+    }
+    return key
+}
+
+function parseValue(value, lineNumber) {
+    if(value.startsWith('"')) {
+        try {
+            const string = JSON.parse(value)
+            if(typeof string == 'string') {
+                return string
+            }
+        } catch {
+            // reported below
+        }
+    } else if(value.startsWith('\'')) {
+        if(value.length > 1 && value.endsWith('\'') && !value.slice(1, -1).includes('\'')) {
+            return value.slice(1, -1)
+        }
+    } else if(value == 'true' || value == 'false') {
+        return value == 'true'
+    } else if(value.startsWith('[') && value.endsWith(']')) {
+        const inner = value.slice(1, -1).trim()
+        if(inner == '') {
+            return []
+        }
+        return splitArray(inner).map((item) => parseValue(item.trim(), lineNumber))
+    } else if(/^[+-]?[0-9_]+(\.[0-9_]+)?$/.test(value)) {
+        return Number(value.replaceAll('_', ''))
+    }
+    throw new InputError(t`Valeur TOML invalide ligne ${lineNumber} : ${value}`)
+}
+
+function splitArray(inner) {
+    const items = []
+    let quote = null
+    let depth = 0
+    let start = 0
+    for(let i = 0; i < inner.length; i++) {
+        const char = inner[i]
+        if(quote) {
+            if(char == '\\' && quote == '"') {
+                i++
+            } else if(char == quote) {
+                quote = null
+            }
+        } else if(char == '"' || char == '\'') {
+            quote = char
+        } else if(char == '[') {
+            depth++
+        } else if(char == ']') {
+            depth--
+        } else if(char == ',' && depth == 0) {
+            items.push(inner.slice(start, i))
+            start = i + 1
+        }
+    }
+    const last = inner.slice(start)
+    if(last.trim() != '') {
+        items.push(last)
+    }
+    return items
+}
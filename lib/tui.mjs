@@ -0,0 +1,329 @@
+import { t } from './i18n.mjs'
+import { compareNames, pairKey, sortNames } from './names.mjs'
+import { pairCounts, repeatStats } from './stats.mjs'
+
+/**
+ * Screens of `groupgen tui`, kept free of terminal handling: the state is
+ * a plain object, `handleKey` updates it and names the actions the
+ * command has to carry out (drawing groups, saving), and `renderScreen`
+ * turns it into the lines to display.
+ */
+
+export const TABS = ['Promo', 'Matrice', 'Groupes']
+
+const INVERSE = '\x1b[7m'
+const NORMAL = '\x1b[27m'
+
+/**
+ * @typedef {object} TuiState
+ * @property {number} tab index in TABS
+ * @property {string[]} roster in alphabetical order
+ * @property {Map<string, import('./stats.mjs').StudentStats>} stats
+ * @property {Map<string, number>} counts briefs spent together, by `pairKey`
+ * @property {number} student cursor of the Promo screen
+ * @property {{row: number, column: number}} cell cursor of the Matrice screen
+ * @property {string[][] | null} groups groups drawn, not saved yet
+ * @property {number | null} seed of the draw
+ * @property {[string, string][]} swaps made by hand since the draw
+ * @property {number} member cursor of the Groupes screen, over all members in order
+ * @property {number | null} marked member chosen for a swap
+ * @property {number | null} saved id of the brief the groups were saved as
+ * @property {string} message shown at the bottom
+ */
+
+/**
+ * @param {string[]} roster
+ * @param {string[][][]} history groups of each past brief
+ * @returns {TuiState}
+ */
+export function createState(roster, history) {
+    const state = {
+        tab: 0,
+        roster: [...roster].sort(compareNames),
+        student: 0,
+        cell: { row: 0, column: 1 },
+        groups: null,
+        seed: null,
+        swaps: [],
+        member: 0,
+        marked: null,
+        saved: null,
+        message: '',
+    }
+    return refreshHistory(state, history)
+}
+
+/**
+ * Recomputes what comes from the history, after a brief was saved.
+ *
+ * @param {TuiState} state
+ * @param {string[][][]} history
+ */
+export function refreshHistory(state, history) {
+    state.stats = repeatStats(history)
+    state.counts = pairCounts(history)
+    return state
+}
+
+/**
+ * Shows freshly drawn groups on the Groupes screen.
+ *
+ * @param {TuiState} state
+ * @param {string[][]} groups
+ * @param {number} seed
+ */
+export function showDraw(state, groups, seed) {
+    state.groups = groups.map(sortNames)
+    state.seed = seed
+    state.swaps = []
+    state.member = 0
+    state.marked = null
+    state.saved = null
+    state.message = t`Tirage avec la graine ${seed}.`
+}
+
+/**
+ * Position of a member of `groups` from their index over all members.
+ *
+ * @param {string[][]} groups
+ * @param {number} member
+ * @returns {[number, number]} group and index in it
+ */
+function locate(groups, member) {
+    let group = 0
+    while(member >= groups[group].length) {
+        member -= groups[group].length
+        group++
+    }
+    return [group, member]
+}
+
+/**
+ * @param {number} value
+ * @param {number} length
+ */
+function clamp(value, length) {
+    return Math.max(0, Math.min(length - 1, value))
+}
+
+/**
+ * Applies a key press, given as the name readline gives it (`up`, `tab`,
+ * `space`...) or the character typed.
+ *
+ * @param {TuiState} state
+ * @param {string} key
+ * @returns {'generate' | 'save' | 'quit' | null} what is left to the command
+ */
+export function handleKey(state, key) {
+    state.message = ''
+    if(key == 'q' || key == 'escape') {
+        return 'quit'
+    }
+    if(key == 'tab') {
+        state.tab = (state.tab + 1) % TABS.length
+        return null
+    }
+    if(/^[1-9]$/.test(key) && Number(key) <= TABS.length) {
+        state.tab = Number(key) - 1
+        return null
+    }
+    const size = state.roster.length
+    const step = { up: [-1, 0], down: [1, 0], left: [0, -1], right: [0, 1] }[key]
+    if(state.tab == 0) {
+        if(step != null) {
+            state.student = clamp(state.student + step[0], size)
+        }
+    } else if(state.tab == 1) {
+        if(step != null) {
+            state.cell.row = clamp(state.cell.row + step[0], size)
+            state.cell.column = clamp(state.cell.column + step[1], size)
+        }
+    } else {
+        if(key == 'g') {
+            return 'generate'
+        }
+        if(state.groups == null) {
+            state.message = t`Aucun tirage, g pour former des groupes.`
+            return null
+        }
+        if(key == 's') {
+            if(state.saved != null) {
+                state.message = t`Déjà enregistré comme brief n°${state.saved}.`
+                return null
+            }
+            return 'save'
+        }
+        if(step != null && step[0] != 0) {
+            state.member = clamp(state.member + step[0], state.groups.flat().length)
+        } else if(key == 'space' || key == 'return') {
+            swap(state)
+        }
+    }
+    return null
+}
+
+/**
+ * Marks the member under the cursor, or swaps them with the one marked
+ * before.
+ *
+ * @param {TuiState} state
+ */
+function swap(state) {
+    if(state.saved != null) {
+        state.message = t`Déjà enregistré comme brief n°${state.saved}, g pour un nouveau tirage.`
+        return
+    }
+    if(state.marked == null || state.marked == state.member) {
+        state.marked = state.marked == null ? state.member : null
+        return
+    }
+    const [groupA, indexA] = locate(state.groups, state.marked)
+    const [groupB, indexB] = locate(state.groups, state.member)
+    state.marked = null
+    if(groupA == groupB) {
+        state.message = t`Ces deux apprenants sont déjà dans le même groupe.`
+        return
+    }
+    const a = state.groups[groupA][indexA]
+    const b = state.groups[groupB][indexB]
+    state.groups[groupA][indexA] = b
+    state.groups[groupB][indexB] = a
+    state.swaps.push([a, b])
+    state.message = t`${a} et ${b} échangés.`
+}
+
+/**
+ * Pairs inside `group` that already worked together.
+ *
+ * @param {TuiState} state
+ * @param {string[]} group
+ */
+function knownPairs(state, group) {
+    let known = 0
+    for(let i = 0; i < group.length; i++) {
+        for(let j = i + 1; j < group.length; j++) {
+            known += state.counts.has(pairKey(group[i], group[j])) ? 1 : 0
+        }
+    }
+    return known
+}
+
+/**
+ * Lines from `lines` that fit in `height`, scrolled to keep line
+ * `cursor` visible.
+ *
+ * @param {string[]} lines
+ * @param {number} cursor
+ * @param {number} height
+ */
+function scrolled(lines, cursor, height) {
+    const start = Math.max(0, cursor - height + 1)
+    return lines.slice(start, start + height)
+}
+
+/**
+ * @param {string} text
+ * @param {number} width
+ */
+function fit(text, width) {
+    return text.length > width ? `${text.slice(0, Math.max(0, width - 1))}…` : text.padEnd(width)
+}
+
+/**
+ * @param {TuiState} state
+ * @param {number} height
+ */
+function promoScreen(state, height) {
+    const width = Math.max(...state.roster.map((name) => name.length))
+    const lines = state.roster.map((name, index) => {
+        const stats = state.stats.get(name)
+        const line = `${name.padEnd(width)}  ${stats == null ? t`aucun brief` : t`${stats.briefs} brief(s), ${stats.partners} partenaire(s) distinct(s), ${stats.repeats} répétition(s)`}`
+        return index == state.student ? INVERSE + line + NORMAL : line
+    })
+    return [t`${state.roster.length} apprenants`, '', ...scrolled(lines, state.student, height - 2)]
+}
+
+/**
+ * @param {TuiState} state
+ * @param {number} height
+ * @param {number} columns
+ */
+function matrixScreen(state, height, columns) {
+    const { roster, cell } = state
+    const label = Math.min(20, Math.max(...roster.map((name) => name.length)))
+    const margin = 4 + label + 1
+    const visible = Math.max(1, Math.floor((columns - margin) / 3))
+    const first = Math.max(0, cell.column - visible + 1)
+    const shown = roster.slice(first, first + visible).map((_, offset) => first + offset)
+    const header = ' '.repeat(margin) + shown.map((column) => String(column + 1).padStart(3)).join('')
+    const rows = roster.map((name, row) => {
+        const cells = shown.map((column) => {
+            const text = row == column ? '  -' : String(state.counts.get(pairKey(name, roster[column])) ?? '·').padStart(3)
+            return row == cell.row && column == cell.column ? INVERSE + text + NORMAL : text
+        })
+        return `${String(row + 1).padStart(3)} ${fit(name, label)} ${cells.join('')}`
+    })
+    const [a, b] = [roster[cell.row], roster[cell.column]]
+    const count = state.counts.get(pairKey(a, b)) ?? 0
+    const detail = a == b ? a : count == 0 ? t`${a} et ${b} : jamais ensemble` : t`${a} et ${b} : ${count} brief(s) ensemble`
+    return [detail, '', header, ...scrolled(rows, cell.row, height - 3)]
+}
+
+/**
+ * @param {TuiState} state
+ * @param {number} height
+ */
+function groupsScreen(state, height) {
+    if(state.groups == null) {
+        return [t`Aucun tirage : g pour former des groupes en évitant le brief précédent.`]
+    }
+    const lines = []
+    let member = 0
+    let cursorLine = 0
+    state.groups.forEach((group, index) => {
+        // Once saved, the groups count among the pairs already formed.
+        const known = state.saved == null ? knownPairs(state, group) : 0
+        lines.push(known > 0 ? t`Groupe ${index + 1} (${known} paire(s) déjà formée(s))` : t`Groupe ${index + 1}`)
+        for(const name of group) {
+            const line = `${member == state.marked ? '* ' : '  '}${name}`
+            if(member == state.member) {
+                cursorLine = lines.length
+            }
+            lines.push(member == state.member ? INVERSE + line + NORMAL : line)
+            member++
+        }
+    })
+    const status = state.saved != null
+        ? t`Enregistré comme brief n°${state.saved}.`
+        : t`Graine ${state.seed}${state.swaps.length > 0 ? t`, ${state.swaps.length} échange(s)` : ''}, non enregistré.`
+    return [status, '', ...scrolled(lines, cursorLine, height - 2)]
+}
+
+/** Key help of each tab, to show through `t`. */
+const HELP = [
+    '↑↓ parcourir',
+    '↑↓←→ parcourir',
+    'g tirer  ↑↓ parcourir  espace choisir puis échanger  s enregistrer',
+]
+
+/**
+ * @param {TuiState} state
+ * @param {{columns: number, rows: number}} size of the terminal
+ * @returns {string[]} one entry per line, at most `size.rows`
+ */
+export function renderScreen(state, { columns, rows }) {
+    const tabs = TABS.map((name, index) => {
+        const text = ` ${index + 1} ${t(name)} `
+        return index == state.tab ? INVERSE + text + NORMAL : text
+    }).join(' ')
+    const height = Math.max(1, rows - 4)
+    const body = [promoScreen, matrixScreen, groupsScreen][state.tab](state, height, columns)
+    return [
+        tabs,
+        '',
+        ...body.slice(0, height),
+        ...Array(Math.max(0, height - body.length)).fill(''),
+        state.message,
+        `${t(HELP[state.tab])}  ${t`tab/1-${TABS.length} écrans  q quitter`}`,
+    ]
+}
@@ -0,0 +1,88 @@
+import crypto from 'crypto'
+import { t } from './i18n.mjs'
+
+export const RELEASES_URL = 'https://api.github.com/repos/shiipou/simplon-group-generator/releases/latest'
+
+/**
+ * Name of the checksum file attached to each release, in `sha256sum`
+ * format.
+ */
+export const CHECKSUMS_ASSET = 'SHA256SUMS'
+
+/**
+ * @typedef {object} Release
+ * @property {string} version without the leading `v`
+ * @property {string} url release page
+ * @property {{name: string, browser_download_url: string}[]} assets
+ */
+
+/**
+ * @param {string} url
+ * @returns {Promise<Response>}
+ */
+async function get(url) {
+    let response
+    try {
+        response = await fetch(url, { headers: { 'user-agent': 'simplon-group-generator' }, signal: AbortSignal.timeout(60000) })
+    } catch(error) {
+        throw new Error(t`connexion impossible à ${new URL(url).host} (${error.cause?.code ?? error.message})`)
+    }
+    if(!response.ok) {
+        throw new Error(t`HTTP ${response.status} sur ${url}`)
+    }
+    return response
+}
+
+/**
+ * @returns {Promise<Release>}
+ */
+export async function latestRelease() {
+    const release = await (await get(RELEASES_URL)).json()
+    return { version: release.tag_name.replace(/^v/, ''), url: release.html_url, assets: release.assets }
+}
+
+/**
+ * @param {string} url
+ * @returns {Promise<Buffer>}
+ */
+export async function download(url) {
+    return Buffer.from(await (await get(url)).arrayBuffer())
+}
+
+/**
+ * Compares dotted version numbers, ignoring pre-release suffixes.
+ *
+ * @param {string} a
+ * @param {string} b
+ * @returns {number} negative when a < b, positive when a > b
+ */
+export function compareVersions(a, b) {
+    const parts = (version) => version.split('-')[0].split('.').map(Number)
+    const [left, right] = [parts(a), parts(b)]
+    for(let i = 0; i < Math.max(left.length, right.length); i++) {
+        const difference = (left[i] ?? 0) - (right[i] ?? 0)
+        if(difference != 0) {
+            return difference
+        }
+    }
+    return 0
+}
+
+/**
+ * Throws unless `content` matches the SHA-256 listed for `filename` in a
+ * `sha256sum` output.
+ *
+ * @param {Buffer} content
+ * @param {string} sums
+ * @param {string} filename
+ */
+export function verifyChecksum(content, sums, filename) {
+    const line = sums.split('\n').map((line) => line.trim().split(/\s+\*?/)).find(([, name]) => name == filename)
+    if(line == null) {
+        throw new Error(t`${filename} absent de ${CHECKSUMS_ASSET}`)
+    }
+    const actual = crypto.createHash('sha256').update(content).digest('hex')
+    if(actual != line[0].toLowerCase()) {
+        throw new Error(t`somme de contrôle invalide pour ${filename}, téléchargement corrompu ou modifié`)
+    }
+}
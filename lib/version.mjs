@@ -0,0 +1,37 @@
+import { spawnSync } from 'child_process'
+import fs from 'fs'
+import path from 'path'
+import { fileURLToPath } from 'url'
+import { t } from './i18n.mjs'
+import { SCHEMA_VERSION } from './model.mjs'
+
+export const ROOT = path.resolve(path.dirname(fileURLToPath(import.meta.url)), '..')
+
+/**
+ * @returns {string} version of package.json
+ */
+export function packageVersion() {
+    return JSON.parse(fs.readFileSync(path.join(ROOT, 'package.json'), 'utf8')).version
+}
+
+/**
+ * Short commit hash when running from a git checkout, null for an
+ * installed release.
+ *
+ * @returns {string | null}
+ */
+export function gitCommit() {
+    if(!fs.existsSync(path.join(ROOT, '.git'))) {
+        return null
+    }
+    const result = spawnSync('git', ['rev-parse', '--short', 'HEAD'], { cwd: ROOT, encoding: 'utf8' })
+    return result.status == 0 ? result.stdout.trim() : null
+}
+
+/**
+ * Line printed by `groupgen --version`.
+ */
+export function versionString() {
+    const commit = gitCommit()
+    return t`groupgen ${packageVersion()}${commit ? ` (${commit})` : ''}, schéma de données ${SCHEMA_VERSION}, Node ${process.versions.node}`
+}
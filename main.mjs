@@ -1,38 +1,142 @@
-import fs from 'fs'
-
-const FILE_PATH = './last_brief.json'
-const last_brief = fs.existsSync(FILE_PATH) ? JSON.parse(fs.readFileSync(FILE_PATH)) : null
-
-let peoples = JSON.parse(fs.readFileSync('./students.json'))
-
-/**
- * 
- * @param {string[]} group 
- * @param {string[][] | null} last_brief
- */
-function hasSameGroup(group, last_brief) {
-    if(last_brief == null){
-        return false
-    }
-    return Object.values(last_brief)
-        .some(
-            (oneGroup)=>group
-                .every(
-                    (people)=>oneGroup.includes(people)
-                )
-        )
-}
-
-let groups = []
-
-do{
-    while(peoples.length > 0) {
-        const leader = peoples.pop()
-        const index = Math.floor(Math.random() * peoples.length) - 1
-        const [member] = peoples.splice(index, 1)
-        groups = [...groups, [leader, member]]
-    }
-} while(hasSameGroup(groups, last_brief))
-    
-fs.writeFileSync(FILE_PATH, JSON.stringify(groups))
-console.log("Liste des groupes :", groups)
\ No newline at end of file
+#!/usr/bin/env node
+import fs from 'fs'
+import path from 'path'
+import { parseArgs } from 'util'
+import { COMMANDS, DEFAULT_COMMAND } from './lib/commands/index.mjs'
+import { detectLang, t, useLang } from './lib/i18n.mjs'
+import { configureLogging, log, takeLoggingFlags } from './lib/log.mjs'
+
+const argv = process.argv.slice(2)
+
+// Like --db below, -q/-v/--log-json and --screen-reader apply to every command.
+configureLogging(takeLoggingFlags(argv))
+
+// --lang first, for the messages of the other flags. Without it: SGG_LANG,
+// `lang` of [defaults] once the configuration is read, then the language
+// of LANG when there is a translation for it.
+const langFlag = argv.findIndex((arg) => arg == '--lang' || arg.startsWith('--lang='))
+try {
+    if(langFlag != -1) {
+        const [flag] = argv.splice(langFlag, 1)
+        useLang(flag == '--lang' ? argv.splice(langFlag, 1)[0] ?? '' : flag.slice('--lang='.length))
+    } else {
+        useLang(process.env.SGG_LANG || detectLang() || 'fr')
+    }
+} catch(error) {
+    log.error(`Erreur : ${error.message}`)
+    process.exit(2)
+}
+const screenReaderFlag = argv.indexOf('--screen-reader')
+if(screenReaderFlag != -1) {
+    argv.splice(screenReaderFlag, 1)
+    const { useScreenReader } = await import('./lib/console.mjs')
+    useScreenReader()
+}
+
+// So does --color, over NO_COLOR and the terminal detection.
+const colorFlag = argv.findIndex((arg) => arg == '--color' || arg.startsWith('--color='))
+if(colorFlag != -1) {
+    const [flag] = argv.splice(colorFlag, 1)
+    const { useColor } = await import('./lib/console.mjs')
+    try {
+        useColor(flag == '--color' ? argv.splice(colorFlag, 1)[0] ?? '' : flag.slice('--color='.length))
+    } catch(error) {
+        log.error(t`Erreur : ${error.message}`)
+        process.exit(2)
+    }
+}
+
+// --db applies to every command, so it is taken out before their own options.
+const dbFlag = argv.findIndex((arg) => arg == '--db' || arg.startsWith('--db='))
+if(dbFlag != -1) {
+    const [flag] = argv.splice(dbFlag, 1)
+    const file = flag == '--db' ? argv.splice(dbFlag, 1)[0] : flag.slice('--db='.length)
+    if(!file) {
+        log.error(t`Erreur : chemin manquant après --db`)
+        process.exit(2)
+    }
+    const { useDbPath } = await import('./lib/paths.mjs')
+    useDbPath(file)
+}
+
+// --cohort too: every command then works on that promo of the database.
+const cohortFlag = argv.findIndex((arg) => arg == '--cohort' || arg.startsWith('--cohort='))
+if(cohortFlag != -1) {
+    const [flag] = argv.splice(cohortFlag, 1)
+    const cohort = flag == '--cohort' ? argv.splice(cohortFlag, 1)[0] : flag.slice('--cohort='.length)
+    if(!cohort) {
+        log.error(t`Erreur : nom de cohorte manquant après --cohort`)
+        process.exit(2)
+    }
+    const { useCohort } = await import('./lib/paths.mjs')
+    useCohort(cohort)
+}
+
+const [first, ...rest] = argv
+if(first == '--version' || first == '-V') {
+    const { versionString } = await import('./lib/version.mjs')
+    console.log(versionString())
+    process.exit(0)
+}
+if(first == 'help' || first == '--help' || first == '-h') {
+    const { commandHelp, commandList } = await import('./lib/help.mjs')
+    if(rest[0] != null && Object.hasOwn(COMMANDS, rest[0])) {
+        console.log(commandHelp(rest[0], await COMMANDS[rest[0]]()))
+    } else {
+        const modules = Object.fromEntries(await Promise.all(Object.entries(COMMANDS).map(async ([name, load]) => [name, await load()])))
+        console.log(commandList(modules, DEFAULT_COMMAND))
+    }
+    process.exit(0)
+}
+// `groupgen promo-12.json` generates from that roster.
+const isRoster = (arg) => !Object.hasOwn(COMMANDS, arg) && (fs.existsSync(arg) || /^ldaps?:\/\//.test(arg))
+const name = first != null && !first.startsWith('-') && !isRoster(first) ? first : DEFAULT_COMMAND
+const args = name == first ? rest : argv
+
+if(!Object.hasOwn(COMMANDS, name)) {
+    log.error(t`Commande inconnue : ${name}`)
+    log.error(t`Commandes disponibles : ${Object.keys(COMMANDS).join(', ')} (groupgen --help pour le détail)`)
+    process.exit(2)
+}
+
+try {
+    const command = await COMMANDS[name]()
+    // [defaults] of the configuration, overridden by SGG_* variables, stand
+    // in for flags not given. Paths in the file are relative to it.
+    const { configPath, envDefaults, loadConfig, withDefaults } = await import('./lib/config.mjs')
+    const configFile = configPath()
+    log.verbose(`Configuration : ${configFile}${fs.existsSync(configFile) ? '' : ' (absente)'}`)
+    const fromFile = loadConfig(configFile).defaults ?? {}
+    const fromEnv = envDefaults()
+    const { db, students, locale, cohort, lang, ...defaults } = { ...fromFile, ...fromEnv }
+    const { useCohort, useDbPath, useStudentsPath } = await import('./lib/paths.mjs')
+    const relative = (key, file) => path.isAbsolute(file) || Object.hasOwn(fromEnv, key) ? file : path.join(path.dirname(configFile), file)
+    if(db != null && dbFlag == -1) {
+        useDbPath(relative('db', db))
+    }
+    if(students != null) {
+        useStudentsPath(relative('students', students))
+    }
+    if(cohort != null && cohortFlag == -1) {
+        useCohort(String(cohort))
+    }
+    if(lang != null && langFlag == -1 && !Object.hasOwn(fromEnv, 'lang')) {
+        useLang(String(lang))
+    }
+    if(locale != null) {
+        const { useLocale } = await import('./lib/format.mjs')
+        useLocale(String(locale))
+    }
+    const source = Object.fromEntries(Object.keys(fromEnv).map((key) => [key, `SGG_${key.toUpperCase()}`]))
+    const options = withDefaults(command.options, defaults, source)
+    if(args.includes('--help') || args.includes('-h')) {
+        const { commandHelp } = await import('./lib/help.mjs')
+        console.log(commandHelp(name, { ...command, options }))
+        process.exit(0)
+    }
+    const { values, positionals } = parseArgs({ args, options, allowPositionals: true })
+    await command.run(values, positionals)
+} catch(error) {
+    log.error(t`Erreur : ${error.message}`)
+    process.exitCode = 1
+}
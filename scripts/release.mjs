@@ -0,0 +1,25 @@
+#!/usr/bin/env node
+// Builds the files to attach to a GitHub release: the npm tarball and its
+// SHA256SUMS, as expected by `groupgen self-update`.
+import { spawnSync } from 'child_process'
+import crypto from 'crypto'
+import fs from 'fs'
+import path from 'path'
+import { CHECKSUMS_ASSET } from '../lib/update.mjs'
+import { ROOT } from '../lib/version.mjs'
+
+const dist = path.join(ROOT, 'dist')
+fs.rmSync(dist, { recursive: true, force: true })
+fs.mkdirSync(dist)
+const npm = process.platform == 'win32' ? 'npm.cmd' : 'npm'
+const pack = spawnSync(npm, ['pack', '--pack-destination', dist], { cwd: ROOT, encoding: 'utf8', shell: process.platform == 'win32' })
+if(pack.status != 0) {
+    console.error(pack.stderr)
+    process.exit(1)
+}
+const sums = fs.readdirSync(dist).filter((name) => name.endsWith('.tgz')).map((name) => {
+    const hash = crypto.createHash('sha256').update(fs.readFileSync(path.join(dist, name))).digest('hex')
+    return `${hash}  ${name}\n`
+})
+fs.writeFileSync(path.join(dist, CHECKSUMS_ASSET), sums.join(''))
+console.log(`Fichiers à joindre à la release dans ${dist} :\n${fs.readdirSync(dist).join('\n')}`)
@@ -0,0 +1,1829 @@
+import assert from 'assert/strict'
+import fs from 'fs'
+import http from 'http'
+import net from 'net'
+import { afterEach, beforeEach, describe, test } from 'node:test'
+import { verifyWebhook } from '../lib/publishers/webhook.mjs'
+import { Sandbox, pairsOf } from './harness.mjs'
+
+let sandbox
+
+beforeEach(() => {
+    sandbox = new Sandbox()
+})
+
+afterEach(() => {
+    sandbox.cleanup()
+})
+
+/**
+ * Local HTTP server standing in for the API of a publisher: records each
+ * request and answers with the `[status, body]` that `reply` returns.
+ *
+ * @param {(request: {method: string, url: URL, headers: http.IncomingHttpHeaders, body: string}) => [number, unknown]} reply
+ */
+async function apiServer(reply) {
+    const requests = []
+    const server = http.createServer((request, response) => {
+        let body = ''
+        request.on('data', (chunk) => body += chunk)
+        request.on('end', () => {
+            const received = { method: request.method, url: new URL(request.url, 'http://localhost'), headers: request.headers, body }
+            requests.push(received)
+            const [status, content] = reply(received)
+            response.writeHead(status, { 'content-type': typeof content == 'string' ? 'text/plain' : 'application/json' })
+            response.end(typeof content == 'string' ? content : JSON.stringify(content))
+        })
+    })
+    await new Promise((resolve) => server.listen(0, '127.0.0.1', resolve))
+    return { requests, url: `http://127.0.0.1:${server.address().port}`, close: () => server.close() }
+}
+
+describe('generate', () => {
+    test('records every student exactly once', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        const { status } = sandbox.run([])
+        assert.equal(status, 0)
+
+        const [brief] = sandbox.db().briefs
+        assert.equal(brief.id, 1)
+        assert.deepEqual(brief.groups.flat().sort(), JSON.parse(sandbox.read('students.json')).sort())
+        assert.ok(brief.groups.every((group) => group.length == 2))
+    })
+
+    test('puts the odd student in a trio', () => {
+        sandbox.fixture('promo-5.json', 'students.json')
+        sandbox.run([])
+
+        const [brief] = sandbox.db().briefs
+        assert.deepEqual(brief.groups.map((group) => group.length).sort(), [2, 3])
+    })
+
+    test('avoids the pairs of the previous brief', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.run([])
+        sandbox.run([])
+
+        const [first, second] = sandbox.db().briefs
+        const repeated = [...pairsOf(second.groups)].filter((pair) => pairsOf(first.groups).has(pair))
+        assert.deepEqual(repeated, [])
+    })
+
+    test('refuses an empty roster without creating the database', () => {
+        sandbox.fixture('promo-0.json', 'students.json')
+        const { status, stderr } = sandbox.run([])
+
+        assert.equal(status, 1)
+        assert.match(stderr, /vide/)
+        assert.equal(sandbox.db(), null)
+    })
+
+    test('reports a single student without saving', () => {
+        sandbox.fixture('promo-1.json', 'students.json')
+        const { status, stdout } = sandbox.run([])
+
+        assert.equal(status, 0)
+        assert.match(stdout, /Un seul apprenant/)
+        assert.equal(sandbox.db(), null)
+    })
+
+    test('gives matrix and stats the same treatment of tiny rosters', () => {
+        sandbox.fixture('promo-0.json', 'empty.json')
+        sandbox.fixture('promo-1.json', 'one.json')
+        const empty = sandbox.run(['matrix', '--students', 'empty.json'])
+        assert.equal(empty.status, 1)
+        assert.match(empty.stderr, /vide/)
+        const single = sandbox.run(['matrix', '--students', 'one.json'])
+        assert.equal(single.status, 0)
+        assert.match(single.stdout, /^Un seul apprenant/)
+
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: '2025-01-06T08:00:00.000Z', label: null, groups: [['Alice']] },
+        ] }))
+        const stats = sandbox.run(['stats'])
+        assert.equal(stats.status, 0)
+        assert.match(stats.stdout, /^Un seul apprenant \(Alice\)/)
+    })
+
+    test('rejects rosters above the supported size', () => {
+        const names = Array.from({ length: 501 }, (_, index) => `Student ${index}`)
+        sandbox.write('students.json', JSON.stringify(names))
+        const { status, stderr } = sandbox.run([])
+
+        assert.equal(status, 1)
+        assert.match(stderr, /limite supportée est de 500/)
+    })
+
+    test('asks before using a roster unrelated to the history', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.fixture('last_brief-other-promo.json', 'last_brief.json')
+
+        const refused = sandbox.run([], { input: 'n\n' })
+        assert.equal(refused.status, 1)
+        assert.match(refused.stderr, /Mauvais dossier/)
+        assert.equal(sandbox.db(), null)
+
+        assert.equal(sandbox.run(['--yes']).status, 0)
+        assert.equal(sandbox.db().briefs.length, 2)
+    })
+
+    test('imports a legacy last_brief.json next to the new database', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.fixture('last_brief-legacy.json', 'last_brief.json')
+        sandbox.run([])
+
+        assert.ok(sandbox.find('db.json'))
+        const briefs = sandbox.db().briefs
+        assert.deepEqual(briefs.map((brief) => brief.id), [1, 2])
+        assert.equal(briefs[0].date, null)
+    })
+
+    test('reads the roster given as argument or with --students', () => {
+        sandbox.fixture('promo-5.json', 'promo-a.json')
+        sandbox.fixture('promo-12.json', 'promo-b.json')
+
+        assert.equal(sandbox.run(['promo-a.json']).status, 0)
+        assert.equal(sandbox.run(['generate', '--students', 'promo-b.json', '--yes']).status, 0)
+        const [a, b] = sandbox.db().briefs
+        assert.deepEqual(a.groups.flat().sort(), JSON.parse(sandbox.read('promo-a.json')).sort())
+        assert.deepEqual(b.groups.flat().sort(), JSON.parse(sandbox.read('promo-b.json')).sort())
+        assert.equal(sandbox.run(['promo-c.json']).status, 2)
+    })
+
+    test('forms groups of --group-size and counts every pair inside them', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.run(['--group-size', '4'])
+        sandbox.run(['--group-size', '4'])
+
+        const [first, second] = sandbox.db().briefs
+        assert.deepEqual(first.groups.map((group) => group.length), [4, 4, 4])
+        assert.equal(first.context.group_size, 4)
+        const repeated = [...pairsOf(second.groups)].filter((pair) => pairsOf(first.groups).has(pair))
+        assert.equal(second.context.repeats, repeated.length)
+        assert.ok(repeated.length < 6)
+        assert.match(sandbox.run(['--group-size', '1']).stderr, /Taille de groupe invalide : 1/)
+    })
+
+    test('breaks ties between equally good groupings with --tie-break', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: null, label: null, groups: [['Alice', 'Bob'], ['Carol', 'Dan']] },
+            { id: 2, date: null, label: null, groups: [['Alice', 'Carol'], ['Bob', 'Dan']] },
+        ] }))
+        sandbox.run(['--tie-break', 'new-pairs'])
+
+        const { groups, context } = sandbox.db().briefs[2]
+        assert.deepEqual([...pairsOf(groups)].sort(), [...pairsOf([['Alice', 'Dan'], ['Bob', 'Carol']])].sort())
+        assert.equal(context.tie_break, 'new-pairs')
+        const draw = (seed) => JSON.parse(sandbox.run(['--tie-break', 'alphabetical', '--seed', seed, '--format', 'json', '--dry-run']).stdout).groups
+        assert.deepEqual(draw('1'), [['Alice', 'Bob'], ['Carol', 'Dan']])
+        assert.deepEqual(draw('2'), draw('1'))
+        assert.match(sandbox.run(['--tie-break', 'random']).stderr, /Départage invalide : random \(none, new-pairs, churn, alphabetical\)/)
+    })
+
+    test('takes defaults from the user configuration file', () => {
+        sandbox.fixture('promo-12.json')
+        sandbox.write('config/simplon-group-generator/groupgen.toml', '[defaults]\nstudents = "../../promo-12.json"\ndb = "../../promo-12/"\ngroup_size = 3\n')
+        sandbox.run([])
+        sandbox.run(['--group-size', '4'])
+
+        const [first, second] = JSON.parse(sandbox.read('promo-12/db.json')).briefs
+        assert.deepEqual(first.groups.map((group) => group.length), [3, 3, 3, 3])
+        assert.equal(second.context.group_size, 4)
+        assert.match(sandbox.run(['quick', '--help']).stdout, /--group-size <valeur> \(défaut : 3\)/)
+    })
+
+    test('lets SGG_* variables override the configuration file', () => {
+        sandbox.fixture('promo-12.json')
+        sandbox.write('groupgen.toml', '[defaults]\ngroup_size = 4\nstudents = "missing.json"\n')
+        const env = { SGG_GROUP_SIZE: '3', SGG_STUDENTS: 'promo-12.json' }
+        assert.equal(sandbox.run([], { env }).status, 0)
+        sandbox.run([], { env: { ...env, SGG_DRY_RUN: 'oui' } })
+
+        const briefs = sandbox.db().briefs
+        assert.equal(briefs.length, 1)
+        assert.equal(briefs[0].context.group_size, 3)
+        assert.match(sandbox.run([], { env: { SGG_DRY_RUN: 'peut-être' } }).stderr, /SGG_DRY_RUN invalide : "peut-être"/)
+    })
+
+    test('offers to fix copy-paste accidents in the roster', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice BobMartin', { name: 'carol.dan@example.org', avatar: 'c.png' }, 'Eve,', 'Paul McCartney']))
+        const { status, stdout } = sandbox.run([], { input: '1\n1\n0\n' })
+
+        assert.equal(status, 0)
+        assert.match(stdout, /« Alice BobMartin » ressemble à une erreur de copier-coller \(noms collés\)/)
+        assert.doesNotMatch(stdout, /McCartney » ressemble/)
+        assert.deepEqual(JSON.parse(sandbox.read('students.json')), ['Alice Bob', 'Martin', { name: 'Carol Dan', avatar: 'c.png' }, 'Eve,', 'Paul McCartney'])
+        assert.deepEqual(sandbox.db().briefs[0].groups.flat().sort(), ['Alice Bob', 'Carol Dan', 'Eve,', 'Martin', 'Paul McCartney'])
+
+        assert.match(sandbox.run(['--yes']).stderr, /« Eve, » ressemble à une erreur de copier-coller \(ponctuation en trop\), peut-être « Eve »/)
+    })
+
+    test('draws the same groups again from the same seed and history', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.run([])
+        const preview = sandbox.run(['--seed', '18446744', '--dry-run']).stdout
+        assert.equal(sandbox.run(['--seed', '18446744', '--dry-run']).stdout, preview)
+
+        const saved = sandbox.run(['--seed', '18446744']).stdout
+        assert.equal(saved.split('Brief n°2 enregistré')[0], preview.split('Simulation')[0])
+        assert.equal(sandbox.db().briefs[1].context.seed, 18446744)
+        assert.equal(typeof sandbox.db().briefs[0].context.seed, 'number')
+    })
+
+    test('asks before saving with --confirm, r drawing again', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        const declined = sandbox.run(['--confirm'], { input: 'n\n' })
+        assert.equal(declined.status, 0)
+        assert.match(declined.stdout, /Enregistrer ces groupes \? \[o\/N\/r\] Résultat non enregistré\./)
+        assert.equal(sandbox.find('db.json', 'data/simplon-group-generator/db.json'), null)
+
+        const { stdout } = sandbox.run(['--confirm', '--seed', '5'], { input: 'r\no\n' })
+        const [, seed] = stdout.match(/Nouveau tirage, graine (\d+) :/)
+        assert.equal(sandbox.db().briefs[0].context.seed, Number(seed))
+        assert.equal(sandbox.db().briefs.length, 1)
+    })
+
+    test('scaffolds a directory per group with --scaffold', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('groupgen.toml', '[scaffold]\ndeliverables = ["Maquette", "Code source"]\n')
+        assert.equal(sandbox.run(['--scaffold', 'briefs/brief-1']).status, 0)
+        const first = sandbox.db().briefs[0]
+        assert.equal(sandbox.read('briefs/brief-1/names.txt'), 'brief-1-groupe-01\nbrief-1-groupe-02\n')
+        assert.match(sandbox.read('briefs/brief-1/groupe-02/README.md'), new RegExp(`^# Groupe 2 du brief n°1\n\n## Membres\n\n- ${first.groups[1].sort().join('\n- ')}\n\n## Livrables\n\n- \\[ \\] Maquette\n- \\[ \\] Code source\n$`))
+
+        sandbox.write('template/docs/TODO.md', 'Brief {{brief}}, groupe {{group}} : {{members}}\n{{deliverables}}\n')
+        sandbox.write('groupgen.toml', '[scaffold]\ntemplate = "template"\ndeliverables = ["Démo"]\n')
+        assert.match(sandbox.run(['--scaffold', 'briefs/brief-1']).stderr, /briefs\/brief-1 n'est pas vide/)
+        sandbox.run(['--scaffold', 'briefs/brief-2'])
+        const second = sandbox.db().briefs[1]
+        assert.equal(sandbox.read('briefs/brief-2/groupe-01/docs/TODO.md'), `Brief 2, groupe 1 : ${second.groups[0].sort().join(', ')}\n- [ ] Démo\n`)
+    })
+
+    test('leaves out the students given to --exclude and --exclude-file', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan', 'Eve', 'Frank']))
+        sandbox.write('absences.txt', 'Eve\nFrank\n')
+
+        assert.match(sandbox.run(['--exclude', 'Zoé']).stderr, /Absent\(s\) inconnu\(s\) de la liste des apprenants : Zoé/)
+        const { status, stderr } = sandbox.run(['--exclude', 'Alice', '--exclude-file', 'absences.txt'])
+        assert.equal(status, 0)
+        assert.match(stderr, /laissé\(s\) de côté pour ce brief : Alice, Eve, Frank/)
+        const [brief] = sandbox.db().briefs
+        assert.deepEqual(brief.groups.flat().sort(), ['Bob', 'Carol', 'Dan'])
+        assert.deepEqual(brief.context.excluded, ['Alice', 'Eve', 'Frank'])
+    })
+
+    test('names the brief with --name, shown in history and exports', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        assert.match(sandbox.run(['--name', 'Brief React #3']).stdout, /Brief n°1 « Brief React #3 » enregistré\./)
+
+        assert.equal(sandbox.db().briefs[0].label, 'Brief React #3')
+        assert.match(sandbox.run(['history']).stdout, /n°1 .*\| Brief React #3\n/)
+        assert.match(sandbox.run(['export', '--csv']).stdout, /^1,[^,]+,Brief React #3,1,/m)
+    })
+
+    test('prints the groups alone on stdout with --format', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+
+        const json = sandbox.run(['--format', 'json', '--seed', '1'])
+        assert.equal(json.status, 0)
+        assert.deepEqual(JSON.parse(json.stdout), { schema_version: 1, groups: sandbox.db().briefs[0].groups.map((group) => group.sort()) })
+        assert.match(json.stderr, /Brief n°1 enregistré/)
+
+        const csv = sandbox.run(['--format', 'csv', '--dry-run'])
+        assert.match(csv.stdout, /^group_number,member_1,member_2\r\n1,\w+,\w+\r\n2,\w+,\w+\n$/)
+        assert.match(sandbox.run(['--format', 'markdown', '--dry-run']).stdout, /^\| Groupe \| Membres \|\n\| --- \| --- \|\n\| 1 \| \w+, \w+ \|\n/)
+        assert.match(sandbox.run(['--format', 'yaml']).stderr, /--format invalide : yaml \(table, json, csv, markdown\)/)
+    })
+
+    test('writes the groups to --output in the format of its extension', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+
+        const { stdout } = sandbox.run(['--output', 'groups.md', '--seed', '1'])
+        assert.match(stdout, /Groupe 1/)
+        assert.match(stdout, /Groupes écrits dans groups\.md\./)
+        const [first, second] = sandbox.db().briefs[0].groups.map((group) => group.sort().join(', '))
+        assert.equal(sandbox.read('groups.md'), `| Groupe | Membres |\n| --- | --- |\n| 1 | ${first} |\n| 2 | ${second} |\n`)
+        sandbox.run(['-o', 'out/groups.txt', '--format', 'json', '--dry-run'])
+        const written = JSON.parse(sandbox.read('out/groups.txt'))
+        assert.deepEqual([written.schema_version, written.groups.length], [1, 2])
+        assert.match(sandbox.run(['--output', 'groups.yaml']).stderr, /Format de groups\.yaml inconnu/)
+        assert.equal(sandbox.db().briefs.length, 1)
+    })
+
+    test('stops the search at --iterations', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.run([])
+        sandbox.write('groupgen.toml', '[[constraints]]\ntype = "never"\nstudents = ["Alice", "Bob"]\n')
+        sandbox.run(['--iterations', '1'])
+
+        assert.equal(sandbox.db().briefs[1].context.iterations, 1)
+        assert.match(sandbox.run(['--time-budget', '0']).stderr, /--time-budget invalide : 0/)
+    })
+
+    test('saves nothing with --dry-run', () => {
+        sandbox.fixture('promo-5.json', 'students.json')
+        sandbox.write('groupgen.toml', '[[constraints]]\ntype = "never"\nstudents = ["Alice", "Bob"]\n')
+        const { status, stdout } = sandbox.run(['--dry-run'])
+
+        assert.equal(status, 0)
+        assert.match(stdout, /brief non enregistré/)
+        assert.equal(sandbox.db(), null)
+    })
+
+    test('keeps the history in the --db file', () => {
+        sandbox.fixture('promo-5.json', 'students.json')
+        sandbox.run(['--db', 'promo-a.json'])
+        sandbox.run(['--db=promo-a.json'])
+
+        assert.equal(sandbox.db(), null)
+        assert.equal(JSON.parse(sandbox.read('promo-a.json')).briefs.length, 2)
+        assert.match(sandbox.run(['history', '--db', 'promo-a.json']).stdout, /^n°2 /)
+    })
+})
+
+describe('quick', () => {
+    test('does not touch the database without --save, until adopted', () => {
+        const { stdout } = sandbox.run(['quick', 'Alice,Bob,Carol,Dan'])
+        assert.equal(sandbox.db(), null)
+
+        const token = stdout.match(/adopt (\w+)/)[1]
+        assert.equal(sandbox.run(['adopt', token]).status, 0)
+        assert.equal(sandbox.db().briefs.length, 1)
+        assert.notEqual(sandbox.run(['adopt', token]).status, 0)
+    })
+
+    test('keeps the session when the history cannot be written', () => {
+        const { stdout } = sandbox.run(['quick', 'Alice,Bob,Carol,Dan'])
+        const token = stdout.match(/adopt (\w+)/)[1]
+
+        const data = 'data/simplon-group-generator'
+        // The temporary file of the atomic write gets a name too long to create.
+        const failed = sandbox.run(['--db', `${data}/${'d'.repeat(245)}.json`, 'adopt', token])
+        assert.equal(failed.status, 1)
+        assert.match(failed.stderr, /ENAMETOOLONG/)
+        assert.deepEqual(JSON.parse(sandbox.read(`${data}/sessions.json`)).map((session) => session.token), [token])
+
+        assert.equal(sandbox.run(['adopt', token]).status, 0)
+        assert.equal(sandbox.db().briefs.length, 1)
+        assert.deepEqual(JSON.parse(sandbox.read(`${data}/sessions.json`)), [])
+    })
+
+    test('refuses an empty list', () => {
+        assert.equal(sandbox.run(['quick', ' , ']).status, 1)
+    })
+
+    test('--save records the brief', () => {
+        sandbox.run(['quick', 'Alice,Bob', '--save'])
+        assert.deepEqual(sandbox.db().briefs[0].groups.flat().sort(), ['Alice', 'Bob'])
+    })
+})
+
+describe('brief clone', () => {
+    test('copies the composition under a new id', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.run([])
+        sandbox.run(['brief', 'clone', '1', '--label', 'suite'])
+
+        const [original, clone] = sandbox.db().briefs
+        assert.equal(clone.id, 2)
+        assert.equal(clone.label, 'suite')
+        assert.deepEqual(clone.groups, original.groups)
+        assert.equal(clone.cloned_from, 1)
+        assert.equal(original.cloned_from, undefined)
+        assert.match(sandbox.run(['history']).stdout, /n°2 .*copie du n°1/)
+        assert.match(sandbox.run(['explain', '--brief', '2']).stdout, /Copie des groupes du brief n°1 \(brief clone\), pas un nouveau tirage/)
+    })
+
+    test('fails on an unknown brief', () => {
+        const { status, stderr } = sandbox.run(['brief', 'clone', '42'])
+        assert.equal(status, 1)
+        assert.match(stderr, /introuvable/)
+    })
+
+    test('rejects ids that are not positive integers and inherited action names', () => {
+        for(const id of ['abc', '1.5', '0']) {
+            const { status, stderr } = sandbox.run(['brief', 'clone', id])
+            assert.equal(status, 1)
+            assert.match(stderr, new RegExp(`Identifiant de brief invalide : ${id}`))
+        }
+        for(const action of ['constructor', 'toString']) {
+            assert.match(sandbox.run(['brief', action, '1']).stderr, new RegExp(`Action inconnue : ${action}, actions disponibles`))
+        }
+    })
+})
+
+describe('brief close', () => {
+    test('closes the brief and keeps threads it could not close', () => {
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: null, label: null, groups: [['Alice', 'Bob'], ['Carol', 'Dan']], threads: [{ group: 1, channel: 'gone', id: '42' }] },
+            { id: 2, date: null, label: null, groups: [['Alice', 'Carol'], ['Bob', 'Dan']] },
+        ] }))
+
+        const { status, stderr } = sandbox.run(['brief', 'close', '1'])
+        assert.equal(status, 1)
+        assert.match(stderr, /groupe 2 : Canal inconnu : gone/)
+        assert.equal(sandbox.run(['brief', 'close', '2']).stdout, 'Brief n°2 clos.\n')
+        const [first, second] = sandbox.db().briefs
+        assert.ok(first.closed_at && second.closed_at)
+        assert.equal(first.threads[0].closed, undefined)
+    })
+})
+
+describe('brief categories', () => {
+    test('matches a brief against the past briefs of the categories of its profile', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('groupgen.toml', '[categories.project]\ncount = ["project"]\n')
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: null, label: null, groups: [['Alice', 'Bob'], ['Carol', 'Dan']], category: 'project' },
+            { id: 2, date: null, label: null, groups: [['Alice', 'Carol'], ['Bob', 'Dan']] },
+        ] }))
+        sandbox.run(['--category', 'project', '--seed', '1'])
+
+        const brief = sandbox.db().briefs[2]
+        assert.equal(brief.category, 'project')
+        assert.equal(brief.context.previous_brief, 1)
+        assert.deepEqual(brief.context.counted_categories, ['project'])
+        assert.ok([...pairsOf(brief.groups)].every((pair) => !pairsOf([['Alice', 'Bob'], ['Carol', 'Dan']]).has(pair)))
+        assert.match(sandbox.run(['explain', '--brief', '3']).stdout, /Catégorie : project, seuls les briefs project pris en compte\./)
+
+        assert.equal(sandbox.run(['brief', 'categorize', '2', 'workshop']).stdout, 'Brief n°2 classé dans workshop.\n')
+        assert.equal(sandbox.db().briefs[1].category, 'workshop')
+        assert.match(sandbox.run(['history']).stdout, /n°3 .*\| project/)
+        assert.match(sandbox.run(['--category', 'talk']).stderr, /Catégorie inconnue : talk \(solo-kata, project, workshop/)
+    })
+})
+
+describe('brief lifecycle', () => {
+    test('keeps drafts out of the history until they are activated', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('groupgen.toml', '[lifecycle]\non_close = ["archive"]\n\n[[channels]]\nname = "archive"\ntype = "file"\npath = "archive/brief-{id}.md"\n')
+        sandbox.run(['--seed', '1'])
+
+        assert.match(sandbox.run(['--draft', '--seed', '2']).stdout, /Brouillon n°2 enregistré/)
+        assert.match(sandbox.run(['history']).stdout, /n°2 .*\| brouillon\n/)
+        assert.match(sandbox.run(['status']).stdout, /Dernier brief : n°1 .*\nBrouillon\(s\) en attente : n°2/)
+        assert.equal(sandbox.run(['brief', 'close', '2']).status, 1)
+
+        assert.equal(sandbox.run(['brief', 'activate', '2']).stdout, 'Brief n°2 activé.\n')
+        assert.equal(sandbox.db().briefs[1].state, undefined)
+        assert.equal(sandbox.run(['brief', 'close', '2']).status, 0)
+        assert.equal(sandbox.db().briefs[1].state, 'closed')
+        assert.ok(sandbox.find('archive/brief-2.md'))
+        assert.match(sandbox.run(['drop', 'Alice', '--brief', '2']).stderr, /est clos/)
+    })
+})
+
+describe('teams', () => {
+    test('freezes teams and rotates roles until the phase ends', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.run([])
+
+        const created = sandbox.run(['teams', 'create', '--names', 'Alpha,Bravo', '--roles', 'Scrum master'])
+        assert.equal(created.status, 0)
+        assert.match(created.stdout, /Alpha :\n {2}\S+ \(Scrum master\)\n {2}\S+\n/)
+
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan', 'Eve']))
+        const skipped = sandbox.run([])
+        assert.match(skipped.stdout, /pas de nouveau brief/)
+        assert.match(skipped.stderr, /Sans équipe : Eve/)
+        assert.equal(sandbox.db().briefs.length, 1)
+
+        assert.match(sandbox.run(['teams', 'rotate-roles']).stdout, /Alpha :\n {2}\S+\n {2}\S+ \(Scrum master\)\n/)
+        assert.equal(sandbox.run(['teams', 'end']).status, 0)
+        sandbox.run([])
+        assert.equal(sandbox.db().briefs.length, 2)
+    })
+})
+
+describe('teams pairs', () => {
+    test('rotates pairs inside each team until everyone met', () => {
+        const teams = [['Alice', 'Bob', 'Carol', 'Dan'], ['Eve', 'Fay', 'Gus', 'Hal']]
+        sandbox.write('students.json', JSON.stringify(teams.flat()))
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [{ id: 1, date: null, label: null, groups: teams }] }))
+        assert.equal(sandbox.run(['teams', 'create']).status, 0)
+
+        for(let day = 0; day < 3; day++) {
+            assert.equal(sandbox.run(['teams', 'pairs']).status, 0)
+        }
+        for(const [index, team] of sandbox.db().teams[0].teams.entries()) {
+            const rotations = team.pairs.map((rotation) => rotation.groups)
+            assert.ok(rotations.flat(2).every((name) => teams[index].includes(name)))
+            assert.equal(pairsOf(rotations.flat()).size, 6)
+        }
+    })
+})
+
+describe('trash', () => {
+    test('keeps deleted briefs until restored or emptied', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.run([])
+        sandbox.run([])
+
+        assert.equal(sandbox.run(['brief', 'delete', '2']).status, 0)
+        assert.deepEqual(sandbox.db().briefs.map((brief) => brief.id), [1])
+        sandbox.run([])
+        assert.deepEqual(sandbox.db().briefs.map((brief) => brief.id), [1, 3])
+        assert.match(sandbox.run(['trash', 'list']).stdout, /^n°2 du \d{2}\/\d{2}\/\d{4}, supprimé le /)
+
+        assert.equal(sandbox.run(['trash', 'restore', '2']).status, 0)
+        assert.deepEqual(sandbox.db().briefs.map((brief) => brief.id), [1, 2, 3])
+
+        sandbox.run(['brief', 'delete', '1'])
+        assert.match(sandbox.run(['trash', 'empty']).stdout, /Rien à effacer/)
+        assert.match(sandbox.run(['trash', 'empty', '--all']).stdout, /1 brief\(s\) effacé\(s\) définitivement/)
+        assert.deepEqual(sandbox.db().trash, [])
+    })
+})
+
+describe('constraints', () => {
+    test('add builds a constraint interactively and generate honours it', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        const { status } = sandbox.run(['constraints', 'add'], { input: '2\nali\nnij\n\n' })
+        assert.equal(status, 0)
+        assert.deepEqual(sandbox.db().constraints[0].students, ['Alice', 'Niaj'])
+
+        sandbox.run([])
+        const { briefs: [brief], constraints: [constraint] } = sandbox.db()
+        assert.ok(brief.groups.some((group) => group.includes('Alice') && group.includes('Niaj')))
+        assert.equal(constraint.first_brief, 1)
+        assert.equal(constraint.last_brief, 1)
+    })
+
+    test('remove keeps the constraint in the history', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.run(['constraints', 'add'], { input: '1\nali\nbob\n2030-01-01\n' })
+        sandbox.run(['constraints', 'remove', '1'])
+
+        assert.ok(sandbox.db().constraints[0].removed_at)
+        assert.match(sandbox.run(['constraints', 'list']).stdout, /Aucune contrainte en vigueur/)
+        assert.match(sandbox.run(['constraints', 'list', '--all']).stdout, /1\. Alice et Bob : jamais ensemble jusqu'au 01\/01\/2030 \(créée le .*, retirée le .*, jamais appliquée\)/)
+    })
+
+    test('imports constraints from groupgen.toml and ignores expired ones', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.write('groupgen.toml', '[[constraints]]\ntype = "together"\nstudents = ["Alice", "Bob"]\nexpires = "2000-01-01"\n')
+        const { stdout } = sandbox.run(['constraints', 'list', '--all'])
+        assert.match(stdout, /Alice et Bob : toujours ensemble jusqu'au 01\/01\/2000/)
+        assert.equal(sandbox.db().constraints.length, 1)
+
+        sandbox.run(['--log-file', 'run.log'])
+        assert.doesNotMatch(sandbox.read('run.log'), /toujours ensemble/)
+    })
+
+    test('keeps confidential pairs apart without showing them outside --admin', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        const env = { SGG_ADMIN_KEY: 'secret' }
+        assert.equal(sandbox.run(['constraints', 'add', '--admin'], { input: 'ali\nbob\nconflit familial\n', env }).status, 0)
+        assert.doesNotMatch(JSON.stringify(sandbox.db()), /Alice|conflit/)
+        assert.match(sandbox.run(['constraints', 'list', '--admin'], { env }).stdout, /1\. Alice et Bob : jamais ensemble \(conflit familial\)/)
+        assert.match(sandbox.run(['constraints', 'list']).stdout, /Aucune contrainte en vigueur/)
+        assert.match(sandbox.run(['constraints', 'list', '--admin'], { env: { SGG_ADMIN_KEY: 'wrong' } }).stderr, /clé SGG_ADMIN_KEY incorrecte/)
+
+        assert.match(sandbox.run(['--yes']).stderr, /Registre confidentiel chiffré : définissez SGG_ADMIN_KEY/)
+        for(let seed = 1; seed <= 5; seed++) {
+            assert.equal(sandbox.run(['--yes', '--seed', String(seed)], { env }).status, 0)
+        }
+        const { briefs } = sandbox.db()
+        assert.ok(briefs.every((brief) => !brief.groups.some((group) => group.includes('Alice') && group.includes('Bob'))))
+        assert.deepEqual(briefs[0].context.constraints, [])
+        assert.doesNotMatch(sandbox.run(['explain', '1']).stdout, /jamais/)
+    })
+})
+
+describe('explain', () => {
+    test('reports the context stored with a generated brief', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.run(['constraints', 'add'], { input: '1\nali\nbob\n\n' })
+        sandbox.run([])
+        sandbox.run([])
+
+        const { stdout } = sandbox.run(['explain', '--brief', '2'])
+        assert.match(stdout, /12 apprenants, binômes du brief n°1 évités/)
+        assert.match(stdout, /n°1 Alice et Bob : jamais ensemble/)
+        assert.match(stdout, /1\. .* : aucun binôme répété/)
+    })
+
+    test('says so when no context was recorded', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.fixture('last_brief-legacy.json', 'last_brief.json')
+        sandbox.run([])
+
+        assert.match(sandbox.run(['explain', '--brief', '1']).stdout, /Aucun contexte/)
+    })
+})
+
+describe('export --html', () => {
+    test('lays out every group with avatars or initials', () => {
+        sandbox.write('students.json', JSON.stringify([
+            { name: 'Alice', avatar: 'https://example.org/a.png' },
+            'Bob <script>',
+        ]))
+        sandbox.run([])
+
+        const { status, stdout } = sandbox.run(['export', '--html'])
+        assert.equal(status, 0)
+        assert.match(stdout, /<img src="https:\/\/example.org\/a.png"/)
+        assert.match(stdout, /<span class="initials">B/)
+        assert.match(stdout, /Bob &lt;script&gt;/)
+    })
+})
+
+describe('publish', () => {
+    test('fans out to channels and reports each one', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.write('groupgen.toml', [
+            '[[channels]]', 'name = "archive"', 'type = "file"', 'path = "out/brief-{id}.json"',
+            '[[channels]]', 'name = "chat"', 'type = "discord"', 'webhook_url_env = "SGG_TEST_UNSET"',
+        ].join('\n'))
+        sandbox.run([])
+
+        const { status, stdout, stderr } = sandbox.run(['publish', '--all-channels'])
+        assert.equal(status, 1)
+        assert.match(stdout, /archive \(Fichier\) : publié/)
+        assert.match(stderr, /chat \(discord\) : échec, Variable d'environnement SGG_TEST_UNSET manquante/)
+        assert.match(stderr, /1\/2 canaux publiés/)
+
+        const document = JSON.parse(sandbox.read('out/brief-1.json'))
+        assert.equal(document.schema_version, 1)
+        assert.equal(document.id, 1)
+    })
+
+    test('queues failed announcements until --retry-pending delivers them', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.write('groupgen.toml', '[[channels]]\nname = "chat"\ntype = "discord"\nwebhook_url_env = "SGG_TEST_UNSET"\n')
+        sandbox.run([])
+
+        assert.match(sandbox.run(['publish', '--channel', 'chat']).stderr, /1 annonce\(s\) mise\(s\) en attente/)
+        const [pending] = sandbox.db().pending_publications
+        assert.equal(pending.brief, 1)
+        assert.equal(pending.channel, 'chat')
+
+        assert.equal(sandbox.run(['publish', '--retry-pending']).status, 1)
+        assert.equal(sandbox.db().pending_publications[0].attempts, 2)
+
+        sandbox.write('groupgen.toml', '[[channels]]\nname = "chat"\ntype = "file"\npath = "brief-{id}.md"\n')
+        const { status, stdout } = sandbox.run(['publish', '--retry-pending'])
+        assert.equal(status, 0)
+        assert.match(stdout, /chat \(Fichier\), brief n°1 : publié/)
+        assert.equal(sandbox.db().pending_publications, undefined)
+        assert.match(sandbox.run(['publish', '--retry-pending']).stdout, /Aucune annonce en attente/)
+    })
+})
+
+describe('generate --publish', () => {
+    test('keeps the brief pending or rolls it back when announcing fails', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.write('groupgen.toml', [
+            '[[channels]]', 'name = "archive"', 'type = "file"', 'path = "brief-{id}.md"',
+            '[[channels]]', 'name = "chat"', 'type = "discord"', 'webhook_url_env = "SGG_TEST_UNSET"',
+        ].join('\n'))
+
+        assert.equal(sandbox.run(['--publish', 'archive']).status, 0)
+        assert.match(sandbox.read('brief-1.md'), /Brief n°1/)
+
+        assert.equal(sandbox.run(['--publish', 'chat']).status, 1)
+        assert.deepEqual(sandbox.db().pending_publications.map(({ brief, channel }) => [brief, channel]), [[2, 'chat']])
+
+        const { status, stderr } = sandbox.run(['--publish', 'chat', '--on-publish-failure', 'rollback'])
+        assert.equal(status, 1)
+        assert.match(stderr, /Aucun canal n'a publié le brief n°3 : son enregistrement est annulé/)
+        assert.deepEqual(sandbox.db().briefs.map((brief) => brief.id), [1, 2])
+    })
+
+    test('saves the brief now and announces it at --publish-at', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.write('groupgen.toml', '[[channels]]\nname = "archive"\ntype = "file"\npath = "brief-{id}.md"\n')
+
+        assert.match(sandbox.run(['--publish', 'archive', '--publish-at', '2000-01-01T09:00']).stderr, /déjà passé/)
+        const { status, stdout } = sandbox.run(['--publish', 'archive', '--publish-at', '2999-03-10T09:00'])
+        assert.equal(status, 0)
+        assert.match(stdout, /Annonce programmée le 10\/03\/2999 09:00 sur 1 canal/)
+        assert.equal(sandbox.find('brief-1.md'), null)
+        assert.match(sandbox.run(['publish', '--retry-pending']).stdout, /1 annonce\(s\) programmée\(s\), la prochaine le 10\/03\/2999 09:00/)
+
+        const db = sandbox.db()
+        db.pending_publications[0].publish_at = '2001-01-01T08:00:00.000Z'
+        sandbox.write('data/simplon-group-generator/db.json', JSON.stringify(db))
+        assert.match(sandbox.run(['publish', '--retry-pending']).stdout, /archive \(Fichier\), brief n°1 : publié/)
+        assert.match(sandbox.read('brief-1.md'), /Brief n°1/)
+        assert.equal(sandbox.db().pending_publications, undefined)
+    })
+})
+
+describe('webhook', () => {
+    test('signs payloads so that receivers can check where they come from', async () => {
+        const received = []
+        const server = http.createServer((request, response) => {
+            let body = ''
+            request.on('data', (chunk) => body += chunk)
+            request.on('end', () => {
+                received.push({ headers: request.headers, body })
+                response.end()
+            })
+        })
+        await new Promise((resolve) => server.listen(0, '127.0.0.1', resolve))
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('groupgen.toml', `[[channels]]\nname = "hook"\ntype = "webhook"\nurl = "http://127.0.0.1:${server.address().port}/"\nsecret_env = "HOOK_SECRET"\n`)
+        sandbox.run([])
+        const { status } = await sandbox.runAsync(['publish', '--channel', 'hook'], { env: { HOOK_SECRET: 's3cret' } })
+        server.close()
+
+        assert.equal(status, 0)
+        const [{ headers, body }] = received
+        assert.equal(JSON.parse(body).id, 1)
+        assert.match(headers['x-groupgen-signature'], /^sha256=[0-9a-f]{64}$/)
+        assert.ok(verifyWebhook('s3cret', body, headers))
+        assert.ok(!verifyWebhook('other', body, headers))
+        assert.ok(!verifyWebhook('s3cret', body.replace('Alice', 'Mallory'), headers))
+        assert.ok(!verifyWebhook('s3cret', body, headers, { now: Date.now() + 3600_000 }))
+    })
+})
+
+describe('trello', () => {
+    test('adds a list named after the brief with a card per group, and reports API errors', async () => {
+        const api = await apiServer(({ url }) => {
+            if(url.searchParams.get('token') != 'tok') {
+                return [401, 'invalid token']
+            }
+            return url.pathname == '/lists' ? [200, { id: 'L1', name: url.searchParams.get('name') }] : [200, { id: 'C1' }]
+        })
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('groupgen.toml', `[[channels]]\nname = "board"\ntype = "trello"\nboard = "B42"\napi_url = "${api.url}"\n`)
+        sandbox.run(['--name', 'React'])
+        const groups = sandbox.db().briefs[0].groups
+
+        const published = await sandbox.runAsync(['publish', '--channel', 'board'], { env: { TRELLO_KEY: 'k', TRELLO_TOKEN: 'tok' } })
+        assert.equal(published.status, 0, published.stderr)
+        assert.match(published.stdout, /board \(Trello\) : publié \(liste « Brief n°1 : React » créée\)/)
+        const [list, ...cards] = api.requests
+        assert.deepEqual([list.method, list.url.pathname], ['POST', '/lists'])
+        assert.deepEqual(Object.fromEntries(list.url.searchParams), { key: 'k', token: 'tok', idBoard: 'B42', name: 'Brief n°1 : React', pos: 'bottom' })
+        assert.deepEqual(cards.map((card) => [card.url.pathname, card.url.searchParams.get('idList'), card.url.searchParams.get('name'), card.url.searchParams.get('desc')]),
+            groups.map((group, index) => ['/cards', 'L1', `Groupe ${index + 1}`, group.map((name) => `- ${name}`).join('\n')]))
+
+        const refused = await sandbox.runAsync(['publish', '--channel', 'board'], { env: { TRELLO_KEY: 'k', TRELLO_TOKEN: 'bad' } })
+        assert.equal(refused.status, 1)
+        assert.match(refused.stderr, /board \(Trello\) : échec, HTTP 401 sur 127\.0\.0\.1:\d+ : invalid token/)
+        const missing = await sandbox.runAsync(['publish', '--channel', 'board'], { env: { TRELLO_KEY: '', TRELLO_TOKEN: 'tok' } })
+        assert.match(missing.stderr, /board \(Trello\) : échec, Variable d'environnement TRELLO_KEY manquante/)
+        api.close()
+        assert.equal(api.requests.length, 1 + groups.length + 1)
+    })
+})
+
+describe('github-project', () => {
+    test('adds a draft item per group to the project, and reports GraphQL errors', async () => {
+        const api = await apiServer(({ headers, body }) => {
+            if(headers.authorization != 'Bearer gh-token') {
+                return [401, { message: 'Bad credentials' }]
+            }
+            const { variables } = JSON.parse(body)
+            return variables.project == 'PVT_1'
+                ? [200, { data: { addProjectV2DraftIssue: { projectItem: { id: 'PVTI_1' } } } }]
+                : [200, { data: null, errors: [{ message: `Could not resolve to a node with the global id of '${variables.project}'` }] }]
+        })
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('groupgen.toml', [
+            '[[channels]]', 'name = "board"', 'type = "github-project"', 'project = "PVT_1"', `api_url = "${api.url}/graphql"`,
+            '[[channels]]', 'name = "other"', 'type = "github-project"', 'project = "PVT_404"', `api_url = "${api.url}/graphql"`,
+        ].join('\n'))
+        sandbox.run([])
+        const groups = sandbox.db().briefs[0].groups
+
+        const published = await sandbox.runAsync(['publish', '--channel', 'board'], { env: { GITHUB_TOKEN: 'gh-token' } })
+        assert.equal(published.status, 0, published.stderr)
+        assert.match(published.stdout, /board \(GitHub Projects\) : publié \(2 éléments ajoutés\)/)
+        assert.deepEqual(api.requests.map(({ method, url, headers }) => [method, url.pathname, headers['content-type']]),
+            groups.map(() => ['POST', '/graphql', 'application/json']))
+        assert.deepEqual(api.requests.map(({ body }) => JSON.parse(body).variables), groups.map((group, index) => ({
+            project: 'PVT_1',
+            title: `Brief n°1, groupe ${index + 1}`,
+            body: group.map((name) => `- ${name}`).join('\n'),
+        })))
+        assert.match(JSON.parse(api.requests[0].body).query, /addProjectV2DraftIssue/)
+
+        const failed = await sandbox.runAsync(['publish', '--channel', 'other'], { env: { GITHUB_TOKEN: 'gh-token' } })
+        assert.equal(failed.status, 1)
+        assert.match(failed.stderr, /other \(GitHub Projects\) : échec, Could not resolve to a node with the global id of 'PVT_404'/)
+        const refused = await sandbox.runAsync(['publish', '--channel', 'board'], { env: { GITHUB_TOKEN: 'wrong' } })
+        assert.match(refused.stderr, /board \(GitHub Projects\) : échec, HTTP 401 sur 127\.0\.0\.1:\d+ : \{"message":"Bad credentials"\}/)
+        api.close()
+    })
+})
+
+describe('notion', () => {
+    test('appends a page with the groups to the database, and reports API errors', async () => {
+        const api = await apiServer(({ headers, body }) => headers.authorization == 'Bearer secret_1'
+            ? [200, { object: 'page', url: `https://www.notion.so/${JSON.parse(body).parent.database_id}-page` }]
+            : [401, { object: 'error', code: 'unauthorized', message: 'API token is invalid.' }])
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('groupgen.toml', [
+            '[notion]', 'label_property = "Sujet"',
+            '[[channels]]', 'name = "wiki"', 'type = "notion"', 'database = "db42"', 'title_property = "Titre"', `api_url = "${api.url}/v1"`,
+        ].join('\n'))
+        sandbox.run(['--name', 'React'])
+        const { date, groups } = sandbox.db().briefs[0]
+
+        const published = await sandbox.runAsync(['publish', '--channel', 'wiki'], { env: { NOTION_TOKEN: 'secret_1' } })
+        assert.equal(published.status, 0, published.stderr)
+        assert.match(published.stdout, /wiki \(Notion\) : publié \(https:\/\/www\.notion\.so\/db42-page\)/)
+        const [{ method, url, headers, body }] = api.requests
+        assert.deepEqual([method, url.pathname, headers['notion-version']], ['POST', '/v1/pages', '2022-06-28'])
+        const page = JSON.parse(body)
+        assert.deepEqual(page.parent, { database_id: 'db42' })
+        assert.deepEqual(page.properties, {
+            Titre: { title: [{ type: 'text', text: { content: 'Brief n°1 : React' } }] },
+            Date: { date: { start: date.slice(0, 10) } },
+            Sujet: { rich_text: [{ type: 'text', text: { content: 'React' } }] },
+        })
+        const texts = page.children.map((block) => [block.type, block[block.type].rich_text[0].text.content])
+        assert.deepEqual(texts, groups.flatMap((group, index) => [['heading_3', `Groupe ${index + 1}`], ...group.map((name) => ['bulleted_list_item', name])]))
+
+        const refused = await sandbox.runAsync(['publish', '--channel', 'wiki'], { env: { NOTION_TOKEN: 'expired' } })
+        assert.equal(refused.status, 1)
+        assert.match(refused.stderr, /wiki \(Notion\) : échec, HTTP 401 sur 127\.0\.0\.1:\d+ : .*API token is invalid\./)
+        const missing = await sandbox.runAsync(['publish', '--channel', 'wiki'], { env: { NOTION_TOKEN: '' } })
+        assert.match(missing.stderr, /wiki \(Notion\) : échec, Variable d'environnement NOTION_TOKEN manquante/)
+        api.close()
+        assert.equal(api.requests.length, 2)
+    })
+})
+
+describe('matrix', () => {
+    test('resolves the room alias then sends the announcement, and reports API errors', async () => {
+        const api = await apiServer(({ method, url, headers }) => {
+            if(headers.authorization != 'Bearer syt_1') {
+                return [401, { errcode: 'M_UNKNOWN_TOKEN', error: 'Invalid access token passed.' }]
+            }
+            if(method == 'GET') {
+                return url.pathname.endsWith(encodeURIComponent('#promo:example.org')) ? [200, { room_id: '!abc:example.org' }] : [404, { errcode: 'M_NOT_FOUND', error: 'Room alias not found' }]
+            }
+            return [200, { event_id: '$event1' }]
+        })
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('groupgen.toml', [
+            '[matrix]', `homeserver = "${api.url}/"`,
+            '[[channels]]', 'name = "salon"', 'type = "matrix"', 'room = "#promo:example.org"',
+            '[[channels]]', 'name = "perdu"', 'type = "matrix"', 'room = "#nope:example.org"',
+        ].join('\n'))
+        sandbox.run([])
+
+        const published = await sandbox.runAsync(['publish', '--channel', 'salon'], { env: { MATRIX_ACCESS_TOKEN: 'syt_1' } })
+        assert.equal(published.status, 0, published.stderr)
+        assert.match(published.stdout, /salon \(Matrix\) : publié \(\$event1\)/)
+        const [alias, send] = api.requests
+        assert.deepEqual([alias.method, alias.url.pathname], ['GET', `/_matrix/client/v3/directory/room/${encodeURIComponent('#promo:example.org')}`])
+        assert.equal(send.method, 'PUT')
+        assert.match(send.url.pathname, new RegExp(`^/_matrix/client/v3/rooms/${encodeURIComponent('!abc:example.org')}/send/m\\.room\\.message/[0-9a-f-]{36}$`))
+        const message = JSON.parse(send.body)
+        assert.deepEqual([message.msgtype, message.format], ['m.text', 'org.matrix.custom.html'])
+        for(const name of ['Brief n°1', 'Alice', 'Bob', 'Carol', 'Dan']) {
+            assert.ok(message.body.includes(name) && message.formatted_body.includes(name), name)
+        }
+
+        const lost = await sandbox.runAsync(['publish', '--channel', 'perdu'], { env: { MATRIX_ACCESS_TOKEN: 'syt_1' } })
+        assert.equal(lost.status, 1)
+        assert.match(lost.stderr, /perdu \(Matrix\) : échec, HTTP 404 sur 127\.0\.0\.1:\d+ : .*Room alias not found/)
+        const refused = await sandbox.runAsync(['publish', '--channel', 'salon'], { env: { MATRIX_ACCESS_TOKEN: 'old' } })
+        assert.match(refused.stderr, /salon \(Matrix\) : échec, HTTP 401 sur 127\.0\.0\.1:\d+ : .*M_UNKNOWN_TOKEN/)
+        api.close()
+    })
+})
+
+describe('gitlab', () => {
+    test('creates a project per group with its members, reuses existing ones, and reports API errors', async () => {
+        const projects = new Map([['simplon/promo-12/brief-1-groupe-02', { id: 102, web_url: 'https://gitlab.test/simplon/promo-12/brief-1-groupe-02' }]])
+        const users = { alice: 1, carol: 3, 'bob@example.org': 2 }
+        const api = await apiServer(({ method, url, headers, body }) => {
+            if(headers['private-token'] != 'glpat-1') {
+                return [401, { message: '401 Unauthorized' }]
+            }
+            const path = decodeURIComponent(url.pathname.replace(/^\/api\/v4/, ''))
+            if(method == 'GET' && path == '/groups/simplon/promo-12') {
+                return [200, { id: 7 }]
+            }
+            if(method == 'GET' && path.startsWith('/projects/')) {
+                const project = projects.get(path.slice('/projects/'.length))
+                return project ? [200, project] : [404, { message: '404 Project Not Found' }]
+            }
+            if(method == 'POST' && path == '/projects') {
+                const { path: name } = JSON.parse(body)
+                const project = { id: 100 + projects.size, web_url: `https://gitlab.test/simplon/promo-12/${name}` }
+                projects.set(`simplon/promo-12/${name}`, project)
+                return [201, project]
+            }
+            if(method == 'GET' && path == '/users') {
+                const id = users[url.searchParams.get('username') ?? url.searchParams.get('search')]
+                return [200, id ? [{ id }] : []]
+            }
+            if(method == 'POST' && path.endsWith('/members')) {
+                // Carol is already a member of every project.
+                return JSON.parse(body).user_id == 3 ? [409, { message: 'Member already exists' }] : [201, {}]
+            }
+            return [404, { message: 'unexpected' }]
+        })
+        sandbox.write('students.json', JSON.stringify([
+            { name: 'Alice', attributes: { gitlab: 'alice' } },
+            { name: 'Bob', email: 'bob@example.org' },
+            { name: 'Carol', attributes: { gitlab: 'carol' } },
+            { name: 'Dan' },
+        ]))
+        sandbox.write('groupgen.toml', `[[channels]]\nname = "forge"\ntype = "gitlab"\nurl = "${api.url}/"\nnamespace = "simplon/promo-12"\nvisibility = "internal"\n`)
+        sandbox.run([])
+        const groups = sandbox.db().briefs[0].groups
+
+        const published = await sandbox.runAsync(['publish', '--channel', 'forge'], { env: { GITLAB_TOKEN: 'glpat-1' } })
+        assert.equal(published.status, 0, published.stderr)
+        assert.match(published.stdout, /forge \(GitLab\) : publié \(2 projets dans simplon\/promo-12, comptes introuvables : Dan\)/)
+        const created = api.requests.filter((request) => request.method == 'POST' && request.url.pathname == '/api/v4/projects').map((request) => JSON.parse(request.body))
+        assert.deepEqual(created.map(({ name, path, namespace_id, visibility }) => [name, path, namespace_id, visibility]), [['brief-1-groupe-01', 'brief-1-groupe-01', 7, 'internal']])
+        assert.match(created[0].description, new RegExp(`^Brief n°1, groupe 1 : ${[...groups[0]].sort().join(', ')}$`))
+        const members = api.requests.filter((request) => request.url.pathname.endsWith('/members')).map((request) => [request.url.pathname, JSON.parse(request.body)])
+        const ids = { Alice: 1, Bob: 2, Carol: 3 }
+        assert.deepEqual(members, groups.flatMap((group, index) => group.filter((name) => ids[name]).map((name) => [
+            `/api/v4/projects/${index == 0 ? 100 + 1 : 102}/members`, { user_id: ids[name], access_level: 30 },
+        ])))
+        assert.deepEqual(sandbox.db().briefs[0].repositories, [
+            { group: 0, provider: 'gitlab', id: 101, url: 'https://gitlab.test/simplon/promo-12/brief-1-groupe-01' },
+            { group: 1, provider: 'gitlab', id: 102, url: 'https://gitlab.test/simplon/promo-12/brief-1-groupe-02' },
+        ])
+
+        api.requests.length = 0
+        assert.equal((await sandbox.runAsync(['publish', '--channel', 'forge'], { env: { GITLAB_TOKEN: 'glpat-1' } })).status, 0)
+        assert.ok(!api.requests.some((request) => request.method == 'POST' && request.url.pathname == '/api/v4/projects'))
+
+        const refused = await sandbox.runAsync(['publish', '--channel', 'forge'], { env: { GITLAB_TOKEN: 'revoked' } })
+        assert.equal(refused.status, 1)
+        assert.match(refused.stderr, /forge \(GitLab\) : échec, HTTP 401 sur 127\.0\.0\.1:\d+ : .*401 Unauthorized/)
+        api.close()
+    })
+})
+
+describe('email', () => {
+    test('keeps SMTP credentials off a connection without STARTTLS', async () => {
+        const received = []
+        const server = net.createServer((socket) => {
+            socket.write('220 test\r\n')
+            socket.on('data', (chunk) => {
+                for(const line of chunk.toString().split('\r\n').filter(Boolean)) {
+                    received.push(line)
+                    socket.write(line.startsWith('EHLO') ? '250-test\r\n250 AUTH PLAIN\r\n' : '221 bye\r\n')
+                }
+            })
+        })
+        await new Promise((resolve) => server.listen(0, '127.0.0.1', resolve))
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('groupgen.toml', `[[channels]]\nname = "mail"\ntype = "email"\nto = "promo@example.org"\nfrom = "trainer@example.org"\nsmtp_host = "127.0.0.1"\nsmtp_port = ${server.address().port}\nsmtp_user = "trainer"\nsmtp_password_env = "SMTP_PASSWORD"\n`)
+        sandbox.run([])
+        const { status, stderr } = await sandbox.runAsync(['publish', '--channel', 'mail'], { env: { SMTP_PASSWORD: 's3cret' } })
+        server.close()
+
+        assert.equal(status, 1)
+        assert.match(stderr, /ne propose pas STARTTLS, identifiants SMTP non envoyés en clair/)
+        assert.ok(received.every((line) => !line.startsWith('AUTH')))
+    })
+})
+
+describe('secrets', () => {
+    test('resolves channel secrets from the keyring at publish time', { skip: process.platform != 'linux' }, () => {
+        // Stand-in for libsecret's secret-tool, keeping secrets as files.
+        sandbox.write('secret-tool', [
+            '#!/bin/sh',
+            'case "$1" in',
+            '    store) cat > "$(dirname "$0")/secret-$6" ;;',
+            '    lookup) cat "$(dirname "$0")/secret-$5" 2>/dev/null ;;',
+            '    clear) rm -f "$(dirname "$0")/secret-$5" ;;',
+            'esac',
+        ].join('\n'), 0o755)
+        const env = { PATH: `${sandbox.dir}:${process.env.PATH}` }
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('groupgen.toml', '[[channels]]\nname = "hook"\ntype = "webhook"\nurl_keyring = "hook_url"\n')
+        sandbox.run([])
+
+        assert.match(sandbox.run(['publish', '--channel', 'hook'], { env }).stderr, /Secret hook_url absent du trousseau/)
+
+        assert.equal(sandbox.run(['secrets', 'set', 'hook_url'], { env, input: 'http://127.0.0.1:1/hook\n' }).status, 0)
+        assert.equal(sandbox.read('secret-hook_url'), 'http://127.0.0.1:1/hook')
+        assert.match(sandbox.run(['publish', '--channel', 'hook'], { env }).stderr, /connexion impossible à 127\.0\.0\.1:1/)
+
+        assert.equal(sandbox.run(['secrets', 'delete', 'hook_url'], { env }).status, 0)
+        assert.deepEqual(sandbox.list('.').filter((file) => file.startsWith('secret-h')), [])
+    })
+
+    test('does not echo the secret typed in a terminal', { skip: process.platform != 'linux' }, async () => {
+        sandbox.write('secret-tool', '#!/bin/sh\n[ "$1" = store ] && cat > "$(dirname "$0")/secret-$6"\n', 0o755)
+        const env = { PATH: `${sandbox.dir}:${process.env.PATH}` }
+        const { status, output } = await sandbox.runInTerminal(['secrets', 'set', 'token'], { prompt: /Valeur de token \(masquée\) : $/, answer: 'hunter2\x7f2-s3cret\r', env })
+        assert.equal(status, 0, output)
+        assert.equal(sandbox.read('secret-token'), 'hunter2-s3cret')
+        assert.ok(!output.includes('hunter'), output)
+        assert.match(output, /token enregistré dans le trousseau/)
+    })
+})
+
+describe('ldap', () => {
+    test('escapes the group in the filter and keeps the password off the command line', { skip: process.platform != 'linux' }, () => {
+        // Stand-in for ldapsearch, recording its arguments and the password file.
+        sandbox.write('ldapsearch', [
+            '#!/bin/sh',
+            'out="$(dirname "$0")"',
+            'printf \'%s\\n\' "$@" > "$out/ldap-args"',
+            'while [ $# -gt 0 ]; do [ "$1" = -y ] && cat "$2" > "$out/ldap-password"; shift; done',
+            'printf \'dn: uid=a\\ndisplayName: Alice\\n\\ndn: uid=b\\ndisplayName: Bob\\n\'',
+        ].join('\n'), 0o755)
+        sandbox.write('groupgen.toml', '[ldap]\nbind_dn = "cn=reader,dc=example,dc=org"\n')
+        const env = { PATH: `${sandbox.dir}:${process.env.PATH}`, SGG_LDAP_PASSWORD: 's3cret' }
+
+        const { status } = sandbox.run(['--students', 'ldap://ldap.example.org/dc=example,dc=org?group=promo*)(uid=*', '--dry-run'], { env })
+        assert.equal(status, 0)
+        const args = sandbox.read('ldap-args').split('\n')
+        assert.ok(args.includes('(memberOf=cn=promo\\2a\\29\\28uid=\\2a,ou=groups,dc=example,dc=org)'))
+        assert.ok(!args.includes('s3cret'))
+        assert.equal(sandbox.read('ldap-password'), 's3cret')
+        assert.ok(!fs.existsSync(args[args.indexOf('-y') + 1]))
+    })
+})
+
+describe('invites', () => {
+    test('writes one calendar file per student', () => {
+        sandbox.write('students.json', JSON.stringify(['Émile Zola', { name: 'Bob', email: 'bob@example.org' }]))
+        sandbox.run([])
+
+        const { status } = sandbox.run(['invites', '--date', '2024-10-14', '--rooms', 'Salle 3'])
+        assert.equal(status, 0)
+        const ics = sandbox.read('invites/brief-1/emile-zola.ics')
+        assert.match(ics, /SUMMARY:Brief n°1 avec Bob/)
+        assert.match(ics, /DTSTART:20241014T090000/)
+        assert.match(ics, /LOCATION:Salle 3/)
+        assert.match(ics, /ATTENDEE;CN="Bob";RSVP=FALSE:mailto:bob@example.org/)
+    })
+})
+
+describe('late-add', () => {
+    test('joins the pair not seen in the previous brief', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.run([])
+        const db = sandbox.db()
+        db.briefs.push({ id: 2, date: db.briefs[0].date, label: null, groups: [['Alice', 'Bob'], ['Carol', 'Eve']] })
+        db.briefs[0].groups = [['Alice', 'Dan'], ['Bob', 'Carol', 'Eve']]
+        sandbox.write('db.json', JSON.stringify(db))
+
+        const { status, stdout } = sandbox.run(['late-add', 'Dan', '--brief', '2'])
+        assert.equal(status, 0, stdout)
+        const brief = sandbox.db().briefs[1]
+        assert.deepEqual(brief.groups, [['Alice', 'Bob'], ['Carol', 'Eve', 'Dan']])
+        assert.equal(brief.amendments[0].action, 'add')
+
+        assert.notEqual(sandbox.run(['late-add', 'Zoé', '--brief', '2', '--max-size', '2']).status, 0)
+    })
+})
+
+describe('drop', () => {
+    test('removes the student and promotes a solo one into the free place', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.run([])
+        const db = sandbox.db()
+        db.briefs[0].groups = [['Alice', 'Bob'], ['Carol'], ['Dan', 'Eve']]
+        sandbox.write('db.json', JSON.stringify(db))
+
+        const { status } = sandbox.run(['drop', 'Eve', '--promote'])
+        assert.equal(status, 0)
+        const brief = sandbox.db().briefs[0]
+        assert.deepEqual(brief.groups, [['Alice', 'Bob'], ['Dan', 'Carol']])
+        assert.deepEqual(brief.amendments.map(({ action, student }) => `${action} ${student}`), ['drop Eve', 'drop Carol', 'add Carol'])
+    })
+
+    test('keeps slots, observers, threads and repositories in step when a group goes', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [{
+            id: 1, date: '2025-01-06T08:00:00.000Z', label: null, groups: [['Alice'], ['Bob', 'Carol'], ['Dan', 'Eve']],
+            slots: { names: ['9h', '11h', '14h'], assignment: ['9h', '11h', '14h'] },
+            observers: { trainers: ['T1', 'T2', 'T3'], assignment: ['T1', 'T2', 'T3'] },
+            threads: [{ group: 0, channel: null, id: 'a' }, { group: 2, channel: null, id: 'c' }],
+            repositories: [{ group: 1, provider: 'gitlab', id: 2, url: 'https://gitlab.example.org/b' }],
+            context: { roster: ['Alice', 'Bob', 'Carol', 'Dan', 'Eve'], previous_brief: null, constraints: [], seed: 1, iterations: 1, repeats: 0, violations: 0, group_repeats: [0, 1, 2], improvements: [0] },
+        }] }))
+
+        assert.equal(sandbox.run(['drop', 'Alice']).status, 0)
+        const brief = sandbox.db().briefs[0]
+        assert.deepEqual(brief.groups, [['Bob', 'Carol'], ['Dan', 'Eve']])
+        assert.deepEqual(brief.slots.assignment, ['11h', '14h'])
+        assert.deepEqual(brief.observers.assignment, ['T2', 'T3'])
+        assert.deepEqual(brief.threads.map(({ group, id }) => [group, id]), [[null, 'a'], [1, 'c']])
+        assert.deepEqual(brief.repositories.map(({ group }) => group), [0])
+        assert.deepEqual(brief.context.group_repeats, [1, 2])
+    })
+})
+
+describe('assign-slots', () => {
+    test('uses every slot when groups do not divide evenly', () => {
+        const groups = [['Alice', 'Bob'], ['Carol', 'Dan'], ['Eve', 'Fay'], ['Gus', 'Hal'], ['Ivy', 'Jo']]
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: '2025-01-06T08:00:00.000Z', label: null, groups: groups.slice(0, 4) },
+            { id: 2, date: '2025-01-13T08:00:00.000Z', label: null, groups },
+        ] }))
+        const perSlot = (id) => {
+            const { names, assignment } = sandbox.db().briefs[id - 1].slots
+            return names.map((name) => assignment.filter((slot) => slot == name).length)
+        }
+
+        assert.equal(sandbox.run(['assign-slots', '--brief', '1', '--slots', '9h,11h,14h']).status, 0)
+        assert.deepEqual(perSlot(1), [2, 1, 1])
+        assert.equal(sandbox.run(['assign-slots', '--brief', '2', '--slots', '9h,14h']).status, 0)
+        assert.deepEqual(perSlot(2), [3, 2])
+    })
+})
+
+describe('regenerate', () => {
+    test('draws a brief again under the same id, and needs --force once later briefs were drawn', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan', 'Eve', 'Fay']))
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: '2025-01-06T08:00:00.000Z', label: null, groups: [['Alice', 'Bob'], ['Carol', 'Dan'], ['Eve', 'Fay']] },
+            { id: 2, date: '2025-01-13T08:00:00.000Z', label: 'React', groups: [['Alice', 'Carol']],
+                context: { excluded: ['Bob', 'Dan', 'Eve', 'Fay'] }, amendments: [{ date: '2025-01-13T10:00:00.000Z', action: 'add', student: 'Bob', group: 0 }] },
+        ] }))
+
+        const { status, stdout } = sandbox.run(['regenerate', '--brief', '2', '--exclude', 'Fay', '--seed', '7'])
+        assert.equal(status, 0, stdout)
+        assert.match(stdout, /Brief n°2 « React » refait avec 5 apprenants : 0 binôme\(s\) répété\(s\)\./)
+        const brief = sandbox.db().briefs[1]
+        assert.deepEqual([brief.id, brief.label, brief.date], [2, 'React', '2025-01-13T08:00:00.000Z'])
+        assert.deepEqual(brief.groups.flat().sort(), ['Alice', 'Bob', 'Carol', 'Dan', 'Eve'])
+        assert.deepEqual([brief.context.previous_brief, brief.context.seed, brief.context.excluded, brief.amendments], [1, 7, ['Fay'], undefined])
+        assert.equal(sandbox.db().briefs.length, 2)
+
+        const refused = sandbox.run(['regenerate', '--brief', '1'])
+        assert.match(refused.stderr, /Le brief n°1 a servi depuis : 1 brief\(s\) tiré\(s\) après lui \(n°2\)\. Relancez avec --force/)
+        assert.equal(sandbox.run(['regenerate', '--brief', '1', '--force']).status, 0)
+    })
+
+    test('draws like generate and keeps the replaced composition in the trash', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('groupgen.toml', '[categories.project]\ncount = ["project"]\n')
+        sandbox.write('absents.txt', 'Dan\n')
+        const original = [['Alice', 'Carol'], ['Bob', 'Dan']]
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: null, label: null, groups: [['Alice', 'Bob'], ['Carol', 'Dan']], category: 'project' },
+            { id: 2, date: null, label: null, groups: [['Alice', 'Carol'], ['Bob', 'Dan']] },
+            { id: 3, date: null, label: null, groups: original, category: 'project', context: { tie_break: 'alphabetical' } },
+        ] }))
+
+        const { status, stdout } = sandbox.run(['regenerate', '--brief', '3', '--exclude-file', 'absents.txt', '--seed', '1'])
+        assert.equal(status, 0, stdout)
+        assert.match(stdout, /groupgen trash restore 3 pour y revenir/)
+        const brief = sandbox.db().briefs[2]
+        assert.deepEqual(brief.groups.flat().sort(), ['Alice', 'Bob', 'Carol'])
+        assert.deepEqual([brief.context.previous_brief, brief.context.counted_categories, brief.context.tie_break, brief.context.excluded],
+            [1, ['project'], 'alphabetical', ['Dan']])
+        assert.deepEqual(sandbox.db().trash.map((entry) => [entry.brief.id, entry.brief.groups, entry.replaced]), [[3, original, true]])
+        assert.match(sandbox.run(['trash', 'list']).stdout, /^n°3 du date inconnue, version remplacée le /)
+
+        assert.equal(sandbox.run(['trash', 'restore', '3']).status, 0)
+        assert.deepEqual(sandbox.db().briefs.map((brief) => brief.id), [1, 2, 3])
+        assert.deepEqual(sandbox.db().briefs[2].groups, original)
+        assert.deepEqual(sandbox.db().trash.map((entry) => entry.brief.groups), [brief.groups])
+    })
+
+    test('rejects brief ids that are not whole numbers', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.run([])
+        for(const args of [['regenerate', '--brief', '1.5'], ['explain', '--brief', 'abc'], ['drop', 'Alice', '--brief', '0'], ['export', '--csv', '--brief', '1e0x'], ['trash', 'restore', 'NaN']]) {
+            assert.match(sandbox.run(args).stderr, /Identifiant de brief invalide/, args.join(' '))
+        }
+    })
+})
+
+describe('simulate-policies', () => {
+    test('reports a coverage curve for each policy', () => {
+        const { status, stdout } = sandbox.run(['simulate-policies', '--briefs', '4', '--students', '4', '--iterations', '50'])
+        assert.equal(status, 0)
+        assert.match(stdout, /après 1 \/ 2 \/ 3 \/ 4 briefs/)
+        assert.match(stdout, /tout l'historique .* 100\s%/)
+        assert.equal(sandbox.db(), null)
+    })
+})
+
+describe('stats', () => {
+    test('--expected flags a pair that is always together', () => {
+        const others = ['Carol', 'Dan', 'Eve', 'Fay', 'Gus', 'Hal']
+        const briefs = Array.from({ length: 6 }, (_, id) => {
+            const rotated = [...others.slice(id), ...others.slice(0, id)]
+            return { id: id + 1, date: null, label: null, groups: [['Alice', 'Bob'], rotated.slice(0, 2), rotated.slice(2, 4), rotated.slice(4)] }
+        })
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, briefs, constraints: [] }))
+
+        const { status, stdout } = sandbox.run(['stats', '--expected', '--runs', '50'])
+        assert.equal(status, 0)
+        assert.match(stdout, /Alice : 6 brief\(s\), 1 partenaire\(s\) distinct\(s\), 5 répétition\(s\), 6 fois avec Bob/)
+        assert.match(stdout, /Anomalie\(s\) .* Alice, Bob/)
+    })
+})
+
+describe('--version', () => {
+    test('reports the package and schema versions', () => {
+        const { status, stdout } = sandbox.run(['--version'])
+        assert.equal(status, 0)
+        assert.match(stdout, /^groupgen 0\.1\.0( \([0-9a-f]+\))?, schéma de données 1, Node /)
+    })
+})
+
+describe('site', () => {
+    test('writes the current groups, the matrix and one page per student', () => {
+        sandbox.write('students.json', JSON.stringify(['Émile Zola', 'Bob', 'Carol', 'Dan']))
+        sandbox.run([])
+        sandbox.run([])
+
+        const { status, stdout } = sandbox.run(['site', '--out', 'public'])
+        assert.equal(status, 0)
+        assert.match(stdout, /7 pages/)
+        assert.match(sandbox.read('public/index.html'), /Brief n°2/)
+        assert.match(sandbox.read('public/history.html'), /Brief n°1/)
+        assert.match(sandbox.read('public/matrix.html'), /title="Bob et Carol"/)
+        assert.match(sandbox.read('public/students/emile-zola.html'), /<td>Brief n°1, /)
+    })
+})
+
+describe('status', () => {
+    test('warns when the last brief is too old', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.run([])
+        assert.equal(sandbox.run(['status']).status, 0)
+
+        const db = sandbox.db()
+        db.briefs[0].date = '2020-01-06T08:00:00.000Z'
+        sandbox.write('db.json', JSON.stringify(db))
+        const { status, stderr } = sandbox.run(['status', '--brief-days', '7'])
+        assert.equal(status, 1)
+        assert.match(stderr, /aucun brief depuis \d+ jours \(seuil : 7\)/)
+    })
+})
+
+describe('assign-observers', () => {
+    test('rotates trainers between briefs', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.run([])
+        sandbox.run(['brief', 'clone', '1'])
+
+        assert.equal(sandbox.run(['assign-observers', '--brief', '1', '--trainers', 'Anne,Marc']).status, 0)
+        assert.equal(sandbox.run(['assign-observers', '--brief', '2', '--trainers', 'Anne,Marc']).status, 0)
+        const [first, second] = sandbox.db().briefs
+        assert.deepEqual(second.observers.assignment, [...first.observers.assignment].reverse())
+    })
+})
+
+describe('skills', () => {
+    test('--require mixes students so that every group covers the skills', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('competencies.csv', 'name;HTML;SQL\r\nAlice;3;0\r\nBob;2;0\r\nCarol;0;2\r\nDan;0;3\r\n')
+
+        const { status } = sandbox.run(['--require', 'HTML,SQL'])
+        assert.equal(status, 0)
+        const [brief] = sandbox.db().briefs
+        assert.equal(brief.context.missing_skills, 0)
+        for(const group of brief.groups) {
+            assert.equal(group.filter((name) => ['Alice', 'Bob'].includes(name)).length, 1)
+        }
+    })
+})
+
+describe('intake', () => {
+    test('merges form answers into the competency file', () => {
+        sandbox.write('groupgen.toml', '[intake]\nname_column = "Nom"\n[intake.skills]\nHTML = "Niveau HTML ?"\n[intake.levels]\n"Débutant" = 1\n"Avancé" = 3\n')
+        sandbox.write('competencies.csv', 'name,HTML,SQL\r\nAlice,1,2\r\n')
+        sandbox.write('responses.csv', 'Horodateur,Nom,"Niveau HTML ?"\n2024/10/01 09:00,Alice,Débutant\n2024/10/01 09:05,Bob,2\n2024/10/02 10:00,Alice,Avancé\n')
+
+        const { status } = sandbox.run(['intake', '--form', 'responses.csv'])
+        assert.equal(status, 0)
+        assert.equal(sandbox.read('competencies.csv'), 'name,HTML,SQL\r\nAlice,3,2\r\nBob,2,\r\n')
+    })
+})
+
+describe('survey', () => {
+    test('hands out a grid and turns the answers into constraints, "together" only when mutual', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+
+        assert.equal(sandbox.run(['survey', '--out', 'form.csv']).status, 0)
+        const form = sandbox.read('form.csv').split('\r\n')
+        assert.deepEqual(form.slice(0, 3), ['Apprenant,Alice,Bob,Carol,Dan', 'Alice,-,,,', 'Bob,,-,,'])
+
+        sandbox.write('form.csv', [
+            'Apprenant,Alice,Bob,Carol,Dan',
+            'Alice,-,ensemble,,jamais',
+            'Bob,Ensemble,-,ensemble,',
+            'Carol,,,-,',
+            'Zoé,jamais,,,',
+        ].join('\n'))
+        const { status, stdout, stderr } = sandbox.run(['survey', 'import', 'form.csv'])
+        assert.equal(status, 0, stderr)
+        assert.match(stderr, /Zoé n'est pas dans la liste des apprenants/)
+        assert.match(stdout, /3 réponse\(s\) lue\(s\) : 1 binôme\(s\) à éviter, 1 binôme\(s\) à réunir, 2 contrainte\(s\) ajoutée\(s\)\.\n1 souhait/)
+        assert.deepEqual(sandbox.db().constraints.map(({ type, students }) => `${type} ${students.join(' ')}`), ['never Alice Dan', 'together Alice Bob'])
+
+        assert.match(sandbox.run(['survey', 'import', 'form.csv']).stdout, /0 contrainte\(s\) ajoutée\(s\)/)
+        sandbox.write('form.csv', 'Apprenant,Alice,Bob\nAlice,-,peut-être\n')
+        assert.match(sandbox.run(['survey', 'import', 'form.csv']).stderr, /form\.csv, ligne 2 : réponse "peut-être" pour Bob/)
+    })
+})
+
+describe('compare-cohorts', () => {
+    test('reports coverage and repeats of each history', () => {
+        const brief = (id, groups) => ({ id, date: null, label: null, groups })
+        sandbox.write('a.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            brief(1, [['Alice', 'Bob'], ['Carol', 'Dan']]),
+            brief(2, [['Alice', 'Carol'], ['Bob', 'Dan']]),
+        ] }))
+        sandbox.write('b.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            brief(1, [['Eve', 'Fay'], ['Gus', 'Hal']]),
+            brief(2, [['Eve', 'Fay'], ['Gus', 'Hal']]),
+        ] }))
+
+        const { status, stdout } = sandbox.run(['compare-cohorts', 'a.json', 'b.json', '--briefs', '2'])
+        assert.equal(status, 0)
+        const [a, b] = stdout.trim().split('\n')
+        assert.match(a, /^a \| 2 briefs, 4 apprenants \| couverture 67\s% après 2 brief\(s\) \| 50 % en 2 brief\(s\) \| 0,0 binôme/)
+        assert.match(b, /^b \| .* couverture 33\s% .* 50 % non atteints \| 1,0 binôme\(s\) répété\(s\) par brief \| 1 à 1 partenaires/)
+    })
+})
+
+describe('help', () => {
+    test('lists commands and details the options of one', () => {
+        const { status, stdout } = sandbox.run(['--help'])
+        assert.equal(status, 0)
+        assert.match(stdout, /generate +Forme les groupes .* \(par défaut\)/)
+        assert.match(stdout, /history +Liste les briefs/)
+        assert.match(sandbox.run(['history', '--help']).stdout, /Usage : groupgen history \[options\][\s\S]*--last, -n <valeur>/)
+    })
+})
+
+describe('--lang', () => {
+    test('prints groups, notices and errors in English', () => {
+        sandbox.fixture('promo-5.json', 'students.json')
+        const { status, stdout } = sandbox.run(['--lang', 'en', '--yes'])
+        assert.equal(status, 0)
+        assert.match(stdout, /^Groups:\n\+- Group 1 -/)
+        assert.match(stdout, /Briefs spent together, this draw included:/)
+        assert.match(stdout, /Brief #1 saved\./)
+        assert.match(sandbox.run(['--lang=en', '--group-size', '1']).stderr, /^Error: Invalid group size: 1 \(2 or more\)/)
+        assert.match(sandbox.run(['--lang', 'de']).stderr, /Erreur : Langue inconnue : de \(fr, en\)/)
+    })
+
+    test('translates the other commands too', () => {
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: '2025-01-06T08:00:00.000Z', label: 'HTML', groups: [['Alice', 'Bob'], ['Carol', 'Dan']] },
+            { id: 2, date: '2025-01-20T08:00:00.000Z', label: null, groups: [['Alice', 'Carol'], ['Bob', 'Dan']], state: 'draft' },
+        ] }))
+        assert.equal(sandbox.run(['--lang', 'en', '--screen-reader', 'calendar', '--month', '2025-02']).stdout, 'février 2025: no brief.\n')
+        assert.match(sandbox.run(['--lang', 'en', 'calendar', '--month', '2025-01']).stdout,
+            /  Brief #1 "HTML" on 06\/01\/2025, 2 groups\n  Brief #2 \(planned\) on 20\/01\/2025, 2 groups\n\n\* past brief, \+ planned brief/)
+        assert.match(sandbox.run(['--lang', 'en', 'graph', 'stats']).stdout, /^Collaboration graph after 1 brief\(s\):\n  4 students, 2 pairs formed/)
+        assert.match(sandbox.run(['--lang', 'en', 'brief', 'close']).stderr, /^Error: Missing brief id, e\.g\. groupgen brief close 12/)
+        assert.match(sandbox.run(['--lang', 'en', 'trash', 'list']).stdout, /^The trash is empty\.\n$/)
+    })
+
+    test('leaves no French in the messages of the shared modules', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        sandbox.write('groupgen.toml', [
+            '[[channels]]', 'name = "archive"', 'type = "file"', 'path = "out/brief-{id}.json"',
+            '[[channels]]', 'name = "chat"', 'type = "discord"', 'webhook_url_env = "SGG_TEST_UNSET"',
+        ].join('\n'))
+        sandbox.run([])
+        const french = /[éèêàçù]| : |n°|\b(le|la|les|des|du|une|pour|avec|sans|dans|introuvable|inconnue?|invalide)\b/
+        for(const args of [
+            ['--category', 'bogus'],
+            ['publish', '--channel', 'nope'],
+            ['publish', '--all-channels'],
+            ['--seed', 'x'],
+            ['adopt', 'zzz'],
+            ['completions', 'tcsh'],
+            ['fake-roster', '--locale', 'xx'],
+            ['--version'],
+            ['history'],
+        ]) {
+            const { stdout, stderr } = sandbox.run(['--lang', 'en', ...args])
+            assert.doesNotMatch(stdout + stderr, french, args.join(' '))
+        }
+        assert.match(sandbox.run(['--lang', 'en', 'publish', '--channel', 'nope']).stderr, /Unknown channel: nope \(groupgen\.toml channels: archive, chat\)/)
+
+        sandbox.write('groupgen.toml', 'seed = @\n')
+        assert.match(sandbox.run(['--lang', 'en', 'history']).stderr, /^Error: Invalid TOML value line 1: @/)
+    })
+
+    test('follows LANG, then lang in [defaults]', () => {
+        const env = { SGG_LANG: '', LC_ALL: '', LC_MESSAGES: '', LANG: 'en_GB.UTF-8' }
+        assert.match(sandbox.run(['--help'], { env }).stdout, /^Usage: groupgen .*\n\nCommands:\n/)
+        assert.match(sandbox.run(['--help'], { env: { ...env, LANG: 'de_DE.UTF-8' } }).stdout, /^Usage : groupgen/)
+
+        sandbox.write('groupgen.toml', '[defaults]\nlang = "fr"\n')
+        assert.match(sandbox.run(['matrix'], { env }).stderr, /Aucun apprenant/)
+    })
+})
+
+describe('calendar', () => {
+    test('marks past and planned briefs on a month grid and exports them as events', () => {
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: '2025-01-06T08:00:00.000Z', label: 'HTML', groups: [['Alice', 'Bob'], ['Carol', 'Dan']] },
+            { id: 2, date: '2025-01-20T08:00:00.000Z', label: null, groups: [['Alice', 'Carol'], ['Bob', 'Dan']], state: 'draft' },
+            { id: 3, date: '2025-03-03T08:00:00.000Z', label: 'React', groups: [['Alice', 'Dan'], ['Bob', 'Carol']] },
+        ] }))
+
+        assert.equal(sandbox.run(['calendar', '--month', '2025-01']).stdout, [
+            'janvier 2025',
+            ' lu  ma  me  je  ve  sa  di',
+            '          1   2   3   4   5',
+            '  6*  7   8   9  10  11  12',
+            ' 13  14  15  16  17  18  19',
+            ' 20+ 21  22  23  24  25  26',
+            ' 27  28  29  30  31',
+            '  Brief n°1 « HTML » le 06/01/2025, 2 groupes',
+            '  Brief n°2 (prévu) le 20/01/2025, 2 groupes',
+            '',
+            '* brief passé, + brief prévu (brouillon ou daté plus tard)',
+            '',
+        ].join('\n'))
+        assert.match(sandbox.run(['calendar']).stdout, /février 2025\n(.*\n){6}\nmars 2025\n/)
+        assert.match(sandbox.run(['--screen-reader', 'calendar', '--month', '2025-02']).stdout, /^février 2025 : aucun brief\.\n$/)
+
+        assert.equal(sandbox.run(['calendar', '--ics', 'promo.ics']).status, 0)
+        const ics = sandbox.read('promo.ics')
+        assert.equal(ics.match(/BEGIN:VEVENT/g).length, 3)
+        assert.match(ics, /DTSTART;VALUE=DATE:20250303\r\nSUMMARY:Brief n°3 « React »\r\nDESCRIPTION:Groupe 1 : Alice\\, Dan\\nGroupe 2 : Bob\\, Carol\r\n/)
+    })
+})
+
+describe('history', () => {
+    test('lists the last briefs, newest first', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.run([])
+        sandbox.run([])
+        sandbox.run(['brief', 'clone', '1', '--label', 'Portfolio'])
+
+        const lines = sandbox.run(['history', '--last', '2']).stdout.trim().split('\n')
+        assert.match(lines[0], /^n°3  \d{2}\/\d{2}\/\d{4} \| 2 groupes, 4 apprenants \| Portfolio \| copie du n°1$/)
+        assert.match(lines[1], /^n°2 /)
+        assert.equal(lines[2], '… 1 brief(s) plus ancien(s).')
+    })
+
+    test('shows history and stats as they stood with --as-of', () => {
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: '2025-01-10T08:00:00.000Z', label: null, groups: [['Alice', 'Bob'], ['Carol', 'Dan', 'Eve']], amendments: [
+                { date: '2025-02-05T08:00:00.000Z', action: 'drop', student: 'Fay', group: 1, emptied: false },
+                { date: '2025-02-06T08:00:00.000Z', action: 'add', student: 'Eve', group: 1 },
+            ] },
+            { id: 2, date: '2025-02-03T08:00:00.000Z', label: null, groups: [['Alice', 'Carol'], ['Bob', 'Dan']] },
+        ] }))
+
+        assert.equal(sandbox.run(['history', '--as-of', '2025-02-01']).stdout, 'n°1  10/01/2025 | 2 groupes, 5 apprenants\n')
+        assert.equal(sandbox.run(['history', '--as-of', '2025-02-01'], { env: { SGG_LOCALE: 'en-US' } }).stdout, 'n°1  1/10/25 | 2 groupes, 5 apprenants\n')
+        assert.match(sandbox.run(['history'], { env: { SGG_LOCALE: 'xx-!!' } }).stderr, /Locale inconnue : xx-!!/)
+        const { stdout } = sandbox.run(['stats', '--as-of', '2025-02-01'])
+        assert.match(stdout, /^1 briefs, 5 apprenants au 01\/02\/2025\./)
+        assert.match(stdout, /Fay : 1 brief\(s\), 2 partenaire\(s\) distinct\(s\)/)
+        assert.doesNotMatch(stdout, /Eve/)
+        assert.match(sandbox.run(['stats', '--as-of', '01/02/2025']).stderr, /Date invalide : 01\/02\/2025/)
+    })
+})
+
+describe('graph stats', () => {
+    test('reports components, diameter and clustering of the collaboration graph', () => {
+        const brief = (id, groups) => ({ id, date: null, label: null, groups })
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            brief(1, [['Alice', 'Bob'], ['Carol', 'Dan'], ['Eve', 'Fay']]),
+            brief(2, [['Alice', 'Carol'], ['Bob', 'Dan'], ['Eve', 'Fay']]),
+        ] }))
+
+        const { status, stdout } = sandbox.run(['graph', 'stats'])
+        assert.equal(status, 0)
+        assert.match(stdout, /6 apprenants, 5 binômes formés au moins une fois \(densité 33\s%\)/)
+        assert.match(stdout, /Composantes connexes : 2\n {4}1\. Alice, Bob, Carol, Dan\n {4}2\. Eve, Fay/)
+        assert.match(stdout, /Diamètre : 2 /)
+        assert.match(stdout, /clustering moyen : 0,00/)
+    })
+})
+
+describe('suggest-config', () => {
+    test('turns repeated pairs and isolated students into constraints', () => {
+        const brief = (id, groups) => ({ id, date: null, label: null, groups })
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            brief(1, [['Alice', 'Bob'], ['Carol', 'Dan'], ['Eve', 'Fay']]),
+            brief(2, [['Alice', 'Bob'], ['Carol', 'Eve'], ['Dan', 'Fay']]),
+            brief(3, [['Bob', 'Alice'], ['Carol', 'Fay'], ['Dan', 'Eve']]),
+            brief(4, [['Alice', 'Bob'], ['Carol', 'Dan'], ['Eve', 'Fay']]),
+        ] }))
+
+        const { status, stdout } = sandbox.run(['suggest-config'])
+        assert.equal(status, 0)
+        assert.match(stdout, /# ensemble dans 4 briefs sur 4\n\[\[constraints\]\]\ntype = "never"\nstudents = \["Alice", "Bob"\]/)
+        assert.match(stdout, /type = "together"\nstudents = \["Alice", "Carol"\]/)
+        assert.match(stdout, /type = "together"\nstudents = \["Bob", "Dan"\]/)
+
+        sandbox.write('groupgen.toml', stdout)
+        assert.equal(sandbox.run(['constraints', 'list']).status, 0)
+        assert.match(sandbox.run(['suggest-config']).stdout, /Aucune suggestion/)
+    })
+})
+
+describe('export --csv', () => {
+    test('writes one row per group with a column per member', () => {
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: '2024-10-07T08:00:00.000Z', label: 'Site vitrine, "v2"', groups: [['Bob', 'Alice'], ['Carol', 'Dan', 'Eve']] },
+            { id: 2, date: null, label: null, groups: [['Alice', 'Carol'], ['Bob', 'Dan']] },
+        ] }))
+
+        assert.equal(sandbox.run(['export', '--csv', '--all', '--out', 'all.csv']).status, 0)
+        assert.equal(sandbox.read('all.csv'), [
+            'brief_id,date,label,group_number,member_1,member_2,member_3',
+            '1,2024-10-07,"Site vitrine, ""v2""",1,Alice,Bob',
+            '1,2024-10-07,"Site vitrine, ""v2""",2,Carol,Dan,Eve',
+            '2,,,1,Alice,Carol',
+            '2,,,2,Bob,Dan',
+            '',
+        ].join('\r\n'))
+        assert.match(sandbox.run(['export', '--csv', '--brief', '1']).stdout, /^brief_id,.*member_3\r\n1,/)
+    })
+})
+
+describe('safety', () => {
+    test('backs up the database before dropping a student', () => {
+        sandbox.write('groupgen.toml', '[safety]\nrequire_backup_before_destructive = true\n')
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('db.json', '{"schema_version": 1, "briefs": [], "constraints": []}')
+        sandbox.run([])
+        const before = sandbox.read('db.json')
+
+        assert.equal(sandbox.run(['drop', 'Alice']).status, 0)
+        const [backup] = sandbox.list('backups')
+        assert.equal(sandbox.read(`backups/${backup}`), before)
+        assert.match(sandbox.read('audit.log'), new RegExp(`\tdrop Alice du brief n°1\tsauvegarde .*${backup}`))
+    })
+
+    test('refuses to go on when the backup fails', () => {
+        sandbox.write('groupgen.toml', '[safety]\nrequire_backup_before_destructive = true\n')
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('db.json', '{"schema_version": 1, "briefs": [], "constraints": []}')
+        sandbox.run([])
+        sandbox.write('backups', 'not a directory')
+        const before = sandbox.read('db.json')
+
+        const { status, stderr } = sandbox.run(['drop', 'Alice'])
+        assert.equal(status, 1)
+        assert.match(stderr, /sauvegarde impossible avant « drop Alice du brief n°1 »/)
+        assert.equal(sandbox.read('db.json'), before)
+    })
+
+    test('sets unreadable briefs aside and keeps generating', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: '2025-01-06T08:00:00.000Z', label: null, groups: [['Alice', 'Bob'], ['Carol', 'Dan']] },
+            { id: 2, date: '2025-01-13T08:00:00.000Z', label: null, groups: [['Alice', ''], ['Carol', 'Dan']] },
+            { id: 2, date: '2025-01-20T08:00:00.000Z', label: null, groups: [['Alice', 'Carol'], ['Bob', 'Dan']] },
+            { id: 2, date: '2025-01-27T08:00:00.000Z', label: null, groups: [['Alice', 'Dan'], ['Bob', 'Carol']] },
+        ] }))
+
+        const { status, stderr } = sandbox.run(['--yes'])
+        assert.equal(status, 0)
+        assert.match(stderr, /brief 2 mis de côté dans invalid_briefs \(membre vide ou invalide dans le groupe 1 \(""\)\)/)
+        assert.match(stderr, /brief 2 mis de côté dans invalid_briefs \(id 2 en double\)/)
+        const db = sandbox.db()
+        assert.deepEqual(db.briefs.map((brief) => brief.id), [1, 2, 3])
+        assert.deepEqual(db.invalid_briefs.map((entry) => entry.brief.date), ['2025-01-13T08:00:00.000Z', '2025-01-27T08:00:00.000Z'])
+        assert.ok([...pairsOf(db.briefs[2].groups)].every((pair) => !pairsOf(db.briefs[1].groups).has(pair)))
+
+        const report = sandbox.run(['status'])
+        assert.equal(report.status, 1)
+        assert.match(report.stderr, /2 brief\(s\) illisible\(s\) mis de côté dans invalid_briefs/)
+    })
+})
+
+describe('db encrypt', () => {
+    test('keeps the history unreadable without the passphrase', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('db.json', '{"schema_version": 1, "briefs": [], "constraints": []}')
+        sandbox.run([])
+
+        const env = { SGG_DB_KEY: 'correct horse battery staple' }
+        assert.equal(sandbox.run(['db', 'encrypt'], { env }).status, 0)
+        assert.doesNotMatch(sandbox.read('db.json'), /Alice/)
+        assert.match(sandbox.run(['usage']).stderr, /db\.json est chiffré : définissez SGG_DB_KEY/)
+        assert.match(sandbox.run(['usage'], { env: { SGG_DB_KEY: 'wrong' } }).stderr, /clé SGG_DB_KEY incorrecte/)
+
+        assert.equal(sandbox.run([], { env }).status, 0)
+        assert.equal(sandbox.run(['db', 'decrypt'], { env }).status, 0)
+        assert.equal(sandbox.db().briefs.length, 2)
+    })
+
+    test('encrypts its backup and deals with the side files left in clear', () => {
+        sandbox.write('groupgen.toml', '[safety]\nrequire_backup_before_destructive = true\n')
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('db.json', '{"schema_version": 1, "briefs": [], "constraints": []}')
+        sandbox.run([])
+        sandbox.run(['drop', 'Alice'])
+        sandbox.write('sessions.json', JSON.stringify({ abcd1234: { groups: [['Alice', 'Bob']] } }))
+        const [old] = sandbox.list('backups')
+
+        const env = { SGG_DB_KEY: 'correct horse battery staple' }
+        const { status, stderr } = sandbox.run(['db', 'encrypt'], { env })
+        assert.equal(status, 0)
+        const [fresh] = sandbox.list('backups').filter((name) => name != old)
+        assert.doesNotMatch(sandbox.read(`backups/${fresh}`), /Alice/)
+        assert.match(stderr, new RegExp(`Toujours en clair à côté de l'historique : .*${old}.*sessions\\.json.*audit\\.log\\. groupgen db encrypt --purge`))
+
+        assert.equal(sandbox.run(['db', 'encrypt', '--purge'], { env }).status, 0)
+        assert.deepEqual(sandbox.list('backups'), [fresh])
+        assert.equal(sandbox.find('sessions.json'), null)
+
+        assert.equal(sandbox.run(['db', 'decrypt'], { env }).status, 0)
+        assert.equal(sandbox.list('backups').length, 2)
+    })
+})
+
+describe('fake-roster', () => {
+    test('writes a structured roster and a history the tool accepts', () => {
+        const { status, stderr } = sandbox.run(['fake-roster', '--size', '28', '--seed', '7', '--history', '3'], { input: '' })
+        assert.equal(status, 0)
+        assert.match(stderr, /3 brief\(s\) fictif\(s\) enregistré\(s\)/)
+        sandbox.write('students.json', sandbox.run(['fake-roster', '--size', '28', '--seed', '7']).stdout)
+
+        const students = JSON.parse(sandbox.read('students.json'))
+        assert.equal(new Set(students.map((student) => student.name)).size, 28)
+        assert.match(students[0].email, /^[a-z.-]+@example\.org$/)
+        assert.ok(students[0].attributes.track)
+        assert.deepEqual(sandbox.db().briefs[0].groups.flat().sort(), students.map((student) => student.name).sort())
+        const { status: generated, stdout } = sandbox.run([])
+        assert.equal(generated, 0)
+        assert.doesNotMatch(stdout, /copier-coller/)
+        assert.match(sandbox.run(['fake-roster', '--history', '1']).stderr, /contient déjà des briefs/)
+    })
+})
+
+describe('tui', () => {
+    test('needs an interactive terminal', () => {
+        sandbox.fixture('promo-12.json', 'students.json')
+        const { status, stderr } = sandbox.run(['tui'])
+        assert.equal(status, 1)
+        assert.match(stderr, /tui demande un terminal interactif/)
+    })
+})
+
+describe('shell', () => {
+    test('swaps, undoes and saves the draw in progress', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice Martin', 'Bob Durand', 'Carol', 'Dan']))
+        const input = 'save\ngenerate\nswap alice "Bob Durand"\nswap alice carol\nsave\nswap alice dan\nmatrix\n'
+        const { status, stdout } = sandbox.run(['shell'], { input })
+        assert.equal(status, 0)
+        assert.match(stdout, /Aucun tirage en cours, tapez generate/)
+        assert.match(stdout, /Alice Martin et (Bob Durand|Carol) échangés\./)
+        assert.match(stdout, /Brief n°1 enregistré\.\ngroupgen> Tirage déjà enregistré comme brief n°1/)
+
+        const [brief] = sandbox.db().briefs
+        assert.ok(brief.context.swaps.length >= 1)
+        assert.deepEqual(brief.groups.flat().sort(), ['Alice Martin', 'Bob Durand', 'Carol', 'Dan'])
+        assert.equal(brief.context.repeats, 0)
+        assert.match(stdout, /1 Alice Martin +- /)
+    })
+
+    test('undoes every swap and generate, then has nothing left to undo', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        const input = 'generate\nswap alice bob\nswap alice carol\nundo\nundo\nundo\nsave\nquit\ngenerate\n'
+        const { stdout } = sandbox.run(['shell'], { input })
+        assert.match(stdout, /Rien à annuler/)
+        assert.doesNotMatch(stdout, /Dernier tirage non enregistré/)
+        assert.equal(sandbox.db().briefs[0].context.swaps, undefined)
+    })
+})
+
+describe('matrix', () => {
+    test('counts the briefs each pair spent together, as of a date and in every format', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: '2025-01-06T08:00:00.000Z', label: null, groups: [['Alice', 'Bob'], ['Carol', 'Dan']] },
+            { id: 2, date: '2025-02-03T08:00:00.000Z', label: null, groups: [['Alice', 'Bob'], ['Carol', 'Dan']] },
+        ] }))
+
+        assert.equal(sandbox.run(['matrix']).stdout, [
+            'Briefs passés ensemble, sur 2 brief(s) :',
+            '         1 2 3 4',
+            '1 Alice  - 2 . .',
+            '2 Bob    2 - . .',
+            '3 Carol  . . - 2',
+            '4 Dan    . . 2 -',
+            '',
+        ].join('\n'))
+        assert.match(sandbox.run(['matrix', '--as-of', '2025-01-31', '--format', 'csv']).stdout, /^name,Alice,Bob,Carol,Dan\r\nAlice,,1,0,0\r\n/)
+        const matrix = JSON.parse(sandbox.run(['matrix', '--format', 'json']).stdout)
+        assert.equal(matrix.schema_version, 1)
+        assert.deepEqual(matrix.pairs[0], { students: ['Alice', 'Bob'], briefs: 2 })
+
+        assert.match(sandbox.run(['--yes']).stdout, /ce tirage compris :\n {9}1 2 3 4\n/)
+        assert.doesNotMatch(sandbox.run(['--yes', '--no-matrix']).stdout, /ce tirage compris/)
+    })
+
+    test('colours pairs with --color always, even with NO_COLOR, and never when piped', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob']))
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: '2025-01-06T08:00:00.000Z', label: null, groups: [['Alice', 'Bob']] },
+        ] }))
+
+        assert.match(sandbox.run(['--color', 'always', 'matrix'], { env: { NO_COLOR: '1' } }).stdout, /1 Alice  -\x1b\[32m 1\x1b\[39m\n/)
+        assert.doesNotMatch(sandbox.run(['matrix']).stdout, /\x1b/)
+        assert.doesNotMatch(sandbox.run(['matrix', '--color=never']).stdout, /\x1b/)
+        assert.match(sandbox.run(['--color', 'rainbow', 'matrix']).stderr, /--color invalide : rainbow \(auto, always, never\)/)
+    })
+
+    test('marks students who missed many briefs, not those who joined late', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan', 'Eve']))
+        sandbox.write('db.json', JSON.stringify({ schema_version: 1, constraints: [], briefs: [
+            { id: 1, date: '2025-01-06T08:00:00.000Z', label: null, groups: [['Alice', 'Bob'], ['Carol', 'Dan']] },
+            { id: 2, date: '2025-01-13T08:00:00.000Z', label: null, groups: [['Alice', 'Carol'], ['Bob', 'Eve']], context: { excluded: ['Dan'] } },
+            { id: 3, date: '2025-01-20T08:00:00.000Z', label: null, groups: [['Alice', 'Eve'], ['Bob', 'Carol']],
+                amendments: [{ date: '2025-01-20T10:00:00.000Z', action: 'drop', student: 'Dan', group: 1, emptied: false }] },
+        ] }))
+
+        const table = sandbox.run(['matrix']).stdout
+        assert.match(table, /4 Dan \*/)
+        assert.doesNotMatch(table, /Eve \*/)
+        assert.match(table, /\* souvent absent\(e\), ses cases vides tiennent à ses absences : Dan \(1 brief\(s\) sur 3\)\n$/)
+        assert.deepEqual(JSON.parse(sandbox.run(['matrix', '--format', 'json']).stdout).low_attendance, [{ student: 'Dan', present: 1, briefs: 3 }])
+        assert.match(sandbox.run(['stats']).stdout, /Dan : 1 brief\(s\), absent\(e\) à 2 brief\(s\) \*/)
+    })
+})
+
+describe('cohort', () => {
+    test('keeps the roster and history of each promo apart in one database', () => {
+        sandbox.write('promo-a.json', JSON.stringify(['Alice', 'Bob', 'Carol', 'Dan']))
+        sandbox.write('promo-b.json', JSON.stringify(['Wanda', 'Xavier', 'Yann', 'Zoé']))
+        assert.match(sandbox.run(['cohort', 'create', 'promo-a', '--students', 'promo-a.json']).stdout, /Cohorte promo-a créée avec 4 apprenant\(s\)/)
+        sandbox.run(['cohort', 'create', 'promo-b', '--students', 'promo-b.json'])
+        sandbox.run(['--cohort', 'promo-a', '--yes'])
+        sandbox.run(['--yes'], { env: { SGG_COHORT: 'promo-b' } })
+        sandbox.run(['--cohort=promo-b', '--yes'])
+
+        const db = sandbox.db()
+        assert.deepEqual(db.briefs, [])
+        assert.equal(db.cohorts['promo-a'].briefs.length, 1)
+        assert.deepEqual(db.cohorts['promo-b'].briefs.map((brief) => brief.groups.flat().sort()), [['Wanda', 'Xavier', 'Yann', 'Zoé'], ['Wanda', 'Xavier', 'Yann', 'Zoé']])
+        assert.equal(db.cohorts['promo-b'].briefs[1].context.previous_brief, 1)
+        assert.equal(sandbox.run(['cohort', 'list']).stdout, 'promo-a : 4 apprenant(s), 1 brief(s)\npromo-b : 4 apprenant(s), 2 brief(s)\n')
+        assert.match(sandbox.run(['compare-cohorts', 'promo-a', 'promo-b']).stdout, /^promo-a \| 1 briefs[\s\S]*\npromo-b \| 2 briefs/)
+        assert.match(sandbox.run(['--cohort', 'promo-c', 'history']).stderr, /Cohorte inconnue : promo-c \(promo-a, promo-b\), à créer avec : groupgen cohort create promo-c/)
+    })
+})
+
+describe('init', () => {
+    test('prepares a promo directory and keeps existing files', () => {
+        sandbox.write('promo/students.json', JSON.stringify(['Alice', 'Bob']))
+        const { status, stdout } = sandbox.run(['init', 'promo'])
+        assert.equal(status, 0)
+        assert.match(stdout, /Conservé : promo\/students.json/)
+        assert.match(stdout, /Créé : promo\/db.json\nCréé : promo\/groupgen.toml/)
+        assert.deepEqual(JSON.parse(sandbox.read('promo/db.json')), { schema_version: 1, briefs: [], constraints: [] })
+
+        assert.equal(sandbox.run(['generate', '--yes'], { cwd: 'promo' }).status, 0)
+        assert.equal(JSON.parse(sandbox.read('promo/db.json')).briefs.length, 1)
+        assert.match(sandbox.run(['init', 'other']).stdout, /Ajoutez les apprenants dans other\/students.json/)
+        assert.deepEqual(JSON.parse(sandbox.read('other/students.json')), [])
+    })
+
+    test('asks for the roster, group size and history with --wizard', () => {
+        const input = 'promo.json\nAlice\nBob\nCarol\n\n1\n3\nhistorique/\n'
+        const { status, stdout } = sandbox.run(['init', 'promo', '--wizard'], { input })
+        assert.equal(status, 0)
+        assert.match(stdout, /Taille de groupe invalide : 1/)
+        assert.deepEqual(JSON.parse(sandbox.read('promo/promo.json')), ['Alice', 'Bob', 'Carol'])
+        assert.match(sandbox.read('promo/groupgen.toml'), /db = "historique\/"\nstudents = "promo.json"\ngroup_size = 3\n/)
+
+        sandbox.run(['--yes'], { cwd: 'promo' })
+        assert.deepEqual(JSON.parse(sandbox.read('promo/historique/db.json')).briefs[0].groups.map((group) => group.length), [3])
+        assert.match(sandbox.run(['init', 'promo', '--wizard']).stderr, /groupgen\.toml existe déjà/)
+        assert.match(sandbox.run([]).stderr, /Liste des apprenants introuvable : .*students\.json \(groupgen init pour démarrer\)/)
+    })
+})
+
+describe('completions', () => {
+    test('prints completion scripts built from the commands', () => {
+        const bash = sandbox.run(['completions', 'bash'])
+        assert.equal(bash.status, 0)
+        assert.match(bash.stdout, /trash\) words='list restore empty --all --help' ;;/)
+        assert.match(bash.stdout, /complete -o default -F _groupgen groupgen/)
+        assert.match(sandbox.run(['completions', 'fish']).stdout, /-n '__fish_seen_subcommand_from generate' -l group-size -r/)
+        assert.match(sandbox.run(['completions', 'zsh']).stdout, /'history:Liste les briefs/)
+        assert.match(sandbox.run(['completions', 'powershell']).stdout, /'quick' = @\('--save', '--group-size', '--name', '--help'\)/)
+        assert.match(sandbox.run(['completions', 'tcsh']).stderr, /Shell non pris en charge : tcsh/)
+    })
+})
+
+describe('logging', () => {
+    test('-q, -v and --log-json control the diagnostics on stderr', () => {
+        sandbox.write('students.json', JSON.stringify(['Alice,', 'Bob', 'Carol', 'Dan']))
+        assert.match(sandbox.run(['--yes', '--dry-run']).stderr, /« Alice, » ressemble à une erreur de copier-coller/)
+        assert.equal(sandbox.run(['--yes', '--dry-run', '-q']).stderr, '')
+        assert.match(sandbox.run(['history', '-v']).stderr, /^Configuration : .*\nHistorique : /)
+
+        const lines = sandbox.run(['--yes', '--dry-run', '--log-json', '-vv']).stderr.trim().split('\n').map((line) => JSON.parse(line))
+        assert.deepEqual([...new Set(lines.map((line) => line.level))].sort(), ['debug', 'verbose', 'warn'])
+        assert.match(lines.find((line) => line.level == 'warn').message, /« Alice, »/)
+        const error = JSON.parse(sandbox.run(['brief', 'clone', '9', '--log-json']).stderr)
+        assert.equal(error.level, 'error')
+        assert.match(error.message, /^Erreur : .*introuvable/)
+    })
+})
@@ -0,0 +1,12 @@
+import assert from 'assert/strict'
+import { test } from 'node:test'
+import { fuzz } from '../fuzz/fuzz.mjs'
+import { TARGETS } from '../fuzz/targets.mjs'
+
+// Short fixed-seed runs so regressions found by `npm run fuzz` stay fixed.
+for(const name of Object.keys(TARGETS)) {
+    test(`fuzz ${name}`, async () => {
+        const failure = await fuzz(name, { iterations: 2000, seed: 42 })
+        assert.equal(failure, null, failure && `${JSON.stringify(failure.input)} : ${failure.error.stack}`)
+    })
+}
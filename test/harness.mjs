@@ -0,0 +1,132 @@
+import { spawn, spawnSync } from 'child_process'
+import fs from 'fs'
+import os from 'os'
+import path from 'path'
+import { fileURLToPath } from 'url'
+
+const ROOT = path.resolve(path.dirname(fileURLToPath(import.meta.url)), '..')
+const FIXTURES = path.join(ROOT, 'test', 'fixtures')
+
+/**
+ * A throw-away working directory in which the real binary is run, with its
+ * data directory redirected inside it.
+ */
+export class Sandbox {
+    constructor() {
+        this.dir = fs.mkdtempSync(path.join(os.tmpdir(), 'groupgen-'))
+        this.dataDir = path.join(this.dir, 'data')
+    }
+
+    /**
+     * Copies `test/fixtures/<fixture>` into the sandbox as `name`.
+     */
+    fixture(fixture, name = fixture) {
+        fs.copyFileSync(path.join(FIXTURES, fixture), path.join(this.dir, name))
+    }
+
+    /**
+     * @param {string[]} args
+     * @param {{input?: string, env?: Record<string, string>, cwd?: string}} [options] `cwd` relative to the sandbox
+     */
+    run(args, { input, env, cwd = '.' } = {}) {
+        const result = spawnSync(process.execPath, [path.join(ROOT, 'main.mjs'), ...args], {
+            cwd: path.join(this.dir, cwd),
+            input,
+            encoding: 'utf8',
+            env: this.#env(env),
+        })
+        return { status: result.status, stdout: result.stdout, stderr: result.stderr }
+    }
+
+    /**
+     * Like `run`, without blocking the test process, for tests serving
+     * HTTP requests to the binary.
+     *
+     * @param {string[]} args
+     * @param {{env?: Record<string, string>}} [options]
+     * @returns {Promise<{status: number, stdout: string, stderr: string}>}
+     */
+    runAsync(args, { env } = {}) {
+        const child = spawn(process.execPath, [path.join(ROOT, 'main.mjs'), ...args], { cwd: this.dir, env: this.#env(env) })
+        const output = { stdout: '', stderr: '' }
+        child.stdout.on('data', (chunk) => output.stdout += chunk)
+        child.stderr.on('data', (chunk) => output.stderr += chunk)
+        return new Promise((resolve) => child.on('close', (status) => resolve({ status, ...output })))
+    }
+
+    /**
+     * Like `runAsync`, in a pseudo-terminal made by util-linux `script`,
+     * typing `answer` once the output ends with `prompt`. The output is
+     * what the terminal showed, echo included.
+     *
+     * @param {string[]} args
+     * @param {{prompt: RegExp, answer: string, env?: Record<string, string>}} options
+     * @returns {Promise<{status: number, output: string}>}
+     */
+    runInTerminal(args, { prompt, answer, env }) {
+        const command = [process.execPath, path.join(ROOT, 'main.mjs'), ...args].map((arg) => `'${arg.replace(/'/g, `'\\''`)}'`).join(' ')
+        const child = spawn('script', ['-qec', command, '/dev/null'], { cwd: this.dir, env: this.#env(env) })
+        let output = ''
+        child.stdout.on('data', (chunk) => {
+            output += chunk
+            if(prompt.test(output)) {
+                child.stdin.write(answer)
+            }
+        })
+        return new Promise((resolve) => child.on('close', (status) => resolve({ status, output })))
+    }
+
+    #env(env) {
+        return { ...process.env, HOME: this.dir, XDG_DATA_HOME: this.dataDir, XDG_CONFIG_HOME: path.join(this.dir, 'config'), APPDATA: this.dataDir, SGG_ASCII: '1', SGG_LANG: 'fr', ...env }
+    }
+
+    /**
+     * Path of a file in the sandbox, `null` when it does not exist.
+     */
+    find(...candidates) {
+        return candidates.map((candidate) => path.join(this.dir, candidate)).find((file) => fs.existsSync(file)) ?? null
+    }
+
+    write(file, content, mode) {
+        fs.mkdirSync(path.dirname(path.join(this.dir, file)), { recursive: true })
+        fs.writeFileSync(path.join(this.dir, file), content, { mode })
+    }
+
+    read(file) {
+        return fs.readFileSync(path.join(this.dir, file), 'utf8')
+    }
+
+    list(dir) {
+        return fs.readdirSync(path.join(this.dir, dir))
+    }
+
+    /**
+     * Contents of the history database, wherever the binary put it.
+     */
+    db() {
+        const file = this.find('db.json', 'data/simplon-group-generator/db.json')
+        return file == null ? null : JSON.parse(fs.readFileSync(file))
+    }
+
+    cleanup() {
+        fs.rmSync(this.dir, { recursive: true, force: true })
+    }
+}
+
+/**
+ * @param {string[][]} groups
+ * @returns {Set<string>}
+ */
+export function pairsOf(groups) {
+    const pairs = new Set()
+    for(const group of groups) {
+        for(const a of group) {
+            for(const b of group) {
+                if(a < b) {
+                    pairs.add(`${a}|${b}`)
+                }
+            }
+        }
+    }
+    return pairs
+}
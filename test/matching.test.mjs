@@ -0,0 +1,76 @@
+import assert from 'node:assert/strict'
+import { describe, test } from 'node:test'
+import { NEVER_PENALTY } from '../lib/matcher.mjs'
+import { minCostPerfectMatching } from '../lib/matching.mjs'
+import { seededRandom } from '../lib/random.mjs'
+
+/**
+ * Least total cost of pairing `vertices`, trying every pairing. With an
+ * odd count, one vertex is left out at no cost.
+ *
+ * @param {number[]} vertices
+ * @param {(i: number, j: number) => number} cost
+ */
+function bruteForce(vertices, cost) {
+    if(vertices.length < 2) {
+        return 0
+    }
+    const [first, ...rest] = vertices
+    const paired = rest.map((other) => cost(first, other) + bruteForce(rest.filter((vertex) => vertex != other), cost))
+    return Math.min(...paired, ...(vertices.length % 2 == 1 ? [bruteForce(rest, cost)] : []))
+}
+
+/**
+ * Symmetric random cost matrix, with a share of "never" pairs.
+ *
+ * @param {number} size
+ * @param {() => number} random
+ * @param {number} never share of pairs costing NEVER_PENALTY more
+ */
+function randomCosts(size, random, never) {
+    const costs = Array.from({ length: size }, () => new Array(size).fill(0))
+    for(let i = 0; i < size; i++) {
+        for(let j = i + 1; j < size; j++) {
+            costs[i][j] = costs[j][i] = Math.floor(random() * 6) + (random() < never ? NEVER_PENALTY : 0)
+        }
+    }
+    return costs
+}
+
+/**
+ * Pairs `size` vertices as the matcher does, an extra free vertex taking
+ * the odd one out, and returns the cost of the pairs found.
+ */
+function matchedCost(size, costs) {
+    const mate = minCostPerfectMatching(size + size % 2, (i, j) => j >= size ? 0 : costs[i][j])
+    mate.forEach((other, i) => assert.equal(mate[other], i, `appariement incohérent : ${JSON.stringify(mate)}`))
+    return mate.reduce((sum, other, i) => other > i && other < size ? sum + costs[i][other] : sum, 0)
+}
+
+describe('minCostPerfectMatching', () => {
+    test('finds the least costly pairing of random small graphs', () => {
+        const random = seededRandom(2024)
+        for(let size = 1; size <= 9; size++) {
+            for(const never of [0, 0.3, 1]) {
+                for(let run = 0; run < 20; run++) {
+                    const costs = randomCosts(size, random, never)
+                    const vertices = Array.from({ length: size }, (_, i) => i)
+                    const expected = bruteForce(vertices, (i, j) => costs[i][j])
+                    assert.equal(matchedCost(size, costs), expected, `${size} sommets : ${JSON.stringify(costs)}`)
+                }
+            }
+        }
+    })
+
+    test('avoids "never" pairs whenever a pairing without them exists', () => {
+        // A cycle of cheap pairs 0-1-2-3-4-5-0 with every other pair forbidden.
+        const size = 6
+        const costs = Array.from({ length: size }, (_, i) => Array.from({ length: size }, (_, j) =>
+            i == j ? 0 : (j - i + size) % size == 1 || (i - j + size) % size == 1 ? 3 : NEVER_PENALTY))
+        assert.equal(matchedCost(size, costs), 9)
+    })
+
+    test('handles no vertex at all', () => {
+        assert.deepEqual(minCostPerfectMatching(0, () => 0), [])
+    })
+})
@@ -0,0 +1,96 @@
+import assert from 'node:assert/strict'
+import { describe, test } from 'node:test'
+import { ASCII, SCREEN_READER, UNICODE } from '../lib/console.mjs'
+import { avatarSheet } from '../lib/export/avatars.mjs'
+import { calendar } from '../lib/ics.mjs'
+import { useLang } from '../lib/i18n.mjs'
+import { briefDocument } from '../lib/model.mjs'
+import { announcement } from '../lib/publishers/index.mjs'
+import { formatMatrix, renderGroups } from '../lib/render.mjs'
+import { pairCounts } from '../lib/stats.mjs'
+import { createState, handleKey, renderScreen, showDraw } from '../lib/tui.mjs'
+import { assertSnapshot } from './snapshot.mjs'
+
+const brief = {
+    id: 14,
+    date: '2024-10-14T07:30:00.000Z',
+    label: 'Projet <fil rouge> & co',
+    groups: [['Émile Zola', 'Bob'], ['Carol', 'Dan', 'Ève Lefèvre-Dubois de la Tour']],
+    slots: { names: ['9h', '11h'], assignment: ['11h', '9h'] },
+}
+
+const students = new Map([
+    ['Bob', { name: 'Bob', email: 'bob@example.org', avatar: 'https://example.org/bob.jpg?size=64&format=png' }],
+])
+
+describe('renderers', () => {
+    test('terminal boxes', () => {
+        assertSnapshot('groups-unicode.txt', renderGroups(brief.groups, UNICODE))
+        assertSnapshot('groups-ascii.txt', renderGroups(brief.groups, ASCII))
+        assertSnapshot('groups-screen-reader.txt', renderGroups(brief.groups, SCREEN_READER))
+    })
+
+    test('meeting matrix', () => {
+        const counts = pairCounts([brief.groups, [['Bob', 'Carol'], ['Émile Zola', 'Dan', 'Ève Lefèvre-Dubois de la Tour']]])
+        const roster = brief.groups.flat()
+        assertSnapshot('matrix-unicode.txt', formatMatrix(roster, counts, 'table', { chars: UNICODE }))
+        assertSnapshot('matrix-screen-reader.txt', formatMatrix(roster, counts, 'table', { chars: SCREEN_READER }))
+        assertSnapshot('matrix.md', formatMatrix(roster, counts, 'markdown'))
+        const lowAttendance = new Map([['Dan', { present: 2, briefs: 5 }]])
+        assertSnapshot('matrix-attendance.txt', [
+            formatMatrix(roster, counts, 'table', { chars: UNICODE, lowAttendance }),
+            formatMatrix(roster, counts, 'table', { chars: SCREEN_READER, lowAttendance }),
+            formatMatrix(roster, counts, 'markdown', { lowAttendance }),
+        ].join('\n\n'))
+        useLang('en')
+        try {
+            assertSnapshot('matrix-attendance-en.txt', formatMatrix(roster, counts, 'table', { chars: SCREEN_READER, lowAttendance }))
+        } finally {
+            useLang('fr')
+        }
+        assertSnapshot('matrix-color.txt', formatMatrix(roster, counts, 'table', { chars: ASCII, color: true }).replace(/\x1b\[(\d+)m/g, '<$1>'))
+    })
+
+    test('tui screens', () => {
+        const state = createState(brief.groups.flat(), [brief.groups])
+        const screen = () => renderScreen(state, { columns: 60, rows: 12 }).join('\n').replaceAll('\x1b[7m', '[').replaceAll('\x1b[27m', ']')
+        handleKey(state, '2')
+        handleKey(state, 'right')
+        const matrix = screen()
+        handleKey(state, '3')
+        showDraw(state, [['Bob', 'Carol'], ['Émile Zola', 'Dan', 'Ève Lefèvre-Dubois de la Tour']], 7)
+        for(const key of ['space', 'down', 'down', 'space']) {
+            handleKey(state, key)
+        }
+        assertSnapshot('tui.txt', `${matrix}\n\n${screen()}`)
+    })
+
+    test('announcements', () => {
+        const { text, markdown, html } = announcement(brief)
+        assertSnapshot('announcement.txt', text)
+        assertSnapshot('announcement.md', markdown)
+        assertSnapshot('announcement.html', html)
+    })
+
+    test('brief document', () => {
+        assertSnapshot('brief.json', JSON.stringify(briefDocument(brief), null, 4))
+    })
+
+    test('avatar sheet', () => {
+        assertSnapshot('avatars.html', avatarSheet(brief, students))
+    })
+
+    test('calendar invitation', () => {
+        assertSnapshot('invitation.ics', calendar([{
+            uid: 'brief-14-bob@groupgen',
+            summary: 'Brief n°14 avec Émile Zola',
+            description: 'Groupe 1 : Émile Zola, Bob\nPassage : 11h',
+            location: 'Salle 3; 2e étage',
+            start: '2024-10-14T09:00',
+            end: '2024-10-14T17:00',
+            attendees: [{ name: 'Émile Zola' }, { name: 'Bob', email: 'bob@example.org' }],
+        }], { now: new Date('2024-10-01T12:00:00Z') }))
+        const escaped = calendar([{ uid: 'x@groupgen', summary: 'a\\n b;c, d', start: '2024-10-14' }])
+        assert.match(escaped, /^SUMMARY:a\\\\n b\\;c\\, d\r$/m)
+    })
+})
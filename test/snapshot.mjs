@@ -0,0 +1,24 @@
+import assert from 'node:assert/strict'
+import fs from 'fs'
+import path from 'path'
+import { fileURLToPath } from 'url'
+
+const SNAPSHOTS = path.join(path.dirname(fileURLToPath(import.meta.url)), 'snapshots')
+
+/**
+ * Compares `actual` with `test/snapshots/<name>`. A missing snapshot is
+ * written, and `SGG_UPDATE_SNAPSHOTS=1` rewrites them all: review the
+ * diff before committing.
+ *
+ * @param {string} name file name, extension included
+ * @param {string} actual
+ */
+export function assertSnapshot(name, actual) {
+    const file = path.join(SNAPSHOTS, name)
+    if(process.env.SGG_UPDATE_SNAPSHOTS || !fs.existsSync(file)) {
+        fs.mkdirSync(SNAPSHOTS, { recursive: true })
+        fs.writeFileSync(file, actual)
+        return
+    }
+    assert.equal(actual, fs.readFileSync(file, 'utf8'), `${name} a changé, relancer avec SGG_UPDATE_SNAPSHOTS=1 si c'est voulu`)
+}
@@ -0,0 +1,44 @@
+import assert from 'node:assert/strict'
+import { describe, test } from 'node:test'
+import { InputError } from '../lib/errors.mjs'
+import { parseToml } from '../lib/toml.mjs'
+
+describe('parseToml', () => {
+    test('reads arrays spread over several lines', () => {
+        const config = parseToml([
+            '[skills]',
+            'required = [',
+            '    "HTML", # markup',
+            '    "SQL",',
+            ']',
+            'pairs = [',
+            '    ["Alice", "Bob"],',
+            '    ["Carol", "Dan, Jr"]]',
+            'weight = 2',
+        ].join('\n'))
+        assert.deepEqual(config, { skills: {
+            required: ['HTML', 'SQL'],
+            pairs: [['Alice', 'Bob'], ['Carol', 'Dan, Jr']],
+            weight: 2,
+        } })
+    })
+
+    test('reports the line of an array left open', () => {
+        assert.throws(() => parseToml('a = 1\nrequired = [\n    "HTML",\n'), new InputError('TOML invalide ligne 2 : tableau non fermé'))
+        assert.throws(() => parseToml('note = "[" # ]\nb = ['), new InputError('TOML invalide ligne 2 : tableau non fermé'))
+    })
+
+    test('nests dotted keys into tables', () => {
+        const config = parseToml('ldap.base = "dc=example,dc=org"\nldap."name.attr" = "cn"\n[intake]\nlevels."Avancé" = 3\n')
+        assert.deepEqual(config, {
+            ldap: { 'base': 'dc=example,dc=org', 'name.attr': 'cn' },
+            intake: { levels: { Avancé: 3 } },
+        })
+    })
+
+    test('rejects dotted keys going through a value or an empty part', () => {
+        assert.throws(() => parseToml('ldap = "x"\nldap.base = "y"'), new InputError('TOML invalide ligne 2 : ldap n\'est pas une table'))
+        assert.throws(() => parseToml('[ldap..x]'), new InputError('Clé TOML invalide ligne 1 : ""'))
+        assert.throws(() => parseToml('a. = 1'), InputError)
+    })
+})